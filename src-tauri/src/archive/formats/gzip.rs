@@ -1,11 +1,14 @@
 use crate::archive::formats::{common::*, CompressionHandlerDispatcher};
 /// GZIP 格式处理器
 use crate::archive::types::*;
-use crate::storage::traits::{ProgressCallback, StorageClient};
+use crate::error::AppError;
+use crate::storage::traits::{ByteStream, ProgressCallback, StorageClient, StorageError};
+use crate::utils::stream_io::StreamIo;
 use flate2::read::GzDecoder;
 use std::collections::HashMap;
 use std::io::{Cursor, Read};
 use std::sync::Arc;
+use tokio_util::io::SyncIoBridge;
 pub struct GzipHandler;
 
 #[async_trait::async_trait]
@@ -16,7 +19,7 @@ impl CompressionHandlerDispatcher for GzipHandler {
         file_path: &str,
         _filename: &str,
         max_size: Option<u32>,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         Self::analyze_with_storage_client(client, file_path, max_size.map(|s| s as usize)).await
     }
 
@@ -29,7 +32,7 @@ impl CompressionHandlerDispatcher for GzipHandler {
         offset: Option<u64>,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         Self::extract_gzip_preview_streaming(
             client,
             file_path,
@@ -56,14 +59,14 @@ impl GzipHandler {
         client: Arc<dyn StorageClient>,
         file_path: &str,
         max_size: Option<usize>,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         log::debug!("使用storage client分析GZIP文件: {}", file_path);
 
         // 获取文件大小
         let file_size = client
             .get_file_size(file_path)
             .await
-            .map_err(|e| format!("Failed to get file size: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
 
         log::debug!("GZIP文件大小: {} 字节", file_size);
 
@@ -78,7 +81,7 @@ impl GzipHandler {
         file_path: &str,
         file_size: u64,
         max_sample_size: Option<usize>,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         log::debug!("开始流式分析GZIP文件: {} ({} 字节)", file_path, file_size);
 
         // 读取GZIP头部用于验证和提取元数据
@@ -88,10 +91,10 @@ impl GzipHandler {
         let header_data = client
             .read_file_range(file_path, 0, header_size)
             .await
-            .map_err(|e| format!("Failed to read GZIP header: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read GZIP header"))?;
 
         if !Self::validate_gzip_header(&header_data) {
-            return Err("Invalid GZIP header".to_string());
+            return Err(AppError::internal("Invalid GZIP header"));
         }
 
         // 提取原始文件名
@@ -105,7 +108,7 @@ impl GzipHandler {
         let compressed_data = client
             .read_file_range(file_path, 0, read_size as u64)
             .await
-            .map_err(|e| format!("Failed to read GZIP data for analysis: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read GZIP data for analysis"))?;
 
         // 流式解压缩样本数据来估算大小
         let uncompressed_sample = Self::decompress_sample(&compressed_data, sample_size)?;
@@ -144,17 +147,17 @@ impl GzipHandler {
         _offset: Option<u64>, // GZIP 格式不支持偏移量
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         log::debug!("开始流式提取GZIP预览: {}", file_path);
 
         let file_size = client
             .get_file_size(file_path)
             .await
-            .map_err(|e| format!("Failed to get file size: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
 
         // 估算需要读取的压缩数据大小（考虑压缩比）
         // 通常文本压缩比在3-5倍，二进制文件1.5-2倍
-        let estimated_compressed_size = (max_size * 3).max(4096); // 至少读取4KB
+        let estimated_compressed_size = max_size.saturating_mul(3).max(4096); // 至少读取4KB
         let read_size = std::cmp::min(estimated_compressed_size as u64, file_size);
 
         // 直接读取全部数据，避免人为分块导致的性能问题
@@ -167,10 +170,10 @@ impl GzipHandler {
         let compressed_data = client
             .read_file_range_with_progress(file_path, 0, read_size, progress_cb, cancel_rx)
             .await
-            .map_err(|e| format!("Failed to read GZIP data: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read GZIP data"))?;
 
         if !Self::validate_gzip_header(&compressed_data) {
-            return Err("Invalid GZIP header".to_string());
+            return Err(AppError::internal("Invalid GZIP header"));
         }
 
         // 流式解压缩预览数据
@@ -200,20 +203,46 @@ impl GzipHandler {
             .build())
     }
 
+    /// 以数据流的方式读取解压后的内容，从 start 处开始按 chunk_size 分块输出
+    pub async fn open_entry_stream(
+        client: Arc<dyn StorageClient>,
+        file_path: &str,
+        start: u64,
+        chunk_size: u64,
+    ) -> Result<ByteStream, StorageError> {
+        let stream = client.open_read_stream(file_path, 0).await?;
+        let reader = SyncIoBridge::new(StreamIo::into_async_read(stream));
+
+        Ok(StreamIo::spawn_blocking_stream(move |tx| {
+            StreamIo::send_from_reader(GzDecoder::new(reader), start, chunk_size, tx)
+        }))
+    }
+
     /// 解压缩样本数据
     fn decompress_sample(
         compressed_data: &[u8],
         max_output_size: usize,
-    ) -> Result<Vec<u8>, String> {
-        let mut decoder = GzDecoder::new(Cursor::new(compressed_data));
-        let mut buffer = vec![0u8; max_output_size];
-
-        let bytes_read = decoder
-            .read(&mut buffer)
-            .map_err(|e| format!("Failed to decompress data: {}", e))?;
+    ) -> Result<Vec<u8>, AppError> {
+        let mut decoder = GzDecoder::new(Cursor::new(compressed_data)).take(max_output_size as u64);
+        let mut output = Vec::new();
+        let mut buffer = vec![0u8; max_output_size.min(64 * 1024)];
+
+        // 循环读取直到达到上限或数据结束，压缩数据只读取了一部分时保留已解压的内容
+        loop {
+            match decoder.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => output.extend_from_slice(&buffer[..n]),
+                Err(_) if !output.is_empty() => break,
+                Err(e) => {
+                    return Err(AppError::internal(format!(
+                        "Failed to decompress data: {}",
+                        e
+                    )))
+                }
+            }
+        }
 
-        buffer.truncate(bytes_read);
-        Ok(buffer)
+        Ok(output)
     }
 
     // 辅助方法
@@ -9,6 +9,7 @@ pub mod tar_gz;
 pub mod zip;
 
 use crate::archive::types::*;
+use crate::error::AppError;
 use crate::storage::traits::StorageClient;
 use std::sync::Arc;
 
@@ -22,7 +23,7 @@ pub trait CompressionHandlerDispatcher: Send + Sync {
         file_path: &str,
         filename: &str,
         max_size: Option<u32>,
-    ) -> Result<ArchiveInfo, String>;
+    ) -> Result<ArchiveInfo, AppError>;
 
     /// 通过存储客户端提取文件预览（统一接口，支持流式提取、进度回调和取消信号）
     async fn extract_preview_with_client(
@@ -34,7 +35,7 @@ pub trait CompressionHandlerDispatcher: Send + Sync {
         offset: Option<u64>,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String>;
+    ) -> Result<FilePreview, AppError>;
 
     /// 获取压缩类型
     #[allow(dead_code)] // API 保留方法，保持接口完整性
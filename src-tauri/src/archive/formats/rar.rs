@@ -1,6 +1,7 @@
 use crate::archive::formats::{common::*, CompressionHandlerDispatcher};
 /// RAR 格式处理器
 use crate::archive::types::*;
+use crate::error::AppError;
 use std::collections::HashMap;
 use unrar::Archive as RarArchive;
 
@@ -8,7 +9,7 @@ pub struct RarHandler;
 
 #[async_trait::async_trait]
 impl CompressionHandlerDispatcher for RarHandler {
-    async fn analyze_complete(&self, data: &[u8]) -> Result<ArchiveInfo, String> {
+    async fn analyze_complete(&self, data: &[u8]) -> Result<ArchiveInfo, AppError> {
         Self::analyze_rar_complete(data)
     }
 
@@ -18,7 +19,7 @@ impl CompressionHandlerDispatcher for RarHandler {
         headers: &HashMap<String, String>,
         filename: &str,
         file_size: u64,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         // RAR 格式不支持流式分析，需要下载完整文件
         Self::analyze_rar_by_download(url, headers, filename, file_size).await
     }
@@ -28,7 +29,7 @@ impl CompressionHandlerDispatcher for RarHandler {
         url: &str,
         headers: &HashMap<String, String>,
         filename: &str,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         // RAR 格式不支持流式分析，需要下载完整文件
         Self::analyze_rar_by_download_no_size(url, headers, filename).await
     }
@@ -39,7 +40,7 @@ impl CompressionHandlerDispatcher for RarHandler {
         headers: &HashMap<String, String>,
         entry_path: &str,
         max_size: usize,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         Self::extract_rar_preview(url, headers, entry_path, max_size).await
     }
 
@@ -52,8 +53,10 @@ impl CompressionHandlerDispatcher for RarHandler {
         _offset: Option<u64>,
         _progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         _cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
-        Err("RAR format does not support client-based extraction yet".to_string())
+    ) -> Result<FilePreview, AppError> {
+        Err(AppError::internal(
+            "RAR format does not support client-based extraction yet",
+        ))
     }
 
     fn compression_type(&self) -> CompressionType {
@@ -67,16 +70,16 @@ impl CompressionHandlerDispatcher for RarHandler {
 
 impl RarHandler {
     /// 完整RAR文件分析
-    fn analyze_rar_complete(data: &[u8]) -> Result<ArchiveInfo, String> {
+    fn analyze_rar_complete(data: &[u8]) -> Result<ArchiveInfo, AppError> {
         println!("开始分析RAR文件，数据长度: {} 字节", data.len());
 
         if !Self::validate_rar_header(data) {
-            return Err("Invalid RAR header".to_string());
+            return Err(AppError::internal("Invalid RAR header"));
         }
 
         // 创建临时文件，因为unrar库需要文件路径
         let temp_path = format!("/tmp/temp_rar_{}.rar", uuid::Uuid::new_v4());
-        std::fs::write(&temp_path, data).map_err(|e| e.to_string())?;
+        std::fs::write(&temp_path, data).map_err(AppError::from)?;
 
         let result = Self::analyze_rar_file(&temp_path);
 
@@ -87,7 +90,7 @@ impl RarHandler {
     }
 
     /// 分析RAR文件
-    fn analyze_rar_file(file_path: &str) -> Result<ArchiveInfo, String> {
+    fn analyze_rar_file(file_path: &str) -> Result<ArchiveInfo, AppError> {
         let archive = RarArchive::new(file_path.to_string());
         let mut entries = Vec::new();
         let mut total_uncompressed_size = 0;
@@ -145,7 +148,7 @@ impl RarHandler {
         headers: &HashMap<String, String>,
         filename: &str,
         file_size: u64,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         println!(
             "开始下载RAR文件进行分析: {} (大小: {} 字节)",
             filename, file_size
@@ -160,7 +163,7 @@ impl RarHandler {
         url: &str,
         headers: &HashMap<String, String>,
         filename: &str,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         println!("开始下载RAR文件进行分析: {}", filename);
 
         let data = HttpClient::download_file(url, headers).await?;
@@ -174,7 +177,7 @@ impl RarHandler {
         headers: &HashMap<String, String>,
         entry_path: &str,
         max_size: usize,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         println!("开始从RAR文件提取预览: {}", entry_path);
 
         // 下载完整文件
@@ -182,7 +185,7 @@ impl RarHandler {
 
         // 创建临时文件
         let temp_path = format!("/tmp/temp_rar_{}.rar", uuid::Uuid::new_v4());
-        std::fs::write(&temp_path, data).map_err(|e| e.to_string())?;
+        std::fs::write(&temp_path, data).map_err(AppError::from)?;
 
         let result = Self::extract_from_rar_file(&temp_path, entry_path, max_size);
 
@@ -197,7 +200,7 @@ impl RarHandler {
         file_path: &str,
         entry_path: &str,
         max_size: usize,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         let archive = RarArchive::new(file_path.to_string());
 
         for entry_result in archive.iter() {
@@ -208,7 +211,7 @@ impl RarHandler {
                         let mut output = Vec::new();
                         entry
                             .extract_to_writer(&mut output)
-                            .map_err(|e| e.to_string())?;
+                            .map_err(|e| AppError::internal(e.to_string()))?;
                         let total_size = entry.unpacked_size;
                         let preview_size = max_size.min(output.len());
 
@@ -240,7 +243,10 @@ impl RarHandler {
             }
         }
 
-        Err(format!("File '{}' not found in RAR archive", entry_path))
+        Err(AppError::not_found(format!(
+            "File '{}' not found in RAR archive",
+            entry_path
+        )))
     }
 
     /// 验证RAR文件头
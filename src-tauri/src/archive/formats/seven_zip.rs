@@ -1,6 +1,7 @@
 use crate::archive::formats::{common::*, CompressionHandlerDispatcher};
 /// 7z 格式处理器
 use crate::archive::types::*;
+use crate::error::AppError;
 use std::collections::HashMap;
 use std::io::Cursor;
 
@@ -8,7 +9,7 @@ pub struct SevenZipHandler;
 
 #[async_trait::async_trait]
 impl CompressionHandlerDispatcher for SevenZipHandler {
-    async fn analyze_complete(&self, data: &[u8]) -> Result<ArchiveInfo, String> {
+    async fn analyze_complete(&self, data: &[u8]) -> Result<ArchiveInfo, AppError> {
         Self::analyze_7z_complete(data)
     }
 
@@ -18,7 +19,7 @@ impl CompressionHandlerDispatcher for SevenZipHandler {
         headers: &HashMap<String, String>,
         filename: &str,
         file_size: u64,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         // 7z 格式不支持流式分析，需要下载完整文件
         Self::analyze_7z_by_download(url, headers, filename, file_size).await
     }
@@ -28,7 +29,7 @@ impl CompressionHandlerDispatcher for SevenZipHandler {
         url: &str,
         headers: &HashMap<String, String>,
         filename: &str,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         // 7z 格式不支持流式分析，需要下载完整文件
         Self::analyze_7z_by_download_no_size(url, headers, filename).await
     }
@@ -39,7 +40,7 @@ impl CompressionHandlerDispatcher for SevenZipHandler {
         headers: &HashMap<String, String>,
         entry_path: &str,
         max_size: usize,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         Self::extract_7z_preview(url, headers, entry_path, max_size).await
     }
 
@@ -52,8 +53,10 @@ impl CompressionHandlerDispatcher for SevenZipHandler {
         _offset: Option<u64>,
         _progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         _cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
-        Err("7z format does not support client-based extraction yet".to_string())
+    ) -> Result<FilePreview, AppError> {
+        Err(AppError::internal(
+            "7z format does not support client-based extraction yet",
+        ))
     }
 
     fn compression_type(&self) -> CompressionType {
@@ -67,16 +70,16 @@ impl CompressionHandlerDispatcher for SevenZipHandler {
 
 impl SevenZipHandler {
     /// 完整7z文件分析
-    fn analyze_7z_complete(data: &[u8]) -> Result<ArchiveInfo, String> {
+    fn analyze_7z_complete(data: &[u8]) -> Result<ArchiveInfo, AppError> {
         println!("开始分析7z文件，数据长度: {} 字节", data.len());
 
         if !Self::validate_7z_header(data) {
-            return Err("Invalid 7z header".to_string());
+            return Err(AppError::internal("Invalid 7z header"));
         }
 
         let cursor = Cursor::new(data);
-        let mut archive =
-            SevenZReader::new(cursor, data.len() as u64).map_err(|e| e.to_string())?;
+        let mut archive = SevenZReader::new(cursor, data.len() as u64)
+            .map_err(|e| AppError::internal(e.to_string()))?;
         let mut entries = Vec::new();
         let mut total_uncompressed_size = 0;
 
@@ -129,7 +132,7 @@ impl SevenZipHandler {
         headers: &HashMap<String, String>,
         filename: &str,
         file_size: u64,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         println!(
             "开始下载7z文件进行分析: {} (大小: {} 字节)",
             filename, file_size
@@ -144,7 +147,7 @@ impl SevenZipHandler {
         url: &str,
         headers: &HashMap<String, String>,
         filename: &str,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         println!("开始下载7z文件进行分析: {}", filename);
 
         let data = HttpClient::download_file(url, headers).await?;
@@ -158,13 +161,14 @@ impl SevenZipHandler {
         headers: &HashMap<String, String>,
         entry_path: &str,
         max_size: usize,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         println!("开始从7z文件提取预览: {}", entry_path);
 
         // 下载完整文件
         let data = HttpClient::download_file(url, headers).await?;
         let cursor = Cursor::new(data);
-        let mut archive = SevenZArchive::read(cursor).map_err(|e| e.to_string())?;
+        let mut archive =
+            SevenZArchive::read(cursor).map_err(|e| AppError::internal(e.to_string()))?;
 
         // 查找目标文件
         for entry in archive.entries() {
@@ -172,7 +176,7 @@ impl SevenZipHandler {
                 let mut output = Vec::new();
                 archive
                     .extract_to_writer(entry, &mut output)
-                    .map_err(|e| e.to_string())?;
+                    .map_err(|e| AppError::internal(e.to_string()))?;
                 let total_size = entry.size();
                 let preview_size = max_size.min(output.len());
 
@@ -198,7 +202,10 @@ impl SevenZipHandler {
             }
         }
 
-        Err(format!("File '{}' not found in 7z archive", entry_path))
+        Err(AppError::not_found(format!(
+            "File '{}' not found in 7z archive",
+            entry_path
+        )))
     }
 
     /// 验证7z文件头
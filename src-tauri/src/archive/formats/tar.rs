@@ -1,6 +1,7 @@
 use crate::archive::formats::{common::*, CompressionHandlerDispatcher};
 /// TAR 格式处理器
 use crate::archive::types::*;
+use crate::error::AppError;
 use crate::storage::traits::StorageClient;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -14,7 +15,7 @@ impl CompressionHandlerDispatcher for TarHandler {
         file_path: &str,
         _filename: &str,
         _max_size: Option<u32>,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         Self::analyze_with_storage_client(client, file_path).await
     }
 
@@ -27,7 +28,7 @@ impl CompressionHandlerDispatcher for TarHandler {
         offset: Option<u64>,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         Self::extract_tar_preview_with_progress(
             client,
             file_path,
@@ -59,14 +60,14 @@ impl TarHandler {
     async fn analyze_with_storage_client(
         client: Arc<dyn StorageClient>,
         file_path: &str,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         log::debug!("TAR流式分析开始: {}", file_path);
 
         // 获取文件大小
         let file_size = client
             .get_file_size(file_path)
             .await
-            .map_err(|e| format!("Failed to get file size: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
 
         log::debug!("TAR文件大小: {} 字节", file_size);
 
@@ -99,7 +100,7 @@ impl TarHandler {
         offset: Option<u64>,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         log::debug!(
             "开始流式提取TAR文件预览（带进度）: {} -> {}",
             file_path,
@@ -109,7 +110,7 @@ impl TarHandler {
         let file_size = client
             .get_file_size(file_path)
             .await
-            .map_err(|e| format!("Failed to get file size: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
 
         let mut current_offset = 0u64;
         let mut index: u32 = 0;
@@ -121,7 +122,7 @@ impl TarHandler {
             // 检查取消信号
             if let Some(ref mut cancel_rx) = cancel_rx {
                 if let Ok(_) = cancel_rx.try_recv() {
-                    return Err("download.cancelled".to_string());
+                    return Err(AppError::cancelled());
                 }
             }
 
@@ -142,8 +143,11 @@ impl TarHandler {
                     data
                 }
                 Err(e) => {
-                    log::warn!("流式读取TAR头部失败，位置 {}: {}", current_offset, e);
-                    break;
+                    // 读取失败时不能当作文件结束，否则会返回不完整的结果
+                    return Err(AppError::from(e).context(format!(
+                        "Failed to read TAR header at offset {}",
+                        current_offset
+                    )));
                 }
             };
 
@@ -157,7 +161,7 @@ impl TarHandler {
                 // 检查是否是我们要找的文件
                 if entry_info.path == entry_path {
                     if entry_info.is_dir {
-                        return Err("Cannot preview directory".to_string());
+                        return Err(AppError::invalid_argument("Cannot preview directory"));
                     }
 
                     // 找到了目标文件，分块读取其内容
@@ -191,7 +195,7 @@ impl TarHandler {
                             // 检查取消信号
                             if let Some(ref mut cancel_rx) = cancel_rx {
                                 if let Ok(_) = cancel_rx.try_recv() {
-                                    return Err("download.cancelled".to_string());
+                                    return Err(AppError::cancelled());
                                 }
                             }
 
@@ -206,7 +210,9 @@ impl TarHandler {
                                     current_chunk_size,
                                 )
                                 .await
-                                .map_err(|e| format!("Failed to read file content chunk: {}", e))?;
+                                .map_err(|e| {
+                                    AppError::from(e).context("Failed to read file content chunk")
+                                })?;
 
                             all_data.extend_from_slice(&chunk);
                             read_offset_in_chunk += chunk.len() as u64;
@@ -223,7 +229,7 @@ impl TarHandler {
                         client
                             .read_file_range(file_path, actual_file_offset, preview_size as u64)
                             .await
-                            .map_err(|e| format!("Failed to read file content: {}", e))?
+                            .map_err(|e| AppError::from(e).context("Failed to read file content"))?
                     };
 
                     let _mime_type = detect_mime_type(&content_data);
@@ -259,7 +265,7 @@ impl TarHandler {
             }
         }
 
-        Err("File not found in TAR archive".to_string())
+        Err(AppError::not_found("File not found in TAR archive"))
     }
 
     /// 流式分析TAR文件，逐块读取头部信息
@@ -273,7 +279,7 @@ impl TarHandler {
         client: Arc<dyn StorageClient>,
         file_path: &str,
         file_size: u64,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         log::debug!("开始流式分析TAR文件: {} ({} 字节)", file_path, file_size);
 
         let mut entries = Vec::new();
@@ -298,8 +304,11 @@ impl TarHandler {
                     data
                 }
                 Err(e) => {
-                    log::warn!("流式读取TAR头部失败，位置 {}: {}", current_offset, e);
-                    break;
+                    // 读取失败时不能当作文件结束，否则会返回不完整的结果
+                    return Err(AppError::from(e).context(format!(
+                        "Failed to read TAR header at offset {}",
+                        current_offset
+                    )));
                 }
             };
 
@@ -354,9 +363,9 @@ impl TarHandler {
     }
 
     /// 解析TAR头部信息
-    fn parse_tar_header(header: &[u8], index: u32) -> Result<ArchiveEntry, String> {
+    fn parse_tar_header(header: &[u8], index: u32) -> Result<ArchiveEntry, AppError> {
         if header.len() < 512 {
-            return Err("Header too short".to_string());
+            return Err(AppError::internal("Header too short"));
         }
 
         // 提取文件名（前100字节，以null结尾）
@@ -365,7 +374,7 @@ impl TarHandler {
         let name = String::from_utf8_lossy(&name_bytes[..name_end]).to_string();
 
         if name.is_empty() {
-            return Err("Empty file name".to_string());
+            return Err(AppError::internal("Empty file name"));
         }
 
         // 提取文件大小（八进制字符串，位置124-135）
@@ -373,7 +382,7 @@ impl TarHandler {
         let size_string = String::from_utf8_lossy(size_bytes);
         let size_str = size_string.trim_end_matches('\0').trim();
         let size = u64::from_str_radix(size_str, 8)
-            .map_err(|_| format!("Invalid size field: {}", size_str))?;
+            .map_err(|_| AppError::internal(format!("Invalid size field: {}", size_str)))?;
 
         // 提取修改时间（八进制字符串，位置136-147）
         let mtime_bytes = &header[136..148];
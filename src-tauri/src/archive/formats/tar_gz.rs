@@ -1,12 +1,17 @@
 use crate::archive::formats::{common::*, CompressionHandlerDispatcher};
 /// TAR.GZ 格式处理器（组合GZIP和TAR）
 use crate::archive::types::*;
-use crate::storage::traits::{ProgressCallback, StorageClient};
+use crate::error::AppError;
+use crate::storage::traits::{ByteStream, StorageClient, StorageError};
+use crate::utils::stream_io::StreamIo;
 use flate2::read::GzDecoder;
+use futures_util::StreamExt;
 use std::collections::HashMap;
 use std::io::{Cursor, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tar::Archive;
+use tokio_util::io::SyncIoBridge;
 pub struct TarGzHandler;
 
 #[async_trait::async_trait]
@@ -17,7 +22,7 @@ impl CompressionHandlerDispatcher for TarGzHandler {
         file_path: &str,
         _filename: &str,
         _max_size: Option<u32>,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         Self::analyze_with_storage_client(client, file_path).await
     }
 
@@ -30,7 +35,7 @@ impl CompressionHandlerDispatcher for TarGzHandler {
         offset: Option<u64>,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         Self::extract_tar_gz_preview_with_progress(
             client,
             file_path,
@@ -57,7 +62,7 @@ impl TarGzHandler {
     async fn analyze_with_storage_client(
         client: Arc<dyn StorageClient>,
         file_path: &str,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         log::debug!("使用storage client流式分析TAR.GZ文件: {}", file_path);
 
         Self::analyze_tar_gz_streaming(client, file_path).await
@@ -67,25 +72,29 @@ impl TarGzHandler {
     async fn analyze_tar_gz_streaming(
         client: Arc<dyn StorageClient>,
         file_path: &str,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         log::debug!("开始流式分析TAR.GZ文件: {}", file_path);
 
         let file_size = client
             .get_file_size(file_path)
             .await
-            .map_err(|e| format!("Failed to get file size: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
 
         log::info!(
             "TAR.GZ文件大小: {:.2} MB",
             file_size as f64 / (1024.0 * 1024.0)
         );
 
-        let data = client
-            .read_full_file(file_path)
+        let stream = client
+            .open_read_stream(file_path, 0)
             .await
-            .map_err(|e| format!("Failed to read file: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to open file stream"))?;
 
-        Self::analyze_tar_gz_complete(&data)
+        // 在阻塞线程中边读取边解压，内存占用与文件大小无关
+        let reader = SyncIoBridge::new(StreamIo::into_async_read(stream));
+        tokio::task::spawn_blocking(move || Self::analyze_tar_gz_reader(reader, file_size))
+            .await
+            .map_err(|e| AppError::internal(format!("TAR.GZ analysis task failed: {}", e)))?
     }
 
     /// 流式提取TAR.GZ文件预览（支持进度回调和取消信号）
@@ -97,7 +106,7 @@ impl TarGzHandler {
         _offset: Option<u64>, // TAR.GZ 格式不支持偏移量
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         log::debug!(
             "开始流式提取TAR.GZ预览（带进度）: {} -> {}",
             file_path,
@@ -107,53 +116,117 @@ impl TarGzHandler {
         let file_size = client
             .get_file_size(file_path)
             .await
-            .map_err(|e| format!("Failed to get file size: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
 
         log::info!(
             "TAR.GZ文件大小: {:.2} MB",
             file_size as f64 / (1024.0 * 1024.0)
         );
 
-        // 直接读取全部数据，避免人为分块导致的性能问题
-        let progress_cb = progress_callback.map(|cb| {
-            Arc::new(move |current: u64, total: u64| {
-                cb(current, total);
-            }) as ProgressCallback
+        let stream = client
+            .open_read_stream(file_path, 0)
+            .await
+            .map_err(|e| AppError::from(e).context("Failed to open file stream"))?;
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let stream = Self::track_stream(stream, file_size, progress_callback, cancelled.clone());
+
+        let reader = SyncIoBridge::new(StreamIo::into_async_read(stream));
+        let entry_path_owned = entry_path.to_string();
+        let task = tokio::task::spawn_blocking(move || {
+            Self::extract_tar_gz_preview_from_reader(reader, &entry_path_owned, max_size)
         });
 
-        let data = client
-            .read_full_file_with_progress(file_path, progress_cb, cancel_rx)
-            .await
-            .map_err(|e| format!("Failed to read file: {}", e))?;
+        let result = match cancel_rx {
+            Some(cancel_rx) => {
+                tokio::select! {
+                    result = task => result,
+                    Ok(_) = cancel_rx.recv() => {
+                        // 通知读取流停止，阻塞线程会在下一个数据块时退出
+                        cancelled.store(true, Ordering::Relaxed);
+                        return Err(AppError::cancelled());
+                    }
+                }
+            }
+            None => task.await,
+        };
 
-        Self::extract_tar_gz_preview_from_data(&data, entry_path, max_size)
+        result.map_err(|e| AppError::internal(format!("TAR.GZ preview task failed: {}", e)))?
     }
 
-    /// 完整分析TAR.GZ文件（用于小文件）
-    fn analyze_tar_gz_complete(data: &[u8]) -> Result<ArchiveInfo, String> {
-        log::debug!("开始分析TAR.GZ文件，数据长度: {} 字节", data.len());
+    /// 以数据流的方式读取条目内容，从 start 处开始按 chunk_size 分块输出，内存占用与条目大小无关
+    pub async fn open_entry_stream(
+        client: Arc<dyn StorageClient>,
+        file_path: &str,
+        entry_path: &str,
+        start: u64,
+        chunk_size: u64,
+    ) -> Result<ByteStream, StorageError> {
+        let stream = client.open_read_stream(file_path, 0).await?;
+        let reader = SyncIoBridge::new(StreamIo::into_async_read(stream));
+        let entry_path = entry_path.to_string();
+
+        Ok(StreamIo::spawn_blocking_stream(move |tx| {
+            let mut tar_archive = Archive::new(GzDecoder::new(reader));
+            for entry_result in tar_archive.entries().map_err(StreamIo::storage_error)? {
+                let entry = entry_result.map_err(StreamIo::storage_error)?;
+                let path = entry.path().map_err(StreamIo::storage_error)?;
+                if path.to_string_lossy() == entry_path {
+                    return StreamIo::send_from_reader(entry, start, chunk_size, tx);
+                }
+            }
+            Err(StorageError::NotFound(format!(
+                "File not found in archive: {}",
+                entry_path
+            )))
+        }))
+    }
 
-        if !Self::validate_tar_gz_header(data) {
-            return Err("Invalid TAR.GZ header".to_string());
-        }
+    /// 为数据流添加进度回调和取消检查
+    fn track_stream(
+        mut stream: ByteStream,
+        file_size: u64,
+        progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
+        cancelled: Arc<AtomicBool>,
+    ) -> ByteStream {
+        Box::pin(async_stream::stream! {
+            let mut read = 0u64;
+
+            while let Some(chunk) = stream.next().await {
+                if cancelled.load(Ordering::Relaxed) {
+                    yield Err(StorageError::Cancelled);
+                    break;
+                }
+
+                if let Ok(ref bytes) = chunk {
+                    read += bytes.len() as u64;
+                    if let Some(ref callback) = progress_callback {
+                        callback(read, file_size);
+                    }
+                }
+
+                yield chunk;
+            }
+        })
+    }
+
+    /// 从读取器中逐条解析TAR.GZ条目（只读取头部，条目内容直接跳过）
+    fn analyze_tar_gz_reader<R: Read>(reader: R, file_size: u64) -> Result<ArchiveInfo, AppError> {
+        log::debug!("开始分析TAR.GZ文件，压缩数据长度: {} 字节", file_size);
 
         // 解压缩GZIP数据
-        let gz_decoder = GzDecoder::new(Cursor::new(data));
+        let gz_decoder = GzDecoder::new(reader);
         let mut tar_archive = Archive::new(gz_decoder);
 
         let mut entries = Vec::new();
         let mut total_uncompressed_size = 0;
 
-        for (index, entry_result) in tar_archive
-            .entries()
-            .map_err(|e| e.to_string())?
-            .enumerate()
-        {
+        for (index, entry_result) in tar_archive.entries().map_err(Self::read_error)?.enumerate() {
             match entry_result {
                 Ok(entry) => {
                     let header = entry.header();
-                    let path = entry.path().map_err(|e| e.to_string())?;
-                    let size = header.size().map_err(|e| e.to_string())?;
+                    let path = entry.path().map_err(AppError::from)?;
+                    let size = header.size().map_err(AppError::from)?;
                     let is_dir = header.entry_type().is_dir();
 
                     total_uncompressed_size += size;
@@ -178,8 +251,9 @@ impl TarGzHandler {
                     });
                 }
                 Err(e) => {
-                    log::warn!("Failed to read TAR.GZ entry {}: {}", index, e);
-                    continue;
+                    // 数据流中途出错（网络错误、取消等）时不能返回不完整的条目列表
+                    return Err(Self::read_error(e)
+                        .context(format!("Failed to read TAR.GZ entry {}", index)));
                 }
             }
         }
@@ -187,46 +261,37 @@ impl TarGzHandler {
         Ok(ArchiveInfoBuilder::new(CompressionType::TarGz)
             .entries(entries)
             .total_uncompressed_size(total_uncompressed_size)
-            .total_compressed_size(data.len() as u64)
+            .total_compressed_size(file_size)
             .supports_streaming(true)
             .supports_random_access(false)
             .analysis_status(AnalysisStatus::Complete)
             .build())
     }
 
-    /// 从TAR.GZ数据中提取文件预览（用于小文件）
-    fn extract_tar_gz_preview_from_data(
-        data: &[u8],
+    /// 从读取器中提取TAR.GZ文件预览，找到目标条目后只读取预览所需的数据
+    fn extract_tar_gz_preview_from_reader<R: Read>(
+        reader: R,
         entry_path: &str,
         max_size: usize,
-    ) -> Result<FilePreview, String> {
-        let gz_decoder = GzDecoder::new(Cursor::new(data));
+    ) -> Result<FilePreview, AppError> {
+        let gz_decoder = GzDecoder::new(reader);
         let mut tar_archive = Archive::new(gz_decoder);
 
-        for entry_result in tar_archive.entries().map_err(|e| e.to_string())? {
+        for entry_result in tar_archive.entries().map_err(Self::read_error)? {
             match entry_result {
-                Ok(mut entry) => {
-                    let path = entry.path().map_err(|e| e.to_string())?;
+                Ok(entry) => {
+                    let path = entry.path().map_err(AppError::from)?;
                     if path.to_string_lossy() == entry_path {
-                        let total_size = entry.header().size().map_err(|e| e.to_string())?;
+                        let total_size = entry.header().size().map_err(AppError::from)?;
 
-                        // 读取完整文件内容，然后截取预览部分（参考ZIP格式的处理方式）
-                        let mut full_content = Vec::new();
+                        // 只读取预览大小的数据
+                        let mut preview_data = Vec::new();
                         entry
-                            .read_to_end(&mut full_content)
-                            .map_err(|e| e.to_string())?;
-
-                        // 保存完整内容长度
-                        let full_content_len = full_content.len();
+                            .take(max_size as u64)
+                            .read_to_end(&mut preview_data)
+                            .map_err(Self::read_error)?;
 
-                        // 截取预览数据
-                        let preview_data = if full_content_len > max_size {
-                            full_content[..max_size].to_vec()
-                        } else {
-                            full_content
-                        };
-
-                        let is_truncated = preview_data.len() < full_content_len;
+                        let is_truncated = (preview_data.len() as u64) < total_size;
 
                         return Ok(PreviewBuilder::new()
                             .content(preview_data)
@@ -235,11 +300,19 @@ impl TarGzHandler {
                             .build());
                     }
                 }
-                Err(_) => continue,
+                Err(e) => {
+                    // 取消或读取失败时底层流会持续报错，直接返回原始错误
+                    return Err(Self::read_error(e).context("Failed to read TAR.GZ archive"));
+                }
             }
         }
 
-        Err("File not found in archive".to_string())
+        Err(AppError::not_found("File not found in archive"))
+    }
+
+    /// 读取器错误中保留着数据流的存储错误，取消、超时等错误码原样返回
+    fn read_error(error: std::io::Error) -> AppError {
+        AppError::from(StreamIo::storage_error(error))
     }
 
     // 辅助方法
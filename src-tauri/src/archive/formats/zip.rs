@@ -1,6 +1,7 @@
 use crate::archive::formats::{common::*, CompressionHandlerDispatcher};
 /// ZIP 格式处理器
 use crate::archive::types::*;
+use crate::error::AppError;
 use crate::storage::traits::StorageClient;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -15,7 +16,7 @@ impl CompressionHandlerDispatcher for ZipHandler {
         file_path: &str,
         _filename: &str,
         _max_size: Option<u32>,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         Self::analyze_with_storage_client(client, file_path).await
     }
 
@@ -28,7 +29,7 @@ impl CompressionHandlerDispatcher for ZipHandler {
         offset: Option<u64>,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         Self::extract_zip_preview_with_progress(
             client,
             file_path,
@@ -58,12 +59,12 @@ impl ZipHandler {
     async fn analyze_with_storage_client(
         client: Arc<dyn StorageClient>,
         file_path: &str,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         // 获取文件大小
         let file_size = client
             .get_file_size(file_path)
             .await
-            .map_err(|e| format!("Failed to get file size: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
 
         // 调用现有的分析方法
         Self::analyze_zip_with_client(client, file_path, file_size).await
@@ -138,10 +139,10 @@ impl ZipHandler {
         zip64_locator_pos: usize,
         _file_size: u64,
         start_pos: u64,
-    ) -> Result<(u64, u64, u64), String> {
+    ) -> Result<(u64, u64, u64), AppError> {
         // 从ZIP64 EOCD定位器中读取ZIP64 EOCD记录的偏移量
         if zip64_locator_pos + 16 > footer_data.len() {
-            return Err("ZIP64 EOCD locator data insufficient".to_string());
+            return Err(AppError::internal("ZIP64 EOCD locator data insufficient"));
         }
 
         let zip64_eocd_offset = u64::from_le_bytes([
@@ -166,7 +167,7 @@ impl ZipHandler {
                 let zip64_data = client
                     .read_file_range(file_path, zip64_eocd_offset, 56)
                     .await
-                    .map_err(|e| format!("Failed to read ZIP64 EOCD record: {}", e))?;
+                    .map_err(|e| AppError::from(e).context("Failed to read ZIP64 EOCD record"))?;
                 return Self::parse_zip64_eocd_data(&zip64_data);
             }
         } else {
@@ -174,7 +175,7 @@ impl ZipHandler {
             let zip64_data = client
                 .read_file_range(file_path, zip64_eocd_offset, 56)
                 .await
-                .map_err(|e| format!("Failed to read ZIP64 EOCD record: {}", e))?;
+                .map_err(|e| AppError::from(e).context("Failed to read ZIP64 EOCD record"))?;
             return Self::parse_zip64_eocd_data(&zip64_data);
         };
 
@@ -182,18 +183,18 @@ impl ZipHandler {
     }
 
     /// 解析ZIP64 EOCD记录数据
-    fn parse_zip64_eocd_data(data: &[u8]) -> Result<(u64, u64, u64), String> {
+    fn parse_zip64_eocd_data(data: &[u8]) -> Result<(u64, u64, u64), AppError> {
         if data.len() < 56 {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "ZIP64 EOCD record too short: {} bytes, need 56",
                 data.len()
-            ));
+            )));
         }
 
         // 检查ZIP64 EOCD签名
         let zip64_eocd_signature = [0x50, 0x4b, 0x06, 0x06];
         if data[0..4] != zip64_eocd_signature {
-            return Err("Invalid ZIP64 EOCD signature".to_string());
+            return Err(AppError::internal("Invalid ZIP64 EOCD signature"));
         }
 
         // 解析ZIP64 EOCD记录字段
@@ -367,7 +368,7 @@ impl ZipHandler {
     fn parse_central_directory_optimized(
         cd_data: &[u8],
         total_entries: u64,
-    ) -> Result<Vec<ArchiveEntry>, String> {
+    ) -> Result<Vec<ArchiveEntry>, AppError> {
         // 使用优化的解析逻辑
         Self::parse_central_directory(cd_data, total_entries)
     }
@@ -376,7 +377,7 @@ impl ZipHandler {
     fn parse_central_directory(
         cd_data: &[u8],
         total_entries: u64,
-    ) -> Result<Vec<ArchiveEntry>, String> {
+    ) -> Result<Vec<ArchiveEntry>, AppError> {
         const CD_HEADER_SIGNATURE: u32 = 0x02014b50;
         const MIN_CD_HEADER_SIZE: usize = 46;
         const MAX_FIELD_SIZE: usize = 65535;
@@ -401,10 +402,10 @@ impl ZipHandler {
             ]);
 
             if signature != CD_HEADER_SIGNATURE {
-                return Err(format!(
+                return Err(AppError::internal(format!(
                     "Invalid central directory file header signature: 0x{:08x}, expected: 0x{:08x}",
                     signature, CD_HEADER_SIGNATURE
-                ));
+                )));
             }
 
             let compressed_size_32 = u32::from_le_bytes([
@@ -435,18 +436,18 @@ impl ZipHandler {
                 || extra_len > MAX_FIELD_SIZE
                 || comment_len > MAX_FIELD_SIZE
             {
-                return Err(format!("Abnormal central directory entry field length: filename={}, extra={}, comment={}", filename_len, extra_len, comment_len));
+                return Err(AppError::internal(format!("Abnormal central directory entry field length: filename={}, extra={}, comment={}", filename_len, extra_len, comment_len)));
             }
 
             // 检查总的记录大小是否合理
             let total_record_size = MIN_CD_HEADER_SIZE + filename_len + extra_len + comment_len;
             if offset + total_record_size > cd_data.len() {
-                return Err(format!(
+                return Err(AppError::internal(format!(
                     "Central directory entry exceeds data range: offset={}, size={}, data_len={}",
                     offset,
                     total_record_size,
                     cd_data.len()
-                ));
+                )));
             }
 
             if filename_len == 0 {
@@ -504,10 +505,10 @@ impl ZipHandler {
         }
 
         if parsed_entries != total_entries && parsed_entries < max_entries {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Parsed entry count ({}) does not match expected count ({})",
                 parsed_entries, total_entries
-            ));
+            )));
         }
 
         Ok(entries)
@@ -516,7 +517,7 @@ impl ZipHandler {
     fn find_file_in_central_directory(
         cd_data: &[u8],
         target_path: &str,
-    ) -> Result<Option<ZipFileInfo>, String> {
+    ) -> Result<Option<ZipFileInfo>, AppError> {
         let mut offset = 0;
 
         while offset + 46 <= cd_data.len() {
@@ -612,7 +613,7 @@ impl ZipHandler {
         client: Arc<dyn StorageClient>,
         file_path: &str,
         file_size: u64,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         const MIN_ZIP_SIZE: u64 = 22; // 最小ZIP文件大小（EOCD记录）
         const MAX_FOOTER_SIZE: u64 = 65536; // 最多读取64KB的文件尾部
         const MAX_ZIP_SIZE: u64 = 500 * 1024 * 1024 * 1024; // 500GB文件大小限制
@@ -621,18 +622,18 @@ impl ZipHandler {
 
         // 检查文件大小是否足够
         if file_size < MIN_ZIP_SIZE {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "File too small to be a valid ZIP file ({} bytes < {} bytes)",
                 file_size, MIN_ZIP_SIZE
-            ));
+            )));
         }
 
         // 检查最大文件大小限制（防止处理过大的文件）
         if file_size > MAX_ZIP_SIZE {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "ZIP file too large: {} bytes, exceeds 10GB limit",
                 file_size
-            ));
+            )));
         }
 
         // 读取文件末尾来查找中央目录
@@ -642,26 +643,26 @@ impl ZipHandler {
         let footer_data = client
             .read_file_range(file_path, start_pos, footer_size)
             .await
-            .map_err(|e| format!("Failed to read file footer: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read file footer"))?;
 
         if footer_data.len() != footer_size as usize {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Read data length mismatch: expected {}, actual {}",
                 footer_size,
                 footer_data.len()
-            ));
+            )));
         }
 
         // 查找EOCD记录
         let eocd_pos = Self::find_eocd(&footer_data)
-            .ok_or_else(|| "Could not find EOCD record in ZIP file, file may be corrupted or not a valid ZIP file".to_string())?;
+            .ok_or_else(|| AppError::internal("Could not find EOCD record in ZIP file, file may be corrupted or not a valid ZIP file"))?;
 
         let eocd_data = &footer_data[eocd_pos..];
         if eocd_data.len() < 22 {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Insufficient EOCD record length: only {} bytes, need 22 bytes",
                 eocd_data.len()
-            ));
+            )));
         }
 
         // 解析EOCD记录
@@ -673,25 +674,25 @@ impl ZipHandler {
 
         // 验证条目数量的合理性
         if total_entries > MAX_ENTRIES {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Too many entries in ZIP file: {}, exceeds {} limit",
                 total_entries, MAX_ENTRIES
-            ));
+            )));
         }
 
         if cd_size > file_size {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Central directory size ({}) exceeds file size ({})",
                 cd_size, file_size
-            ));
+            )));
         }
 
         // 验证中央目录大小的合理性
         if cd_size > MAX_CD_SIZE {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Central directory too large: {} bytes, exceeds 500MB limit",
                 cd_size
-            ));
+            )));
         }
 
         // 检查是否需要处理ZIP64格式
@@ -713,24 +714,23 @@ impl ZipHandler {
 
                 // 验证ZIP64解析结果的合理性
                 if zip64_result.1 > MAX_CD_SIZE {
-                    return Err(format!(
+                    return Err(AppError::internal(format!(
                         "ZIP64 central directory too large: {} bytes, exceeds 500MB limit",
                         zip64_result.1
-                    ));
+                    )));
                 }
                 if zip64_result.2 > MAX_ENTRIES {
-                    return Err(format!(
+                    return Err(AppError::internal(format!(
                         "Too many files in ZIP64: {} files, exceeds {} limit",
                         zip64_result.2, MAX_ENTRIES
-                    ));
+                    )));
                 }
 
                 zip64_result
             } else {
-                return Err(
-                    "ZIP64 format detected but ZIP64 EOCD locator not found, file may be corrupted"
-                        .to_string(),
-                );
+                return Err(AppError::internal(
+                    "ZIP64 format detected but ZIP64 EOCD locator not found, file may be corrupted",
+                ));
             }
         } else {
             (cd_offset_32 as u64, cd_size, total_entries)
@@ -738,32 +738,32 @@ impl ZipHandler {
 
         // 验证中央目录偏移量的合理性
         if cd_offset >= file_size {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Central directory offset ({}) exceeds file range ({})",
                 cd_offset, file_size
-            ));
+            )));
         }
 
         if cd_offset + cd_size > file_size {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Central directory end position ({}) exceeds file range ({})",
                 cd_offset + cd_size,
                 file_size
-            ));
+            )));
         }
 
         // 读取中央目录
         let cd_data = client
             .read_file_range(file_path, cd_offset, cd_size)
             .await
-            .map_err(|e| format!("Failed to read central directory: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read central directory"))?;
 
         if cd_data.len() != cd_size as usize {
-            return Err(format!(
+            return Err(AppError::internal(format!(
                 "Central directory data length mismatch: expected {}, actual {}",
                 cd_size,
                 cd_data.len()
-            ));
+            )));
         }
 
         // 使用优化的解析方法
@@ -792,17 +792,17 @@ impl ZipHandler {
         offset: Option<u64>,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         // 先找到文件信息
         let file_size = client
             .get_file_size(file_path)
             .await
-            .map_err(|e| format!("Failed to get file size: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
 
         let file_info =
             Self::find_file_in_zip_with_client(client.clone(), file_path, file_size, entry_path)
                 .await?
-                .ok_or_else(|| "File not found in archive".to_string())?;
+                .ok_or_else(|| AppError::not_found("File not found in archive"))?;
 
         // 空文件直接返回
         if file_info.compressed_size == 0 {
@@ -835,7 +835,7 @@ impl ZipHandler {
         offset: Option<u64>,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         let offset_val = offset.unwrap_or(0);
 
         // 获取数据偏移量（跳过本地文件头）
@@ -874,10 +874,10 @@ impl ZipHandler {
                 )
                 .await
             }
-            _ => Err(format!(
+            _ => Err(AppError::internal(format!(
                 "Unsupported compression method: {}",
                 file_info.compression_method
-            )),
+            ))),
         }
     }
 
@@ -891,7 +891,7 @@ impl ZipHandler {
         max_size: usize,
         progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         let actual_offset = offset_val.min(total_size);
         let remaining = total_size - actual_offset;
         let read_size = (max_size as u64).min(remaining);
@@ -919,7 +919,7 @@ impl ZipHandler {
                 cancel_rx.take(),
             )
             .await
-            .map_err(|e| format!("Failed to read uncompressed data: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read uncompressed data"))?;
 
         let is_truncated = actual_offset + (data.len() as u64) < total_size;
 
@@ -940,7 +940,7 @@ impl ZipHandler {
         max_size: usize,
         _progress_callback: Option<Box<dyn Fn(u64, u64) + Send + Sync>>,
         _cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String> {
+    ) -> Result<FilePreview, AppError> {
         // 智能估算初始读取大小
         let estimated_compressed_needed =
             Self::estimate_compressed_size_needed(max_size, offset_val);
@@ -969,7 +969,7 @@ impl ZipHandler {
             let compressed_data = client
                 .read_file_range(file_path, data_offset, target_size)
                 .await
-                .map_err(|e| format!("Failed to read compressed data: {}", e))?;
+                .map_err(|e| AppError::from(e).context("Failed to read compressed data"))?;
 
             println!(
                 "第{}次尝试: 读取{}字节压缩数据",
@@ -1020,7 +1020,10 @@ impl ZipHandler {
                             .total_size(0)
                             .build());
                     }
-                    return Err(format!("Deflate decompression failed: {}", e));
+                    return Err(AppError::internal(format!(
+                        "Deflate decompression failed: {}",
+                        e
+                    )));
                 }
             }
         }
@@ -1035,7 +1038,9 @@ impl ZipHandler {
                 .build());
         }
 
-        Err("Failed to decompress deflate data with optimized strategy".to_string())
+        Err(AppError::internal(
+            "Failed to decompress deflate data with optimized strategy",
+        ))
     }
 
     /// 估算获取指定大小输出所需的压缩数据量
@@ -1061,7 +1066,7 @@ impl ZipHandler {
         compressed_data: &[u8],
         offset: u64,
         max_output_size: usize,
-    ) -> Result<Vec<u8>, String> {
+    ) -> Result<Vec<u8>, AppError> {
         use flate2::read::DeflateDecoder;
         use std::io::{Cursor, Read};
 
@@ -1078,7 +1083,9 @@ impl ZipHandler {
                 match decoder.read(&mut skip_buffer[..to_read]) {
                     Ok(0) => break, // EOF
                     Ok(n) => remaining_skip -= n,
-                    Err(e) => return Err(format!("Error skipping bytes: {}", e)),
+                    Err(e) => {
+                        return Err(AppError::internal(format!("Error skipping bytes: {}", e)))
+                    }
                 }
             }
         }
@@ -1105,7 +1112,7 @@ impl ZipHandler {
                         max_output_size
                     );
                 }
-                Err(e) => return Err(format!("Decompression error: {}", e)),
+                Err(e) => return Err(AppError::internal(format!("Decompression error: {}", e))),
             }
         }
 
@@ -1128,15 +1135,15 @@ impl ZipHandler {
         client: Arc<dyn StorageClient>,
         file_path: &str,
         local_header_offset: u64,
-    ) -> Result<u64, String> {
+    ) -> Result<u64, AppError> {
         // Read local file header fixed part (30 bytes)
         let local_header = client
             .read_file_range(file_path, local_header_offset, 30)
             .await
-            .map_err(|e| format!("Failed to read local header: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read local header"))?;
 
         if local_header.len() < 30 {
-            return Err("Invalid local header".to_string());
+            return Err(AppError::internal("Invalid local header"));
         }
 
         // Extract filename and extra field lengths
@@ -1153,7 +1160,7 @@ impl ZipHandler {
         file_path: &str,
         file_size: u64,
         target_path: &str,
-    ) -> Result<Option<ZipFileInfo>, String> {
+    ) -> Result<Option<ZipFileInfo>, AppError> {
         // Read file footer to find central directory
         let footer_size = std::cmp::min(65536, file_size);
         let start_pos = file_size.saturating_sub(footer_size);
@@ -1161,14 +1168,14 @@ impl ZipHandler {
         let footer_data = client
             .read_file_range(file_path, start_pos, footer_size)
             .await
-            .map_err(|e| format!("Failed to read file footer: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read file footer"))?;
 
         let eocd_pos = Self::find_eocd(&footer_data)
             .ok_or("Could not find End of Central Directory record")?;
 
         let eocd_data = &footer_data[eocd_pos..];
         if eocd_data.len() < 22 {
-            return Err("Invalid EOCD record".to_string());
+            return Err(AppError::internal("Invalid EOCD record"));
         }
 
         let cd_size =
@@ -1194,15 +1201,17 @@ impl ZipHandler {
 
                 (zip64_cd_offset, zip64_cd_size)
             } else {
-                return Err("ZIP64 format detected but ZIP64 EOCD locator not found".to_string());
+                return Err(AppError::internal(
+                    "ZIP64 format detected but ZIP64 EOCD locator not found",
+                ));
             }
         } else {
             // Check if offset is reasonable
             if (cd_offset as u64) >= file_size {
-                return Err(format!(
+                return Err(AppError::internal(format!(
                     "Invalid central directory offset: {} >= file size {}",
                     cd_offset, file_size
-                ));
+                )));
             }
             (cd_offset as u64, cd_size as u64)
         };
@@ -1211,7 +1220,7 @@ impl ZipHandler {
         let cd_data = client
             .read_file_range(file_path, final_cd_offset, final_cd_size)
             .await
-            .map_err(|e| format!("Failed to read central directory: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to read central directory"))?;
 
         Self::find_file_in_central_directory(&cd_data, target_path)
     }
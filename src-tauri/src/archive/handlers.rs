@@ -1,4 +1,5 @@
 use crate::archive::{formats, types::*};
+use crate::error::{AppError, ErrorCode};
 use crate::storage::traits::StorageClient;
 use std::sync::Arc;
 
@@ -17,25 +18,40 @@ impl ArchiveHandler {
         file_path: String,
         filename: String,
         max_size: Option<u32>,
-    ) -> Result<ArchiveInfo, String> {
+    ) -> Result<ArchiveInfo, AppError> {
         let compression_type = CompressionType::from_filename(&filename);
 
         // 检查是否支持该格式
         match compression_type {
             CompressionType::SevenZip => {
-                return Err("archive.format.7z.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.7z.not.supported",
+                ));
             }
             CompressionType::Rar => {
-                return Err("archive.format.rar.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.rar.not.supported",
+                ));
             }
             CompressionType::Brotli => {
-                return Err("archive.format.brotli.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.brotli.not.supported",
+                ));
             }
             CompressionType::Lz4 => {
-                return Err("archive.format.lz4.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.lz4.not.supported",
+                ));
             }
             CompressionType::Zstd => {
-                return Err("archive.format.zstd.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.zstd.not.supported",
+                ));
             }
             _ => {}
         }
@@ -45,12 +61,11 @@ impl ArchiveHandler {
             let header_data = client
                 .read_file_range(&file_path, 0, 512)
                 .await
-                .map_err(|e| format!("Failed to read file header: {}", e))?;
+                .map_err(|e| AppError::from(e).context("Failed to read file header"))?;
             formats::detect_format_and_get_handler(&header_data)
-                .ok_or_else(|| "Unsupported archive format".to_string())?
+                .ok_or_else(Self::unsupported_format)?
         } else {
-            formats::get_handler(&compression_type)
-                .ok_or_else(|| "Unsupported archive format".to_string())?
+            formats::get_handler(&compression_type).ok_or_else(Self::unsupported_format)?
         };
 
         // 通过 StorageClient 进行流式分析
@@ -70,7 +85,7 @@ impl ArchiveHandler {
         offset: Option<u64>,
         progress_callback: Option<F>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
-    ) -> Result<FilePreview, String>
+    ) -> Result<FilePreview, AppError>
     where
         F: Fn(u64, u64) + Send + Sync + 'static,
     {
@@ -79,19 +94,34 @@ impl ArchiveHandler {
         // 检查是否支持该格式
         match compression_type {
             CompressionType::SevenZip => {
-                return Err("archive.format.7z.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.7z.not.supported",
+                ));
             }
             CompressionType::Rar => {
-                return Err("archive.format.rar.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.rar.not.supported",
+                ));
             }
             CompressionType::Brotli => {
-                return Err("archive.format.brotli.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.brotli.not.supported",
+                ));
             }
             CompressionType::Lz4 => {
-                return Err("archive.format.lz4.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.lz4.not.supported",
+                ));
             }
             CompressionType::Zstd => {
-                return Err("archive.format.zstd.not.supported".to_string());
+                return Err(AppError::i18n(
+                    ErrorCode::Unsupported,
+                    "archive.format.zstd.not.supported",
+                ));
             }
             _ => {}
         }
@@ -100,12 +130,11 @@ impl ArchiveHandler {
             let header_data = client
                 .read_file_range(&file_path, 0, 512)
                 .await
-                .map_err(|e| format!("Failed to read file header: {}", e))?;
+                .map_err(|e| AppError::from(e).context("Failed to read file header"))?;
             formats::detect_format_and_get_handler(&header_data)
-                .ok_or_else(|| "Unsupported archive format".to_string())?
+                .ok_or_else(Self::unsupported_format)?
         } else {
-            formats::get_handler(&compression_type)
-                .ok_or_else(|| "Unsupported archive format".to_string())?
+            formats::get_handler(&compression_type).ok_or_else(Self::unsupported_format)?
         };
 
         // 如果没有指定大小限制，使用尽可能大的限制（用于下载完整文件）
@@ -130,4 +159,8 @@ impl ArchiveHandler {
     }
 
     // 辅助方法
+
+    fn unsupported_format() -> AppError {
+        AppError::new(ErrorCode::Unsupported, "Unsupported archive format")
+    }
 }
@@ -1,8 +1,10 @@
 use dataset_viewer_lib::create_specta_builder;
+use dataset_viewer_lib::error::{AppError, ErrorCode};
 use specta_typescript::Typescript;
 
 fn main() {
-    let builder = create_specta_builder();
+    // 命令的错误类型会随命令导出，这里显式注册，保证前端始终能引用统一错误类型
+    let builder = create_specta_builder().typ::<AppError>().typ::<ErrorCode>();
 
     builder
         .export(
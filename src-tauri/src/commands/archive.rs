@@ -2,7 +2,8 @@
 // 提供压缩包分析、预览和格式支持功能
 
 use crate::archive::{handlers::ArchiveHandler, types::*};
-use crate::storage::get_storage_manager;
+use crate::error::{AppError, ErrorCode};
+use crate::storage::manager::{get_storage_manager, resolve_client, StorageManager};
 use std::sync::{Arc, LazyLock};
 
 // 全局压缩包处理器
@@ -14,24 +15,22 @@ static ARCHIVE_HANDLER: LazyLock<Arc<ArchiveHandler>> =
 #[tauri::command]
 #[specta::specta]
 pub async fn archive_get_file_info(
+    window: tauri::Window,
+    connection_id: Option<String>,
     url: String,
     filename: String,
     max_size: Option<u32>,
-) -> Result<ArchiveInfo, String> {
+) -> Result<ArchiveInfo, AppError> {
     // 统一使用StorageClient接口进行流式分析
-    let manager_arc = get_storage_manager().await;
-    let manager = manager_arc.read().await;
-
-    if let Some(client) = manager.get_current_client() {
+    if let Ok(client) = resolve_client(connection_id.as_deref(), window.label()).await {
         let protocol = client.protocol();
         println!("使用{}存储客户端进行流式分析: {}", protocol, url);
-        drop(manager);
 
         ARCHIVE_HANDLER
             .analyze_archive_with_client(client, url, filename, max_size)
             .await
     } else {
-        Err("No storage client available. Please connect to a storage first (Local, WebDAV, S3, or HuggingFace)".to_string())
+        Err(AppError::new(ErrorCode::NotConnected, "No storage client available. Please connect to a storage first (Local, WebDAV, S3, or HuggingFace)"))
     }
 }
 
@@ -40,23 +39,21 @@ pub async fn archive_get_file_info(
 #[tauri::command(rename_all = "camelCase")]
 #[specta::specta]
 pub async fn archive_get_file_content(
+    window: tauri::Window,
+    connection_id: Option<String>,
     url: String,
     filename: String,
     entry_path: String,
     max_preview_size: Option<u32>,
     offset: Option<String>, // 使用字符串表示大数字
-) -> Result<FilePreview, String> {
+) -> Result<FilePreview, AppError> {
     // 统一使用StorageClient接口进行流式预览
-    let manager_arc = get_storage_manager().await;
-    let manager = manager_arc.read().await;
-
-    if let Some(client) = manager.get_current_client() {
+    if let Ok(client) = resolve_client(connection_id.as_deref(), window.label()).await {
         let protocol = client.protocol();
         println!(
             "使用{}存储客户端进行流式预览: {} -> {}",
             protocol, url, entry_path
         );
-        drop(manager);
 
         ARCHIVE_HANDLER
             .get_file_preview_with_client(
@@ -71,6 +68,28 @@ pub async fn archive_get_file_content(
             )
             .await
     } else {
-        Err("No storage client available. Please connect to a storage first (Local, WebDAV, S3, or HuggingFace)".to_string())
+        Err(AppError::new(ErrorCode::NotConnected, "No storage client available. Please connect to a storage first (Local, WebDAV, S3, or HuggingFace)"))
     }
 }
+
+/// 将压缩包挂载为只读连接，其中的条目可以像目录和文件一样浏览、预览和下载
+/// 压缩包所在的连接本身也可以是挂载的压缩包，用于打开嵌套的压缩包
+/// 返回新连接的 ID，调用的窗口绑定关系保持不变
+#[tauri::command(rename_all = "camelCase")]
+#[specta::specta]
+pub async fn archive_mount(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+) -> Result<String, AppError> {
+    let parent = resolve_client(connection_id.as_deref(), window.label()).await?;
+
+    // 分析压缩包时不持有管理器的锁
+    let connection = StorageManager::open_archive(parent, &path)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to open archive"))?;
+
+    let manager_arc = get_storage_manager().await;
+    let mut manager = manager_arc.write().await;
+    Ok(manager.add_connection(connection))
+}
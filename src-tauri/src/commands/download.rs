@@ -2,10 +2,12 @@
 // 提供文件下载、进度监控和取消功能
 
 use crate::download::{DownloadManager, DownloadRequest};
+use crate::error::{AppError, ErrorCode};
+use crate::storage::manager::resolve_client;
 use std::sync::LazyLock;
 
 // 全局下载管理器
-static DOWNLOAD_MANAGER: LazyLock<DownloadManager> = LazyLock::new(DownloadManager::new);
+pub(crate) static DOWNLOAD_MANAGER: LazyLock<DownloadManager> = LazyLock::new(DownloadManager::new);
 
 /// 开始文件下载
 /// 支持实时进度更新和下载取消功能
@@ -13,34 +15,38 @@ static DOWNLOAD_MANAGER: LazyLock<DownloadManager> = LazyLock::new(DownloadManag
 #[specta::specta]
 pub async fn download_start(
     app: tauri::AppHandle,
+    window: tauri::Window,
+    connection_id: Option<String>,
     url: String,
     filename: String,
     save_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // 如果没有指定保存路径，使用默认下载路径
     let final_save_path = match save_path {
         Some(path) => Some(path),
         None => Some(get_default_download_path(&filename)?),
     };
 
+    let client = resolve_client(connection_id.as_deref(), window.label()).await?;
+
     let request = DownloadRequest { url, filename };
 
     DOWNLOAD_MANAGER
-        .download_with_progress(app, request, final_save_path)
+        .download_with_progress(app, client, request, final_save_path)
         .await
 }
 
-/// 取消指定文件的下载
+/// 取消指定任务，下载任务按文件名，上传任务按进度事件中的 task_id
 #[tauri::command]
 #[specta::specta]
-pub async fn download_cancel(filename: String) -> Result<String, String> {
+pub async fn download_cancel(filename: String) -> Result<String, AppError> {
     DOWNLOAD_MANAGER.cancel_download(&filename)
 }
 
 /// 取消所有正在进行的下载
 #[tauri::command]
 #[specta::specta]
-pub async fn download_cancel_all() -> Result<String, String> {
+pub async fn download_cancel_all() -> Result<String, AppError> {
     DOWNLOAD_MANAGER.cancel_all_downloads()
 }
 
@@ -48,24 +54,30 @@ pub async fn download_cancel_all() -> Result<String, String> {
 /// 支持从压缩包中提取单个文件并下载
 #[tauri::command]
 #[specta::specta]
+#[allow(clippy::too_many_arguments)]
 pub async fn download_extract_file(
     app: tauri::AppHandle,
+    window: tauri::Window,
+    connection_id: Option<String>,
     archive_path: String,
     archive_filename: String,
     entry_path: String,
     entry_filename: String,
     save_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // 如果没有指定保存路径，使用默认下载路径
     let final_save_path = match save_path {
         Some(path) => Some(path),
         None => Some(get_default_download_path(&entry_filename)?),
     };
 
+    let client = resolve_client(connection_id.as_deref(), window.label()).await?;
+
     // 使用统一的下载管理器来处理压缩包文件下载，支持取消功能
     DOWNLOAD_MANAGER
         .download_archive_file_with_progress(
             app,
+            client,
             archive_path,
             archive_filename,
             entry_path,
@@ -77,7 +89,7 @@ pub async fn download_extract_file(
 
 /// 获取系统默认下载路径的内部函数
 /// 当用户未指定保存路径时自动调用
-fn get_default_download_path(filename: &str) -> Result<String, String> {
+fn get_default_download_path(filename: &str) -> Result<String, AppError> {
     // 获取系统默认下载目录
     if let Some(download_dir) = dirs::download_dir() {
         let save_path = download_dir.join(filename);
@@ -88,7 +100,7 @@ fn get_default_download_path(filename: &str) -> Result<String, String> {
             let save_path = home_dir.join("Downloads").join(filename);
             Ok(save_path.to_string_lossy().to_string())
         } else {
-            Err("无法确定下载路径".to_string())
+            Err(AppError::new(ErrorCode::NotFound, "无法确定下载路径"))
         }
     }
 }
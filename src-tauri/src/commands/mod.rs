@@ -3,11 +3,15 @@
 
 pub mod archive; // 压缩包处理命令
 pub mod download; // 下载管理命令
+pub mod profile; // 连接配置库命令
 pub mod storage; // 统一存储接口命令
 pub mod system; // 其他系统控制命令
+pub mod transfer; // 跨存储传输命令
 
 // 重新导出所有命令，便于在 lib.rs 中统一注册
 pub use archive::*;
 pub use download::*;
+pub use profile::*;
 pub use storage::*;
 pub use system::*;
+pub use transfer::*;
@@ -0,0 +1,189 @@
+// 连接配置库命令
+// 在 Rust 侧加密保存连接配置，密钥字段不经过 IPC 返回前端，可按配置 ID 直接建立连接
+
+use crate::commands::storage::storage_connect;
+use crate::error::AppError;
+use crate::storage::config_import::{ConnectionImporter, ImportedConnection};
+use crate::storage::profile_store::{
+    redact_secrets, ConnectionProfile, ProfileStore, ProfileStoreStatus,
+};
+use crate::storage::ConnectionConfig;
+use std::sync::{LazyLock, Mutex};
+
+// 全局连接配置库
+static PROFILE_STORE: LazyLock<Mutex<ProfileStore>> =
+    LazyLock::new(|| Mutex::new(ProfileStore::new(ProfileStore::default_path())));
+
+/// 在阻塞线程中操作配置库
+/// 解锁、导入导出包含 Argon2id 密钥派生和文件读写，不能占用异步运行时的工作线程
+async fn with_store<T, F>(operation: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut ProfileStore) -> Result<T, AppError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || operation(&mut PROFILE_STORE.lock().unwrap()))
+        .await
+        .map_err(|e| AppError::internal(format!("Profile store task failed: {}", e)))?
+}
+
+/// 获取配置库状态
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_status() -> Result<ProfileStoreStatus, AppError> {
+    with_store(|store| Ok(store.status())).await
+}
+
+/// 解锁配置库
+/// 首次使用时以该主密码创建配置库；未提供密码时使用系统钥匙串中保存的密钥
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_unlock(
+    master_password: Option<String>,
+    remember: Option<bool>,
+) -> Result<ProfileStoreStatus, AppError> {
+    with_store(move |store| {
+        store.unlock(master_password.as_deref(), remember)?;
+        Ok(store.status())
+    })
+    .await
+}
+
+/// 锁定配置库
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_lock() -> Result<bool, AppError> {
+    with_store(|store| {
+        store.lock();
+        Ok(true)
+    })
+    .await
+}
+
+/// 修改主密码
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_change_password(new_password: String) -> Result<bool, AppError> {
+    with_store(move |store| {
+        store.change_password(&new_password)?;
+        Ok(true)
+    })
+    .await
+}
+
+/// 列出保存的连接配置，不包含密钥字段
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_list() -> Result<Vec<ConnectionProfile>, AppError> {
+    with_store(|store| store.list()).await
+}
+
+/// 保存新的连接配置
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_create(
+    name: String,
+    config: ConnectionConfig,
+) -> Result<ConnectionProfile, AppError> {
+    with_store(move |store| store.create(name, config)).await
+}
+
+/// 更新连接配置
+/// 未提供的密钥字段保留原值，设为空字符串时清除
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_update(
+    profile_id: String,
+    name: Option<String>,
+    config: Option<ConnectionConfig>,
+) -> Result<ConnectionProfile, AppError> {
+    with_store(move |store| store.update(&profile_id, name, config)).await
+}
+
+/// 删除连接配置
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_delete(profile_id: String) -> Result<bool, AppError> {
+    with_store(move |store| store.delete(&profile_id)).await
+}
+
+/// 导出连接配置到加密文件
+/// 导出文件使用单独的密码加密，未指定配置 ID 时导出全部，返回导出的数量
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_export(
+    path: String,
+    passphrase: String,
+    profile_ids: Option<Vec<String>>,
+) -> Result<u32, AppError> {
+    let count = with_store(move |store| {
+        store.export(
+            std::path::Path::new(&path),
+            &passphrase,
+            profile_ids.as_deref(),
+        )
+    })
+    .await?;
+    Ok(count as u32)
+}
+
+/// 从加密的导出文件导入连接配置，返回导入的数量
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_import(path: String, passphrase: String) -> Result<u32, AppError> {
+    let count =
+        with_store(move |store| store.import(std::path::Path::new(&path), &passphrase)).await?;
+    Ok(count as u32)
+}
+
+/// 按保存的配置建立连接
+/// 密钥只在后端读取，返回新连接的 ID，并将调用的窗口绑定到该连接
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_connect(
+    window: tauri::Window,
+    profile_id: String,
+) -> Result<String, AppError> {
+    let config = with_store(move |store| store.connection_config(&profile_id)).await?;
+
+    storage_connect(window, config).await
+}
+
+/// 扫描其他工具的配置文件中可导入的连接
+/// source 为 "aws"、"ssh" 或 "rclone"，path 覆盖默认位置；返回的配置不包含密钥字段
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_scan_external(
+    source: String,
+    path: Option<String>,
+) -> Result<Vec<ImportedConnection>, AppError> {
+    let mut connections = ConnectionImporter::import(&source, path.as_deref())?;
+    for connection in &mut connections {
+        connection.secret_fields = redact_secrets(&mut connection.config);
+    }
+    Ok(connections)
+}
+
+/// 将其他工具的配置文件中的连接保存到配置库
+/// 密钥在后端重新读取，未指定名称时保存全部，返回保存后的配置
+#[tauri::command]
+#[specta::specta]
+pub async fn profile_import_external(
+    source: String,
+    path: Option<String>,
+    names: Option<Vec<String>>,
+) -> Result<Vec<ConnectionProfile>, AppError> {
+    let connections = ConnectionImporter::import(&source, path.as_deref())?;
+
+    with_store(move |store| {
+        connections
+            .into_iter()
+            .filter(|connection| {
+                names
+                    .as_ref()
+                    .is_none_or(|names| names.contains(&connection.name))
+            })
+            .map(|connection| store.create(connection.name, connection.config))
+            .collect()
+    })
+    .await
+}
@@ -1,8 +1,31 @@
 // 统一存储接口命令
 // 提供多协议存储连接和文件操作能力
 
-use crate::storage::{get_storage_manager, ConnectionConfig, DirectoryResult, ListOptions};
+use crate::commands::download::DOWNLOAD_MANAGER;
+use crate::error::{AppError, ErrorCode};
+use crate::storage::caching_client::CacheStats;
+use crate::storage::disk_usage::{DiskUsageAnalyzer, DiskUsageOptions, DiskUsageSummary};
+use crate::storage::manager::{
+    resolve_client, resolve_writable_client, ConnectionInfo, StorageManager,
+};
+use crate::storage::search::{SearchOptions, SearchResults, SearchSummary, StorageSearcher};
+use crate::storage::{
+    get_storage_manager, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions,
+    ObjectVersion,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, LazyLock, Mutex};
+use tauri::Emitter;
+
+// 正在进行的搜索（搜索 ID -> 取消标志）
+static ACTIVE_SEARCHES: LazyLock<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// 正在进行的目录占用统计（统计 ID -> 取消标志）
+static ACTIVE_DISK_USAGE: LazyLock<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
 /// 文件信息结构
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -19,38 +42,25 @@ pub struct FileInfo {
 #[tauri::command]
 #[specta::specta]
 pub async fn storage_get_file_content(
+    window: tauri::Window,
+    connection_id: Option<String>,
     path: String,
     start: Option<String>,
     length: Option<String>,
-) -> Result<Vec<u8>, String> {
-    let manager_arc = get_storage_manager().await;
-    let manager = manager_arc.read().await;
-
-    // 获取当前客户端
-    let client = manager
-        .get_current_client()
-        .ok_or_else(|| "No storage client connected".to_string())?;
+) -> Result<Vec<u8>, AppError> {
+    // 获取连接对应的客户端
+    let client = resolve_client(connection_id.as_deref(), window.label()).await?;
 
     // 解析字符串参数为数字
-    let start_u64 = if let Some(start_str) = start {
-        Some(
-            start_str
-                .parse::<u64>()
-                .map_err(|e| format!("Invalid start parameter: {}", e))?,
-        )
-    } else {
-        None
-    };
+    let start_u64 = start
+        .map(|value| value.parse::<u64>())
+        .transpose()
+        .map_err(|e| AppError::invalid_argument(format!("Invalid start parameter: {}", e)))?;
 
-    let length_u64 = if let Some(length_str) = length {
-        Some(
-            length_str
-                .parse::<u64>()
-                .map_err(|e| format!("Invalid length parameter: {}", e))?,
-        )
-    } else {
-        None
-    };
+    let length_u64 = length
+        .map(|value| value.parse::<u64>())
+        .transpose()
+        .map_err(|e| AppError::invalid_argument(format!("Invalid length parameter: {}", e)))?;
 
     // 根据参数选择读取方式
     let result = if let Some(start_pos) = start_u64 {
@@ -62,7 +72,7 @@ pub async fn storage_get_file_content(
             let total_size = client
                 .get_file_size(&path)
                 .await
-                .map_err(|e| format!("Failed to get file size: {}", e))?;
+                .map_err(|e| AppError::from(e).context("Failed to get file size"))?;
             let read_length = total_size.saturating_sub(start_pos);
             client.read_file_range(&path, start_pos, read_length).await
         }
@@ -71,90 +81,170 @@ pub async fn storage_get_file_content(
         client.read_full_file(&path).await
     };
 
-    result.map_err(|e| format!("Failed to read file: {}", e))
+    result.map_err(|e| AppError::from(e).context("Failed to read file"))
 }
 
 /// 获取文件信息
 /// 返回文件元数据信息，包括大小、修改时间等
 #[tauri::command]
 #[specta::specta]
-pub async fn storage_get_file_info(path: String) -> Result<FileInfo, String> {
-    let manager_arc = get_storage_manager().await;
-    let manager = manager_arc.read().await;
+pub async fn storage_get_file_info(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+) -> Result<FileInfo, AppError> {
+    let metadata = storage_stat(window, connection_id, path).await?;
 
-    let client = manager
-        .get_current_client()
-        .ok_or_else(|| "No storage client connected".to_string())?;
-
-    // 首先尝试获取文件大小来检查文件是否存在
-    match client.get_file_size(&path).await {
-        Ok(size) => {
-            // 文件存在
-            Ok(FileInfo {
-                size: size.to_string(),
-                modified_time: None, // TODO: 从存储客户端获取修改时间
-            })
-        }
-        Err(_) => {
-            // 文件不存在或无法访问，尝试检查是否为目录
-            match manager.list_directory(&path, None).await {
-                Ok(_) => {
-                    // 是目录
-                    Ok(FileInfo {
-                        size: "0".to_string(),
-                        modified_time: None,
-                    })
-                }
-                Err(e) => {
-                    // 不存在
-                    Err(format!("File not found: {}", e))
-                }
-            }
-        }
-    }
+    Ok(FileInfo {
+        size: metadata.size,
+        modified_time: Some(metadata.lastmod).filter(|lastmod| !lastmod.is_empty()),
+    })
+}
+
+/// 获取文件或目录的完整元数据
+/// 包括校验和、权限、所有者以及各后端特有的扩展信息
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_stat(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+) -> Result<FileMetadata, AppError> {
+    let client = resolve_client(connection_id.as_deref(), window.label()).await?;
+
+    client
+        .stat(&path)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to stat file"))
+}
+
+/// 列出文件的历史版本和删除标记（S3/OSS 版本控制）
+/// 返回的版本路径可直接用于读取文件内容和下载
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_list_versions(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+) -> Result<Vec<ObjectVersion>, AppError> {
+    let client = resolve_client(connection_id.as_deref(), window.label()).await?;
+
+    client
+        .list_versions(&path)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to list versions"))
 }
 
 /// 连接到存储服务
 /// 支持本地文件系统、WebDAV、S3、HuggingFace 等多种协议
+/// 返回新连接的 ID，并将调用的窗口绑定到该连接
 #[tauri::command]
 #[specta::specta]
-pub async fn storage_connect(config: ConnectionConfig) -> Result<bool, String> {
+pub async fn storage_connect(
+    window: tauri::Window,
+    config: ConnectionConfig,
+) -> Result<String, AppError> {
+    // 建立连接时不持有管理器的锁，其他连接上的操作不受影响
+    let connection = StorageManager::create_connection(&config)
+        .await
+        .map_err(|e| AppError::from(e).context("Connection failed"))?;
+
     let manager_arc = get_storage_manager().await;
     let mut manager = manager_arc.write().await;
 
-    match manager.connect(&config).await {
+    let connection_id = manager.add_connection(connection);
+    manager
+        .bind_window(window.label(), &connection_id)
+        .map_err(|e| AppError::from(e).context("Connection failed"))?;
+
+    Ok(connection_id)
+}
+
+/// 断开存储连接
+/// 未指定连接 ID 时断开窗口绑定的连接
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_disconnect(
+    window: tauri::Window,
+    connection_id: Option<String>,
+) -> Result<bool, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let mut manager = manager_arc.write().await;
+
+    let result = match manager.resolve_connection_id(connection_id.as_deref(), window.label()) {
+        Ok(connection_id) => manager.disconnect(&connection_id).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
         Ok(_) => Ok(true),
-        Err(e) => Err(format!("Connection failed: {}", e)),
+        Err(e) => Err(AppError::from(e).context("Disconnect failed")),
     }
 }
 
-/// 断开存储连接
+/// 列出所有已建立的连接
 #[tauri::command]
 #[specta::specta]
-pub async fn storage_disconnect() -> Result<bool, String> {
+pub async fn storage_list_connections() -> Result<Vec<ConnectionInfo>, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+
+    Ok(manager.list_connections())
+}
+
+/// 将调用的窗口绑定到指定连接
+/// 绑定后该窗口中未指定连接 ID 的命令都使用此连接
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_bind_window(
+    window: tauri::Window,
+    connection_id: String,
+) -> Result<bool, AppError> {
     let manager_arc = get_storage_manager().await;
     let mut manager = manager_arc.write().await;
 
-    match manager.disconnect().await {
+    match manager.bind_window(window.label(), &connection_id) {
         Ok(_) => Ok(true),
-        Err(e) => Err(format!("Disconnect failed: {}", e)),
+        Err(e) => Err(AppError::from(e).context("Bind window failed")),
     }
 }
 
+/// 解除调用的窗口与连接的绑定
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_unbind_window(window: tauri::Window) -> Result<bool, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let mut manager = manager_arc.write().await;
+
+    manager.unbind_window(window.label());
+    Ok(true)
+}
+
 /// 列出目录内容
 /// 支持分页和过滤选项
 #[tauri::command]
 #[specta::specta]
 pub async fn storage_list(
+    window: tauri::Window,
+    connection_id: Option<String>,
     path: String,
     options: Option<ListOptions>,
-) -> Result<DirectoryResult, String> {
+) -> Result<DirectoryResult, AppError> {
     let manager_arc = get_storage_manager().await;
     let manager = manager_arc.read().await;
 
-    match manager.list_directory(&path, options.as_ref()).await {
+    let result = match manager.resolve_connection_id(connection_id.as_deref(), window.label()) {
+        Ok(connection_id) => {
+            manager
+                .list_directory(&connection_id, &path, options.as_ref())
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
         Ok(result) => Ok(result),
-        Err(e) => Err(format!("List directory failed: {}", e)),
+        Err(e) => Err(AppError::from(e).context("List directory failed")),
     }
 }
 
@@ -162,12 +252,296 @@ pub async fn storage_list(
 /// 根据存储类型生成相应的下载链接
 #[tauri::command]
 #[specta::specta]
-pub async fn storage_get_url(path: String) -> Result<String, String> {
+pub async fn storage_get_url(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+) -> Result<String, AppError> {
     let manager_arc = get_storage_manager().await;
     let manager = manager_arc.read().await;
 
+    let connection_id = manager
+        .resolve_connection_id(connection_id.as_deref(), window.label())
+        .map_err(|e| AppError::from(e).context("Failed to get download URL"))?;
+
     manager
-        .get_download_url(&path)
+        .get_download_url(&connection_id, &path)
         .await
-        .map_err(|e| format!("Failed to get download URL: {}", e))
+        .map_err(|e| AppError::from(e).context("Failed to get download URL"))
+}
+
+/// 上传本地文件
+/// 支持进度更新，上传任务以唯一的 task_id 登记，可通过 download_cancel 按进度事件中的 task_id 取消
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_upload_file(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    connection_id: Option<String>,
+    local_path: String,
+    path: String,
+) -> Result<String, AppError> {
+    let client = resolve_writable_client(connection_id.as_deref(), window.label()).await?;
+
+    DOWNLOAD_MANAGER
+        .upload_with_progress(app, client, std::path::PathBuf::from(local_path), path)
+        .await
+}
+
+/// 创建目录
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_create_directory(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+) -> Result<bool, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+
+    let result = match manager.resolve_connection_id(connection_id.as_deref(), window.label()) {
+        Ok(connection_id) => manager.create_directory(&connection_id, &path).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(e) => Err(AppError::from(e).context("Create directory failed")),
+    }
+}
+
+/// 重命名或移动文件/目录
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_rename(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<bool, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+
+    let result = match manager.resolve_connection_id(connection_id.as_deref(), window.label()) {
+        Ok(connection_id) => manager.rename(&connection_id, &from, &to).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(e) => Err(AppError::from(e).context("Rename failed")),
+    }
+}
+
+/// 删除文件或目录
+/// 目录会被递归删除
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_delete(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+) -> Result<bool, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+
+    let result = match manager.resolve_connection_id(connection_id.as_deref(), window.label()) {
+        Ok(connection_id) => manager.delete(&connection_id, &path).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(_) => Ok(true),
+        Err(e) => Err(AppError::from(e).context("Delete failed")),
+    }
+}
+
+/// 获取连接的块缓存统计信息
+/// 未启用块缓存时返回 None
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_get_cache_stats(
+    window: tauri::Window,
+    connection_id: Option<String>,
+) -> Result<Option<CacheStats>, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+
+    manager
+        .resolve_connection_id(connection_id.as_deref(), window.label())
+        .and_then(|connection_id| manager.cache_stats(&connection_id))
+        .map_err(AppError::from)
+}
+
+/// 清空连接的块缓存
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_clear_cache(
+    window: tauri::Window,
+    connection_id: Option<String>,
+) -> Result<bool, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+
+    let connection_id = manager.resolve_connection_id(connection_id.as_deref(), window.label())?;
+    manager.clear_cache(&connection_id).await?;
+    Ok(true)
+}
+
+/// 刷新目录列表缓存
+/// 使指定路径及其子路径的列表缓存失效，未指定路径时清空整个连接的列表缓存
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_refresh(
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: Option<String>,
+) -> Result<u32, AppError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+
+    let connection_id = manager.resolve_connection_id(connection_id.as_deref(), window.label())?;
+    let refreshed = manager.refresh_listing(&connection_id, path.as_deref().unwrap_or(""))?;
+    Ok(refreshed as u32)
+}
+
+/// 递归搜索连接中的文件
+/// 匹配结果通过 search-results 事件分批推送，完成后返回汇总，可通过 storage_search_cancel 取消
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_search(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+    options: Option<SearchOptions>,
+) -> Result<SearchSummary, AppError> {
+    let client = resolve_client(connection_id.as_deref(), window.label()).await?;
+
+    let options = options.unwrap_or_default();
+    let search_id = options
+        .search_id
+        .clone()
+        .unwrap_or_else(|| format!("search_{}", uuid::Uuid::new_v4().simple()));
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut searches = ACTIVE_SEARCHES.lock().unwrap();
+        if searches.contains_key(&search_id) {
+            return Err(AppError::new(
+                ErrorCode::Conflict,
+                format!("Search already running: {}", search_id),
+            ));
+        }
+        searches.insert(search_id.clone(), cancelled.clone());
+    }
+
+    let on_batch = {
+        let search_id = search_id.clone();
+        move |files| {
+            let _ = app.emit(
+                "search-results",
+                &SearchResults {
+                    search_id: search_id.clone(),
+                    files,
+                },
+            );
+        }
+    };
+
+    let result = StorageSearcher::search(
+        client.as_ref(),
+        &path,
+        &search_id,
+        &options,
+        &cancelled,
+        &on_batch,
+    )
+    .await;
+
+    ACTIVE_SEARCHES.lock().unwrap().remove(&search_id);
+
+    result.map_err(|e| AppError::from(e).context("Search failed"))
+}
+
+/// 取消正在进行的搜索
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_search_cancel(search_id: String) -> Result<bool, AppError> {
+    match ACTIVE_SEARCHES.lock().unwrap().get(&search_id) {
+        Some(cancelled) => {
+            cancelled.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Err(AppError::not_found(format!(
+            "No active search found for: {}",
+            search_id
+        ))),
+    }
+}
+
+/// 统计目录及其子目录的占用
+/// 进度通过 disk-usage-progress 事件推送，完成后返回目录树，可通过 storage_disk_usage_cancel 取消
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_disk_usage(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    connection_id: Option<String>,
+    path: String,
+    options: Option<DiskUsageOptions>,
+) -> Result<DiskUsageSummary, AppError> {
+    let client = resolve_client(connection_id.as_deref(), window.label()).await?;
+
+    let options = options.unwrap_or_default();
+    let usage_id = options
+        .usage_id
+        .clone()
+        .unwrap_or_else(|| format!("usage_{}", uuid::Uuid::new_v4().simple()));
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut active = ACTIVE_DISK_USAGE.lock().unwrap();
+        if active.contains_key(&usage_id) {
+            return Err(AppError::new(
+                ErrorCode::Conflict,
+                format!("Disk usage already running: {}", usage_id),
+            ));
+        }
+        active.insert(usage_id.clone(), cancelled.clone());
+    }
+
+    let on_progress = move |progress| {
+        let _ = app.emit("disk-usage-progress", &progress);
+    };
+
+    let result = DiskUsageAnalyzer::analyze(
+        client.as_ref(),
+        &path,
+        &usage_id,
+        &options,
+        &cancelled,
+        &on_progress,
+    )
+    .await;
+
+    ACTIVE_DISK_USAGE.lock().unwrap().remove(&usage_id);
+
+    result.map_err(|e| AppError::from(e).context("Disk usage failed"))
+}
+
+/// 取消正在进行的目录占用统计
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_disk_usage_cancel(usage_id: String) -> Result<bool, AppError> {
+    match ACTIVE_DISK_USAGE.lock().unwrap().get(&usage_id) {
+        Some(cancelled) => {
+            cancelled.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Err(AppError::not_found(format!(
+            "No active disk usage found for: {}",
+            usage_id
+        ))),
+    }
 }
@@ -1,6 +1,8 @@
 // 系统控制命令
 // 提供系统集成、窗口管理和平台特定功能
 
+use crate::error::AppError;
+
 /// 显示文件夹选择对话框
 /// 跨平台的目录选择功能
 #[tauri::command]
@@ -8,10 +10,13 @@
 pub async fn system_select_folder(
     _app: tauri::AppHandle,
     title: String,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, AppError> {
     #[cfg(target_os = "ios")]
     {
-        return Err("Folder selection is not supported on iOS platform".to_string());
+        return Err(AppError::new(
+            crate::error::ErrorCode::Unsupported,
+            "Folder selection is not supported on iOS platform",
+        ));
     }
 
     #[cfg(desktop)]
@@ -32,14 +37,14 @@ pub async fn system_select_folder(
             Ok(Some(folder)) => {
                 let path_buf = folder
                     .into_path()
-                    .map_err(|e| format!("Failed to get path: {}", e))?;
+                    .map_err(|e| AppError::internal(format!("Failed to get path: {}", e)))?;
 
                 // 规范为绝对路径
                 let abs_path = if path_buf.is_absolute() {
                     path_buf
                 } else {
                     std::env::current_dir()
-                        .map_err(|e| format!("Failed to get current dir: {}", e))?
+                        .map_err(|e| AppError::from(e).context("Failed to get current dir"))?
                         .join(path_buf)
                 };
                 let path_str = abs_path.to_string_lossy().to_string();
@@ -48,7 +53,10 @@ pub async fn system_select_folder(
                 Ok(Some(path_str))
             }
             Ok(None) => Ok(None),
-            Err(e) => Err(format!("Failed to receive folder selection: {}", e)),
+            Err(e) => Err(AppError::internal(format!(
+                "Failed to receive folder selection: {}",
+                e
+            ))),
         }
     }
 }
@@ -60,10 +68,13 @@ pub async fn system_select_folder(
 pub async fn system_select_file(
     _app: tauri::AppHandle,
     title: String,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, AppError> {
     #[cfg(target_os = "ios")]
     {
-        return Err("File selection is not supported on iOS platform".to_string());
+        return Err(AppError::new(
+            crate::error::ErrorCode::Unsupported,
+            "File selection is not supported on iOS platform",
+        ));
     }
 
     #[cfg(desktop)]
@@ -84,14 +95,14 @@ pub async fn system_select_file(
             Ok(Some(file)) => {
                 let path_buf = file
                     .into_path()
-                    .map_err(|e| format!("Failed to get path: {}", e))?;
+                    .map_err(|e| AppError::internal(format!("Failed to get path: {}", e)))?;
 
                 // 规范为绝对路径
                 let abs_path = if path_buf.is_absolute() {
                     path_buf
                 } else {
                     std::env::current_dir()
-                        .map_err(|e| format!("Failed to get current dir: {}", e))?
+                        .map_err(|e| AppError::from(e).context("Failed to get current dir"))?
                         .join(path_buf)
                 };
                 let path_str = abs_path.to_string_lossy().to_string();
@@ -100,7 +111,10 @@ pub async fn system_select_file(
                 Ok(Some(path_str))
             }
             Ok(None) => Ok(None),
-            Err(e) => Err(format!("Failed to receive file selection: {}", e)),
+            Err(e) => Err(AppError::internal(format!(
+                "Failed to receive file selection: {}",
+                e
+            ))),
         }
     }
 }
@@ -109,7 +123,7 @@ pub async fn system_select_file(
 /// 支持自动、亮色、暗色三种主题模式
 #[tauri::command]
 #[specta::specta]
-pub async fn system_set_theme(app: tauri::AppHandle, theme: String) -> Result<String, String> {
+pub async fn system_set_theme(app: tauri::AppHandle, theme: String) -> Result<String, AppError> {
     use tauri::Manager;
 
     if let Some(window) = app.get_webview_window("main") {
@@ -117,7 +131,12 @@ pub async fn system_set_theme(app: tauri::AppHandle, theme: String) -> Result<St
             "dark" => Some(tauri::Theme::Dark),
             "light" => Some(tauri::Theme::Light),
             "system" => None, // None 表示使用系统默认主题
-            _ => return Err(format!("Unknown theme: {}", theme)),
+            _ => {
+                return Err(AppError::invalid_argument(format!(
+                    "Unknown theme: {}",
+                    theme
+                )))
+            }
         };
 
         match window.set_theme(tauri_theme) {
@@ -130,9 +149,12 @@ pub async fn system_set_theme(app: tauri::AppHandle, theme: String) -> Result<St
                 };
                 Ok(format!("Window theme set to {}", theme_description))
             }
-            Err(e) => Err(format!("Failed to set window theme: {}", e)),
+            Err(e) => Err(AppError::internal(format!(
+                "Failed to set window theme: {}",
+                e
+            ))),
         }
     } else {
-        Err("Main window not found".to_string())
+        Err(AppError::not_found("Main window not found"))
     }
 }
@@ -0,0 +1,76 @@
+// 跨存储传输命令
+// 在两个已建立的连接之间直接流式复制或同步文件和目录，提供进度、取消和汇总报告
+
+use crate::download::sync::SyncPlanner;
+use crate::download::{SyncPlan, SyncRequest, TransferManager, TransferRequest, TransferSummary};
+use crate::error::AppError;
+use crate::storage::manager::{resolve_client, resolve_writable_client};
+use std::sync::LazyLock;
+
+// 全局传输管理器
+static TRANSFER_MANAGER: LazyLock<TransferManager> = LazyLock::new(TransferManager::new);
+
+/// 开始跨存储传输
+/// 进度通过 transfer-* 事件推送，完成后返回汇总报告
+#[tauri::command]
+#[specta::specta]
+pub async fn transfer_start(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    request: TransferRequest,
+) -> Result<TransferSummary, AppError> {
+    let source = resolve_client(request.source_connection_id.as_deref(), window.label()).await?;
+    let destination =
+        resolve_writable_client(request.destination_connection_id.as_deref(), window.label())
+            .await?;
+
+    TRANSFER_MANAGER
+        .transfer_with_progress(app, source, destination, request)
+        .await
+}
+
+/// 生成目录同步计划（dry-run），不修改任何一端
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_plan(window: tauri::Window, request: SyncRequest) -> Result<SyncPlan, AppError> {
+    let source = resolve_client(request.source_connection_id.as_deref(), window.label()).await?;
+    let destination =
+        resolve_client(request.destination_connection_id.as_deref(), window.label()).await?;
+
+    SyncPlanner::plan(source.as_ref(), destination.as_ref(), &request, None)
+        .await
+        .map_err(AppError::from)
+}
+
+/// 执行目录同步
+/// 开始时重新生成同步计划，进度通过 transfer-* 事件推送，可通过 transfer_cancel 取消
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_start(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    request: SyncRequest,
+) -> Result<TransferSummary, AppError> {
+    let source = resolve_client(request.source_connection_id.as_deref(), window.label()).await?;
+    let destination =
+        resolve_writable_client(request.destination_connection_id.as_deref(), window.label())
+            .await?;
+
+    TRANSFER_MANAGER
+        .sync_with_progress(app, source, destination, request)
+        .await
+}
+
+/// 取消指定的传输任务
+#[tauri::command]
+#[specta::specta]
+pub async fn transfer_cancel(job_id: String) -> Result<String, AppError> {
+    TRANSFER_MANAGER.cancel_transfer(&job_id)
+}
+
+/// 取消所有正在进行的传输任务
+#[tauri::command]
+#[specta::specta]
+pub async fn transfer_cancel_all() -> Result<String, AppError> {
+    TRANSFER_MANAGER.cancel_all_transfers()
+}
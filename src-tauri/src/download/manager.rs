@@ -4,7 +4,8 @@ use tauri_plugin_dialog::DialogExt;
 use tokio::sync::broadcast;
 
 use crate::download::{progress::ProgressTracker, provider::DownloadProviderFactory, types::*};
-use crate::storage::traits::ProgressCallback;
+use crate::error::{AppError, ErrorCode};
+use crate::storage::traits::{ProgressCallback, StorageClient};
 
 /// 简化的下载管理器
 /// 专注于任务管理、UI交互和进度跟踪
@@ -23,11 +24,12 @@ impl DownloadManager {
     pub async fn download_with_progress(
         &self,
         app: tauri::AppHandle,
+        client: Arc<dyn StorageClient + Send + Sync>,
         request: DownloadRequest,
         save_path: Option<String>,
     ) -> DownloadResult {
         // 获取合适的下载提供者
-        let provider = DownloadProviderFactory::get_provider(&request.url).await?;
+        let provider = DownloadProviderFactory::get_provider(client, &request.url).await?;
 
         // 尝试获取文件大小，失败时回退到流式下载
         let file_size = provider.get_file_size(&request).await.unwrap_or_else(|e| {
@@ -44,13 +46,18 @@ impl DownloadManager {
 
         // 发送开始下载事件
         progress_tracker.emit_started(DownloadStarted {
+            task_id: request.filename.clone(),
             filename: request.filename.clone(),
             total_size: file_size,
         });
 
         // 创建进度回调
-        let progress_callback =
-            self.create_progress_callback(&progress_tracker, &request.filename, file_size);
+        let progress_callback = self.create_progress_callback(
+            &progress_tracker,
+            &request.filename,
+            &request.filename,
+            file_size,
+        );
 
         // 执行下载
         let download_result = provider
@@ -71,23 +78,26 @@ impl DownloadManager {
         )
     }
 
-    /// 取消指定文件的下载
-    pub fn cancel_download(&self, filename: &str) -> Result<String, String> {
+    /// 取消指定任务，下载任务的 ID 为文件名，上传任务的 ID 见进度事件中的 task_id
+    pub fn cancel_download(&self, task_id: &str) -> Result<String, AppError> {
         let mut downloads = self.active_downloads.lock().unwrap();
 
-        if let Some(cancel_sender) = downloads.remove(filename) {
+        if let Some(cancel_sender) = downloads.remove(task_id) {
             let _ = cancel_sender.send(());
             Ok(format!(
                 "Download cancellation signal sent for: {}",
-                filename
+                task_id
             ))
         } else {
-            Err(format!("No active download found for: {}", filename))
+            Err(AppError::not_found(format!(
+                "No active download found for: {}",
+                task_id
+            )))
         }
     }
 
     /// 取消所有活跃的下载
-    pub fn cancel_all_downloads(&self) -> Result<String, String> {
+    pub fn cancel_all_downloads(&self) -> Result<String, AppError> {
         let mut downloads = self.active_downloads.lock().unwrap();
         let count = downloads.len();
 
@@ -103,9 +113,11 @@ impl DownloadManager {
     }
 
     /// 下载压缩包内文件
+    #[allow(clippy::too_many_arguments)]
     pub async fn download_archive_file_with_progress(
         &self,
         app: tauri::AppHandle,
+        client: Arc<dyn StorageClient + Send + Sync>,
         archive_path: String,
         archive_filename: String,
         entry_path: String,
@@ -119,6 +131,7 @@ impl DownloadManager {
         // 执行压缩包文件下载
         let result = self
             .execute_archive_download(
+                client,
                 &progress_tracker,
                 &archive_path,
                 &archive_filename,
@@ -132,13 +145,89 @@ impl DownloadManager {
         self.handle_download_completion(&entry_filename, result, &save_path, &progress_tracker)
     }
 
+    /// 上传本地文件到当前存储
+    /// 复用下载的进度事件和取消信号，以唯一的任务 ID 登记，避免与同名文件的下载冲突，
+    /// 可通过 download_cancel 按事件中的 task_id 取消
+    pub async fn upload_with_progress(
+        &self,
+        app: tauri::AppHandle,
+        client: Arc<dyn StorageClient + Send + Sync>,
+        local_path: std::path::PathBuf,
+        remote_path: String,
+    ) -> DownloadResult {
+        let filename = local_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| {
+                AppError::invalid_argument(format!("Invalid local path: {}", local_path.display()))
+            })?;
+
+        let file_size = tokio::fs::metadata(&local_path)
+            .await
+            .map_err(|e| AppError::from(e).context("Failed to get file metadata"))?
+            .len();
+
+        // 创建取消信号
+        let task_id = format!("upload-{}", uuid::Uuid::new_v4());
+        let (cancel_tx, mut cancel_rx) = broadcast::channel::<()>(1);
+        {
+            let mut downloads = self.active_downloads.lock().unwrap();
+            downloads.insert(task_id.clone(), cancel_tx);
+        }
+
+        let progress_tracker = ProgressTracker::new(app);
+        progress_tracker.emit_started(DownloadStarted {
+            task_id: task_id.clone(),
+            filename: filename.clone(),
+            total_size: file_size,
+        });
+
+        let progress_callback =
+            self.create_progress_callback(&progress_tracker, &task_id, &filename, file_size);
+
+        let result = client
+            .put_file(
+                &local_path,
+                &remote_path,
+                Some(progress_callback),
+                Some(&mut cancel_rx),
+            )
+            .await;
+
+        {
+            let mut downloads = self.active_downloads.lock().unwrap();
+            downloads.remove(&task_id);
+        }
+
+        match result {
+            Ok(()) => {
+                progress_tracker.emit_completed(DownloadCompleted {
+                    task_id,
+                    filename: filename.clone(),
+                    file_path: remote_path,
+                });
+                Ok(format!("Uploaded {}", filename))
+            }
+            Err(e) => {
+                let error = AppError::from(e);
+                progress_tracker.emit_error(DownloadError {
+                    task_id,
+                    filename,
+                    error: error.message.clone(),
+                    code: error.code,
+                });
+                Err(error)
+            }
+        }
+    }
+
     // === 私有辅助方法 ===
 
     /// 显示文件保存对话框
     fn show_save_file_dialog(
         app: &tauri::AppHandle,
         filename: &str,
-    ) -> Result<Option<std::path::PathBuf>, String> {
+    ) -> Result<Option<std::path::PathBuf>, AppError> {
         use std::sync::mpsc;
         let (tx, rx) = mpsc::channel();
 
@@ -153,9 +242,9 @@ impl DownloadManager {
             Ok(Some(file)) => file
                 .into_path()
                 .map(Some)
-                .map_err(|e| format!("Failed to get path: {}", e)),
+                .map_err(|e| AppError::internal(format!("Failed to get path: {}", e))),
             Ok(None) => Ok(None),
-            Err(_) => Err("Failed to receive file path".to_string()),
+            Err(_) => Err(AppError::internal("Failed to receive file path")),
         }
     }
 
@@ -173,21 +262,21 @@ impl DownloadManager {
             broadcast::Receiver<()>,
             ProgressTracker,
         ),
-        String,
+        AppError,
     > {
         // 获取保存路径
         let save_path = if let Some(custom_path) = custom_save_path {
             let path = std::path::PathBuf::from(custom_path);
             if let Some(parent) = path.parent() {
                 if let Err(e) = std::fs::create_dir_all(parent) {
-                    return Err(format!("Failed to create directory: {}", e));
+                    return Err(AppError::from(e).context("Failed to create directory"));
                 }
             }
             path
         } else {
             match Self::show_save_file_dialog(app, filename)? {
                 Some(path) => path,
-                None => return Err("download.cancelled".to_string()),
+                None => return Err(AppError::cancelled()),
             }
         };
 
@@ -208,10 +297,12 @@ impl DownloadManager {
     fn create_progress_callback(
         &self,
         progress_tracker: &ProgressTracker,
+        task_id: &str,
         filename: &str,
         total_size: u64,
     ) -> ProgressCallback {
         let progress_tracker_clone = progress_tracker.clone();
+        let task_id = task_id.to_string();
         let filename_clone = filename.to_string();
 
         std::sync::Arc::new(move |downloaded: u64, actual_total: u64| {
@@ -226,6 +317,7 @@ impl DownloadManager {
                 let progress =
                     progress_tracker_clone.calculate_progress(downloaded, effective_total);
                 progress_tracker_clone.emit_progress(DownloadProgress {
+                    task_id: task_id.clone(),
                     filename: filename_clone.clone(),
                     downloaded,
                     total_size: effective_total,
@@ -239,7 +331,7 @@ impl DownloadManager {
     fn handle_download_completion(
         &self,
         filename: &str,
-        result: Result<String, String>,
+        result: Result<String, AppError>,
         save_path: &std::path::Path,
         progress_tracker: &ProgressTracker,
     ) -> DownloadResult {
@@ -251,18 +343,21 @@ impl DownloadManager {
         match result {
             Ok(success_msg) => {
                 progress_tracker.emit_completed(DownloadCompleted {
+                    task_id: filename.to_string(),
                     filename: filename.to_string(),
                     file_path: save_path.display().to_string(),
                 });
                 Ok(success_msg)
             }
             Err(error) => {
-                if !error.contains("cancelled") {
+                if !error.is_cancelled() {
                     let _ = std::fs::remove_file(save_path);
                 }
                 progress_tracker.emit_error(DownloadError {
+                    task_id: filename.to_string(),
                     filename: filename.to_string(),
-                    error: error.clone(),
+                    error: error.message.clone(),
+                    code: error.code,
                 });
                 Err(error)
             }
@@ -272,6 +367,7 @@ impl DownloadManager {
     /// 执行压缩包文件下载
     async fn execute_archive_download(
         &self,
+        _client: Arc<dyn StorageClient + Send + Sync>,
         _progress_tracker: &ProgressTracker,
         _archive_path: &str,
         _archive_filename: &str,
@@ -279,10 +375,13 @@ impl DownloadManager {
         _entry_filename: &str,
         _save_path: &std::path::Path,
         _cancel_rx: &mut broadcast::Receiver<()>,
-    ) -> Result<String, String> {
+    ) -> Result<String, AppError> {
         // TODO: 实现压缩包文件下载
         // 这需要压缩包处理服务的支持
-        Err("Archive download not implemented yet".to_string())
+        Err(AppError::new(
+            ErrorCode::Unsupported,
+            "Archive download not implemented yet",
+        ))
     }
 }
 
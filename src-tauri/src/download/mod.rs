@@ -1,7 +1,10 @@
 pub mod manager;
 pub mod progress;
 pub mod provider;
+pub mod sync;
+pub mod transfer;
 pub mod types;
 
 pub use manager::DownloadManager;
+pub use transfer::TransferManager;
 pub use types::*;
@@ -39,6 +39,30 @@ impl ProgressTracker {
         let _ = self.app.emit("download-error", &event);
     }
 
+    pub fn emit_transfer_started(&self, event: TransferStarted) {
+        let _ = self.app.emit("transfer-started", &event);
+        // 重置进度跟踪
+        if let Ok(mut last_progress) = self.last_emitted_progress.lock() {
+            *last_progress = 0;
+        }
+    }
+
+    pub fn emit_transfer_progress(&self, event: TransferProgress) {
+        let _ = self.app.emit("transfer-progress", &event);
+        // 更新最后发送的进度
+        if let Ok(mut last_progress) = self.last_emitted_progress.lock() {
+            *last_progress = event.progress;
+        }
+    }
+
+    pub fn emit_transfer_file_completed(&self, event: TransferFileCompleted) {
+        let _ = self.app.emit("transfer-file-completed", &event);
+    }
+
+    pub fn emit_transfer_completed(&self, event: TransferSummary) {
+        let _ = self.app.emit("transfer-completed", &event);
+    }
+
     pub fn should_emit_progress(&self, downloaded: u64, total_size: u64) -> bool {
         let current_progress = self.calculate_progress(downloaded, total_size);
 
@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::download::types::*;
+use crate::storage::search::parse_lastmod;
+use crate::storage::traits::{ListOptions, StorageClient, StorageError, StorageFile};
+use crate::utils::path_utils::PathUtils;
+
+/// 遍历目录时每页的条目数
+const LIST_PAGE_SIZE: u32 = 1000;
+/// 比较修改时间时允许的误差，部分协议只保留秒级精度
+const MTIME_TOLERANCE_SECS: i64 = 1;
+
+/// 递归列出的目录树，路径均相对于根目录
+#[derive(Debug, Default)]
+pub struct TreeListing {
+    pub files: BTreeMap<String, StorageFile>,
+    pub directories: BTreeSet<String>,
+}
+
+/// 判断文件是否需要更新的比较项
+#[derive(Debug, Clone, Copy)]
+struct SyncChecks {
+    size: bool,
+    mtime: bool,
+    etag: bool,
+}
+
+impl SyncChecks {
+    fn parse(checks: Option<&[String]>) -> Result<Self, StorageError> {
+        let Some(checks) = checks else {
+            return Ok(Self {
+                size: true,
+                mtime: true,
+                etag: false,
+            });
+        };
+
+        let mut result = Self {
+            size: false,
+            mtime: false,
+            etag: false,
+        };
+        for check in checks {
+            match check.as_str() {
+                "size" => result.size = true,
+                "mtime" => result.mtime = true,
+                "etag" => result.etag = true,
+                other => {
+                    return Err(StorageError::InvalidConfig(format!(
+                        "Unsupported sync check: {}",
+                        other
+                    )))
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// 目录同步规划器
+/// 基于 list_directory 递归比较两端目录，生成创建、更新和删除操作
+pub struct SyncPlanner;
+
+impl SyncPlanner {
+    /// 生成同步计划，不修改任何一端
+    pub async fn plan(
+        source: &(dyn StorageClient + Send + Sync),
+        destination: &(dyn StorageClient + Send + Sync),
+        request: &SyncRequest,
+        cancelled: Option<&AtomicBool>,
+    ) -> Result<SyncPlan, StorageError> {
+        let checks = SyncChecks::parse(request.checks.as_deref())?;
+        let destination_root = request.destination_path.trim_end_matches('/');
+
+        let source_tree = Self::list_tree(source, &request.source_path, true, cancelled).await?;
+
+        // 目标目录不存在时视为空目录
+        let destination_tree = match destination.stat(destination_root).await {
+            Ok(metadata) if metadata.file_type == "directory" => {
+                Self::list_tree(destination, destination_root, true, cancelled).await?
+            }
+            Ok(_) => {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Sync destination is not a directory: {}",
+                    request.destination_path
+                )))
+            }
+            Err(StorageError::NotFound(_)) => TreeListing::default(),
+            Err(e) => return Err(e),
+        };
+
+        let mut actions = Vec::new();
+        let (mut creates, mut updates, mut deletes, mut unchanged) = (0u32, 0u32, 0u32, 0u32);
+        let mut bytes_to_transfer = 0u64;
+
+        // 目录按字典序排列，父目录总在子目录之前
+        for directory in source_tree
+            .directories
+            .difference(&destination_tree.directories)
+        {
+            actions.push(SyncAction {
+                action: "mkdir".to_string(),
+                source_path: Some(PathUtils::join_storage_path(
+                    &request.source_path,
+                    directory,
+                )),
+                destination_path: PathUtils::join_storage_path(destination_root, directory),
+                size: "0".to_string(),
+                reason: None,
+            });
+        }
+
+        for (relative_path, file) in &source_tree.files {
+            let (action, reason) = match destination_tree.files.get(relative_path) {
+                None => ("create", None),
+                Some(existing) => match Self::compare(file, existing, checks) {
+                    Some(reason) => ("update", Some(reason.to_string())),
+                    None => {
+                        unchanged += 1;
+                        continue;
+                    }
+                },
+            };
+
+            if action == "create" {
+                creates += 1;
+            } else {
+                updates += 1;
+            }
+            bytes_to_transfer += file.size.parse::<u64>().unwrap_or(0);
+
+            actions.push(SyncAction {
+                action: action.to_string(),
+                source_path: Some(PathUtils::join_storage_path(
+                    &request.source_path,
+                    relative_path,
+                )),
+                destination_path: PathUtils::join_storage_path(destination_root, relative_path),
+                size: file.size.clone(),
+                reason,
+            });
+        }
+
+        if request.delete_extraneous.unwrap_or(false) {
+            // 目录删除是递归的，只需删除最上层的多余目录
+            let extraneous_directories: Vec<&String> = destination_tree
+                .directories
+                .difference(&source_tree.directories)
+                .collect();
+            let covered = |path: &str| {
+                extraneous_directories
+                    .iter()
+                    .any(|directory| path.starts_with(&format!("{}/", directory)))
+            };
+
+            for (relative_path, file) in &destination_tree.files {
+                if source_tree.files.contains_key(relative_path) || covered(relative_path) {
+                    continue;
+                }
+                deletes += 1;
+                actions.push(SyncAction {
+                    action: "delete".to_string(),
+                    source_path: None,
+                    destination_path: PathUtils::join_storage_path(destination_root, relative_path),
+                    size: file.size.clone(),
+                    reason: None,
+                });
+            }
+
+            for directory in &extraneous_directories {
+                if covered(directory) {
+                    continue;
+                }
+                deletes += 1;
+                actions.push(SyncAction {
+                    action: "delete".to_string(),
+                    source_path: None,
+                    destination_path: PathUtils::join_storage_path(destination_root, directory),
+                    size: "0".to_string(),
+                    reason: None,
+                });
+            }
+        }
+
+        Ok(SyncPlan {
+            source_path: request.source_path.clone(),
+            destination_path: destination_root.to_string(),
+            actions,
+            creates,
+            updates,
+            deletes,
+            unchanged,
+            bytes_to_transfer: bytes_to_transfer.to_string(),
+        })
+    }
+
+    /// 递归列出目录树，处理分页，收到取消时中止
+    pub async fn list_tree(
+        client: &(dyn StorageClient + Send + Sync),
+        root: &str,
+        recursive: bool,
+        cancelled: Option<&AtomicBool>,
+    ) -> Result<TreeListing, StorageError> {
+        let mut listing = TreeListing::default();
+        let mut pending = vec![String::new()];
+
+        while let Some(relative_dir) = pending.pop() {
+            let directory = PathUtils::join_storage_path(root, &relative_dir);
+            let mut marker: Option<String> = None;
+            loop {
+                if cancelled.is_some_and(|cancelled| cancelled.load(Ordering::SeqCst)) {
+                    return Err(StorageError::Cancelled);
+                }
+
+                let options = ListOptions {
+                    page_size: Some(LIST_PAGE_SIZE),
+                    marker: marker.clone(),
+                    prefix: None,
+                    recursive: Some(false),
+                    ..Default::default()
+                };
+                let result = client.list_directory(&directory, Some(&options)).await?;
+
+                for file in result.files {
+                    let relative_path = PathUtils::join_storage_path(&relative_dir, &file.basename);
+                    if file.file_type == "directory" {
+                        if recursive {
+                            listing.directories.insert(relative_path.clone());
+                            pending.push(relative_path);
+                        }
+                    } else {
+                        listing.files.insert(relative_path, file);
+                    }
+                }
+
+                if result.has_more && result.next_marker.is_some() {
+                    marker = result.next_marker;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        Ok(listing)
+    }
+
+    /// 比较两端文件，需要更新时返回原因
+    fn compare(
+        source: &StorageFile,
+        destination: &StorageFile,
+        checks: SyncChecks,
+    ) -> Option<&'static str> {
+        if checks.size && source.size != destination.size {
+            return Some("size");
+        }
+
+        if checks.mtime {
+            // 只在源文件明显更新时才更新，上传后目标端的修改时间总是晚于源文件
+            if let (Some(source_time), Some(destination_time)) = (
+                parse_lastmod(&source.lastmod),
+                parse_lastmod(&destination.lastmod),
+            ) {
+                if source_time.timestamp() > destination_time.timestamp() + MTIME_TOLERANCE_SECS {
+                    return Some("mtime");
+                }
+            }
+        }
+
+        if checks.etag {
+            if let (Some(source_etag), Some(destination_etag)) = (&source.etag, &destination.etag) {
+                if source_etag.trim_matches('"') != destination_etag.trim_matches('"') {
+                    return Some("etag");
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,584 @@
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+use crate::download::sync::SyncPlanner;
+use crate::download::{progress::ProgressTracker, types::*};
+use crate::error::{AppError, ErrorCode};
+use crate::storage::retrying_client::RetryPolicy;
+use crate::storage::traits::{ProgressCallback, StorageClient, StorageError};
+use crate::utils::path_utils::PathUtils;
+
+/// 默认同时传输的文件数
+const DEFAULT_CONCURRENCY: u32 = 4;
+/// 同时传输的文件数上限
+const MAX_CONCURRENCY: u32 = 32;
+
+/// 目标已存在时的跳过策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SkipMode {
+    None,
+    Size,
+    SizeAndEtag,
+}
+
+impl SkipMode {
+    fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.unwrap_or("size") {
+            "none" => Ok(Self::None),
+            "size" => Ok(Self::Size),
+            "sizeAndEtag" => Ok(Self::SizeAndEtag),
+            other => Err(format!("Unsupported skip mode: {}", other)),
+        }
+    }
+}
+
+/// 待传输的单个文件
+#[derive(Debug, Clone)]
+struct TransferEntry {
+    source_path: String,
+    destination_path: String,
+    size: u64,
+    etag: Option<String>,
+}
+
+/// 正在进行的传输任务的取消句柄
+/// 先设置标志再发送信号，保证尚未开始的文件也能看到取消状态
+#[derive(Clone)]
+struct TransferHandle {
+    cancel_tx: broadcast::Sender<()>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TransferHandle {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        let _ = self.cancel_tx.send(());
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// 传输任务的共享状态
+struct TransferState {
+    job_id: String,
+    total_files: u32,
+    total_size: u64,
+    skip_mode: SkipMode,
+    concurrency: usize,
+    started_at: std::time::Instant,
+    // 已处理的字节数，包含已跳过和失败文件的大小，用于计算总体进度
+    processed: AtomicU64,
+    files_completed: AtomicU32,
+    progress_tracker: ProgressTracker,
+}
+
+impl TransferState {
+    fn new(
+        app: tauri::AppHandle,
+        job_id: &str,
+        entries: &[TransferEntry],
+        deletions: u32,
+        skip_mode: SkipMode,
+        concurrency: Option<u32>,
+        started_at: std::time::Instant,
+    ) -> Self {
+        Self {
+            job_id: job_id.to_string(),
+            total_files: entries.len() as u32 + deletions,
+            total_size: entries.iter().map(|entry| entry.size).sum(),
+            skip_mode,
+            concurrency: concurrency
+                .unwrap_or(DEFAULT_CONCURRENCY)
+                .clamp(1, MAX_CONCURRENCY) as usize,
+            started_at,
+            processed: AtomicU64::new(0),
+            files_completed: AtomicU32::new(0),
+            progress_tracker: ProgressTracker::new(app),
+        }
+    }
+
+    /// 记录单个文件的处理结果并发送文件完成事件
+    fn finish_file(
+        &self,
+        source_path: String,
+        destination_path: String,
+        size: u64,
+        result: Result<&str, StorageError>,
+    ) -> TransferFileResult {
+        let (status, error) = match result {
+            Ok(status) => (status, None),
+            Err(StorageError::Cancelled) => ("cancelled", None),
+            Err(e) => ("failed", Some(e.to_string())),
+        };
+
+        let result = TransferFileResult {
+            source_path,
+            destination_path,
+            size: size.to_string(),
+            status: status.to_string(),
+            error,
+        };
+
+        self.progress_tracker
+            .emit_transfer_file_completed(TransferFileCompleted {
+                job_id: self.job_id.clone(),
+                result: result.clone(),
+            });
+
+        result
+    }
+
+    /// 更新单个文件的已处理字节数，并按需发送进度事件
+    fn update_file_progress(&self, file_done: &AtomicU64, done: u64, current_file: &str) {
+        let previous = file_done.swap(done, Ordering::SeqCst);
+        let processed = if done >= previous {
+            self.processed.fetch_add(done - previous, Ordering::SeqCst) + (done - previous)
+        } else {
+            self.processed.fetch_sub(previous - done, Ordering::SeqCst) - (previous - done)
+        };
+
+        if self
+            .progress_tracker
+            .should_emit_progress(processed, self.total_size)
+        {
+            self.progress_tracker
+                .emit_transfer_progress(TransferProgress {
+                    job_id: self.job_id.clone(),
+                    current_file: current_file.to_string(),
+                    files_completed: self.files_completed.load(Ordering::SeqCst),
+                    total_files: self.total_files,
+                    transferred: processed,
+                    total_size: self.total_size,
+                    progress: self
+                        .progress_tracker
+                        .calculate_progress(processed, self.total_size),
+                });
+        }
+    }
+}
+
+/// 跨存储传输管理器
+/// 从一个已连接的存储客户端流式读取，直接写入另一个存储客户端，不经过本地磁盘
+pub struct TransferManager {
+    active_transfers: Arc<Mutex<HashMap<String, TransferHandle>>>,
+}
+
+impl TransferManager {
+    pub fn new() -> Self {
+        Self {
+            active_transfers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 执行传输任务，返回汇总报告
+    /// 单个文件失败不会中断整个任务，失败信息记录在汇总报告中
+    pub async fn transfer_with_progress(
+        &self,
+        app: tauri::AppHandle,
+        source: Arc<dyn StorageClient + Send + Sync>,
+        destination: Arc<dyn StorageClient + Send + Sync>,
+        request: TransferRequest,
+    ) -> Result<TransferSummary, AppError> {
+        let (job_id, handle) = self.register(request.job_id.clone())?;
+
+        let result = Self::run_transfer(app, source, destination, &request, &job_id, &handle).await;
+
+        self.unregister(&job_id);
+        result.map_err(AppError::from)
+    }
+
+    /// 按同步计划执行目录同步：创建缺失的目录，复制新增和变更的文件，最后删除多余的文件
+    /// 中断后重新执行时会重新比较两端，已完成的文件不会再次传输
+    pub async fn sync_with_progress(
+        &self,
+        app: tauri::AppHandle,
+        source: Arc<dyn StorageClient + Send + Sync>,
+        destination: Arc<dyn StorageClient + Send + Sync>,
+        request: SyncRequest,
+    ) -> Result<TransferSummary, AppError> {
+        let (job_id, handle) = self.register(request.job_id.clone())?;
+
+        let result = Self::run_sync(app, source, destination, &request, &job_id, &handle).await;
+
+        self.unregister(&job_id);
+        result.map_err(AppError::from)
+    }
+
+    /// 取消指定的传输任务
+    pub fn cancel_transfer(&self, job_id: &str) -> Result<String, AppError> {
+        let transfers = self.active_transfers.lock().unwrap();
+
+        if let Some(handle) = transfers.get(job_id) {
+            handle.cancel();
+            Ok(format!("Transfer cancellation signal sent for: {}", job_id))
+        } else {
+            Err(AppError::not_found(format!(
+                "No active transfer found for: {}",
+                job_id
+            )))
+        }
+    }
+
+    /// 取消所有正在进行的传输任务
+    pub fn cancel_all_transfers(&self) -> Result<String, AppError> {
+        let transfers = self.active_transfers.lock().unwrap();
+        let count = transfers.len();
+
+        if count == 0 {
+            return Ok("No active transfers to cancel".to_string());
+        }
+
+        for handle in transfers.values() {
+            handle.cancel();
+        }
+
+        Ok(format!("Cancellation signal sent to {} transfers", count))
+    }
+
+    // === 私有辅助方法 ===
+
+    /// 登记任务并创建取消句柄，未指定任务 ID 时自动生成
+    fn register(&self, job_id: Option<String>) -> Result<(String, TransferHandle), AppError> {
+        let job_id =
+            job_id.unwrap_or_else(|| format!("transfer_{}", uuid::Uuid::new_v4().simple()));
+
+        let handle = TransferHandle {
+            cancel_tx: broadcast::channel::<()>(1).0,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+
+        let mut transfers = self.active_transfers.lock().unwrap();
+        if transfers.contains_key(&job_id) {
+            return Err(AppError::new(
+                ErrorCode::Conflict,
+                format!("Transfer already running: {}", job_id),
+            ));
+        }
+        transfers.insert(job_id.clone(), handle.clone());
+
+        Ok((job_id, handle))
+    }
+
+    fn unregister(&self, job_id: &str) {
+        let mut transfers = self.active_transfers.lock().unwrap();
+        transfers.remove(job_id);
+    }
+
+    /// 在目标端创建目录，不支持创建目录的后端（如 HF 仓库）上传文件时目录自动出现，跳过即可
+    async fn ensure_directory(
+        destination: &(dyn StorageClient + Send + Sync),
+        path: &str,
+    ) -> Result<(), StorageError> {
+        match destination.create_directory(path).await {
+            Err(StorageError::ProtocolNotSupported(_)) => Ok(()),
+            result => result,
+        }
+    }
+
+    async fn run_transfer(
+        app: tauri::AppHandle,
+        source: Arc<dyn StorageClient + Send + Sync>,
+        destination: Arc<dyn StorageClient + Send + Sync>,
+        request: &TransferRequest,
+        job_id: &str,
+        handle: &TransferHandle,
+    ) -> Result<TransferSummary, StorageError> {
+        let started_at = std::time::Instant::now();
+        let skip_mode = SkipMode::parse(request.skip_existing.as_deref())
+            .map_err(StorageError::InvalidConfig)?;
+
+        // 确定需要传输的文件列表
+        let metadata = source.stat(&request.source_path).await?;
+        let entries = if metadata.file_type == "directory" {
+            let destination_root = request.destination_path.trim_end_matches('/').to_string();
+            let listing = SyncPlanner::list_tree(
+                source.as_ref(),
+                &request.source_path,
+                request.recursive.unwrap_or(true),
+                Some(&handle.cancelled),
+            )
+            .await?;
+
+            // 先按层级创建目标目录，部分协议上传文件前要求父目录已存在
+            if !destination_root.is_empty() {
+                Self::ensure_directory(destination.as_ref(), &destination_root).await?;
+            }
+            for directory in &listing.directories {
+                Self::ensure_directory(
+                    destination.as_ref(),
+                    &PathUtils::join_storage_path(&destination_root, directory),
+                )
+                .await?;
+            }
+
+            listing
+                .files
+                .into_iter()
+                .map(|(relative_path, file)| TransferEntry {
+                    source_path: PathUtils::join_storage_path(&request.source_path, &relative_path),
+                    destination_path: PathUtils::join_storage_path(
+                        &destination_root,
+                        &relative_path,
+                    ),
+                    size: file.size.parse().unwrap_or(0),
+                    etag: file.etag,
+                })
+                .collect()
+        } else {
+            // 目标路径以斜杠结尾时视为目录，沿用源文件名
+            let destination_path = if request.destination_path.ends_with('/') {
+                format!("{}{}", request.destination_path, metadata.basename)
+            } else {
+                request.destination_path.clone()
+            };
+            vec![TransferEntry {
+                source_path: request.source_path.clone(),
+                destination_path,
+                size: metadata.size.parse().unwrap_or(0),
+                etag: metadata.etag,
+            }]
+        };
+
+        let state = Arc::new(TransferState::new(
+            app,
+            job_id,
+            &entries,
+            0,
+            skip_mode,
+            request.concurrency,
+            started_at,
+        ));
+
+        Ok(Self::execute(
+            source.as_ref(),
+            destination.as_ref(),
+            entries,
+            Vec::new(),
+            handle,
+            state,
+        )
+        .await)
+    }
+
+    async fn run_sync(
+        app: tauri::AppHandle,
+        source: Arc<dyn StorageClient + Send + Sync>,
+        destination: Arc<dyn StorageClient + Send + Sync>,
+        request: &SyncRequest,
+        job_id: &str,
+        handle: &TransferHandle,
+    ) -> Result<TransferSummary, StorageError> {
+        let started_at = std::time::Instant::now();
+        let plan = SyncPlanner::plan(
+            source.as_ref(),
+            destination.as_ref(),
+            request,
+            Some(&handle.cancelled),
+        )
+        .await?;
+
+        if !plan.destination_path.is_empty() {
+            Self::ensure_directory(destination.as_ref(), &plan.destination_path).await?;
+        }
+
+        let mut entries = Vec::new();
+        let mut deletions = Vec::new();
+        for action in plan.actions {
+            match (action.action.as_str(), action.source_path) {
+                ("mkdir", _) => {
+                    Self::ensure_directory(destination.as_ref(), &action.destination_path).await?
+                }
+                ("create" | "update", Some(source_path)) => entries.push(TransferEntry {
+                    source_path,
+                    destination_path: action.destination_path,
+                    size: action.size.parse().unwrap_or(0),
+                    etag: None,
+                }),
+                ("delete", _) => deletions.push(action.destination_path),
+                _ => {}
+            }
+        }
+
+        // 计划已经比较过两端，复制时不再跳过
+        let state = Arc::new(TransferState::new(
+            app,
+            job_id,
+            &entries,
+            deletions.len() as u32,
+            SkipMode::None,
+            request.concurrency,
+            started_at,
+        ));
+
+        Ok(Self::execute(
+            source.as_ref(),
+            destination.as_ref(),
+            entries,
+            deletions,
+            handle,
+            state,
+        )
+        .await)
+    }
+
+    /// 并发复制文件，然后依次删除目标端的多余路径，发送进度事件并生成汇总报告
+    /// 有文件复制失败时跳过删除，避免在同步不完整时删除目标端的数据
+    async fn execute(
+        source: &(dyn StorageClient + Send + Sync),
+        destination: &(dyn StorageClient + Send + Sync),
+        entries: Vec<TransferEntry>,
+        deletions: Vec<String>,
+        handle: &TransferHandle,
+        state: Arc<TransferState>,
+    ) -> TransferSummary {
+        state
+            .progress_tracker
+            .emit_transfer_started(TransferStarted {
+                job_id: state.job_id.clone(),
+                total_files: state.total_files,
+                total_size: state.total_size,
+            });
+
+        let mut files: Vec<TransferFileResult> = futures_util::stream::iter(entries)
+            .map(|entry| Self::transfer_entry(source, destination, entry, handle, &state))
+            .buffer_unordered(state.concurrency)
+            .collect()
+            .await;
+
+        let copy_failed = files.iter().any(|file| file.status == "failed");
+        for path in deletions {
+            let result = if handle.is_cancelled() {
+                Err(StorageError::Cancelled)
+            } else if copy_failed {
+                Ok("skipped")
+            } else {
+                destination.delete(&path).await.map(|_| "deleted")
+            };
+            state.files_completed.fetch_add(1, Ordering::SeqCst);
+            files.push(state.finish_file(String::new(), path, 0, result));
+        }
+
+        let count = |status: &str| files.iter().filter(|file| file.status == status).count() as u32;
+        let bytes_transferred: u64 = files
+            .iter()
+            .filter(|file| file.status == "copied")
+            .map(|file| file.size.parse::<u64>().unwrap_or(0))
+            .sum();
+
+        let summary = TransferSummary {
+            job_id: state.job_id.clone(),
+            total_files: state.total_files,
+            copied: count("copied"),
+            skipped: count("skipped"),
+            failed: count("failed"),
+            deleted: count("deleted"),
+            cancelled: handle.is_cancelled(),
+            bytes_transferred: bytes_transferred.to_string(),
+            duration_ms: state.started_at.elapsed().as_millis().to_string(),
+            files,
+        };
+
+        state
+            .progress_tracker
+            .emit_transfer_completed(summary.clone());
+
+        summary
+    }
+
+    /// 传输单个文件，失败时按默认重试策略重新打开源文件重试
+    async fn transfer_entry(
+        source: &(dyn StorageClient + Send + Sync),
+        destination: &(dyn StorageClient + Send + Sync),
+        entry: TransferEntry,
+        handle: &TransferHandle,
+        state: &Arc<TransferState>,
+    ) -> TransferFileResult {
+        let file_done = Arc::new(AtomicU64::new(0));
+
+        let status = if handle.is_cancelled() {
+            Err(StorageError::Cancelled)
+        } else if Self::should_skip(destination, &entry, state.skip_mode).await {
+            Ok("skipped")
+        } else {
+            let progress_callback: ProgressCallback = {
+                let state = state.clone();
+                let file_done = file_done.clone();
+                let current_file = entry.source_path.clone();
+                Arc::new(move |done: u64, _total: u64| {
+                    state.update_file_progress(&file_done, done, &current_file);
+                })
+            };
+
+            RetryPolicy::new(None)
+                .run("transfer", &entry.source_path, || async {
+                    // 每次尝试重新订阅取消信号，订阅后再检查标志，避免错过取消
+                    let mut cancel_rx = handle.cancel_tx.subscribe();
+                    if handle.is_cancelled() {
+                        return Err(StorageError::Cancelled);
+                    }
+
+                    let stream = source.open_read_stream(&entry.source_path, 0).await?;
+                    destination
+                        .put_stream(
+                            stream,
+                            entry.size,
+                            &entry.destination_path,
+                            Some(progress_callback.clone()),
+                            Some(&mut cancel_rx),
+                        )
+                        .await
+                })
+                .await
+                .map(|_| "copied")
+        };
+
+        // 无论结果如何，该文件都计入已处理的进度
+        state.files_completed.fetch_add(1, Ordering::SeqCst);
+        state.update_file_progress(&file_done, entry.size, &entry.source_path);
+
+        state.finish_file(
+            entry.source_path,
+            entry.destination_path,
+            entry.size,
+            status,
+        )
+    }
+
+    /// 判断目标文件是否已存在且与源文件一致
+    async fn should_skip(
+        destination: &(dyn StorageClient + Send + Sync),
+        entry: &TransferEntry,
+        skip_mode: SkipMode,
+    ) -> bool {
+        if skip_mode == SkipMode::None {
+            return false;
+        }
+
+        let existing = match destination.stat(&entry.destination_path).await {
+            Ok(existing) if existing.file_type == "file" => existing,
+            _ => return false,
+        };
+
+        if existing.size.parse::<u64>().ok() != Some(entry.size) {
+            return false;
+        }
+
+        match skip_mode {
+            SkipMode::SizeAndEtag => {
+                matches!((&existing.etag, &entry.etag), (Some(a), Some(b)) if a == b)
+            }
+            _ => true,
+        }
+    }
+}
+
+impl Default for TransferManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::{AppError, ErrorCode};
+
 #[derive(Debug, Clone)]
 pub struct DownloadRequest {
     pub url: String,
@@ -8,6 +10,8 @@ pub struct DownloadRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadProgress {
+    /// 任务 ID，用于 download_cancel；下载任务为文件名，上传任务为唯一 ID
+    pub task_id: String,
     pub filename: String,
     pub downloaded: u64,
     pub total_size: u64,
@@ -16,20 +20,147 @@ pub struct DownloadProgress {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadStarted {
+    /// 任务 ID，用于 download_cancel；下载任务为文件名，上传任务为唯一 ID
+    pub task_id: String,
     pub filename: String,
     pub total_size: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadCompleted {
+    /// 任务 ID，用于 download_cancel；下载任务为文件名，上传任务为唯一 ID
+    pub task_id: String,
     pub filename: String,
     pub file_path: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadError {
+    /// 任务 ID，用于 download_cancel；下载任务为文件名，上传任务为唯一 ID
+    pub task_id: String,
     pub filename: String,
     pub error: String,
+    pub code: ErrorCode,
+}
+
+pub type DownloadResult = Result<String, AppError>;
+
+/// 跨存储传输请求
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferRequest {
+    /// 任务 ID，未指定时自动生成，可用于 transfer_cancel
+    pub job_id: Option<String>,
+    /// 源连接 ID，未指定时使用窗口绑定的连接
+    pub source_connection_id: Option<String>,
+    pub source_path: String,
+    /// 目标连接 ID，未指定时使用窗口绑定的连接
+    pub destination_connection_id: Option<String>,
+    pub destination_path: String,
+    /// 源路径为目录时是否递归传输子目录，默认 true
+    pub recursive: Option<bool>,
+    /// 同时传输的文件数，默认 4
+    pub concurrency: Option<u32>,
+    /// 目标已存在时的跳过策略："none"、"size"（默认）或 "sizeAndEtag"
+    pub skip_existing: Option<String>,
 }
 
-pub type DownloadResult = Result<String, String>;
+/// 单个文件的传输结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferFileResult {
+    pub source_path: String,
+    pub destination_path: String,
+    pub size: String, // 使用字符串表示大数字
+    /// "copied"、"skipped"、"deleted"、"failed" 或 "cancelled"
+    pub status: String,
+    pub error: Option<String>,
+}
+
+/// 传输任务的汇总报告
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferSummary {
+    pub job_id: String,
+    pub total_files: u32,
+    pub copied: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    /// 同步时删除的目标端多余文件和目录数
+    pub deleted: u32,
+    pub cancelled: bool,
+    pub bytes_transferred: String, // 使用字符串表示大数字
+    pub duration_ms: String,
+    pub files: Vec<TransferFileResult>,
+}
+
+/// 目录同步请求
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncRequest {
+    /// 任务 ID，未指定时自动生成，可用于 transfer_cancel
+    pub job_id: Option<String>,
+    /// 源连接 ID，未指定时使用窗口绑定的连接
+    pub source_connection_id: Option<String>,
+    pub source_path: String,
+    /// 目标连接 ID，未指定时使用窗口绑定的连接
+    pub destination_connection_id: Option<String>,
+    pub destination_path: String,
+    /// 判断文件是否需要更新的比较项："size"、"mtime"、"etag"，默认 size 和 mtime
+    pub checks: Option<Vec<String>>,
+    /// 是否删除目标端存在但源端不存在的文件和目录，默认 false；有文件复制失败时不删除
+    pub delete_extraneous: Option<bool>,
+    /// 同时传输的文件数，默认 4
+    pub concurrency: Option<u32>,
+}
+
+/// 同步计划中的单个操作
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncAction {
+    /// "mkdir"、"create"、"update" 或 "delete"
+    pub action: String,
+    pub source_path: Option<String>,
+    pub destination_path: String,
+    pub size: String, // 使用字符串表示大数字
+    /// 需要更新的原因："size"、"mtime" 或 "etag"
+    pub reason: Option<String>,
+}
+
+/// 同步计划（dry-run 结果）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPlan {
+    pub source_path: String,
+    pub destination_path: String,
+    pub actions: Vec<SyncAction>,
+    pub creates: u32,
+    pub updates: u32,
+    pub deletes: u32,
+    pub unchanged: u32,
+    pub bytes_to_transfer: String, // 使用字符串表示大数字
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferStarted {
+    pub job_id: String,
+    pub total_files: u32,
+    pub total_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProgress {
+    pub job_id: String,
+    pub current_file: String,
+    pub files_completed: u32,
+    pub total_files: u32,
+    pub transferred: u64,
+    pub total_size: u64,
+    pub progress: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferFileCompleted {
+    pub job_id: String,
+    pub result: TransferFileResult,
+}
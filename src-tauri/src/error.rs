@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::storage::traits::StorageError;
+
+/// 稳定的错误码，前端据此做程序化处理，新增错误码时不要修改已有取值
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorCode {
+    ConnectionFailed,
+    AuthenticationFailed,
+    PermissionDenied,
+    NotFound,
+    InvalidArgument,
+    Unsupported,
+    NotConnected,
+    ReadOnly,
+    NetworkError,
+    Timeout,
+    RateLimited,
+    Conflict,
+    Cancelled,
+    IoError,
+    RequestFailed,
+    Locked,
+    Internal,
+}
+
+impl ErrorCode {
+    /// 错误码对应的默认国际化键
+    pub fn i18n_key(&self) -> &'static str {
+        match self {
+            ErrorCode::ConnectionFailed => "error.connection.failed",
+            ErrorCode::AuthenticationFailed => "error.authentication.failed",
+            ErrorCode::PermissionDenied => "error.access.denied",
+            ErrorCode::NotFound => "error.not.found",
+            ErrorCode::InvalidArgument => "error.invalid.argument",
+            ErrorCode::Unsupported => "error.unsupported",
+            ErrorCode::NotConnected => "error.not.connected",
+            ErrorCode::ReadOnly => "storage.read.only",
+            ErrorCode::NetworkError => "error.network.failed",
+            ErrorCode::Timeout => "error.timeout",
+            ErrorCode::RateLimited => "error.rate.limited",
+            ErrorCode::Conflict => "error.conflict",
+            ErrorCode::Cancelled => "download.cancelled",
+            ErrorCode::IoError => "error.io",
+            ErrorCode::RequestFailed => "error.request.failed",
+            ErrorCode::Locked => "profile.store.locked",
+            ErrorCode::Internal => "error.unknown",
+        }
+    }
+
+    /// 该类错误稍后重试是否可能成功
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::NetworkError | ErrorCode::Timeout | ErrorCode::RateLimited
+        )
+    }
+}
+
+/// 应用统一错误类型
+/// 所有 Tauri 命令以此作为错误返回，前端按 code 判断错误类别，
+/// 按 i18n_key 和 params 显示本地化提示，message 保留原始的英文描述
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, thiserror::Error)]
+#[serde(rename_all = "camelCase")]
+#[error("{message}")]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub i18n_key: String,
+    /// 国际化参数，detail 为底层错误的详细信息
+    pub params: BTreeMap<String, String>,
+    pub message: String,
+    pub retryable: bool,
+    /// 错误来源链，由外到内依次为被包装的底层错误描述
+    pub sources: Vec<String>,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            i18n_key: code.i18n_key().to_string(),
+            params: BTreeMap::new(),
+            message: message.into(),
+            retryable: code.is_retryable(),
+            sources: Vec::new(),
+        }
+    }
+
+    /// 以国际化键作为消息的错误，前端可直接按键翻译
+    pub fn i18n(code: ErrorCode, key: impl Into<String>) -> Self {
+        let key = key.into();
+        let mut error = Self::new(code, key.clone());
+        error.i18n_key = key;
+        error
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidArgument, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn cancelled() -> Self {
+        Self::i18n(ErrorCode::Cancelled, "download.cancelled")
+    }
+
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+
+    /// 为错误附加上下文描述，原消息移入来源链；取消错误保持原样，便于前端识别
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        if self.code == ErrorCode::Cancelled {
+            return self;
+        }
+        let context = context.into();
+        self.sources.insert(0, self.message.clone());
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.code == ErrorCode::Cancelled
+    }
+
+    /// 判断字符串是否为国际化键（如 archive.format.7z.not.supported）
+    fn is_i18n_key(message: &str) -> bool {
+        message.contains('.')
+            && !message.starts_with('.')
+            && !message.ends_with('.')
+            && message
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.' || c == '_')
+    }
+
+    /// 按国际化键推断错误码
+    fn code_for_key(key: &str) -> ErrorCode {
+        if key.ends_with(".cancelled") {
+            ErrorCode::Cancelled
+        } else if key.ends_with(".not.supported") {
+            ErrorCode::Unsupported
+        } else if key.ends_with(".not.found") {
+            ErrorCode::NotFound
+        } else if key == "storage.read.only" {
+            ErrorCode::ReadOnly
+        } else {
+            ErrorCode::Internal
+        }
+    }
+}
+
+impl From<StorageError> for AppError {
+    fn from(error: StorageError) -> Self {
+        let message = error.to_string();
+        let retryable = error.is_retryable();
+        let (code, detail) = match error {
+            StorageError::ConnectionFailed(detail) => (ErrorCode::ConnectionFailed, Some(detail)),
+            StorageError::AuthenticationFailed(detail) => {
+                (ErrorCode::AuthenticationFailed, Some(detail))
+            }
+            StorageError::RequestFailed(detail) => (ErrorCode::RequestFailed, Some(detail)),
+            StorageError::NotFound(detail) => (ErrorCode::NotFound, Some(detail)),
+            StorageError::InvalidConfig(detail) => (ErrorCode::InvalidArgument, Some(detail)),
+            StorageError::ProtocolNotSupported(detail)
+            | StorageError::UnsupportedProtocol(detail) => (ErrorCode::Unsupported, Some(detail)),
+            StorageError::NotConnected => (ErrorCode::NotConnected, None),
+            StorageError::IoError(detail) => (ErrorCode::IoError, Some(detail)),
+            StorageError::NetworkError(detail) => (ErrorCode::NetworkError, Some(detail)),
+            StorageError::RateLimited(detail, retry_after) => {
+                let mut error = AppError::new(ErrorCode::RateLimited, message);
+                error.params.insert("detail".to_string(), detail);
+                if let Some(secs) = retry_after {
+                    error
+                        .params
+                        .insert("retryAfter".to_string(), secs.to_string());
+                }
+                return error;
+            }
+            StorageError::PermissionDenied(detail) if detail == "storage.read.only" => {
+                return AppError::i18n(ErrorCode::ReadOnly, detail);
+            }
+            StorageError::PermissionDenied(detail) => (ErrorCode::PermissionDenied, Some(detail)),
+            StorageError::Timeout(detail) => (ErrorCode::Timeout, Some(detail)),
+            StorageError::Cancelled => return AppError::cancelled(),
+            StorageError::Conflict(detail) => (ErrorCode::Conflict, Some(detail)),
+        };
+
+        let mut app_error = AppError::new(code, message);
+        app_error.retryable = retryable;
+        if let Some(detail) = detail {
+            app_error.params.insert("detail".to_string(), detail);
+        }
+        app_error
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        if Self::is_i18n_key(&message) {
+            AppError::i18n(Self::code_for_key(&message), message)
+        } else {
+            AppError::internal(message.clone()).with_param("detail", message)
+        }
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::from(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        let code = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+            std::io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+            std::io::ErrorKind::TimedOut => ErrorCode::Timeout,
+            std::io::ErrorKind::AlreadyExists => ErrorCode::Conflict,
+            _ => ErrorCode::IoError,
+        };
+        let detail = error.to_string();
+        AppError::new(code, detail.clone()).with_param("detail", detail)
+    }
+}
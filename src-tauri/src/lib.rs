@@ -1,6 +1,7 @@
 mod archive; // 压缩包处理功能
 pub mod commands;
 mod download; // 下载管理功能
+pub mod error; // 统一错误类型
 mod storage;
 mod utils; // 通用工具模块 // Tauri 命令模块 - 公开以便外部访问
 
@@ -106,21 +107,58 @@ pub fn create_specta_builder() -> Builder<tauri::Wry> {
         // 统一存储接口命令
         storage_get_file_content,
         storage_get_file_info,
+        storage_stat,
+        storage_list_versions,
         storage_connect,
         storage_disconnect,
+        storage_list_connections,
+        storage_bind_window,
+        storage_unbind_window,
         storage_list,
         storage_get_url,
+        storage_upload_file,
+        storage_create_directory,
+        storage_rename,
+        storage_delete,
+        storage_get_cache_stats,
+        storage_clear_cache,
+        storage_refresh,
+        storage_search,
+        storage_search_cancel,
+        storage_disk_usage,
+        storage_disk_usage_cancel,
+        // 连接配置库命令
+        profile_status,
+        profile_unlock,
+        profile_lock,
+        profile_change_password,
+        profile_list,
+        profile_create,
+        profile_update,
+        profile_delete,
+        profile_export,
+        profile_import,
+        profile_connect,
+        profile_scan_external,
+        profile_import_external,
         // 下载管理命令
         download_start,
         download_cancel,
         download_cancel_all,
         download_extract_file,
+        // 跨存储传输命令
+        transfer_start,
+        sync_plan,
+        sync_start,
+        transfer_cancel,
+        transfer_cancel_all,
         // 系统对话框命令
         system_select_folder,
         system_select_file,
         // 压缩包处理命令（统一接口）
         archive_get_file_info,
         archive_get_file_content,
+        archive_mount,
         // 窗口主题设置命令
         system_set_theme
     ])
@@ -0,0 +1,613 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{stream, StreamExt};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+
+use super::caching_client::LruIndex;
+use crate::archive::formats::gzip::GzipHandler;
+use crate::archive::formats::tar_gz::TarGzHandler;
+use crate::archive::handlers::ArchiveHandler;
+use crate::archive::types::{AnalysisStatus, CompressionType};
+use crate::error::{AppError, ErrorCode};
+use crate::storage::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile,
+};
+use crate::utils::stream_io::StreamIo;
+
+// 完整解压后保留在内存中的单个条目大小上限
+const CACHED_ENTRY_LIMIT: u64 = 32 * 1024 * 1024;
+// 条目内容缓存的总容量
+const ENTRY_CACHE_CAPACITY: u64 = 128 * 1024 * 1024;
+// 流式读取时每次提取的数据块大小
+const STREAM_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+/// 压缩包中的文件或目录
+#[derive(Debug, Clone)]
+struct ArchiveNode {
+    /// 压缩包内的原始条目路径，只在中间目录中出现的目录为 None
+    entry_path: Option<String>,
+    is_dir: bool,
+    size: u64,
+    modified: Option<String>,
+    crc32: Option<u32>,
+}
+
+/// 从上层存储中提取条目内容，由客户端和读取流共享
+struct ArchiveReader {
+    parent: Arc<dyn StorageClient + Send + Sync>,
+    archive_path: String,
+    filename: String,
+    compression_type: CompressionType,
+    // ZIP 和 TAR 可以从条目中间开始提取，其余格式每次都要从条目开头解压
+    seekable: bool,
+    cache: Mutex<LruIndex<String, Arc<Vec<u8>>>>,
+}
+
+impl ArchiveReader {
+    /// 从 offset 开始提取条目内容，length 为 None 时读取到条目末尾
+    async fn extract(
+        &self,
+        entry_path: &str,
+        offset: u64,
+        length: Option<u64>,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        let preview = ArchiveHandler::new()
+            .get_file_preview_with_client(
+                self.parent.clone(),
+                self.archive_path.clone(),
+                self.filename.clone(),
+                entry_path.to_string(),
+                length.map(|length| length.min(u32::MAX as u64) as u32),
+                (offset > 0).then_some(offset),
+                progress_callback
+                    .map(|callback| move |current: u64, total: u64| callback(current, total)),
+                cancel_rx,
+            )
+            .await
+            .map_err(storage_error)?;
+
+        let mut content = preview.content;
+        if let Some(length) = length {
+            content.truncate(length as usize);
+        }
+        Ok(content)
+    }
+
+    /// 读取完整条目，不超过缓存上限的条目保留在内存中供后续的范围读取使用
+    async fn entry_bytes(
+        &self,
+        entry_path: &str,
+        size: u64,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Arc<Vec<u8>>, StorageError> {
+        let key = entry_path.to_string();
+        if let Some(data) = self.cache.lock().unwrap().get(&key) {
+            return Ok(data);
+        }
+
+        let data = Arc::new(
+            self.extract(entry_path, 0, None, progress_callback, cancel_rx)
+                .await?,
+        );
+        if size <= CACHED_ENTRY_LIMIT {
+            self.cache
+                .lock()
+                .unwrap()
+                .insert(key, data.clone(), data.len() as u64);
+        }
+        Ok(data)
+    }
+
+    /// 以数据流的方式解压条目，内存占用与条目大小无关；只有 TAR.GZ 和 GZIP 支持，其余格式返回 None
+    async fn open_entry_stream(
+        &self,
+        entry_path: &str,
+        start: u64,
+    ) -> Result<Option<ByteStream>, StorageError> {
+        let stream = match self.compression_type {
+            CompressionType::TarGz => {
+                TarGzHandler::open_entry_stream(
+                    self.parent.clone(),
+                    &self.archive_path,
+                    entry_path,
+                    start,
+                    STREAM_CHUNK_SIZE,
+                )
+                .await?
+            }
+            CompressionType::Gzip => {
+                GzipHandler::open_entry_stream(
+                    self.parent.clone(),
+                    &self.archive_path,
+                    start,
+                    STREAM_CHUNK_SIZE,
+                )
+                .await?
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(stream))
+    }
+
+    async fn read_range(
+        &self,
+        entry_path: &str,
+        size: u64,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+
+        // 小条目整体解压一次，嵌套压缩包的大量小范围读取直接从内存返回
+        if size <= CACHED_ENTRY_LIMIT {
+            let data = self
+                .entry_bytes(entry_path, size, progress_callback, cancel_rx)
+                .await?;
+            let from = start.min(data.len() as u64) as usize;
+            let to = start.saturating_add(length).min(data.len() as u64) as usize;
+            return Ok(data[from..to].to_vec());
+        }
+
+        if self.seekable {
+            return self
+                .extract(
+                    entry_path,
+                    start,
+                    Some(length),
+                    progress_callback,
+                    cancel_rx,
+                )
+                .await;
+        }
+
+        // 支持流式解压的格式边解压边丢弃 start 之前的数据，只保留所需的范围
+        if let Some(mut stream) = self.open_entry_stream(entry_path, start).await? {
+            let mut cancel_rx = cancel_rx;
+            let mut data = Vec::new();
+            while (data.len() as u64) < length {
+                let chunk = match cancel_rx {
+                    Some(ref mut cancel_rx) => {
+                        tokio::select! {
+                            chunk = stream.next() => chunk,
+                            Ok(_) = cancel_rx.recv() => return Err(StorageError::Cancelled),
+                        }
+                    }
+                    None => stream.next().await,
+                };
+                let Some(chunk) = chunk else {
+                    break;
+                };
+                data.extend_from_slice(&chunk?);
+                if let Some(ref callback) = progress_callback {
+                    callback((data.len() as u64).min(length), length);
+                }
+            }
+            data.truncate(length as usize);
+            return Ok(data);
+        }
+
+        // 其余格式解压到所需位置后丢弃前面的数据
+        let mut data = self
+            .extract(
+                entry_path,
+                0,
+                Some(start.saturating_add(length)),
+                progress_callback,
+                cancel_rx,
+            )
+            .await?;
+        data.drain(..start.min(data.len() as u64) as usize);
+        Ok(data)
+    }
+}
+
+/// 把压缩包作为只读存储挂载的客户端
+/// 包装上层连接中的压缩包文件，按条目层级浏览目录，读取操作映射到条目内容；
+/// 上层连接本身也可以是压缩包，因此可以任意层级嵌套
+pub struct ArchiveStorageClient {
+    reader: Arc<ArchiveReader>,
+    // 规范化路径（不含首尾 /）到节点的映射，根目录为空字符串
+    nodes: HashMap<String, ArchiveNode>,
+    children: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ArchiveStorageClient {
+    /// 分析上层连接中的压缩包并建立条目索引
+    pub async fn open(
+        parent: Arc<dyn StorageClient + Send + Sync>,
+        archive_path: &str,
+    ) -> Result<Self, StorageError> {
+        let filename = archive_path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let info = ArchiveHandler::new()
+            .analyze_archive_with_client(
+                parent.clone(),
+                archive_path.to_string(),
+                filename.clone(),
+                None,
+            )
+            .await
+            .map_err(storage_error)?;
+
+        if let AnalysisStatus::Failed { error } = info.analysis_status {
+            return Err(StorageError::RequestFailed(format!(
+                "Failed to analyze archive {}: {}",
+                archive_path, error
+            )));
+        }
+
+        let mut client = Self {
+            reader: Arc::new(ArchiveReader {
+                parent,
+                archive_path: archive_path.to_string(),
+                filename,
+                compression_type: info.compression_type.clone(),
+                seekable: matches!(
+                    info.compression_type,
+                    CompressionType::Zip | CompressionType::Tar
+                ),
+                cache: Mutex::new(LruIndex::new(ENTRY_CACHE_CAPACITY)),
+            }),
+            nodes: HashMap::new(),
+            children: BTreeMap::new(),
+        };
+        client.insert_directory("");
+
+        for entry in info.entries {
+            let path = Self::normalize(&entry.path);
+            if path.is_empty() {
+                continue;
+            }
+
+            let (parent_path, _) = Self::split(&path);
+            client.insert_directory(parent_path);
+            client
+                .children
+                .entry(parent_path.to_string())
+                .or_default()
+                .insert(path.clone());
+
+            let node = ArchiveNode {
+                is_dir: entry.is_dir,
+                size: if entry.is_dir {
+                    0
+                } else {
+                    entry.size.parse().unwrap_or(0)
+                },
+                modified: entry.modified_time,
+                crc32: entry.crc32,
+                entry_path: Some(entry.path),
+            };
+            if node.is_dir {
+                client.children.entry(path.clone()).or_default();
+            }
+            client.nodes.insert(path, node);
+        }
+
+        Ok(client)
+    }
+
+    /// 去掉 ./ 前缀和首尾的 /
+    fn normalize(path: &str) -> String {
+        let mut path = path.trim_matches('/');
+        while let Some(rest) = path.strip_prefix("./") {
+            path = rest.trim_start_matches('/');
+        }
+        if path == "." {
+            return String::new();
+        }
+        path.to_string()
+    }
+
+    /// 拆分为上级目录和名称
+    fn split(path: &str) -> (&str, &str) {
+        match path.rsplit_once('/') {
+            Some((parent, name)) => (parent, name),
+            None => ("", path),
+        }
+    }
+
+    /// 登记目录及其所有上级目录，压缩包中可能没有单独的目录条目
+    fn insert_directory(&mut self, path: &str) {
+        let mut current = path;
+        loop {
+            if self.nodes.contains_key(current) {
+                return;
+            }
+            self.nodes.insert(
+                current.to_string(),
+                ArchiveNode {
+                    entry_path: None,
+                    is_dir: true,
+                    size: 0,
+                    modified: None,
+                    crc32: None,
+                },
+            );
+            self.children.entry(current.to_string()).or_default();
+            if current.is_empty() {
+                return;
+            }
+
+            let (parent, _) = Self::split(current);
+            self.children
+                .entry(parent.to_string())
+                .or_default()
+                .insert(current.to_string());
+            current = parent;
+        }
+    }
+
+    fn node(&self, path: &str) -> Result<&ArchiveNode, StorageError> {
+        self.nodes
+            .get(&Self::normalize(path))
+            .ok_or_else(|| StorageError::NotFound(path.to_string()))
+    }
+
+    /// 查找文件条目，返回原始条目路径和大小
+    fn file(&self, path: &str) -> Result<(String, u64), StorageError> {
+        let node = self.node(path)?;
+        match (&node.entry_path, node.is_dir) {
+            (Some(entry_path), false) => Ok((entry_path.clone(), node.size)),
+            _ => Err(StorageError::RequestFailed(format!(
+                "{} is a directory",
+                path
+            ))),
+        }
+    }
+
+    fn read_only_error() -> StorageError {
+        StorageError::ProtocolNotSupported("Archive connections are read-only".to_string())
+    }
+}
+
+/// 压缩包处理返回的错误转换为存储错误，保留取消和未找到等语义
+fn storage_error(error: AppError) -> StorageError {
+    match error.code {
+        ErrorCode::Cancelled => StorageError::Cancelled,
+        ErrorCode::NotFound => StorageError::NotFound(error.message),
+        ErrorCode::Unsupported => StorageError::ProtocolNotSupported(error.message),
+        ErrorCode::NetworkError => StorageError::NetworkError(error.message),
+        ErrorCode::Timeout => StorageError::Timeout(error.message),
+        ErrorCode::RateLimited => StorageError::RateLimited(
+            error.message,
+            error
+                .params
+                .get("retryAfter")
+                .and_then(|secs| secs.parse().ok()),
+        ),
+        ErrorCode::ConnectionFailed => StorageError::ConnectionFailed(error.message),
+        ErrorCode::AuthenticationFailed => StorageError::AuthenticationFailed(error.message),
+        ErrorCode::PermissionDenied => StorageError::PermissionDenied(error.message),
+        ErrorCode::IoError => StorageError::IoError(error.message),
+        _ => StorageError::RequestFailed(error.message),
+    }
+}
+
+#[async_trait]
+impl StorageClient for ArchiveStorageClient {
+    /// 条目索引在 open 时已经建立，无需额外连接
+    async fn connect(&mut self, _config: &ConnectionConfig) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.reader.parent.is_connected().await
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        _options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        let key = Self::normalize(path);
+        let children = self
+            .children
+            .get(&key)
+            .ok_or_else(|| StorageError::NotFound(path.to_string()))?;
+
+        let files = children
+            .iter()
+            .filter_map(|child| {
+                let node = self.nodes.get(child)?;
+                let (_, name) = Self::split(child);
+                Some(StorageFile {
+                    filename: name.to_string(),
+                    basename: name.to_string(),
+                    lastmod: node.modified.clone().unwrap_or_default(),
+                    size: node.size.to_string(),
+                    file_type: if node.is_dir { "directory" } else { "file" }.to_string(),
+                    mime: None,
+                    etag: None,
+                })
+            })
+            .collect();
+
+        Ok(DirectoryResult {
+            files,
+            has_more: false,
+            next_marker: None,
+            total_count: None,
+            path: path.to_string(),
+        })
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_file_range_with_progress(path, start, length, None, None)
+            .await
+    }
+
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        let (entry_path, size) = self.file(path)?;
+        self.reader
+            .read_range(
+                &entry_path,
+                size,
+                start,
+                length,
+                progress_callback,
+                cancel_rx,
+            )
+            .await
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        self.read_full_file_with_progress(path, None, None).await
+    }
+
+    async fn read_full_file_with_progress(
+        &self,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        let (entry_path, size) = self.file(path)?;
+        let data = self
+            .reader
+            .entry_bytes(&entry_path, size, progress_callback, cancel_rx)
+            .await?;
+        Ok(Arc::unwrap_or_clone(data))
+    }
+
+    /// 按块提取条目内容；不支持偏移量的格式中较大的条目优先流式解压，
+    /// 不支持流式解压的格式整体解压一次后分块输出
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        let (entry_path, size) = self.file(path)?;
+        let reader = self.reader.clone();
+
+        if !reader.seekable && size > CACHED_ENTRY_LIMIT {
+            if let Some(stream) = reader.open_entry_stream(&entry_path, start).await? {
+                return Ok(stream);
+            }
+            let data = Bytes::from(reader.extract(&entry_path, 0, None, None, None).await?);
+            let mut chunks = Vec::new();
+            let mut position = start.min(data.len() as u64) as usize;
+            while position < data.len() {
+                let end = (position + STREAM_CHUNK_SIZE as usize).min(data.len());
+                chunks.push(Ok(data.slice(position..end)));
+                position = end;
+            }
+            return Ok(Box::pin(stream::iter(chunks)));
+        }
+
+        // 条目大小可能是估算值，读到空块或不足一块时结束
+        let stream = stream::try_unfold((start, false), move |(position, finished)| {
+            let reader = reader.clone();
+            let entry_path = entry_path.clone();
+            async move {
+                if finished {
+                    return Ok(None);
+                }
+                let chunk = reader
+                    .read_range(&entry_path, size, position, STREAM_CHUNK_SIZE, None, None)
+                    .await?;
+                if chunk.is_empty() {
+                    return Ok(None);
+                }
+                let next = position + chunk.len() as u64;
+                let finished = (chunk.len() as u64) < STREAM_CHUNK_SIZE;
+                Ok(Some((Bytes::from(chunk), (next, finished))))
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        let node = self.node(path)?;
+        let key = Self::normalize(path);
+        let (_, name) = Self::split(&key);
+
+        let mut extra = HashMap::new();
+        extra.insert("archive".to_string(), self.reader.archive_path.clone());
+        if let Some(ref entry_path) = node.entry_path {
+            extra.insert("entryPath".to_string(), entry_path.clone());
+        }
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename: name.to_string(),
+            lastmod: node.modified.clone().unwrap_or_default(),
+            size: node.size.to_string(),
+            file_type: if node.is_dir { "directory" } else { "file" }.to_string(),
+            mime: None,
+            etag: None,
+            checksum: node.crc32.map(|crc32| format!("crc32:{:08x}", crc32)),
+            permissions: None,
+            owner: None,
+            group: None,
+            extra,
+        })
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        Ok(self.file(path)?.1)
+    }
+
+    fn protocol(&self) -> &str {
+        "archive"
+    }
+
+    fn validate_config(&self, _config: &ConnectionConfig) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let size = self.get_file_size(path).await?;
+        let stream = self.open_read_stream(path, 0).await?;
+        StreamIo::save_to_file(stream, save_path, size, progress_callback, cancel_rx).await
+    }
+
+    async fn put_file(
+        &self,
+        _local_path: &std::path::Path,
+        _path: &str,
+        _progress_callback: Option<ProgressCallback>,
+        _cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn create_directory(&self, _path: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn rename(&self, _from: &str, _to: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn delete(&self, _path: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+}
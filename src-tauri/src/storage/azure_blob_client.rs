@@ -0,0 +1,1113 @@
+use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
+use chrono::Utc;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::{Client, Method, RequestBuilder};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use url::Url;
+
+use crate::storage::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile,
+};
+use crate::utils::crypto::hmac_sha256_bytes;
+use crate::utils::http_downloader::{HttpDownloadConfig, HttpDownloader};
+use crate::utils::http_uploader::HttpUploader;
+
+// 请求和 SAS 使用的服务版本
+const API_VERSION: &str = "2021-08-06";
+
+/// 认证方式
+enum AzureAuth {
+    /// 账户名和账户密钥，使用 Shared Key 签名
+    SharedKey { account: String, key: Vec<u8> },
+    /// SAS 令牌，附加到每个请求的查询参数中
+    Sas(String),
+    /// 匿名访问公开容器
+    Anonymous,
+}
+
+/// Blob 属性，列表响应和 HEAD 响应都转换为该结构
+#[derive(Debug, Default)]
+struct AzureBlob {
+    name: String,
+    size: Option<String>,
+    last_modified: Option<String>,
+    etag: Option<String>,
+    content_type: Option<String>,
+    content_md5: Option<String>,
+    content_encoding: Option<String>,
+    cache_control: Option<String>,
+    blob_type: Option<String>,
+    access_tier: Option<String>,
+    version_id: Option<String>,
+    metadata: HashMap<String, String>,
+}
+
+/// 一页 Blob 列表
+#[derive(Debug, Default)]
+struct AzureListPage {
+    blobs: Vec<AzureBlob>,
+    prefixes: Vec<String>,
+    next_marker: Option<String>,
+}
+
+pub struct AzureBlobClient {
+    client: Client,
+    connected: AtomicBool,
+    base_url: String, // 账户端点，如 https://account.blob.core.windows.net
+    container: String,
+    prefix: String, // 从 bucket 字段解析出的路径前缀
+    auth: AzureAuth,
+}
+
+impl AzureBlobClient {
+    pub fn new(config: ConnectionConfig) -> Result<Self, StorageError> {
+        let bucket_input = config.bucket.clone().ok_or_else(|| {
+            StorageError::InvalidConfig("Azure container is required".to_string())
+        })?;
+
+        // 解析 bucket 字段，支持 "container/path/prefix" 格式
+        let (container, prefix) = match bucket_input.split_once('/') {
+            Some((container, prefix)) if !prefix.trim_matches('/').is_empty() => (
+                container.to_string(),
+                format!("{}/", prefix.trim_matches('/')),
+            ),
+            Some((container, _)) => (container.to_string(), String::new()),
+            None => (bucket_input.clone(), String::new()),
+        };
+
+        let account = config.access_key.clone().filter(|a| !a.is_empty());
+        let url = config
+            .url
+            .clone()
+            .or_else(|| config.endpoint.clone())
+            .filter(|url| !url.is_empty())
+            .or_else(|| {
+                account
+                    .as_ref()
+                    .map(|account| format!("https://{}.blob.core.windows.net", account))
+            })
+            .ok_or_else(|| {
+                StorageError::InvalidConfig(
+                    "Azure account endpoint or account name is required".to_string(),
+                )
+            })?;
+
+        // SAS URL 的查询参数即为 SAS 令牌，容器级 SAS URL 的路径中可能已包含容器名
+        let mut parsed_url = Url::parse(&url)
+            .map_err(|e| StorageError::InvalidConfig(format!("Invalid Azure endpoint: {}", e)))?;
+        let url_sas = parsed_url.query().map(|query| query.to_string());
+        parsed_url.set_query(None);
+        let base_url = parsed_url.as_str().trim_end_matches('/').to_string();
+        let base_url = base_url
+            .strip_suffix(&format!("/{}", container))
+            .unwrap_or(&base_url)
+            .to_string();
+
+        let secret = config.secret_key.clone().filter(|s| !s.is_empty());
+        let auth = match (url_sas, secret, account) {
+            (Some(sas), _, _) if sas.contains("sig=") => AzureAuth::Sas(sas),
+            (_, Some(secret), _) if secret.contains("sig=") => {
+                AzureAuth::Sas(secret.trim_start_matches('?').to_string())
+            }
+            (_, Some(secret), Some(account)) => {
+                let key = general_purpose::STANDARD
+                    .decode(secret.trim())
+                    .map_err(|e| {
+                        StorageError::InvalidConfig(format!("Invalid Azure account key: {}", e))
+                    })?;
+                AzureAuth::SharedKey { account, key }
+            }
+            (_, Some(_), None) => {
+                return Err(StorageError::InvalidConfig(
+                    "Azure account name is required for shared key authentication".to_string(),
+                ))
+            }
+            _ => AzureAuth::Anonymous,
+        };
+
+        Ok(Self {
+            client: Client::new(),
+            connected: AtomicBool::new(false),
+            base_url,
+            container,
+            prefix,
+            auth,
+        })
+    }
+
+    /// 逐段编码 Blob 名称，保留斜杠
+    fn encode_blob_path(blob_name: &str) -> String {
+        blob_name
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Blob 的 URL（不含认证参数）
+    fn blob_url(&self, blob_name: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.base_url,
+            self.container,
+            Self::encode_blob_path(blob_name)
+        )
+    }
+
+    /// 计算 Shared Key 签名
+    /// 使用 x-ms-date 和 x-ms-range，因此 Date 和 Range 行留空
+    fn shared_key_signature(
+        account: &str,
+        key: &[u8],
+        method: &Method,
+        url: &Url,
+        headers: &HashMap<String, String>,
+        content_length: u64,
+    ) -> String {
+        let header = |name: &str| headers.get(name).map(|v| v.as_str()).unwrap_or("");
+
+        // 规范化头部：所有 x-ms- 头按名称排序
+        let ms_headers: BTreeMap<String, &str> = headers
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.trim()))
+            .filter(|(k, _)| k.starts_with("x-ms-"))
+            .collect();
+        let canonical_headers: String = ms_headers
+            .iter()
+            .map(|(k, v)| format!("{}:{}\n", k, v))
+            .collect();
+
+        // 规范化资源：/账户名/路径，加上按名称排序的查询参数
+        let mut query_params: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (k, v) in url.query_pairs() {
+            query_params
+                .entry(k.to_lowercase())
+                .or_default()
+                .push(v.into_owned());
+        }
+        let mut canonical_resource = format!("/{}{}", account, url.path());
+        for (k, mut values) in query_params {
+            values.sort();
+            canonical_resource.push_str(&format!("\n{}:{}", k, values.join(",")));
+        }
+
+        // 内容长度为 0 时留空
+        let content_length = if content_length > 0 {
+            content_length.to_string()
+        } else {
+            String::new()
+        };
+
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n\n\n\n\n\n\n{}{}",
+            method.as_str(),
+            header("content-encoding"),
+            header("content-language"),
+            content_length,
+            header("content-md5"),
+            header("content-type"),
+            canonical_headers,
+            canonical_resource
+        );
+
+        let signature = hmac_sha256_bytes(key, &string_to_sign);
+        format!(
+            "SharedKey {}:{}",
+            account,
+            general_purpose::STANDARD.encode(signature)
+        )
+    }
+
+    /// 构建请求，blob_name 为 None 时请求容器本身
+    /// 按认证方式附加 SAS 参数或 Shared Key 签名
+    fn request(
+        &self,
+        method: Method,
+        blob_name: Option<&str>,
+        query_params: &[(&str, String)],
+        extra_headers: &HashMap<String, String>,
+        content_length: Option<u64>,
+    ) -> Result<RequestBuilder, StorageError> {
+        let mut url = match blob_name {
+            Some(blob_name) => self.blob_url(blob_name),
+            None => format!("{}/{}", self.base_url, self.container),
+        };
+
+        let mut query: Vec<String> = query_params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .collect();
+        if let AzureAuth::Sas(sas) = &self.auth {
+            query.push(sas.clone());
+        }
+        if !query.is_empty() {
+            url = format!("{}?{}", url, query.join("&"));
+        }
+        let url = Url::parse(&url)
+            .map_err(|e| StorageError::RequestFailed(format!("Invalid request URL: {}", e)))?;
+
+        let mut headers: HashMap<String, String> = extra_headers
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.clone()))
+            .collect();
+        headers.insert(
+            "x-ms-date".to_string(),
+            Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+        );
+        headers.insert("x-ms-version".to_string(), API_VERSION.to_string());
+
+        if let AzureAuth::SharedKey { account, key } = &self.auth {
+            let authorization = Self::shared_key_signature(
+                account,
+                key,
+                &method,
+                &url,
+                &headers,
+                content_length.unwrap_or(0),
+            );
+            headers.insert("authorization".to_string(), authorization);
+        }
+
+        let mut req_builder = self.client.request(method, url);
+        if let Some(content_length) = content_length {
+            req_builder = req_builder.header("Content-Length", content_length.to_string());
+        }
+        for (key, value) in headers {
+            req_builder = req_builder.header(&key, &value);
+        }
+        Ok(req_builder)
+    }
+
+    /// 发送请求，非成功状态转换为对应的存储错误
+    async fn send(
+        req_builder: RequestBuilder,
+        context: &str,
+    ) -> Result<reqwest::Response, StorageError> {
+        let response = req_builder.send().await.map_err(|e| {
+            StorageError::NetworkError(format!("{} request failed: {}", context, e))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("{} failed with status {}: {}", context, status, body),
+            ));
+        }
+        Ok(response)
+    }
+
+    /// 解析路径为 Blob 名称（支持 azblob:// 协议 URL 和相对路径）
+    fn resolve_blob_name(&self, path: &str) -> Result<String, StorageError> {
+        if let Some(azure_path) = path.strip_prefix("azblob://") {
+            let (container, blob_name) = azure_path.split_once('/').unwrap_or((azure_path, ""));
+            if container != self.container {
+                return Err(StorageError::RequestFailed(format!(
+                    "Container mismatch: url='{}' != configured='{}'",
+                    container, self.container
+                )));
+            }
+            return Ok(blob_name.to_string());
+        }
+
+        Ok(format!("{}{}", self.prefix, path.trim_start_matches('/')))
+    }
+
+    /// 解析目录路径为以斜杠结尾的列表前缀，根目录为空字符串
+    fn resolve_directory_prefix(&self, path: &str) -> Result<String, StorageError> {
+        let blob_name = self.resolve_blob_name(path)?;
+        let trimmed = blob_name.trim_end_matches('/');
+        if trimmed.is_empty() {
+            Ok(String::new())
+        } else {
+            Ok(format!("{}/", trimmed))
+        }
+    }
+
+    /// HTTP 日期转换为 RFC3339
+    fn to_rfc3339(value: String) -> String {
+        chrono::DateTime::parse_from_rfc2822(&value)
+            .map(|dt| dt.with_timezone(&Utc).to_rfc3339())
+            .unwrap_or(value)
+    }
+
+    /// 列出一页 Blob，recursive 为 false 时按斜杠分组返回子目录前缀
+    async fn list_page(
+        &self,
+        prefix: &str,
+        recursive: bool,
+        page_size: u32,
+        marker: Option<&str>,
+    ) -> Result<AzureListPage, StorageError> {
+        let mut query_params = vec![
+            ("restype", "container".to_string()),
+            ("comp", "list".to_string()),
+            ("maxresults", page_size.to_string()),
+        ];
+        if !prefix.is_empty() {
+            query_params.push(("prefix", prefix.to_string()));
+        }
+        if !recursive {
+            query_params.push(("delimiter", "/".to_string()));
+        }
+        if let Some(marker) = marker {
+            query_params.push(("marker", marker.to_string()));
+        }
+
+        let req_builder = self.request(Method::GET, None, &query_params, &HashMap::new(), None)?;
+        let response = Self::send(req_builder, "List blobs").await?;
+        let xml_content = response.text().await.map_err(StorageError::from_reqwest)?;
+
+        Self::parse_list_blobs_response(&xml_content)
+    }
+
+    /// 解析 List Blobs 的 XML 响应
+    fn parse_list_blobs_response(xml_content: &str) -> Result<AzureListPage, StorageError> {
+        let mut reader = Reader::from_str(xml_content);
+        reader.trim_text(true);
+
+        let mut page = AzureListPage::default();
+        let mut buf = Vec::new();
+        let mut elements: Vec<String> = Vec::new();
+        let mut current_blob: Option<AzureBlob> = None;
+        let mut current_text = String::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => {
+                    let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    if name == "Blob" {
+                        current_blob = Some(AzureBlob::default());
+                    }
+                    elements.push(name);
+                    current_text.clear();
+                }
+                Ok(Event::Text(e)) => {
+                    current_text = e.unescape().unwrap_or_default().to_string();
+                }
+                Ok(Event::End(_)) => {
+                    let name = elements.pop().unwrap_or_default();
+                    let parent = elements.last().map(|p| p.as_str()).unwrap_or("");
+                    let text = std::mem::take(&mut current_text);
+
+                    match (parent, name.as_str()) {
+                        ("Blobs", "Blob") => page.blobs.extend(current_blob.take()),
+                        ("BlobPrefix", "Name") => page.prefixes.push(text),
+                        ("EnumerationResults", "NextMarker") if !text.is_empty() => {
+                            page.next_marker = Some(text)
+                        }
+                        (parent, name) => {
+                            let Some(blob) = current_blob.as_mut() else {
+                                buf.clear();
+                                continue;
+                            };
+                            match (parent, name) {
+                                ("Blob", "Name") => blob.name = text,
+                                ("Blob", "VersionId") => blob.version_id = Some(text),
+                                ("Metadata", key) => {
+                                    blob.metadata.insert(key.to_string(), text);
+                                }
+                                ("Properties", "Content-Length") => blob.size = Some(text),
+                                ("Properties", "Last-Modified") => {
+                                    blob.last_modified = Some(Self::to_rfc3339(text))
+                                }
+                                ("Properties", "Etag") => {
+                                    blob.etag = Some(text.trim_matches('"').to_string())
+                                }
+                                ("Properties", "Content-Type") => blob.content_type = Some(text),
+                                ("Properties", "Content-MD5") => blob.content_md5 = Some(text),
+                                ("Properties", "Content-Encoding") => {
+                                    blob.content_encoding = Some(text)
+                                }
+                                ("Properties", "Cache-Control") => blob.cache_control = Some(text),
+                                ("Properties", "BlobType") => blob.blob_type = Some(text),
+                                ("Properties", "AccessTier") => blob.access_tier = Some(text),
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    return Err(StorageError::RequestFailed(format!(
+                        "XML parsing error: {}",
+                        e
+                    )))
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(page)
+    }
+
+    /// 将一页 Blob 列表转换为相对于 prefix 的目录结果
+    fn directory_result(page: AzureListPage, prefix: &str, recursive: bool) -> DirectoryResult {
+        let mut files = Vec::new();
+
+        for prefix_path in &page.prefixes {
+            let relative_path = prefix_path.strip_prefix(prefix).unwrap_or(prefix_path);
+            let dir_name = relative_path.trim_end_matches('/');
+            if dir_name.is_empty() || dir_name.contains('/') {
+                continue;
+            }
+            files.push(StorageFile {
+                filename: dir_name.to_string(),
+                basename: dir_name.to_string(),
+                lastmod: String::new(),
+                size: "0".to_string(),
+                file_type: "directory".to_string(),
+                mime: None,
+                etag: None,
+            });
+        }
+
+        for blob in page.blobs {
+            let relative_path = blob.name.strip_prefix(prefix).unwrap_or(&blob.name);
+            // 以斜杠结尾的是目录占位 Blob
+            if relative_path.is_empty()
+                || relative_path.ends_with('/')
+                || (!recursive && relative_path.contains('/'))
+            {
+                continue;
+            }
+            files.push(StorageFile {
+                filename: relative_path.to_string(),
+                basename: blob
+                    .name
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&blob.name)
+                    .to_string(),
+                lastmod: blob.last_modified.clone().unwrap_or_default(),
+                size: blob.size.clone().unwrap_or_else(|| "0".to_string()),
+                file_type: "file".to_string(),
+                mime: blob.content_type.clone(),
+                etag: blob.etag.clone(),
+            });
+        }
+
+        DirectoryResult {
+            files,
+            has_more: page.next_marker.is_some(),
+            next_marker: page.next_marker,
+            total_count: None,
+            path: prefix.to_string(),
+        }
+    }
+
+    /// 获取 Blob 属性，Blob 不存在时返回 None
+    async fn blob_properties(&self, blob_name: &str) -> Result<Option<AzureBlob>, StorageError> {
+        let response = self
+            .request(Method::HEAD, Some(blob_name), &[], &HashMap::new(), None)?
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("Head request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!("Head request failed with status: {}", status),
+            ));
+        }
+
+        Ok(Some(Self::blob_from_headers(blob_name, response.headers())))
+    }
+
+    /// 根据 HEAD 响应头构建 Blob 属性
+    fn blob_from_headers(blob_name: &str, headers: &reqwest::header::HeaderMap) -> AzureBlob {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+
+        AzureBlob {
+            name: blob_name.to_string(),
+            size: header("content-length"),
+            last_modified: header("last-modified").map(Self::to_rfc3339),
+            etag: header("etag").map(|v| v.trim_matches('"').to_string()),
+            content_type: header("content-type"),
+            content_md5: header("content-md5"),
+            content_encoding: header("content-encoding"),
+            cache_control: header("cache-control"),
+            blob_type: header("x-ms-blob-type"),
+            access_tier: header("x-ms-access-tier"),
+            version_id: header("x-ms-version-id"),
+            metadata: headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    let key = name.as_str().strip_prefix("x-ms-meta-")?;
+                    Some((key.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+        }
+    }
+
+    /// 根据 Blob 属性构建元数据
+    fn metadata_from_blob(path: &str, blob: AzureBlob) -> FileMetadata {
+        let mut extra = HashMap::new();
+        for (key, value) in [
+            ("blobType", blob.blob_type),
+            ("accessTier", blob.access_tier),
+            ("versionId", blob.version_id),
+            ("contentEncoding", blob.content_encoding),
+            ("cacheControl", blob.cache_control),
+        ] {
+            if let Some(value) = value {
+                extra.insert(key.to_string(), value);
+            }
+        }
+        // 用户自定义元数据
+        for (key, value) in blob.metadata {
+            extra.insert(format!("meta:{}", key), value);
+        }
+
+        FileMetadata {
+            filename: path.to_string(),
+            basename: blob
+                .name
+                .rsplit('/')
+                .next()
+                .unwrap_or(&blob.name)
+                .to_string(),
+            lastmod: blob.last_modified.unwrap_or_default(),
+            size: blob.size.unwrap_or_else(|| "0".to_string()),
+            file_type: "file".to_string(),
+            mime: blob.content_type,
+            etag: blob.etag,
+            checksum: blob.content_md5.map(|v| format!("md5:{}", v)),
+            permissions: None,
+            owner: None,
+            group: None,
+            extra,
+        }
+    }
+
+    /// 生成下载 URL
+    /// 账户密钥生成只读的服务 SAS，SAS 令牌直接附加，匿名访问返回公开 URL
+    fn generate_download_url(
+        &self,
+        blob_name: &str,
+        expires_in_seconds: i64,
+    ) -> Result<String, StorageError> {
+        let blob_url = self.blob_url(blob_name);
+
+        match &self.auth {
+            AzureAuth::SharedKey { account, key } => {
+                let expiry = (Utc::now() + chrono::Duration::seconds(expires_in_seconds))
+                    .format("%Y-%m-%dT%H:%M:%SZ")
+                    .to_string();
+                let canonical_resource =
+                    format!("/blob/{}/{}/{}", account, self.container, blob_name);
+
+                // 服务 SAS 待签名字符串，未使用的字段留空
+                let string_to_sign = [
+                    "r",                 // signedPermissions
+                    "",                  // signedStart
+                    &expiry,             // signedExpiry
+                    &canonical_resource, // canonicalizedResource
+                    "",                  // signedIdentifier
+                    "",                  // signedIP
+                    "",                  // signedProtocol
+                    API_VERSION,         // signedVersion
+                    "b",                 // signedResource
+                    "",                  // signedSnapshotTime
+                    "",                  // signedEncryptionScope
+                    "",                  // rscc
+                    "",                  // rscd
+                    "",                  // rsce
+                    "",                  // rscl
+                    "",                  // rsct
+                ]
+                .join("\n");
+                let signature =
+                    general_purpose::STANDARD.encode(hmac_sha256_bytes(key, &string_to_sign));
+
+                Ok(format!(
+                    "{}?sv={}&se={}&sr=b&sp=r&sig={}",
+                    blob_url,
+                    API_VERSION,
+                    urlencoding::encode(&expiry),
+                    urlencoding::encode(&signature)
+                ))
+            }
+            AzureAuth::Sas(sas) => Ok(format!("{}?{}", blob_url, sas)),
+            AzureAuth::Anonymous => Ok(blob_url),
+        }
+    }
+
+    /// 删除单个 Blob，Blob 不存在时视为成功
+    async fn delete_blob(&self, blob_name: &str) -> Result<(), StorageError> {
+        let req_builder =
+            self.request(Method::DELETE, Some(blob_name), &[], &HashMap::new(), None)?;
+        match Self::send(req_builder, "Delete blob").await {
+            Ok(_) | Err(StorageError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 在服务端复制 Blob，跨区域等异步复制会轮询到完成为止
+    async fn copy_blob(&self, source_name: &str, dest_name: &str) -> Result<(), StorageError> {
+        let mut copy_source = self.blob_url(source_name);
+        if let AzureAuth::Sas(sas) = &self.auth {
+            copy_source = format!("{}?{}", copy_source, sas);
+        }
+
+        let mut headers = HashMap::new();
+        headers.insert("x-ms-copy-source".to_string(), copy_source);
+        let req_builder = self.request(Method::PUT, Some(dest_name), &[], &headers, Some(0))?;
+        let response = Self::send(req_builder, "Copy blob").await?;
+
+        let copy_status = |headers: &reqwest::header::HeaderMap| {
+            headers
+                .get("x-ms-copy-status")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("success")
+                .to_string()
+        };
+
+        let mut status = copy_status(response.headers());
+        while status == "pending" {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            let req_builder =
+                self.request(Method::HEAD, Some(dest_name), &[], &HashMap::new(), None)?;
+            let response = Self::send(req_builder, "Get copy status").await?;
+            status = copy_status(response.headers());
+        }
+
+        if status != "success" {
+            return Err(StorageError::RequestFailed(format!(
+                "Copy blob finished with status: {}",
+                status
+            )));
+        }
+        Ok(())
+    }
+
+    /// 收集前缀下的所有 Blob 名称，包含目录占位 Blob
+    async fn collect_blob_names(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        let mut blob_names = Vec::new();
+        let mut marker: Option<String> = None;
+        loop {
+            let page = self
+                .list_page(prefix, true, 5000, marker.as_deref())
+                .await?;
+            blob_names.extend(page.blobs.into_iter().map(|blob| blob.name));
+
+            match page.next_marker {
+                Some(next_marker) => marker = Some(next_marker),
+                None => break,
+            }
+        }
+        Ok(blob_names)
+    }
+
+    /// 以 Put Blob 请求上传块 Blob，供 put_file、put_stream 和 create_directory 共用
+    async fn put_blob_body(
+        &self,
+        blob_name: &str,
+        body: reqwest::Body,
+        file_size: u64,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let mut headers = HashMap::new();
+        headers.insert("x-ms-blob-type".to_string(), "BlockBlob".to_string());
+        let req_builder = self
+            .request(Method::PUT, Some(blob_name), &[], &headers, Some(file_size))?
+            .body(body);
+
+        let response = HttpUploader::send_with_cancel(req_builder, cancel_rx).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let error_body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("Put blob failed with status {}: {}", status, error_body),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StorageClient for AzureBlobClient {
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        *self = Self::new(config.clone())?;
+
+        // 通过查询一个不存在的 Blob 测试连接，404 表示认证有效
+        let test_blob = format!("{}__connection_test__", self.prefix);
+        self.blob_properties(&test_blob).await.map_err(|e| {
+            StorageError::ConnectionFailed(format!("Azure connection test failed: {}", e))
+        })?;
+
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// 不带 delimiter 的 List Blobs 请求即可递归列出前缀下的所有 Blob
+    fn supports_recursive_listing(&self, _path: &str) -> bool {
+        true
+    }
+
+    /// List Blobs 通过 maxresults 和 NextMarker 原生分页
+    fn supports_native_paging(&self, _path: &str, _recursive: bool) -> bool {
+        true
+    }
+
+    fn canonical_path(&self, path: &str) -> String {
+        self.resolve_blob_name(path)
+            .unwrap_or_else(|_| path.to_string())
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let options = options.cloned().unwrap_or_default();
+        let recursive = options.recursive.unwrap_or(false);
+        let prefix = self.resolve_directory_prefix(path)?;
+
+        let page = self
+            .list_page(
+                &prefix,
+                recursive,
+                options.page_size.unwrap_or(1000).min(5000),
+                options.marker.as_deref(),
+            )
+            .await?;
+
+        Ok(Self::directory_result(page, &prefix, recursive))
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_file_range_with_progress(path, start, length, None, None)
+            .await
+    }
+
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        use futures_util::StreamExt;
+
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+
+        let blob_name = self.resolve_blob_name(path)?;
+        let mut headers = HashMap::new();
+        headers.insert(
+            "x-ms-range".to_string(),
+            format!("bytes={}-{}", start, start + length - 1),
+        );
+        let req_builder = self.request(Method::GET, Some(&blob_name), &[], &headers, None)?;
+        let response = Self::send(req_builder, "Range request").await?;
+
+        // 使用流式读取以支持进度回调
+        let mut result = Vec::with_capacity(length as usize);
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk_result) = stream.next().await {
+            // 检查取消信号
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    return Err(StorageError::Cancelled);
+                }
+            }
+
+            let chunk = chunk_result
+                .map_err(|e| StorageError::RequestFailed(format!("Failed to read chunk: {}", e)))?;
+            result.extend_from_slice(&chunk);
+
+            if let Some(ref callback) = progress_callback {
+                callback(result.len() as u64, length);
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let blob_name = self.resolve_blob_name(path)?;
+        let req_builder =
+            self.request(Method::GET, Some(&blob_name), &[], &HashMap::new(), None)?;
+        let response = Self::send(req_builder, "Get blob").await?;
+
+        let bytes = response.bytes().await.map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to read file content: {}", e))
+        })?;
+        Ok(bytes.to_vec())
+    }
+
+    /// 使用下载 URL 流式读取 Blob
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let blob_name = self.resolve_blob_name(path)?;
+        let download_url = self.generate_download_url(&blob_name, 3600)?;
+
+        HttpDownloader::open_stream(&self.client, HttpDownloadConfig::new(download_url), start)
+            .await
+    }
+
+    /// 获取 Blob 属性，目录通过占位 Blob 或前缀下的 Blob 判断
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let blob_name = self.resolve_blob_name(path)?;
+        let trimmed_name = blob_name.trim_end_matches('/');
+
+        if !trimmed_name.is_empty() && !blob_name.ends_with('/') {
+            if let Some(blob) = self.blob_properties(&blob_name).await? {
+                return Ok(Self::metadata_from_blob(path, blob));
+            }
+        }
+
+        let directory = FileMetadata {
+            filename: path.to_string(),
+            basename: trimmed_name
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            lastmod: String::new(),
+            size: "0".to_string(),
+            file_type: "directory".to_string(),
+            mime: None,
+            etag: None,
+            checksum: None,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra: HashMap::new(),
+        };
+
+        // 容器根目录
+        if trimmed_name.is_empty() {
+            return Ok(directory);
+        }
+
+        let page = self
+            .list_page(&format!("{}/", trimmed_name), false, 1, None)
+            .await?;
+        if !page.blobs.is_empty() || !page.prefixes.is_empty() {
+            return Ok(directory);
+        }
+
+        Err(StorageError::NotFound(path.to_string()))
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let blob_name = self.resolve_blob_name(path)?;
+        self.blob_properties(&blob_name)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(path.to_string()))?
+            .size
+            .and_then(|size| size.parse().ok())
+            .ok_or_else(|| StorageError::RequestFailed("No content-length header".to_string()))
+    }
+
+    fn protocol(&self) -> &str {
+        "azblob"
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        Self::new(config.clone()).map(|_| ())
+    }
+
+    fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
+        let blob_name = self.resolve_blob_name(path)?;
+
+        // 生成 1 小时有效期的 SAS 下载 URL
+        self.generate_download_url(&blob_name, 3600)
+    }
+
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let blob_name = self.resolve_blob_name(path)?;
+        let download_url = self.generate_download_url(&blob_name, 3600)?;
+
+        HttpDownloader::download_with_auth(
+            &self.client,
+            &download_url,
+            None, // 下载 URL 已包含 SAS，不需要额外认证头
+            save_path,
+            progress_callback,
+            cancel_rx,
+        )
+        .await
+    }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let blob_name = self.resolve_blob_name(path)?;
+        let (body, file_size) = HttpUploader::file_body(local_path, progress_callback).await?;
+
+        self.put_blob_body(&blob_name, body, file_size, cancel_rx)
+            .await
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let blob_name = self.resolve_blob_name(path)?;
+        let body = HttpUploader::stream_body(stream, size, progress_callback);
+
+        self.put_blob_body(&blob_name, body, size, cancel_rx).await
+    }
+
+    /// Blob 存储没有真正的目录，创建以斜杠结尾的空 Blob 作为目录占位
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let directory_name = self.resolve_directory_prefix(path)?;
+        self.put_blob_body(&directory_name, reqwest::Body::from(Vec::new()), 0, None)
+            .await
+    }
+
+    /// 通过服务端复制 + 删除实现重命名，目录会逐个 Blob 处理
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let source_name = self.resolve_blob_name(from)?;
+        let dest_name = self.resolve_blob_name(to)?;
+
+        if !source_name.ends_with('/') && self.blob_properties(&source_name).await?.is_some() {
+            if self.blob_properties(&dest_name).await?.is_some() {
+                return Err(StorageError::Conflict(format!(
+                    "Destination already exists: {}",
+                    to
+                )));
+            }
+            self.copy_blob(&source_name, &dest_name).await?;
+            return self.delete_blob(&source_name).await;
+        }
+
+        // 按目录前缀处理，递归列表已包含目录占位 Blob
+        let source_prefix = format!("{}/", source_name.trim_end_matches('/'));
+        let dest_prefix = format!("{}/", dest_name.trim_end_matches('/'));
+        let blob_names = self.collect_blob_names(&source_prefix).await?;
+        if blob_names.is_empty() {
+            return Err(StorageError::NotFound(from.to_string()));
+        }
+
+        for name in &blob_names {
+            let relative = name.strip_prefix(&source_prefix).unwrap_or(name);
+            self.copy_blob(name, &format!("{}{}", dest_prefix, relative))
+                .await?;
+            self.delete_blob(name).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 删除 Blob，目录前缀下的所有 Blob 会被一并删除
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let blob_name = self.resolve_blob_name(path)?;
+        if blob_name.trim_end_matches('/') == self.prefix.trim_end_matches('/') {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the root directory".to_string(),
+            ));
+        }
+
+        if !blob_name.ends_with('/') && self.blob_properties(&blob_name).await?.is_some() {
+            return self.delete_blob(&blob_name).await;
+        }
+
+        let directory_prefix = format!("{}/", blob_name.trim_end_matches('/'));
+        let blob_names = self.collect_blob_names(&directory_prefix).await?;
+        if blob_names.is_empty() {
+            return Err(StorageError::NotFound(path.to_string()));
+        }
+
+        for name in &blob_names {
+            self.delete_blob(name).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,743 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::traits::{
+    BlockCacheConfig, ByteStream, ConnectionConfig, DirectoryResult, DirectoryUsage, FileMetadata,
+    ListOptions, ObjectVersion, ProgressCallback, StorageClient, StorageError,
+};
+use crate::utils::crypto::sha256_hex;
+
+const DEFAULT_BLOCK_SIZE_KB: u32 = 256;
+const DEFAULT_MEMORY_CAPACITY_MB: u32 = 64;
+const DEFAULT_DISK_CAPACITY_MB: u32 = 512;
+// 文件版本信息的有效期，过期后重新 stat 以发现远端文件的变化
+const VERSION_TTL: Duration = Duration::from_secs(30);
+
+/// 块缓存统计信息
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub block_size: u32,
+    pub hits: u32,
+    pub disk_hits: u32,
+    pub misses: u32,
+    pub evictions: u32,
+    pub hit_rate: f64,
+    pub memory_blocks: u32,
+    pub memory_bytes: String, // 使用字符串表示大数字
+    pub disk_blocks: u32,
+    pub disk_bytes: String,    // 使用字符串表示大数字
+    pub bytes_fetched: String, // 使用字符串表示大数字
+}
+
+/// 缓存块的键：路径 + 文件版本（etag 或修改时间）+ 块序号
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BlockKey {
+    path: String,
+    version: String,
+    index: u64,
+}
+
+/// 远端文件的版本信息
+struct FileVersion {
+    version: String,
+    size: u64,
+    checked_at: Instant,
+}
+
+/// 按容量限制并以最近最少使用顺序淘汰的索引
+pub(super) struct LruIndex<K, V> {
+    entries: HashMap<K, (V, u64, u64)>, // 值、大小、最近访问序号
+    order: BTreeMap<u64, K>,
+    tick: u64,
+    used: u64,
+    capacity: u64,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruIndex<K, V> {
+    pub(super) fn new(capacity: u64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            used: 0,
+            capacity,
+        }
+    }
+
+    pub(super) fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (value, _, last_used) = self.entries.get_mut(key)?;
+        self.order.remove(last_used);
+        *last_used = tick;
+        self.order.insert(tick, key.clone());
+        Some(value.clone())
+    }
+
+    /// 插入条目，返回因超出容量而被淘汰的条目
+    pub(super) fn insert(&mut self, key: K, value: V, size: u64) -> Vec<(K, V)> {
+        self.remove(&key);
+
+        // 超过总容量的条目不缓存
+        if size > self.capacity {
+            return Vec::new();
+        }
+
+        let mut evicted = Vec::new();
+        while self.used + size > self.capacity {
+            match self.order.pop_first() {
+                Some((_, oldest)) => {
+                    if let Some((value, size, _)) = self.entries.remove(&oldest) {
+                        self.used -= size;
+                        evicted.push((oldest, value));
+                    }
+                }
+                None => break,
+            }
+        }
+
+        self.tick += 1;
+        self.order.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, size, self.tick));
+        self.used += size;
+        evicted
+    }
+
+    pub(super) fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, size, last_used) = self.entries.remove(key)?;
+        self.order.remove(&last_used);
+        self.used -= size;
+        Some(value)
+    }
+
+    pub(super) fn remove_where(&mut self, predicate: impl Fn(&K) -> bool) -> usize {
+        let keys: Vec<K> = self
+            .entries
+            .keys()
+            .filter(|key| predicate(key))
+            .cloned()
+            .collect();
+        for key in &keys {
+            self.remove(key);
+        }
+        keys.len()
+    }
+
+    pub(super) fn clear(&mut self) -> Vec<K> {
+        let keys = self.entries.drain().map(|(key, _)| key).collect();
+        self.order.clear();
+        self.used = 0;
+        keys
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// 磁盘缓存层，块文件以哈希命名保存在缓存目录中
+struct DiskTier {
+    dir: PathBuf,
+    index: Mutex<LruIndex<String, ()>>,
+}
+
+impl DiskTier {
+    fn open(dir: PathBuf, capacity: u64) -> Result<Self, StorageError> {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| StorageError::IoError(format!("Failed to create cache dir: {}", e)))?;
+
+        // 登记上次会话遗留的块文件，按修改时间从旧到新加入以保持淘汰顺序
+        let mut existing = Vec::new();
+        if let Ok(read_dir) = std::fs::read_dir(&dir) {
+            for entry in read_dir.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if !name.ends_with(".blk") {
+                    continue;
+                }
+                if let Ok(metadata) = entry.metadata() {
+                    let modified = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+                    existing.push((modified, name, metadata.len()));
+                }
+            }
+        }
+        existing.sort();
+
+        let mut index = LruIndex::new(capacity);
+        for (_, name, size) in existing {
+            for (evicted, _) in index.insert(name, (), size) {
+                let _ = std::fs::remove_file(dir.join(evicted));
+            }
+        }
+
+        Ok(Self {
+            dir,
+            index: Mutex::new(index),
+        })
+    }
+}
+
+/// 块缓存存储客户端
+/// 包装任意存储客户端，将范围读取对齐到固定大小的块并缓存在内存（可选磁盘）中，
+/// 适用于压缩包浏览等反复读取同一区域的场景。其他操作直接转发给内部客户端
+pub struct CachingStorageClient {
+    inner: Arc<dyn StorageClient + Send + Sync>,
+    // 区分不同连接的命名空间，避免磁盘缓存在连接之间串用
+    namespace: String,
+    block_size: u64,
+    memory: Mutex<LruIndex<BlockKey, Bytes>>,
+    disk: Option<DiskTier>,
+    versions: Mutex<HashMap<String, FileVersion>>,
+    hits: AtomicU64,
+    disk_hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    bytes_fetched: AtomicU64,
+}
+
+impl CachingStorageClient {
+    pub fn new(
+        inner: Arc<dyn StorageClient + Send + Sync>,
+        config: &ConnectionConfig,
+        cache_config: &BlockCacheConfig,
+    ) -> Self {
+        let block_size = cache_config
+            .block_size_kb
+            .unwrap_or(DEFAULT_BLOCK_SIZE_KB)
+            .max(4) as u64
+            * 1024;
+        let memory_capacity = cache_config
+            .memory_capacity_mb
+            .unwrap_or(DEFAULT_MEMORY_CAPACITY_MB) as u64
+            * 1024
+            * 1024;
+
+        let disk = if cache_config.disk_cache.unwrap_or(false) {
+            let dir = cache_config
+                .disk_dir
+                .as_ref()
+                .map(PathBuf::from)
+                .or_else(|| dirs::cache_dir().map(|dir| dir.join("dataset-viewer").join("blocks")));
+            let capacity = cache_config
+                .disk_capacity_mb
+                .unwrap_or(DEFAULT_DISK_CAPACITY_MB) as u64
+                * 1024
+                * 1024;
+
+            match dir.map(|dir| DiskTier::open(dir, capacity)) {
+                Some(Ok(tier)) => Some(tier),
+                Some(Err(e)) => {
+                    log::warn!("Disk block cache disabled: {}", e);
+                    None
+                }
+                None => {
+                    log::warn!("Disk block cache disabled: no cache directory available");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let namespace = format!(
+            "{}|{}|{}|{}|{}",
+            config.protocol,
+            config.url.as_deref().unwrap_or(""),
+            config.endpoint.as_deref().unwrap_or(""),
+            config.bucket.as_deref().unwrap_or(""),
+            config.share.as_deref().unwrap_or("")
+        );
+
+        Self {
+            inner,
+            namespace,
+            block_size,
+            memory: Mutex::new(LruIndex::new(memory_capacity)),
+            disk,
+            versions: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            disk_hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            bytes_fetched: AtomicU64::new(0),
+        }
+    }
+
+    /// 获取缓存统计信息
+    pub fn stats(&self) -> CacheStats {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let disk_hits = self.disk_hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let total = hits + disk_hits + misses;
+
+        let (memory_blocks, memory_bytes) = {
+            let memory = self.memory.lock().unwrap();
+            (memory.len(), memory.used)
+        };
+        let (disk_blocks, disk_bytes) = match &self.disk {
+            Some(disk) => {
+                let index = disk.index.lock().unwrap();
+                (index.len(), index.used)
+            }
+            None => (0, 0),
+        };
+
+        CacheStats {
+            block_size: self.block_size as u32,
+            hits: hits.min(u32::MAX as u64) as u32,
+            disk_hits: disk_hits.min(u32::MAX as u64) as u32,
+            misses: misses.min(u32::MAX as u64) as u32,
+            evictions: self.evictions.load(Ordering::Relaxed).min(u32::MAX as u64) as u32,
+            hit_rate: if total > 0 {
+                (hits + disk_hits) as f64 / total as f64
+            } else {
+                0.0
+            },
+            memory_blocks: memory_blocks as u32,
+            memory_bytes: memory_bytes.to_string(),
+            disk_blocks: disk_blocks as u32,
+            disk_bytes: disk_bytes.to_string(),
+            bytes_fetched: self.bytes_fetched.load(Ordering::Relaxed).to_string(),
+        }
+    }
+
+    /// 清空内存和磁盘缓存，并重置统计信息
+    pub async fn clear(&self) {
+        self.memory.lock().unwrap().clear();
+        self.versions.lock().unwrap().clear();
+
+        if let Some(disk) = &self.disk {
+            let names = disk.index.lock().unwrap().clear();
+            for name in names {
+                let _ = tokio::fs::remove_file(disk.dir.join(name)).await;
+            }
+        }
+
+        for counter in [
+            &self.hits,
+            &self.disk_hits,
+            &self.misses,
+            &self.evictions,
+            &self.bytes_fetched,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// 经由内部客户端的路径解析得到缓存键，协议 URL 和相对路径指向同一文件时共用缓存
+    fn cache_key(&self, path: &str) -> String {
+        self.inner
+            .canonical_path(path)
+            .trim_matches('/')
+            .to_string()
+    }
+
+    /// 使指定路径（及其子路径）的缓存失效
+    fn invalidate(&self, path: &str) {
+        let path = self.cache_key(path);
+        let prefix = format!("{}/", path);
+        let matches = |candidate: &str| {
+            path.is_empty() || candidate == path || candidate.starts_with(&prefix)
+        };
+
+        self.versions
+            .lock()
+            .unwrap()
+            .retain(|candidate, _| !matches(candidate));
+        self.memory
+            .lock()
+            .unwrap()
+            .remove_where(|key| matches(&key.path));
+        // 磁盘块以文件版本为键，远端文件变化后旧块不会再被命中，交给 LRU 自然淘汰
+    }
+
+    /// 获取文件版本和大小，无法确定版本时返回 None（此时不使用缓存）
+    async fn file_version(&self, path: &str) -> Option<(String, u64)> {
+        let cache_key = self.cache_key(path);
+        if let Some(cached) = self.versions.lock().unwrap().get(&cache_key) {
+            if cached.checked_at.elapsed() < VERSION_TTL {
+                return Some((cached.version.clone(), cached.size));
+            }
+        }
+
+        let metadata = match self.inner.stat(path).await {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                log::debug!("Block cache bypassed for {}: {}", path, e);
+                return None;
+            }
+        };
+
+        if metadata.file_type == "directory" {
+            return None;
+        }
+        let size = metadata.size.parse::<u64>().ok()?;
+        let version = match metadata.etag.filter(|etag| !etag.is_empty()) {
+            Some(etag) => etag,
+            None if !metadata.lastmod.is_empty() => format!("{}:{}", metadata.lastmod, size),
+            None => return None,
+        };
+
+        let previous = self.versions.lock().unwrap().insert(
+            cache_key.clone(),
+            FileVersion {
+                version: version.clone(),
+                size,
+                checked_at: Instant::now(),
+            },
+        );
+
+        // 文件已变化，丢弃旧版本的内存块
+        if previous.is_some_and(|previous| previous.version != version) {
+            self.memory
+                .lock()
+                .unwrap()
+                .remove_where(|key| key.path == cache_key && key.version != version);
+        }
+
+        Some((version, size))
+    }
+
+    fn disk_file_name(&self, key: &BlockKey) -> String {
+        let digest = sha256_hex(&format!(
+            "{}\n{}\n{}",
+            self.namespace, key.path, key.version
+        ));
+        format!("{}_{}.blk", digest, key.index)
+    }
+
+    /// 依次在内存和磁盘中查找缓存块
+    async fn lookup(&self, key: &BlockKey) -> Option<Bytes> {
+        if let Some(block) = self.memory.lock().unwrap().get(key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Some(block);
+        }
+
+        let disk = self.disk.as_ref()?;
+        let name = self.disk_file_name(key);
+        disk.index.lock().unwrap().get(&name)?;
+
+        match tokio::fs::read(disk.dir.join(&name)).await {
+            Ok(data) => {
+                self.disk_hits.fetch_add(1, Ordering::Relaxed);
+                let block = Bytes::from(data);
+                self.store_memory(key.clone(), block.clone());
+                Some(block)
+            }
+            Err(_) => {
+                disk.index.lock().unwrap().remove(&name);
+                None
+            }
+        }
+    }
+
+    fn store_memory(&self, key: BlockKey, block: Bytes) {
+        let size = block.len() as u64;
+        let evicted = self.memory.lock().unwrap().insert(key, block, size);
+        self.evictions
+            .fetch_add(evicted.len() as u64, Ordering::Relaxed);
+    }
+
+    async fn store(&self, key: BlockKey, block: Bytes) {
+        if let Some(disk) = &self.disk {
+            let name = self.disk_file_name(&key);
+            match tokio::fs::write(disk.dir.join(&name), &block).await {
+                Ok(()) => {
+                    let evicted = disk
+                        .index
+                        .lock()
+                        .unwrap()
+                        .insert(name, (), block.len() as u64);
+                    for (evicted_name, _) in evicted {
+                        let _ = tokio::fs::remove_file(disk.dir.join(evicted_name)).await;
+                    }
+                }
+                Err(e) => log::warn!("Failed to write cache block: {}", e),
+            }
+        }
+
+        self.store_memory(key, block);
+    }
+
+    /// 超过内存容量一半的读取直接转发，避免一次读取冲刷整个缓存
+    fn should_bypass(&self, length: u64) -> bool {
+        length > self.memory.lock().unwrap().capacity / 2
+    }
+
+    /// 通过块缓存读取文件范围
+    async fn read_range_cached(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        let Some((version, size)) = self.file_version(path).await else {
+            return self.inner.read_file_range(path, start, length).await;
+        };
+
+        if length == 0 || start >= size {
+            return self.inner.read_file_range(path, start, length).await;
+        }
+
+        let block_size = self.block_size;
+        let end = start.saturating_add(length).min(size);
+        let first = start / block_size;
+        let last = (end - 1) / block_size;
+        let cache_key = self.cache_key(path);
+        let key = |index: u64| BlockKey {
+            path: cache_key.clone(),
+            version: version.clone(),
+            index,
+        };
+
+        let mut blocks = Vec::with_capacity((last - first + 1) as usize);
+        for index in first..=last {
+            blocks.push(self.lookup(&key(index)).await);
+        }
+
+        // 将连续缺失的块合并为一次范围请求
+        let mut slot = 0;
+        while slot < blocks.len() {
+            if blocks[slot].is_some() {
+                slot += 1;
+                continue;
+            }
+
+            let run_start = slot;
+            while slot < blocks.len() && blocks[slot].is_none() {
+                slot += 1;
+            }
+
+            let fetch_start = (first + run_start as u64) * block_size;
+            let fetch_end = ((first + slot as u64) * block_size).min(size);
+            let data = self
+                .inner
+                .read_file_range(path, fetch_start, fetch_end - fetch_start)
+                .await?;
+
+            self.misses
+                .fetch_add((slot - run_start) as u64, Ordering::Relaxed);
+            self.bytes_fetched
+                .fetch_add(data.len() as u64, Ordering::Relaxed);
+
+            if data.len() as u64 != fetch_end - fetch_start {
+                // 返回长度与记录的文件大小不一致，说明远端文件已变化
+                log::debug!("Block cache detected a changed file: {}", path);
+                self.invalidate(path);
+                return self.inner.read_file_range(path, start, length).await;
+            }
+
+            let data = Bytes::from(data);
+            for (offset, index) in (run_start..slot).enumerate() {
+                let block_start = offset * block_size as usize;
+                let block_end = (block_start + block_size as usize).min(data.len());
+                let block = data.slice(block_start..block_end);
+                self.store(key(first + index as u64), block.clone()).await;
+                blocks[index] = Some(block);
+            }
+        }
+
+        let mut result = Vec::with_capacity((end - start) as usize);
+        for (offset, block) in blocks.into_iter().flatten().enumerate() {
+            let block_start = (first + offset as u64) * block_size;
+            let from = start.saturating_sub(block_start) as usize;
+            let to = ((end - block_start) as usize).min(block.len());
+            result.extend_from_slice(&block[from..to]);
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl StorageClient for CachingStorageClient {
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        // 内部客户端通常在包装前已完成连接，仅在独占时转发
+        match Arc::get_mut(&mut self.inner) {
+            Some(inner) => inner.connect(config).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        self.inner.list_directory(path, options).await
+    }
+
+    fn supports_recursive_listing(&self, path: &str) -> bool {
+        self.inner.supports_recursive_listing(path)
+    }
+
+    fn supports_native_paging(&self, path: &str, recursive: bool) -> bool {
+        self.inner.supports_native_paging(path, recursive)
+    }
+
+    fn canonical_path(&self, path: &str) -> String {
+        self.inner.canonical_path(path)
+    }
+
+    async fn directory_usage(
+        &self,
+        path: &str,
+        max_depth: u32,
+    ) -> Result<Option<Vec<DirectoryUsage>>, StorageError> {
+        self.inner.directory_usage(path, max_depth).await
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        if self.should_bypass(length) {
+            return self.inner.read_file_range(path, start, length).await;
+        }
+        self.read_range_cached(path, start, length).await
+    }
+
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        if self.should_bypass(length) {
+            return self
+                .inner
+                .read_file_range_with_progress(path, start, length, progress_callback, cancel_rx)
+                .await;
+        }
+
+        let result = self.read_range_cached(path, start, length).await?;
+        if let Some(callback) = progress_callback {
+            callback(length, length);
+        }
+        Ok(result)
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        self.inner.read_full_file(path).await
+    }
+
+    async fn read_full_file_with_progress(
+        &self,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.inner
+            .read_full_file_with_progress(path, progress_callback, cancel_rx)
+            .await
+    }
+
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        self.inner.open_read_stream(path, start).await
+    }
+
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        self.inner.stat(path).await
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        // 压缩包处理会反复查询文件大小，版本信息未过期时直接复用
+        if let Some(cached) = self.versions.lock().unwrap().get(&self.cache_key(path)) {
+            if cached.checked_at.elapsed() < VERSION_TTL {
+                return Ok(cached.size);
+            }
+        }
+        self.inner.get_file_size(path).await
+    }
+
+    fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
+        self.inner.get_download_url(path)
+    }
+
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        self.inner
+            .download_file(path, save_path, progress_callback, cancel_rx)
+            .await
+    }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let result = self
+            .inner
+            .put_file(local_path, path, progress_callback, cancel_rx)
+            .await;
+        self.invalidate(path);
+        result
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let result = self
+            .inner
+            .put_stream(stream, size, path, progress_callback, cancel_rx)
+            .await;
+        self.invalidate(path);
+        result
+    }
+
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        self.inner.create_directory(path).await
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        let result = self.inner.rename(from, to).await;
+        self.invalidate(from);
+        self.invalidate(to);
+        result
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        let result = self.inner.delete(path).await;
+        self.invalidate(path);
+        result
+    }
+
+    async fn list_versions(&self, path: &str) -> Result<Vec<ObjectVersion>, StorageError> {
+        self.inner.list_versions(path).await
+    }
+
+    fn protocol(&self) -> &str {
+        self.inner.protocol()
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        self.inner.validate_config(config)
+    }
+}
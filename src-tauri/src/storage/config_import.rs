@@ -0,0 +1,659 @@
+use base64::engine::general_purpose;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::traits::{ConnectionConfig, StorageError};
+use crate::utils::path_utils::PathUtils;
+
+/// 从其他工具的配置文件导入的连接
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedConnection {
+    pub name: String,
+    /// 来源："aws"、"ssh" 或 "rclone"
+    pub source: String,
+    pub config: ConnectionConfig,
+    /// 检测到但未返回前端的密钥字段
+    pub secret_fields: Vec<String>,
+    /// 无法完整转换的设置，例如需要补充的存储桶或不支持的选项
+    pub warnings: Vec<String>,
+}
+
+impl ImportedConnection {
+    fn new(name: &str, source: &str, protocol: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            source: source.to_string(),
+            config: ConnectionConfig {
+                protocol: protocol.to_string(),
+                ..Default::default()
+            },
+            secret_fields: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn set_extra_option(&mut self, key: &str, value: &str) {
+        self.config
+            .extra_options
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_string());
+    }
+}
+
+/// INI 文件中的一个节
+#[derive(Debug, Default)]
+pub(crate) struct IniSection {
+    pub(crate) name: String,
+    values: HashMap<String, String>,
+}
+
+impl IniSection {
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(key)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// 简单的 INI 解析，AWS 配置中缩进的子键（如 s3 = 下的 endpoint_url）以 "父键.子键" 保存
+pub(crate) fn parse_ini(text: &str) -> Vec<IniSection> {
+    let mut sections: Vec<IniSection> = Vec::new();
+    let mut parent_key: Option<String> = None;
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            sections.push(IniSection {
+                name: line[1..line.len() - 1].trim().to_string(),
+                values: HashMap::new(),
+            });
+            parent_key = None;
+            continue;
+        }
+
+        let Some(section) = sections.last_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim().to_string();
+
+        let indented = raw_line.starts_with(|c: char| c.is_whitespace());
+        match &parent_key {
+            Some(parent) if indented => {
+                section.values.insert(format!("{}.{}", parent, key), value);
+            }
+            _ => {
+                parent_key = if value.is_empty() {
+                    Some(key.clone())
+                } else {
+                    None
+                };
+                section.values.insert(key, value);
+            }
+        }
+    }
+
+    sections
+}
+
+pub(crate) fn read_optional(path: &Path) -> Result<Option<String>, StorageError> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(StorageError::IoError(format!(
+            "Failed to read {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+fn expand_path(path: &str) -> Result<PathBuf, StorageError> {
+    Ok(PathBuf::from(PathUtils::expand_home_dir(path)?))
+}
+
+/// AWS 共享凭证文件和配置文件的位置，遵循 AWS_SHARED_CREDENTIALS_FILE、AWS_CONFIG_FILE 环境变量
+pub(crate) fn aws_shared_config_paths() -> Result<(PathBuf, PathBuf), StorageError> {
+    Ok((
+        match std::env::var("AWS_SHARED_CREDENTIALS_FILE") {
+            Ok(path) => expand_path(&path)?,
+            Err(_) => expand_path("~/.aws/credentials")?,
+        },
+        match std::env::var("AWS_CONFIG_FILE") {
+            Ok(path) => expand_path(&path)?,
+            Err(_) => expand_path("~/.aws/config")?,
+        },
+    ))
+}
+
+/// AWS CLI 配置导入
+/// 合并 credentials 与 config 中同名 profile 的密钥、区域和 endpoint，导入为 S3 兼容连接
+pub struct AwsConfigImporter;
+
+impl AwsConfigImporter {
+    /// dir 为包含 credentials 和 config 的目录，未指定时遵循 AWS_SHARED_CREDENTIALS_FILE、
+    /// AWS_CONFIG_FILE 环境变量，默认使用 ~/.aws
+    pub fn import(dir: Option<&str>) -> Result<Vec<ImportedConnection>, StorageError> {
+        let (credentials_path, config_path) = match dir {
+            Some(dir) => {
+                let dir = expand_path(dir)?;
+                (dir.join("credentials"), dir.join("config"))
+            }
+            None => aws_shared_config_paths()?,
+        };
+
+        let credentials = read_optional(&credentials_path)?
+            .map(|text| parse_ini(&text))
+            .unwrap_or_default();
+        let config = read_optional(&config_path)?
+            .map(|text| parse_ini(&text))
+            .unwrap_or_default();
+
+        // 按 profile 名称合并，保持文件中的出现顺序
+        let mut names: Vec<String> = Vec::new();
+        let mut merged: HashMap<String, Vec<&IniSection>> = HashMap::new();
+        let config_sections = config.iter().filter_map(|section| {
+            // config 中除 default 外的 profile 以 "profile " 为前缀，sso-session 等其他节忽略
+            let name = if section.name == "default" {
+                "default"
+            } else {
+                section.name.strip_prefix("profile ")?.trim()
+            };
+            Some((name.to_string(), section))
+        });
+        let credential_sections = credentials
+            .iter()
+            .map(|section| (section.name.clone(), section));
+        for (name, section) in credential_sections.chain(config_sections) {
+            if !merged.contains_key(&name) {
+                names.push(name.clone());
+            }
+            merged.entry(name).or_default().push(section);
+        }
+
+        Ok(names
+            .iter()
+            .map(|name| Self::convert(name, &merged[name]))
+            .collect())
+    }
+
+    fn convert(name: &str, sections: &[&IniSection]) -> ImportedConnection {
+        let get = |key: &str| sections.iter().find_map(|section| section.get(key));
+        let mut imported = ImportedConnection::new(name, "aws", "oss");
+
+        imported.config.access_key = get("aws_access_key_id").map(str::to_string);
+        imported.config.secret_key = get("aws_secret_access_key").map(str::to_string);
+        imported.config.region = Some(get("region").unwrap_or("us-east-1").to_string());
+
+        // S3 专用的 endpoint 优先于全局 endpoint
+        let endpoint = get("s3.endpoint_url").or_else(|| get("endpoint_url"));
+        imported.config.url = Some(match endpoint {
+            Some(endpoint) => endpoint.to_string(),
+            None => format!(
+                "https://s3.{}.amazonaws.com",
+                imported.config.region.as_deref().unwrap_or("us-east-1")
+            ),
+        });
+
+        imported.config.session_token = get("aws_session_token").map(str::to_string);
+        for (key, option) in [
+            ("role_arn", "roleArn"),
+            ("source_profile", "sourceProfile"),
+            ("external_id", "externalId"),
+            ("role_session_name", "roleSessionName"),
+            ("web_identity_token_file", "webIdentityTokenFile"),
+        ] {
+            if let Some(value) = get(key) {
+                imported.set_extra_option(option, value);
+            }
+        }
+        if get("credential_process").is_some() {
+            imported
+                .warnings
+                .push("credential_process is not supported and was ignored".to_string());
+        }
+        if get("sso_session").is_some() || get("sso_start_url").is_some() {
+            imported
+                .warnings
+                .push("AWS SSO is not supported and was ignored".to_string());
+        }
+        if get("s3.addressing_style") == Some("path") {
+            imported.set_extra_option("pathStyle", "true");
+        }
+
+        if imported.config.access_key.is_none() || imported.config.secret_key.is_none() {
+            // 没有固定密钥的 profile 在连接时通过凭证链从配置文件解析
+            imported.set_extra_option("profile", name);
+            imported.warnings.push(
+                "No static access key in this profile; credentials are resolved from the AWS profile when connecting"
+                    .to_string(),
+            );
+        }
+        imported
+            .warnings
+            .push("AWS profiles do not specify a bucket; set one before connecting".to_string());
+
+        imported
+    }
+}
+
+/// ~/.ssh/config 中的一个 Host 块
+#[derive(Debug, Default)]
+struct SshHostBlock {
+    patterns: Vec<String>,
+    options: Vec<(String, String)>,
+}
+
+impl SshHostBlock {
+    /// 按 ssh 的规则匹配主机别名，支持通配符和 ! 排除
+    fn matches(&self, alias: &str) -> bool {
+        let mut matched = false;
+        for pattern in &self.patterns {
+            let (negated, pattern) = match pattern.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, pattern.as_str()),
+            };
+            let is_match = glob::Pattern::new(pattern)
+                .map(|glob| glob.matches(alias))
+                .unwrap_or(false);
+            if is_match && negated {
+                return false;
+            }
+            matched |= is_match;
+        }
+        matched
+    }
+
+    fn is_wildcard(pattern: &str) -> bool {
+        pattern.starts_with('!') || pattern.contains(['*', '?'])
+    }
+}
+
+/// OpenSSH 客户端配置导入
+/// 每个非通配符的 Host 别名导入为一个 SSH 连接，通配符块中的选项按 ssh 的规则作为默认值
+pub struct SshConfigImporter;
+
+impl SshConfigImporter {
+    pub fn import(path: Option<&str>) -> Result<Vec<ImportedConnection>, StorageError> {
+        let path = expand_path(path.unwrap_or("~/.ssh/config"))?;
+        let Some(text) = read_optional(&path)? else {
+            return Ok(Vec::new());
+        };
+
+        let (blocks, skipped) = Self::parse(&text);
+
+        let mut aliases: Vec<&str> = Vec::new();
+        for block in &blocks {
+            for pattern in &block.patterns {
+                if !SshHostBlock::is_wildcard(pattern) && !aliases.contains(&pattern.as_str()) {
+                    aliases.push(pattern);
+                }
+            }
+        }
+
+        Ok(aliases
+            .into_iter()
+            .map(|alias| {
+                let mut imported = Self::convert(alias, &blocks);
+                imported.warnings.extend(skipped.iter().cloned());
+                imported
+            })
+            .collect())
+    }
+
+    /// 解析 Host 块，返回无法处理的指令（Include、Match）作为提示
+    fn parse(text: &str) -> (Vec<SshHostBlock>, Vec<String>) {
+        // Host 之前的全局选项对所有主机生效
+        let mut blocks = vec![SshHostBlock {
+            patterns: vec!["*".to_string()],
+            options: Vec::new(),
+        }];
+        let mut skipped = Vec::new();
+        let mut in_match = false;
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // 支持 "Key Value" 和 "Key=Value" 两种写法
+            let (key, value) = match line.find(|c: char| c.is_whitespace() || c == '=') {
+                Some(pos) => (
+                    &line[..pos],
+                    line[pos..]
+                        .trim_start_matches(|c: char| c.is_whitespace() || c == '=')
+                        .trim(),
+                ),
+                None => (line, ""),
+            };
+            let key = key.to_lowercase();
+
+            match key.as_str() {
+                "host" => {
+                    blocks.push(SshHostBlock {
+                        patterns: value.split_whitespace().map(str::to_string).collect(),
+                        options: Vec::new(),
+                    });
+                    in_match = false;
+                }
+                "match" => {
+                    skipped.push(format!("Match block ignored: {}", value));
+                    in_match = true;
+                }
+                "include" => skipped.push(format!("Include directive ignored: {}", value)),
+                _ if in_match => {}
+                _ => {
+                    if let Some(block) = blocks.last_mut() {
+                        block
+                            .options
+                            .push((key, value.trim_matches('"').to_string()));
+                    }
+                }
+            }
+        }
+
+        (blocks, skipped)
+    }
+
+    /// 查找对别名生效的选项，ssh 对每个选项取第一次出现的值
+    fn option<'a>(blocks: &'a [SshHostBlock], alias: &str, key: &str) -> Option<&'a str> {
+        blocks
+            .iter()
+            .filter(|block| block.matches(alias))
+            .flat_map(|block| block.options.iter())
+            .find(|(option, _)| option == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn convert(alias: &str, blocks: &[SshHostBlock]) -> ImportedConnection {
+        let get = |key: &str| Self::option(blocks, alias, key);
+        let mut imported = ImportedConnection::new(alias, "ssh", "ssh");
+
+        imported.config.url = Some(get("hostname").unwrap_or(alias).replace("%h", alias));
+        imported.config.username = get("user").map(str::to_string);
+
+        match get("port").map(str::parse::<u16>) {
+            Some(Ok(port)) => imported.config.port = Some(port),
+            Some(Err(_)) => imported.warnings.push("Invalid Port ignored".to_string()),
+            None => {}
+        }
+
+        if let Some(identity_file) = get("identityfile") {
+            match PathUtils::expand_home_dir(identity_file) {
+                Ok(path) => imported.config.private_key_path = Some(path),
+                Err(e) => imported.warnings.push(e.to_string()),
+            }
+        }
+
+        // 跳板机写成 Host 别名时按配置解析为实际地址，SSH 客户端从 extraOptions.proxyJump 读取
+        if let Some(proxy_jump) = get("proxyjump").filter(|value| *value != "none") {
+            let hops: Vec<String> = proxy_jump
+                .split(',')
+                .map(|hop| Self::resolve_jump_host(hop.trim(), blocks))
+                .collect();
+            imported.set_extra_option("proxyJump", &hops.join(","));
+            imported.warnings.push(format!(
+                "Jump hosts {} are authenticated with the same credentials as this host",
+                proxy_jump
+            ));
+        }
+
+        if imported.config.username.is_none() {
+            imported
+                .warnings
+                .push("No User configured; set a username before connecting".to_string());
+        }
+
+        imported
+    }
+
+    /// 把 [user@]alias[:port] 中的别名替换为配置中的 HostName、User 和 Port
+    fn resolve_jump_host(hop: &str, blocks: &[SshHostBlock]) -> String {
+        let (user, address) = match hop.rsplit_once('@') {
+            Some((user, address)) => (Some(user), address),
+            None => (None, hop),
+        };
+        let (alias, port) = match address.rsplit_once(':') {
+            Some((alias, port)) if !alias.contains(':') => (alias, Some(port)),
+            _ => (address, None),
+        };
+        let get = |key: &str| Self::option(blocks, alias, key);
+
+        let mut host = get("hostname").unwrap_or(alias).replace("%h", alias);
+        if host.contains(':') {
+            host = format!("[{}]", host);
+        }
+        let mut resolved = match user.or_else(|| get("user")) {
+            Some(user) => format!("{}@{}", user, host),
+            None => host,
+        };
+        if let Some(port) = port.or_else(|| get("port")) {
+            resolved = format!("{}:{}", resolved, port);
+        }
+        resolved
+    }
+}
+
+/// rclone 配置导入
+/// 支持 s3、webdav、sftp 和 smb 类型的 remote，经过 rclone obscure 的密码会被还原
+pub struct RcloneConfigImporter;
+
+impl RcloneConfigImporter {
+    /// rclone obscure 使用的固定密钥，见 rclone 源码 fs/config/obscure
+    const OBSCURE_KEY: [u8; 32] = [
+        0x9c, 0x93, 0x5b, 0x48, 0x73, 0x0a, 0x55, 0x4d, 0x6b, 0xfd, 0x7c, 0x63, 0xc8, 0x86, 0xa9,
+        0x2b, 0xd3, 0x90, 0x19, 0x8e, 0xb8, 0x12, 0x8a, 0xfb, 0xf4, 0xde, 0x16, 0x2b, 0x8b, 0x95,
+        0xf6, 0x38,
+    ];
+
+    /// 未指定路径时遵循 RCLONE_CONFIG 环境变量，默认使用系统配置目录下的 rclone/rclone.conf
+    pub fn import(path: Option<&str>) -> Result<Vec<ImportedConnection>, StorageError> {
+        let path = match path {
+            Some(path) => expand_path(path)?,
+            None => Self::default_path()?,
+        };
+        let Some(text) = read_optional(&path)? else {
+            return Ok(Vec::new());
+        };
+
+        if text.trim_start().starts_with("RCLONE_ENCRYPT_V0:") {
+            return Err(StorageError::InvalidConfig(
+                "Encrypted rclone configuration is not supported".to_string(),
+            ));
+        }
+
+        Ok(parse_ini(&text)
+            .iter()
+            .filter_map(Self::convert)
+            .collect())
+    }
+
+    fn default_path() -> Result<PathBuf, StorageError> {
+        if let Ok(path) = std::env::var("RCLONE_CONFIG") {
+            return expand_path(&path);
+        }
+
+        let config_path = dirs::config_dir().map(|dir| dir.join("rclone").join("rclone.conf"));
+        match config_path {
+            Some(path) if path.exists() => Ok(path),
+            _ => expand_path("~/.rclone.conf"),
+        }
+    }
+
+    fn convert(section: &IniSection) -> Option<ImportedConnection> {
+        let remote_type = section.get("type")?;
+        let imported = match remote_type {
+            "s3" => Self::convert_s3(section),
+            "webdav" => Self::convert_webdav(section),
+            "sftp" => Self::convert_sftp(section),
+            "smb" => Self::convert_smb(section),
+            _ => return None,
+        };
+
+        Some(imported)
+    }
+
+    fn convert_s3(section: &IniSection) -> ImportedConnection {
+        let mut imported = ImportedConnection::new(&section.name, "rclone", "oss");
+
+        imported.config.access_key = section.get("access_key_id").map(str::to_string);
+        imported.config.secret_key = section.get("secret_access_key").map(str::to_string);
+        imported.config.region = Some(section.get("region").unwrap_or("us-east-1").to_string());
+        imported.config.url = Some(match section.get("endpoint") {
+            Some(endpoint) if endpoint.contains("://") => endpoint.to_string(),
+            Some(endpoint) => format!("https://{}", endpoint),
+            None => format!(
+                "https://s3.{}.amazonaws.com",
+                imported.config.region.as_deref().unwrap_or("us-east-1")
+            ),
+        });
+
+        imported.config.session_token = section.get("session_token").map(str::to_string);
+        if let Some(provider) = section.get("provider") {
+            imported.set_extra_option("provider", provider);
+        }
+        if section.get("force_path_style") == Some("true") {
+            imported.set_extra_option("pathStyle", "true");
+        }
+        // env_auth 的 remote 没有固定密钥，连接时由默认凭证链解析
+        if section.get("env_auth") == Some("true") && imported.config.access_key.is_none() {
+            imported.warnings.push(
+                "env_auth: credentials are resolved from the environment, AWS profiles or instance metadata when connecting"
+                    .to_string(),
+            );
+        }
+        imported
+            .warnings
+            .push("rclone remotes do not specify a bucket; set one before connecting".to_string());
+
+        imported
+    }
+
+    fn convert_webdav(section: &IniSection) -> ImportedConnection {
+        let mut imported = ImportedConnection::new(&section.name, "rclone", "webdav");
+
+        imported.config.url = section.get("url").map(str::to_string);
+        imported.config.username = section.get("user").map(str::to_string);
+        imported.config.password = Self::reveal_option(section, "pass", &mut imported.warnings);
+
+        if section.get("bearer_token").is_some() {
+            imported
+                .warnings
+                .push("Bearer token authentication is not supported".to_string());
+        }
+
+        imported
+    }
+
+    fn convert_sftp(section: &IniSection) -> ImportedConnection {
+        let mut imported = ImportedConnection::new(&section.name, "rclone", "ssh");
+
+        imported.config.url = section.get("host").map(str::to_string);
+        imported.config.username = section
+            .get("user")
+            .map(str::to_string)
+            .or_else(|| std::env::var("USER").ok());
+        imported.config.port = section.get("port").and_then(|port| port.parse().ok());
+        imported.config.password = Self::reveal_option(section, "pass", &mut imported.warnings);
+        imported.config.passphrase =
+            Self::reveal_option(section, "key_file_pass", &mut imported.warnings);
+
+        if let Some(key_file) = section.get("key_file") {
+            match PathUtils::expand_home_dir(key_file) {
+                Ok(path) => imported.config.private_key_path = Some(path),
+                Err(e) => imported.warnings.push(e.to_string()),
+            }
+        }
+        if section.get("key_pem").is_some() {
+            imported
+                .warnings
+                .push("Inline key_pem is not supported; use key_file instead".to_string());
+        }
+
+        imported
+    }
+
+    fn convert_smb(section: &IniSection) -> ImportedConnection {
+        let mut imported = ImportedConnection::new(&section.name, "rclone", "smb");
+
+        imported.config.url = section.get("host").map(str::to_string);
+        imported.config.username = section.get("user").map(str::to_string);
+        imported.config.domain = section.get("domain").map(str::to_string);
+        imported.config.port = section.get("port").and_then(|port| port.parse().ok());
+        imported.config.password = Self::reveal_option(section, "pass", &mut imported.warnings);
+
+        // rclone 的 smb remote 在路径中指定共享名
+        imported.warnings.push(
+            "rclone SMB remotes do not specify a share; set one before connecting".to_string(),
+        );
+
+        imported
+    }
+
+    fn reveal_option(
+        section: &IniSection,
+        key: &str,
+        warnings: &mut Vec<String>,
+    ) -> Option<String> {
+        let value = section.get(key)?;
+        match Self::reveal(value) {
+            Some(revealed) => Some(revealed),
+            None => {
+                warnings.push(format!("Failed to decode obscured {}", key));
+                None
+            }
+        }
+    }
+
+    /// 还原 rclone obscure 的密码：base64url 解码后，前 16 字节为 IV，其余部分以 AES-256-CTR 解密
+    fn reveal(obscured: &str) -> Option<String> {
+        use ctr::cipher::{KeyIvInit, StreamCipher};
+
+        let data = general_purpose::URL_SAFE_NO_PAD
+            .decode(obscured.trim_end_matches('='))
+            .ok()?;
+        if data.len() < 16 {
+            return None;
+        }
+
+        let (iv, ciphertext) = data.split_at(16);
+        let mut plaintext = ciphertext.to_vec();
+        ctr::Ctr128BE::<aes::Aes256>::new(&Self::OBSCURE_KEY.into(), iv.into())
+            .apply_keystream(&mut plaintext);
+
+        String::from_utf8(plaintext).ok()
+    }
+}
+
+/// 按来源导入连接配置
+pub struct ConnectionImporter;
+
+impl ConnectionImporter {
+    /// source 为 "aws"、"ssh" 或 "rclone"，path 覆盖默认的配置文件位置（aws 为配置目录）
+    pub fn import(
+        source: &str,
+        path: Option<&str>,
+    ) -> Result<Vec<ImportedConnection>, StorageError> {
+        match source {
+            "aws" => AwsConfigImporter::import(path),
+            "ssh" => SshConfigImporter::import(path),
+            "rclone" => RcloneConfigImporter::import(path),
+            other => Err(StorageError::InvalidConfig(format!(
+                "Unsupported import source: {}",
+                other
+            ))),
+        }
+    }
+}
@@ -0,0 +1,419 @@
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use super::search::StorageSearcher;
+use super::traits::{DirectoryUsage, ListOptions, StorageClient, StorageError};
+use crate::utils::path_utils::PathUtils;
+
+/// 遍历目录时每页的条目数
+const LIST_PAGE_SIZE: u32 = 1000;
+/// 默认返回的目录树深度
+const DEFAULT_MAX_DEPTH: u32 = 3;
+/// 默认同时列出的目录数
+const DEFAULT_CONCURRENCY: u32 = 8;
+/// 同时列出的目录数上限
+const MAX_CONCURRENCY: u32 = 32;
+/// 两次进度推送之间的最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 目录占用统计选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageOptions {
+    /// 统计 ID，未指定时自动生成，可用于 storage_disk_usage_cancel
+    pub usage_id: Option<String>,
+    /// 返回的目录树深度，更深层的占用计入其祖先目录，默认 3
+    pub max_depth: Option<u32>,
+    /// 逐层遍历时同时列出的目录数，默认 8
+    pub concurrency: Option<u32>,
+    /// 是否使用服务端统计（SSH du、HF usedStorage），默认 true
+    pub use_server_usage: Option<bool>,
+}
+
+/// 目录占用树的节点，子节点按占用从大到小排列，可直接用于 treemap
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageNode {
+    pub name: String,
+    pub path: String,
+    pub size: String, // 使用字符串表示大数字
+    /// 服务端统计不提供文件数时为空
+    pub file_count: Option<String>,
+    pub children: Vec<DiskUsageNode>,
+}
+
+/// 目录占用统计结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageSummary {
+    pub usage_id: String,
+    pub root: DiskUsageNode,
+    /// 统计方式："server"、"native" 或 "walk"
+    pub method: String,
+    /// 列出失败而被跳过的子目录数
+    pub errors: u32,
+    pub cancelled: bool,
+    pub duration_ms: String,
+}
+
+/// 统计过程中推送的进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageProgress {
+    pub usage_id: String,
+    pub scanned_files: u64,
+    pub scanned_directories: u64,
+    pub total_size: u64,
+    pub current_path: String,
+}
+
+/// 单个目录的累计占用
+#[derive(Debug, Default, Clone, Copy)]
+struct UsageTotals {
+    size: u64,
+    file_count: Option<u64>,
+}
+
+/// 将文件占用累加到 max_depth 层以内的各级目录
+struct UsageAggregator<'a> {
+    usage_id: &'a str,
+    max_depth: u32,
+    totals: BTreeMap<String, UsageTotals>,
+    scanned_files: u64,
+    scanned_directories: u64,
+    total_size: u64,
+    errors: u32,
+    last_progress: Instant,
+    on_progress: &'a (dyn Fn(DiskUsageProgress) + Send + Sync),
+}
+
+impl<'a> UsageAggregator<'a> {
+    fn new(
+        usage_id: &'a str,
+        max_depth: u32,
+        on_progress: &'a (dyn Fn(DiskUsageProgress) + Send + Sync),
+    ) -> Self {
+        let mut totals = BTreeMap::new();
+        totals.insert(
+            String::new(),
+            UsageTotals {
+                size: 0,
+                file_count: Some(0),
+            },
+        );
+
+        Self {
+            usage_id,
+            max_depth,
+            totals,
+            scanned_files: 0,
+            scanned_directories: 0,
+            total_size: 0,
+            errors: 0,
+            last_progress: Instant::now(),
+            on_progress,
+        }
+    }
+
+    /// 记录一个目录，保证空目录也出现在结果中
+    fn add_directory(&mut self, relative_path: &str) {
+        self.scanned_directories += 1;
+        if Self::depth(relative_path) <= self.max_depth {
+            self.totals
+                .entry(relative_path.to_string())
+                .or_insert(UsageTotals {
+                    size: 0,
+                    file_count: Some(0),
+                });
+        }
+    }
+
+    /// 将文件占用累加到根目录以及 max_depth 层以内的各级父目录
+    fn add_file(&mut self, relative_path: &str, size: u64) {
+        self.scanned_files += 1;
+        self.total_size += size;
+
+        let mut ancestor = String::new();
+        let mut segments = relative_path.split('/').peekable();
+        let mut depth = 0;
+        loop {
+            let totals = self.totals.entry(ancestor.clone()).or_insert(UsageTotals {
+                size: 0,
+                file_count: Some(0),
+            });
+            totals.size += size;
+            totals.file_count = totals.file_count.map(|count| count + 1);
+
+            let Some(segment) = segments.next() else {
+                break;
+            };
+            // 最后一段是文件名
+            if segments.peek().is_none() || depth >= self.max_depth {
+                break;
+            }
+            ancestor = PathUtils::join_storage_path(&ancestor, segment);
+            depth += 1;
+        }
+    }
+
+    /// 距离上次推送超过间隔时推送进度
+    fn report(&mut self, current_path: &str) {
+        if self.last_progress.elapsed() < PROGRESS_INTERVAL {
+            return;
+        }
+        self.last_progress = Instant::now();
+
+        (self.on_progress)(DiskUsageProgress {
+            usage_id: self.usage_id.to_string(),
+            scanned_files: self.scanned_files,
+            scanned_directories: self.scanned_directories,
+            total_size: self.total_size,
+            current_path: current_path.to_string(),
+        });
+    }
+
+    /// 相对路径对应的目录深度，根目录为 0
+    fn depth(relative_path: &str) -> u32 {
+        if relative_path.is_empty() {
+            0
+        } else {
+            relative_path.matches('/').count() as u32 + 1
+        }
+    }
+}
+
+/// 目录占用统计器
+/// 优先使用服务端统计，其次使用原生递归列出，最后回退到并发的逐层遍历
+pub struct DiskUsageAnalyzer;
+
+impl DiskUsageAnalyzer {
+    /// 统计 root 下各级目录的占用，进度通过 on_progress 推送
+    pub async fn analyze(
+        client: &(dyn StorageClient + Send + Sync),
+        root: &str,
+        usage_id: &str,
+        options: &DiskUsageOptions,
+        cancelled: &AtomicBool,
+        on_progress: &(dyn Fn(DiskUsageProgress) + Send + Sync),
+    ) -> Result<DiskUsageSummary, StorageError> {
+        let started_at = Instant::now();
+        let max_depth = options.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+        let concurrency = options
+            .concurrency
+            .unwrap_or(DEFAULT_CONCURRENCY)
+            .clamp(1, MAX_CONCURRENCY) as usize;
+        let mut aggregator = UsageAggregator::new(usage_id, max_depth, on_progress);
+
+        let mut method = "walk";
+        if options.use_server_usage.unwrap_or(true) {
+            match client.directory_usage(root, max_depth).await {
+                Ok(Some(usage)) => {
+                    method = "server";
+                    aggregator.totals = Self::server_totals(usage);
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Server-side usage failed for {}: {}", root, e),
+            }
+        }
+
+        if method == "walk" && client.supports_recursive_listing(root) {
+            match Self::scan_native(client, root, cancelled, &mut aggregator).await {
+                Ok(()) => method = "native",
+                // 服务器拒绝原生递归列出时回退到逐层遍历
+                Err(StorageError::ProtocolNotSupported(reason))
+                    if aggregator.scanned_files == 0 =>
+                {
+                    log::info!(
+                        "Native recursive listing unavailable for {}: {}",
+                        root,
+                        reason
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if method == "walk" {
+            Self::scan_parallel(client, root, concurrency, cancelled, &mut aggregator).await?;
+        }
+
+        Ok(DiskUsageSummary {
+            usage_id: usage_id.to_string(),
+            root: Self::build_tree(root, &aggregator.totals),
+            method: method.to_string(),
+            errors: aggregator.errors,
+            cancelled: cancelled.load(Ordering::SeqCst),
+            duration_ms: started_at.elapsed().as_millis().to_string(),
+        })
+    }
+
+    /// 将服务端统计结果转换为目录占用表，缺失的父目录补为 0
+    fn server_totals(usage: Vec<DirectoryUsage>) -> BTreeMap<String, UsageTotals> {
+        let mut totals = BTreeMap::new();
+        for entry in usage {
+            let mut parent = entry.path.as_str();
+            while let Some((ancestor, _)) = parent.rsplit_once('/') {
+                totals.entry(ancestor.to_string()).or_default();
+                parent = ancestor;
+            }
+            if !entry.path.is_empty() {
+                totals.entry(String::new()).or_default();
+            }
+
+            totals.insert(
+                entry.path,
+                UsageTotals {
+                    size: entry.size,
+                    file_count: entry.file_count,
+                },
+            );
+        }
+        totals
+    }
+
+    /// 使用原生递归列出，逐页累加
+    async fn scan_native(
+        client: &(dyn StorageClient + Send + Sync),
+        root: &str,
+        cancelled: &AtomicBool,
+        aggregator: &mut UsageAggregator<'_>,
+    ) -> Result<(), StorageError> {
+        let mut marker: Option<String> = None;
+
+        while !cancelled.load(Ordering::SeqCst) {
+            let options = ListOptions {
+                page_size: Some(LIST_PAGE_SIZE),
+                marker: marker.clone(),
+                prefix: None,
+                recursive: Some(true),
+                ..Default::default()
+            };
+            let result = client.list_directory(root, Some(&options)).await?;
+
+            // 原生递归列出的 filename 为相对于 root 的路径
+            for file in &result.files {
+                aggregator.add_file(&file.filename, file.size.parse::<u64>().unwrap_or(0));
+            }
+            if let Some(last) = result.files.last() {
+                aggregator.report(&PathUtils::join_storage_path(root, &last.filename));
+            }
+
+            if result.has_more && result.next_marker.is_some() {
+                marker = result.next_marker;
+            } else {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 逐层遍历目录，每层内的目录并发列出
+    async fn scan_parallel(
+        client: &(dyn StorageClient + Send + Sync),
+        root: &str,
+        concurrency: usize,
+        cancelled: &AtomicBool,
+        aggregator: &mut UsageAggregator<'_>,
+    ) -> Result<(), StorageError> {
+        let mut frontier = vec![String::new()];
+
+        while !frontier.is_empty() && !cancelled.load(Ordering::SeqCst) {
+            let mut next_frontier = Vec::new();
+            let mut listings = futures_util::stream::iter(std::mem::take(&mut frontier))
+                .map(|relative_dir| async move {
+                    let directory = PathUtils::join_storage_path(root, &relative_dir);
+                    let result = StorageSearcher::list_all(client, &directory, cancelled).await;
+                    (result, relative_dir, directory)
+                })
+                .buffer_unordered(concurrency);
+
+            while let Some((result, relative_dir, directory)) = listings.next().await {
+                let files = match result {
+                    Ok(files) => files,
+                    // 根目录无法列出时整个统计失败，子目录失败只记录并跳过
+                    Err(e) if relative_dir.is_empty() => return Err(e),
+                    Err(e) => {
+                        log::warn!("Disk usage skipped {}: {}", relative_dir, e);
+                        aggregator.errors += 1;
+                        continue;
+                    }
+                };
+
+                for file in files {
+                    let relative_path = PathUtils::join_storage_path(&relative_dir, &file.basename);
+                    if file.file_type == "directory" {
+                        aggregator.add_directory(&relative_path);
+                        next_frontier.push(relative_path);
+                    } else {
+                        aggregator.add_file(&relative_path, file.size.parse::<u64>().unwrap_or(0));
+                    }
+                }
+                aggregator.report(&directory);
+            }
+
+            frontier = next_frontier;
+        }
+
+        Ok(())
+    }
+
+    /// 根据目录占用表构建目录树
+    fn build_tree(root: &str, totals: &BTreeMap<String, UsageTotals>) -> DiskUsageNode {
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        for relative_path in totals.keys().filter(|path| !path.is_empty()) {
+            let parent = relative_path
+                .rsplit_once('/')
+                .map(|(parent, _)| parent)
+                .unwrap_or("");
+            children.entry(parent).or_default().push(relative_path);
+        }
+
+        let root_name = root
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .unwrap_or(root);
+        Self::build_node(root, "", root_name, totals, &children)
+    }
+
+    fn build_node(
+        root: &str,
+        relative_path: &str,
+        name: &str,
+        totals: &BTreeMap<String, UsageTotals>,
+        children: &HashMap<&str, Vec<&str>>,
+    ) -> DiskUsageNode {
+        let usage = totals.get(relative_path).copied().unwrap_or_default();
+
+        let mut nodes: Vec<DiskUsageNode> = children
+            .get(relative_path)
+            .map(|paths| {
+                paths
+                    .iter()
+                    .map(|path| {
+                        let name = path.rsplit('/').next().unwrap_or(path);
+                        Self::build_node(root, path, name, totals, children)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        nodes.sort_by_key(|node| std::cmp::Reverse(node.size.parse::<u64>().unwrap_or(0)));
+
+        DiskUsageNode {
+            name: name.to_string(),
+            path: if relative_path.is_empty() {
+                root.to_string()
+            } else {
+                PathUtils::join_storage_path(root, relative_path)
+            },
+            size: usage.size.to_string(),
+            file_count: usage.file_count.map(|count| count.to_string()),
+            children: nodes,
+        }
+    }
+}
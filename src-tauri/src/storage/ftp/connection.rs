@@ -0,0 +1,471 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader, ReadBuf};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use tokio_rustls::TlsConnector;
+
+use super::parser::{parse_list_line, parse_mlsd_line, FtpEntry};
+use crate::storage::traits::StorageError;
+
+/// 控制连接上单个响应的超时时间
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// FTP 加密方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FtpTlsMode {
+    /// 明文 FTP
+    None,
+    /// 显式 TLS（FTPES），连接后通过 AUTH TLS 升级
+    Explicit,
+    /// 隐式 TLS，连接建立后立即握手，默认端口 990
+    Implicit,
+}
+
+/// 建立 FTP 连接所需的参数
+#[derive(Debug, Clone)]
+pub struct FtpConnectOptions {
+    pub host: String,
+    pub port: u16,
+    pub tls_mode: FtpTlsMode,
+    pub username: String,
+    pub password: String,
+}
+
+/// 明文或 TLS 加密的 TCP 连接，控制连接和数据连接共用
+pub enum FtpStream {
+    Plain(TcpStream),
+    Tls(Box<tokio_rustls::client::TlsStream<TcpStream>>),
+}
+
+impl AsyncRead for FtpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            FtpStream::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+            FtpStream::Tls(stream) => Pin::new(stream.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl tokio::io::AsyncWrite for FtpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            FtpStream::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+            FtpStream::Tls(stream) => Pin::new(stream.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            FtpStream::Plain(stream) => Pin::new(stream).poll_flush(cx),
+            FtpStream::Tls(stream) => Pin::new(stream.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            FtpStream::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+            FtpStream::Tls(stream) => Pin::new(stream.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
+
+/// 数据连接的 TLS 参数，PROT P 后数据连接也需要加密
+#[derive(Clone)]
+struct TlsContext {
+    connector: TlsConnector,
+    server_name: ServerName,
+}
+
+impl TlsContext {
+    fn new(host: &str) -> Result<Self, StorageError> {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                anchor.subject,
+                anchor.spki,
+                anchor.name_constraints,
+            )
+        }));
+        // 同一个配置在数据连接上复用 TLS 会话，满足要求会话复用的服务器
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let server_name = ServerName::try_from(host)
+            .map_err(|e| StorageError::InvalidConfig(format!("Invalid FTP host: {}", e)))?;
+
+        Ok(Self {
+            connector: TlsConnector::from(Arc::new(config)),
+            server_name,
+        })
+    }
+
+    async fn wrap(&self, stream: TcpStream) -> Result<FtpStream, StorageError> {
+        let tls_stream = self
+            .connector
+            .connect(self.server_name.clone(), stream)
+            .await
+            .map_err(|e| StorageError::ConnectionFailed(format!("TLS handshake failed: {}", e)))?;
+        Ok(FtpStream::Tls(Box::new(tls_stream)))
+    }
+}
+
+/// FTP 控制连接
+/// 所有数据传输都使用被动模式，优先 EPSV，服务器不支持时回退到 PASV
+pub struct FtpConnection {
+    control: BufReader<FtpStream>,
+    host: String,
+    tls: Option<TlsContext>,
+    use_epsv: bool,
+    supports_mlsd: bool,
+}
+
+impl FtpConnection {
+    /// 建立连接并登录，切换到二进制模式
+    pub async fn connect(options: &FtpConnectOptions) -> Result<Self, StorageError> {
+        let tcp = tokio::time::timeout(
+            RESPONSE_TIMEOUT,
+            TcpStream::connect((options.host.as_str(), options.port)),
+        )
+        .await
+        .map_err(|_| StorageError::Timeout(format!("Connecting to {}", options.host)))?
+        .map_err(|e| StorageError::ConnectionFailed(format!("FTP connection failed: {}", e)))?;
+
+        let tls = match options.tls_mode {
+            FtpTlsMode::None => None,
+            _ => Some(TlsContext::new(&options.host)?),
+        };
+        let stream = match (&tls, options.tls_mode) {
+            (Some(tls), FtpTlsMode::Implicit) => tls.wrap(tcp).await?,
+            _ => FtpStream::Plain(tcp),
+        };
+
+        let mut connection = Self {
+            control: BufReader::new(stream),
+            host: options.host.clone(),
+            tls: None,
+            use_epsv: true,
+            supports_mlsd: false,
+        };
+        connection.expect_response(&[220], "Greeting").await?;
+
+        if let Some(tls) = tls {
+            if options.tls_mode == FtpTlsMode::Explicit {
+                connection.expect("AUTH TLS", &[234]).await?;
+                let FtpStream::Plain(tcp) = connection.control.into_inner() else {
+                    unreachable!("control connection is plain before AUTH TLS");
+                };
+                connection.control = BufReader::new(tls.wrap(tcp).await?);
+            }
+            connection.tls = Some(tls);
+        }
+
+        connection
+            .login(&options.username, &options.password)
+            .await?;
+
+        if connection.tls.is_some() {
+            connection.expect("PBSZ 0", &[200]).await?;
+            connection.expect("PROT P", &[200]).await?;
+        }
+
+        connection.expect("TYPE I", &[200]).await?;
+        connection.load_features().await;
+
+        Ok(connection)
+    }
+
+    async fn login(&mut self, username: &str, password: &str) -> Result<(), StorageError> {
+        let (code, text) = self.command(&format!("USER {}", username)).await?;
+        match code {
+            230 => Ok(()),
+            331 | 332 => {
+                let (code, text) = self.command(&format!("PASS {}", password)).await?;
+                if code == 230 || code == 202 {
+                    Ok(())
+                } else {
+                    Err(StorageError::AuthenticationFailed(format!(
+                        "FTP login failed: {} {}",
+                        code, text
+                    )))
+                }
+            }
+            _ => Err(StorageError::AuthenticationFailed(format!(
+                "FTP login failed: {} {}",
+                code, text
+            ))),
+        }
+    }
+
+    /// 通过 FEAT 检查服务器是否支持 MLSD
+    async fn load_features(&mut self) {
+        if let Ok((211, text)) = self.command("FEAT").await {
+            self.supports_mlsd = text
+                .lines()
+                .any(|line| line.trim().to_uppercase().starts_with("MLST"));
+        }
+    }
+
+    /// 读取一个完整的响应，支持多行响应（"123-" 开始，"123 " 结束）
+    async fn read_response(&mut self) -> Result<(u32, String), StorageError> {
+        tokio::time::timeout(RESPONSE_TIMEOUT, async {
+            let mut text = String::new();
+            let mut code: Option<(u32, String)> = None;
+
+            loop {
+                let mut line = String::new();
+                let read =
+                    self.control.read_line(&mut line).await.map_err(|e| {
+                        StorageError::NetworkError(format!("FTP read failed: {}", e))
+                    })?;
+                if read == 0 {
+                    return Err(StorageError::NetworkError(
+                        "FTP control connection closed".to_string(),
+                    ));
+                }
+
+                let line = line.trim_end_matches(['\r', '\n']);
+                text.push_str(line);
+                text.push('\n');
+
+                match &code {
+                    None => {
+                        let value = line
+                            .get(..3)
+                            .and_then(|c| c.parse::<u32>().ok())
+                            .ok_or_else(|| {
+                                StorageError::RequestFailed(format!(
+                                    "Invalid FTP response: {}",
+                                    line
+                                ))
+                            })?;
+                        if line.as_bytes().get(3) != Some(&b'-') {
+                            return Ok((value, text));
+                        }
+                        code = Some((value, format!("{} ", value)));
+                    }
+                    Some((value, terminator)) => {
+                        if line.starts_with(terminator.as_str()) {
+                            return Ok((*value, text));
+                        }
+                    }
+                }
+            }
+        })
+        .await
+        .map_err(|_| StorageError::Timeout("FTP response".to_string()))?
+    }
+
+    /// 发送命令并读取响应
+    async fn command(&mut self, command: &str) -> Result<(u32, String), StorageError> {
+        let stream = self.control.get_mut();
+        stream
+            .write_all(format!("{}\r\n", command).as_bytes())
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("FTP write failed: {}", e)))?;
+        stream
+            .flush()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("FTP write failed: {}", e)))?;
+
+        self.read_response().await
+    }
+
+    /// 发送命令并检查响应码
+    async fn expect(&mut self, command: &str, codes: &[u32]) -> Result<String, StorageError> {
+        let (code, text) = self.command(command).await?;
+        if codes.contains(&code) {
+            Ok(text)
+        } else {
+            // 日志和错误信息中不包含密码
+            let verb = command.split(' ').next().unwrap_or(command);
+            Err(Self::error_for(code, &text, verb))
+        }
+    }
+
+    async fn expect_response(&mut self, codes: &[u32], context: &str) -> Result<(), StorageError> {
+        let (code, text) = self.read_response().await?;
+        if codes.contains(&code) {
+            Ok(())
+        } else {
+            Err(Self::error_for(code, &text, context))
+        }
+    }
+
+    /// 按响应码构造存储错误，4xx 为临时错误（452 存储空间不足重试无效，不作为临时错误）
+    fn error_for(code: u32, text: &str, context: &str) -> StorageError {
+        let message = format!("{} failed: {} {}", context, code, text.trim());
+        match code {
+            421 | 425 | 426 => StorageError::NetworkError(message),
+            450 | 451 => StorageError::RateLimited(message, None),
+            452 => StorageError::RequestFailed(message),
+            530 => StorageError::AuthenticationFailed(message),
+            550 => StorageError::NotFound(message),
+            500 | 501 | 502 | 504 => StorageError::ProtocolNotSupported(message),
+            532 | 553 => StorageError::PermissionDenied(message),
+            _ => StorageError::RequestFailed(message),
+        }
+    }
+
+    /// 进入被动模式并建立数据连接
+    async fn open_data_connection(&mut self) -> Result<TcpStream, StorageError> {
+        let mut port = None;
+        if self.use_epsv {
+            // 229 Entering Extended Passive Mode (|||port|)
+            match self.command("EPSV").await? {
+                (229, text) => {
+                    port = text
+                        .split('|')
+                        .filter_map(|part| part.parse::<u16>().ok())
+                        .next()
+                }
+                _ => self.use_epsv = false,
+            }
+        }
+
+        if port.is_none() {
+            // 227 Entering Passive Mode (h1,h2,h3,h4,p1,p2)
+            let text = self.expect("PASV", &[227]).await?;
+            let numbers: Vec<u16> = text
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|part| part.parse().ok())
+                .collect();
+            // 第一个数字是响应码，最后两个数字是端口
+            if numbers.len() >= 7 {
+                port = Some(numbers[numbers.len() - 2] * 256 + numbers[numbers.len() - 1]);
+            }
+        }
+
+        let port = port.ok_or_else(|| {
+            StorageError::RequestFailed("Failed to parse passive mode response".to_string())
+        })?;
+
+        // 服务器返回的地址在 NAT 后面时往往不可达，始终连接控制连接的主机
+        tokio::time::timeout(
+            RESPONSE_TIMEOUT,
+            TcpStream::connect((self.host.as_str(), port)),
+        )
+        .await
+        .map_err(|_| StorageError::Timeout("FTP data connection".to_string()))?
+        .map_err(|e| StorageError::NetworkError(format!("FTP data connection failed: {}", e)))
+    }
+
+    /// 执行需要数据连接的命令，返回已建立的数据流
+    async fn data_command(&mut self, command: &str) -> Result<FtpStream, StorageError> {
+        let tcp = self.open_data_connection().await?;
+        let verb = command.split(' ').next().unwrap_or(command).to_string();
+        let (code, text) = self.command(command).await?;
+        if code != 125 && code != 150 {
+            return Err(Self::error_for(code, &text, &verb));
+        }
+
+        match &self.tls {
+            Some(tls) => tls.wrap(tcp).await,
+            None => Ok(FtpStream::Plain(tcp)),
+        }
+    }
+
+    /// 数据传输完成后读取结束响应
+    pub async fn finish_transfer(&mut self) -> Result<(), StorageError> {
+        self.expect_response(&[226, 250], "Transfer").await
+    }
+
+    /// 提前关闭数据连接，读取服务器的传输中止响应
+    pub async fn abort_transfer(&mut self, data: FtpStream) -> Result<(), StorageError> {
+        drop(data);
+        self.expect_response(&[226, 250, 425, 426, 450, 451], "Abort transfer")
+            .await
+    }
+
+    /// 列出目录内容，优先使用 MLSD，不支持时解析 LIST 输出
+    pub async fn list(&mut self, path: &str) -> Result<Vec<FtpEntry>, StorageError> {
+        let (command, parse): (&str, fn(&str) -> Option<FtpEntry>) = if self.supports_mlsd {
+            ("MLSD", parse_mlsd_line)
+        } else {
+            ("LIST -a", parse_list_line)
+        };
+
+        let mut data = self.data_command(&format!("{} {}", command, path)).await?;
+        let mut listing = Vec::new();
+        data.read_to_end(&mut listing)
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("FTP listing failed: {}", e)))?;
+        drop(data);
+        self.finish_transfer().await?;
+
+        Ok(String::from_utf8_lossy(&listing)
+            .lines()
+            .filter_map(parse)
+            .filter(|entry| entry.name != "." && entry.name != "..")
+            .collect())
+    }
+
+    /// 获取文件大小，服务器不支持 SIZE 或路径不是文件时返回 None
+    pub async fn size(&mut self, path: &str) -> Result<Option<u64>, StorageError> {
+        match self.command(&format!("SIZE {}", path)).await? {
+            (213, text) => Ok(text
+                .split_whitespace()
+                .nth(1)
+                .and_then(|size| size.parse().ok())),
+            _ => Ok(None),
+        }
+    }
+
+    /// 从指定偏移量开始下载文件，偏移量大于 0 时先发送 REST
+    pub async fn retrieve(&mut self, path: &str, offset: u64) -> Result<FtpStream, StorageError> {
+        if offset > 0 {
+            self.expect(&format!("REST {}", offset), &[350]).await?;
+        }
+        self.data_command(&format!("RETR {}", path)).await
+    }
+
+    /// 上传文件，调用方写完数据并关闭数据流后需要调用 finish_transfer
+    pub async fn store(&mut self, path: &str) -> Result<FtpStream, StorageError> {
+        self.data_command(&format!("STOR {}", path)).await
+    }
+
+    pub async fn make_directory(&mut self, path: &str) -> Result<(), StorageError> {
+        self.expect(&format!("MKD {}", path), &[257, 250]).await?;
+        Ok(())
+    }
+
+    /// 检查路径是否为可进入的目录
+    pub async fn is_directory(&mut self, path: &str) -> Result<bool, StorageError> {
+        let (code, _) = self.command(&format!("CWD {}", path)).await?;
+        Ok(code == 250)
+    }
+
+    pub async fn remove_directory(&mut self, path: &str) -> Result<(), StorageError> {
+        self.expect(&format!("RMD {}", path), &[250]).await?;
+        Ok(())
+    }
+
+    pub async fn delete_file(&mut self, path: &str) -> Result<(), StorageError> {
+        self.expect(&format!("DELE {}", path), &[250]).await?;
+        Ok(())
+    }
+
+    pub async fn rename(&mut self, from: &str, to: &str) -> Result<(), StorageError> {
+        self.expect(&format!("RNFR {}", from), &[350]).await?;
+        self.expect(&format!("RNTO {}", to), &[250]).await?;
+        Ok(())
+    }
+
+    /// 结束会话，忽略服务器的响应
+    pub async fn quit(mut self) {
+        let _ = self.command("QUIT").await;
+    }
+}
@@ -0,0 +1,8 @@
+pub mod connection;
+pub mod parser;
+
+// 重新导出连接相关功能
+pub use connection::{FtpConnectOptions, FtpConnection, FtpStream, FtpTlsMode};
+
+// 重新导出解析相关功能
+pub use parser::FtpEntry;
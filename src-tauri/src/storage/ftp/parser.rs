@@ -0,0 +1,197 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// FTP 目录条目
+#[derive(Debug, Clone)]
+pub struct FtpEntry {
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    /// RFC3339 格式的修改时间
+    pub modified: Option<String>,
+    pub permissions: Option<String>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+}
+
+/// 解析 MLSD 响应的一行
+/// 格式为以分号分隔的事实列表，空格后为文件名，如 "type=file;size=1024;modify=20240101120000; data.csv"
+pub fn parse_mlsd_line(line: &str) -> Option<FtpEntry> {
+    let (facts, name) = line.split_once(' ')?;
+    let name = name.trim_end_matches(['\r', '\n']);
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut entry = FtpEntry {
+        name: name.to_string(),
+        is_dir: false,
+        size: 0,
+        modified: None,
+        permissions: None,
+        owner: None,
+        group: None,
+    };
+
+    for fact in facts.split(';') {
+        let Some((key, value)) = fact.split_once('=') else {
+            continue;
+        };
+        match key.to_lowercase().as_str() {
+            "type" => match value.to_lowercase().as_str() {
+                // 当前目录和上级目录
+                "cdir" | "pdir" => return None,
+                "dir" => entry.is_dir = true,
+                _ => {}
+            },
+            "size" | "sizd" => entry.size = value.parse().unwrap_or(0),
+            "modify" => entry.modified = parse_mlsd_time(value),
+            "unix.mode" => entry.permissions = Some(value.to_string()),
+            "unix.owner" | "unix.uid" if entry.owner.is_none() => {
+                entry.owner = Some(value.to_string())
+            }
+            "unix.group" | "unix.gid" if entry.group.is_none() => {
+                entry.group = Some(value.to_string())
+            }
+            "perm" if entry.permissions.is_none() => entry.permissions = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    Some(entry)
+}
+
+/// 解析 MLSD 的时间格式 YYYYMMDDHHMMSS[.sss]（UTC）
+fn parse_mlsd_time(value: &str) -> Option<String> {
+    let value = value.split('.').next()?;
+    NaiveDateTime::parse_from_str(value, "%Y%m%d%H%M%S")
+        .ok()
+        .map(|dt| Utc.from_utc_datetime(&dt).to_rfc3339())
+}
+
+/// 解析 LIST 响应的一行，支持 Unix ls -l 格式和 Windows/DOS 格式
+pub fn parse_list_line(line: &str) -> Option<FtpEntry> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    if line.is_empty() || line.starts_with("total ") {
+        return None;
+    }
+
+    if line.as_bytes().first()?.is_ascii_digit() {
+        parse_dos_line(line)
+    } else {
+        parse_unix_line(line)
+    }
+}
+
+/// 按空白拆分前 count 个字段，返回字段和剩余部分（保留文件名中的空格）
+fn split_fields(line: &str, count: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut rest = line.trim_start();
+    for _ in 0..count {
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some((fields, rest))
+}
+
+/// 解析 Unix 格式，如 "drwxr-xr-x 2 owner group 4096 Jan 01 12:00 name"
+/// 部分服务器省略 group 列，通过月份所在的列定位大小和日期
+fn parse_unix_line(line: &str) -> Option<FtpEntry> {
+    let (fields, _) = split_fields(line, 7)?;
+    let month_index = (3..fields.len())
+        .find(|&i| month_number(fields[i]).is_some() && fields[i - 1].parse::<u64>().is_ok())?;
+    let (fields, name) = split_fields(line, month_index + 3)?;
+
+    let permissions = fields[0];
+    let kind = permissions.chars().next()?;
+    let name = match kind {
+        // 符号链接显示为 "name -> target"
+        'l' => name.split(" -> ").next().unwrap_or(name),
+        _ => name,
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(FtpEntry {
+        name: name.to_string(),
+        is_dir: kind == 'd',
+        size: fields[month_index - 1].parse().unwrap_or(0),
+        modified: parse_unix_time(
+            fields[month_index],
+            fields[month_index + 1],
+            fields[month_index + 2],
+        ),
+        permissions: Some(permissions.to_string()),
+        owner: fields
+            .get(2)
+            .filter(|_| month_index > 3)
+            .map(|s| s.to_string()),
+        group: fields
+            .get(3)
+            .filter(|_| month_index > 4)
+            .map(|s| s.to_string()),
+    })
+}
+
+fn month_number(month: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let month = month.to_lowercase();
+    MONTHS
+        .iter()
+        .position(|m| *m == month)
+        .map(|index| index as u32 + 1)
+}
+
+/// 解析 "Jan 01 12:00" 或 "Jan 01 2023" 格式的时间
+/// 不带年份时为最近一年内的时间，晚于当前时间则属于去年
+fn parse_unix_time(month: &str, day: &str, time_or_year: &str) -> Option<String> {
+    let month = month_number(month)?;
+    let day: u32 = day.parse().ok()?;
+    let now = Utc::now();
+
+    let datetime = if let Some((hour, minute)) = time_or_year.split_once(':') {
+        let (hour, minute) = (hour.parse().ok()?, minute.parse().ok()?);
+        let datetime =
+            NaiveDate::from_ymd_opt(now.year(), month, day)?.and_hms_opt(hour, minute, 0)?;
+        if datetime > now.naive_utc() + chrono::Duration::days(1) {
+            NaiveDate::from_ymd_opt(now.year() - 1, month, day)?.and_hms_opt(hour, minute, 0)?
+        } else {
+            datetime
+        }
+    } else {
+        NaiveDate::from_ymd_opt(time_or_year.parse().ok()?, month, day)?.and_hms_opt(0, 0, 0)?
+    };
+
+    Some(Utc.from_utc_datetime(&datetime).to_rfc3339())
+}
+
+/// 解析 DOS 格式，如 "01-15-24  10:30AM       <DIR>          name" 或 "01-15-24  10:30AM  1024 name"
+fn parse_dos_line(line: &str) -> Option<FtpEntry> {
+    let (fields, name) = split_fields(line, 3)?;
+    if name.is_empty() {
+        return None;
+    }
+
+    let is_dir = fields[2].eq_ignore_ascii_case("<DIR>");
+    let size = if is_dir { 0 } else { fields[2].parse().ok()? };
+
+    let modified = ["%m-%d-%y %I:%M%p", "%m-%d-%Y %I:%M%p", "%m-%d-%y %H:%M"]
+        .iter()
+        .find_map(|format| {
+            NaiveDateTime::parse_from_str(&format!("{} {}", fields[0], fields[1]), format).ok()
+        })
+        .map(|dt| Utc.from_utc_datetime(&dt).to_rfc3339());
+
+    Some(FtpEntry {
+        name: name.to_string(),
+        is_dir,
+        size,
+        modified,
+        permissions: None,
+        owner: None,
+        group: None,
+    })
+}
@@ -0,0 +1,658 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::storage::ftp::{FtpConnectOptions, FtpConnection, FtpEntry, FtpStream, FtpTlsMode};
+use crate::storage::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile,
+};
+use crate::utils::stream_io::StreamIo;
+
+// 单次读写的缓冲区大小
+const CHUNK_SIZE: usize = 64 * 1024;
+
+pub struct FtpClient {
+    config: ConnectionConfig,
+    options: FtpConnectOptions,
+    root_path: String,
+    // 浏览、范围读取等短操作共用的控制连接，上传、整文件读取和流式读取使用独立连接
+    connection: Mutex<Option<FtpConnection>>,
+    connected: AtomicBool,
+}
+
+impl FtpClient {
+    pub fn new(config: ConnectionConfig) -> Result<Self, StorageError> {
+        let url = config
+            .url
+            .clone()
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| StorageError::InvalidConfig("FTP host is required".to_string()))?;
+
+        // 支持 "ftp://host:port/path" 和单独的主机名
+        let parsed_url = if url.contains("://") {
+            url::Url::parse(&url)
+                .map_err(|e| StorageError::InvalidConfig(format!("Invalid FTP URL: {}", e)))?
+        } else {
+            url::Url::parse(&format!("ftp://{}", url))
+                .map_err(|e| StorageError::InvalidConfig(format!("Invalid FTP host: {}", e)))?
+        };
+        let host = parsed_url
+            .host_str()
+            .filter(|host| !host.is_empty())
+            .ok_or_else(|| StorageError::InvalidConfig("FTP host is required".to_string()))?
+            .to_string();
+
+        // ftps 协议默认使用显式 TLS，可通过 extraOptions.tls 指定 "implicit" 或 "none"
+        let tls_option = config
+            .extra_options
+            .as_ref()
+            .and_then(|options| options.get("tls"))
+            .map(|value| value.to_lowercase());
+        let tls_mode = match tls_option.as_deref() {
+            Some("implicit") => FtpTlsMode::Implicit,
+            Some("explicit") => FtpTlsMode::Explicit,
+            Some("none") => FtpTlsMode::None,
+            _ if config.protocol == "ftps" || parsed_url.scheme() == "ftps" => FtpTlsMode::Explicit,
+            _ => FtpTlsMode::None,
+        };
+
+        let default_port = if tls_mode == FtpTlsMode::Implicit {
+            990
+        } else {
+            21
+        };
+        let port = config
+            .port
+            .or_else(|| parsed_url.port())
+            .unwrap_or(default_port);
+
+        // 未提供用户名时使用匿名登录
+        let username = config
+            .username
+            .clone()
+            .filter(|username| !username.is_empty())
+            .unwrap_or_else(|| "anonymous".to_string());
+        let password = config
+            .password
+            .clone()
+            .unwrap_or_else(|| "anonymous@".to_string());
+
+        let root_path = config
+            .root_path
+            .clone()
+            .filter(|root| !root.is_empty())
+            .unwrap_or_else(|| parsed_url.path().to_string());
+        let root_path = format!("/{}", root_path.trim_matches('/'));
+
+        Ok(Self {
+            config,
+            options: FtpConnectOptions {
+                host,
+                port,
+                tls_mode,
+                username,
+                password,
+            },
+            root_path,
+            connection: Mutex::new(None),
+            connected: AtomicBool::new(false),
+        })
+    }
+
+    /// 获取共享的控制连接，连接断开后会重新建立
+    async fn session(&self) -> Result<MutexGuard<'_, Option<FtpConnection>>, StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let mut guard = self.connection.lock().await;
+        if guard.is_none() {
+            *guard = Some(FtpConnection::connect(&self.options).await?);
+        }
+        Ok(guard)
+    }
+
+    /// 为整文件传输建立独立的控制连接，传输期间不占用共享连接
+    async fn transfer_connection(&self) -> Result<FtpConnection, StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+        FtpConnection::connect(&self.options).await
+    }
+
+    /// 网络错误后丢弃控制连接，下一次操作时重新连接
+    fn release<T>(
+        guard: &mut Option<FtpConnection>,
+        result: Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        if matches!(
+            result,
+            Err(StorageError::NetworkError(_)) | Err(StorageError::Timeout(_))
+        ) {
+            *guard = None;
+        }
+        result
+    }
+
+    /// 将相对路径转换为服务器上的绝对路径（支持 ftp:// 协议 URL）
+    fn get_full_path(&self, path: &str) -> String {
+        if path.starts_with("ftp://") || path.starts_with("ftps://") {
+            if let Ok(url) = url::Url::parse(path) {
+                return urlencoding::decode(url.path())
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|_| url.path().to_string());
+            }
+        }
+
+        let clean_path = path.trim_matches('/');
+        if clean_path.is_empty() {
+            self.root_path.clone()
+        } else if self.root_path == "/" {
+            format!("/{}", clean_path)
+        } else {
+            format!("{}/{}", self.root_path, clean_path)
+        }
+    }
+
+    /// 拆分为父目录和名称
+    fn split_path(full_path: &str) -> (String, String) {
+        let trimmed = full_path.trim_end_matches('/');
+        match trimmed.rsplit_once('/') {
+            Some(("", name)) => ("/".to_string(), name.to_string()),
+            Some((parent, name)) => (parent.to_string(), name.to_string()),
+            None => ("/".to_string(), trimmed.to_string()),
+        }
+    }
+
+    fn join_path(directory: &str, name: &str) -> String {
+        format!("{}/{}", directory.trim_end_matches('/'), name)
+    }
+
+    /// 在父目录的列表中查找条目，根目录返回 None 表示目录
+    async fn find_entry(
+        connection: &mut FtpConnection,
+        full_path: &str,
+    ) -> Result<Option<FtpEntry>, StorageError> {
+        let (parent, name) = Self::split_path(full_path);
+        if name.is_empty() {
+            return Ok(None);
+        }
+
+        let entry = connection
+            .list(&parent)
+            .await?
+            .into_iter()
+            .find(|entry| entry.name == name);
+        match entry {
+            Some(entry) => Ok(Some(entry)),
+            // 部分服务器不列出隐藏条目，目录可以通过 CWD 确认
+            None if connection.is_directory(full_path).await? => Ok(None),
+            None => Err(StorageError::NotFound(full_path.to_string())),
+        }
+    }
+
+    fn metadata_from_entry(path: &str, full_path: &str, entry: Option<FtpEntry>) -> FileMetadata {
+        let basename = Self::split_path(full_path).1;
+        let Some(entry) = entry else {
+            return FileMetadata {
+                filename: path.to_string(),
+                basename,
+                lastmod: String::new(),
+                size: "0".to_string(),
+                file_type: "directory".to_string(),
+                mime: None,
+                etag: None,
+                checksum: None,
+                permissions: None,
+                owner: None,
+                group: None,
+                extra: HashMap::new(),
+            };
+        };
+
+        FileMetadata {
+            filename: path.to_string(),
+            basename,
+            lastmod: entry.modified.unwrap_or_default(),
+            size: if entry.is_dir { 0 } else { entry.size }.to_string(),
+            file_type: if entry.is_dir { "directory" } else { "file" }.to_string(),
+            mime: None,
+            etag: None,
+            checksum: None,
+            permissions: entry.permissions,
+            owner: entry.owner,
+            group: entry.group,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// 从数据流读取最多 length 字节，支持进度回调和取消信号
+    async fn read_exact_range(
+        data: &mut FtpStream,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        let mut result = Vec::with_capacity(length.min(64 * 1024 * 1024) as usize);
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+
+        while (result.len() as u64) < length {
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    return Err(StorageError::Cancelled);
+                }
+            }
+
+            let remaining = (length - result.len() as u64).min(CHUNK_SIZE as u64) as usize;
+            let read = data
+                .read(&mut buffer[..remaining])
+                .await
+                .map_err(|e| StorageError::NetworkError(format!("FTP read failed: {}", e)))?;
+            if read == 0 {
+                break;
+            }
+            result.extend_from_slice(&buffer[..read]);
+
+            if let Some(ref callback) = progress_callback {
+                callback(result.len() as u64, length);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// 将本地数据写入服务器文件，供 put_file 和 put_stream 共用
+    async fn store_from_reader(
+        &self,
+        path: &str,
+        mut reader: impl AsyncRead + Unpin + Send,
+        size: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let full_path = self.get_full_path(path);
+        let mut connection = self.transfer_connection().await?;
+
+        let result = async {
+            let mut data = connection.store(&full_path).await?;
+            let mut buffer = vec![0u8; CHUNK_SIZE];
+            let mut uploaded = 0u64;
+
+            loop {
+                if let Some(ref mut cancel_rx) = cancel_rx {
+                    if cancel_rx.try_recv().is_ok() {
+                        connection.abort_transfer(data).await?;
+                        return Err(StorageError::Cancelled);
+                    }
+                }
+
+                let read = reader
+                    .read(&mut buffer)
+                    .await
+                    .map_err(|e| StorageError::IoError(format!("Failed to read data: {}", e)))?;
+                if read == 0 {
+                    break;
+                }
+                data.write_all(&buffer[..read])
+                    .await
+                    .map_err(|e| StorageError::NetworkError(format!("FTP write failed: {}", e)))?;
+
+                uploaded += read as u64;
+                if let Some(ref callback) = progress_callback {
+                    callback(uploaded, size);
+                }
+            }
+
+            // 关闭数据连接表示上传结束
+            data.shutdown()
+                .await
+                .map_err(|e| StorageError::NetworkError(format!("FTP write failed: {}", e)))?;
+            drop(data);
+            connection.finish_transfer().await
+        }
+        .await;
+
+        // 失败时控制连接状态不确定，直接丢弃
+        if result.is_ok() {
+            connection.quit().await;
+        }
+        result
+    }
+
+    /// 递归删除目录，子项先于父目录删除
+    async fn remove_recursive(
+        connection: &mut FtpConnection,
+        full_path: &str,
+    ) -> Result<(), StorageError> {
+        let mut pending = vec![full_path.to_string()];
+        let mut directories = Vec::new();
+
+        while let Some(directory) = pending.pop() {
+            for entry in connection.list(&directory).await? {
+                let child = Self::join_path(&directory, &entry.name);
+                if entry.is_dir {
+                    pending.push(child);
+                } else {
+                    connection.delete_file(&child).await?;
+                }
+            }
+            directories.push(directory);
+        }
+
+        for directory in directories.iter().rev() {
+            connection.remove_directory(directory).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StorageClient for FtpClient {
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        *self = Self::new(config.clone())?;
+
+        let connection = FtpConnection::connect(&self.options).await?;
+        *self.connection.get_mut() = Some(connection);
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        _options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        let full_path = self.get_full_path(path);
+        let mut guard = self.session().await?;
+        let connection = guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let result = connection.list(&full_path).await;
+        let entries = Self::release(&mut guard, result)?;
+
+        let files: Vec<StorageFile> = entries
+            .into_iter()
+            .map(|entry| StorageFile {
+                filename: entry.name.clone(),
+                basename: entry.name,
+                lastmod: entry.modified.unwrap_or_default(),
+                size: if entry.is_dir { 0 } else { entry.size }.to_string(),
+                file_type: if entry.is_dir { "directory" } else { "file" }.to_string(),
+                mime: None,
+                etag: None,
+            })
+            .collect();
+
+        Ok(DirectoryResult {
+            files,
+            has_more: false,
+            next_marker: None,
+            total_count: None,
+            path: path.to_string(),
+        })
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_file_range_with_progress(path, start, length, None, None)
+            .await
+    }
+
+    /// 通过 REST 从偏移量开始下载，读够所需长度后中止传输
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+
+        let full_path = self.get_full_path(path);
+        let mut guard = self.session().await?;
+        let connection = guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let result = async {
+            let mut data = connection.retrieve(&full_path, start).await?;
+            match Self::read_exact_range(&mut data, length, progress_callback, cancel_rx).await {
+                Ok(bytes) => {
+                    connection.abort_transfer(data).await?;
+                    Ok(bytes)
+                }
+                Err(e) => {
+                    // 中止失败时控制连接状态不确定，交由 release 丢弃
+                    connection.abort_transfer(data).await?;
+                    Err(e)
+                }
+            }
+        }
+        .await;
+
+        Self::release(&mut guard, result)
+    }
+
+    /// 使用独立的控制连接读取整个文件，不阻塞其他操作
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        let full_path = self.get_full_path(path);
+        let mut connection = self.transfer_connection().await?;
+
+        let result = async {
+            let mut data = connection.retrieve(&full_path, 0).await?;
+            let mut bytes = Vec::new();
+            data.read_to_end(&mut bytes)
+                .await
+                .map_err(|e| StorageError::NetworkError(format!("FTP read failed: {}", e)))?;
+            drop(data);
+            connection.finish_transfer().await?;
+            Ok(bytes)
+        }
+        .await;
+
+        if result.is_ok() {
+            connection.quit().await;
+        }
+        result
+    }
+
+    /// 使用独立的控制连接流式读取，不阻塞其他操作
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        let full_path = self.get_full_path(path);
+        let mut connection = self.transfer_connection().await?;
+        let mut data = connection.retrieve(&full_path, start).await?;
+
+        Ok(Box::pin(async_stream::stream! {
+            let mut buffer = vec![0u8; CHUNK_SIZE];
+            loop {
+                match data.read(&mut buffer).await {
+                    Ok(0) => break,
+                    Ok(read) => yield Ok(bytes::Bytes::copy_from_slice(&buffer[..read])),
+                    Err(e) => {
+                        yield Err(StorageError::NetworkError(format!("FTP read failed: {}", e)));
+                        return;
+                    }
+                }
+            }
+
+            drop(data);
+            if let Err(e) = connection.finish_transfer().await {
+                yield Err(e);
+                return;
+            }
+            connection.quit().await;
+        }))
+    }
+
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        let full_path = self.get_full_path(path);
+        let mut guard = self.session().await?;
+        let connection = guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let result = Self::find_entry(connection, &full_path).await;
+        let entry = Self::release(&mut guard, result)?;
+
+        Ok(Self::metadata_from_entry(path, &full_path, entry))
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        let full_path = self.get_full_path(path);
+        let mut guard = self.session().await?;
+        let connection = guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let result = async {
+            // 优先使用 SIZE，不支持时从父目录列表获取
+            if let Some(size) = connection.size(&full_path).await? {
+                return Ok(size);
+            }
+            match Self::find_entry(connection, &full_path).await? {
+                Some(entry) if !entry.is_dir => Ok(entry.size),
+                _ => Err(StorageError::RequestFailed(format!(
+                    "Not a file: {}",
+                    full_path
+                ))),
+            }
+        }
+        .await;
+
+        Self::release(&mut guard, result)
+    }
+
+    fn canonical_path(&self, path: &str) -> String {
+        self.get_full_path(path)
+    }
+
+    fn protocol(&self) -> &str {
+        &self.config.protocol
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        Self::new(config.clone()).map(|_| ())
+    }
+
+    /// 下载失败时由重试层通过 open_read_stream 的 REST 偏移量从已下载的部分继续
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let total_size = self.get_file_size(path).await.unwrap_or(0);
+        let stream = self.open_read_stream(path, 0).await?;
+
+        StreamIo::save_to_file(stream, save_path, total_size, progress_callback, cancel_rx).await
+    }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let file = tokio::fs::File::open(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to open file: {}", e)))?;
+        let size = file
+            .metadata()
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to read file metadata: {}", e)))?
+            .len();
+
+        self.store_from_reader(path, file, size, progress_callback, cancel_rx)
+            .await
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let reader = StreamIo::into_async_read(stream);
+        self.store_from_reader(path, reader, size, progress_callback, cancel_rx)
+            .await
+    }
+
+    /// 逐级创建目录，已存在的目录会被跳过
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        let full_path = self.get_full_path(path);
+        let mut guard = self.session().await?;
+        let connection = guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let result = async {
+            let mut current = String::new();
+            for component in full_path.split('/').filter(|c| !c.is_empty()) {
+                current = format!("{}/{}", current, component);
+                if !connection.is_directory(&current).await? {
+                    connection.make_directory(&current).await?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        Self::release(&mut guard, result)
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        let from_path = self.get_full_path(from);
+        let to_path = self.get_full_path(to);
+        let mut guard = self.session().await?;
+        let connection = guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let result = async {
+            // 部分服务器的 RNTO 会直接覆盖已存在的目标
+            match Self::find_entry(connection, &to_path).await {
+                Err(StorageError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+                Ok(_) => {
+                    return Err(StorageError::Conflict(format!(
+                        "Destination already exists: {}",
+                        to
+                    )))
+                }
+            }
+            connection.rename(&from_path, &to_path).await
+        }
+        .await;
+
+        Self::release(&mut guard, result)
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        let full_path = self.get_full_path(path);
+        if full_path.trim_end_matches('/') == self.root_path.trim_end_matches('/') {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the root directory".to_string(),
+            ));
+        }
+
+        let mut guard = self.session().await?;
+        let connection = guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let result = async {
+            match Self::find_entry(connection, &full_path).await? {
+                Some(entry) if !entry.is_dir => connection.delete_file(&full_path).await,
+                _ => Self::remove_recursive(connection, &full_path).await,
+            }
+        }
+        .await;
+
+        Self::release(&mut guard, result)
+    }
+}
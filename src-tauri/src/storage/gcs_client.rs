@@ -0,0 +1,1360 @@
+use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
+use chrono::Utc;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::{Client, Method, RequestBuilder};
+use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::RsaPrivateKey;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use url::Url;
+
+use crate::storage::oss::{build_aws_auth_headers, generate_aws_presigned_url, Credentials};
+use crate::storage::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile,
+};
+use crate::utils::crypto::sha256_hex;
+use crate::utils::http_downloader::{HttpDownloadConfig, HttpDownloader};
+use crate::utils::http_uploader::HttpUploader;
+use crate::utils::path_utils::PathUtils;
+
+const DEFAULT_ENDPOINT: &str = "https://storage.googleapis.com";
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+const OAUTH_SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_write";
+
+/// 服务账号 JSON 密钥文件中用到的字段
+#[derive(Debug, Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    private_key_id: Option<String>,
+    token_uri: Option<String>,
+}
+
+/// 已解析的服务账号，用于签发 JWT 和签名 URL
+struct ServiceAccount {
+    client_email: String,
+    private_key_id: Option<String>,
+    token_uri: String,
+    signing_key: SigningKey<Sha256>,
+}
+
+impl ServiceAccount {
+    fn from_json(json: &str) -> Result<Self, StorageError> {
+        let key: ServiceAccountKey = serde_json::from_str(json).map_err(|e| {
+            StorageError::InvalidConfig(format!("Invalid service account JSON: {}", e))
+        })?;
+        let private_key = RsaPrivateKey::from_pkcs8_pem(&key.private_key).map_err(|e| {
+            StorageError::InvalidConfig(format!("Invalid service account private key: {}", e))
+        })?;
+
+        Ok(Self {
+            client_email: key.client_email,
+            private_key_id: key.private_key_id,
+            token_uri: key
+                .token_uri
+                .unwrap_or_else(|| DEFAULT_TOKEN_URI.to_string()),
+            signing_key: SigningKey::<Sha256>::new(private_key),
+        })
+    }
+
+    /// RSASSA-PKCS1-v1_5 SHA-256 签名
+    fn sign(&self, data: &[u8]) -> Vec<u8> {
+        use rsa::signature::{SignatureEncoding, Signer};
+        self.signing_key.sign(data).to_vec()
+    }
+
+    /// 构建换取访问令牌用的 JWT 断言
+    fn jwt_assertion(&self) -> String {
+        let now = Utc::now().timestamp();
+        let mut header = serde_json::json!({ "alg": "RS256", "typ": "JWT" });
+        if let Some(key_id) = &self.private_key_id {
+            header["kid"] = serde_json::Value::String(key_id.clone());
+        }
+        let claims = serde_json::json!({
+            "iss": self.client_email,
+            "scope": OAUTH_SCOPE,
+            "aud": self.token_uri,
+            "iat": now,
+            "exp": now + 3600,
+        });
+
+        let signing_input = format!(
+            "{}.{}",
+            general_purpose::URL_SAFE_NO_PAD.encode(header.to_string()),
+            general_purpose::URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let signature =
+            general_purpose::URL_SAFE_NO_PAD.encode(self.sign(signing_input.as_bytes()));
+        format!("{}.{}", signing_input, signature)
+    }
+}
+
+/// 认证方式
+enum GcsAuth {
+    /// 服务账号，通过 OAuth 访问令牌调用 JSON API
+    ServiceAccount(Box<ServiceAccount>),
+    /// HMAC 互操作密钥，通过 AWS V4 签名调用 XML API
+    Hmac(Credentials),
+    /// 匿名访问公开存储桶
+    Anonymous,
+}
+
+/// OAuth 令牌响应
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+/// 对象资源（JSON API），XML API 的列表和 HEAD 响应也转换为该结构
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct GcsObject {
+    name: String,
+    size: Option<String>,
+    updated: Option<String>,
+    content_type: Option<String>,
+    etag: Option<String>,
+    md5_hash: Option<String>,
+    crc32c: Option<String>,
+    storage_class: Option<String>,
+    generation: Option<String>,
+    content_encoding: Option<String>,
+    cache_control: Option<String>,
+    metadata: Option<HashMap<String, String>>,
+}
+
+/// 一页对象列表
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct GcsListPage {
+    items: Vec<GcsObject>,
+    prefixes: Vec<String>,
+    next_page_token: Option<String>,
+}
+
+/// 服务端复制（rewrite）响应，大对象需要多次调用直到 done
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RewriteResponse {
+    done: bool,
+    rewrite_token: Option<String>,
+}
+
+pub struct GcsClient {
+    client: Client,
+    config: ConnectionConfig,
+    connected: AtomicBool,
+    endpoint: String,
+    bucket: String,
+    prefix: String, // 从 bucket 字段解析出的路径前缀
+    auth: GcsAuth,
+    // 缓存的访问令牌及其过期时间
+    token: tokio::sync::Mutex<Option<(String, Instant)>>,
+}
+
+impl GcsClient {
+    pub fn new(config: ConnectionConfig) -> Result<Self, StorageError> {
+        let bucket_input = config
+            .bucket
+            .clone()
+            .ok_or_else(|| StorageError::InvalidConfig("GCS bucket is required".to_string()))?;
+        let (bucket, prefix) = Self::parse_bucket(&bucket_input);
+
+        // 未配置端点时使用 Google Cloud Storage，可指向 fake-gcs-server 等本地服务
+        let endpoint = config
+            .url
+            .clone()
+            .or_else(|| config.endpoint.clone())
+            .filter(|endpoint| !endpoint.is_empty())
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        let auth = Self::build_auth(&config)?;
+
+        Ok(Self {
+            client: Client::new(),
+            config,
+            connected: AtomicBool::new(false),
+            endpoint,
+            bucket,
+            prefix,
+            auth,
+            token: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// 解析 bucket 字段，支持 "bucket/path/prefix" 格式
+    fn parse_bucket(bucket_input: &str) -> (String, String) {
+        match bucket_input.split_once('/') {
+            Some((bucket, prefix)) => {
+                let prefix = prefix.trim_matches('/');
+                let prefix = if prefix.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}/", prefix)
+                };
+                (bucket.to_string(), prefix)
+            }
+            None => (bucket_input.to_string(), String::new()),
+        }
+    }
+
+    /// 根据配置选择认证方式
+    /// private_key_path 指向服务账号 JSON 密钥文件，secret_key 也可直接填写密钥 JSON；
+    /// 同时提供 access_key 和 secret_key 时使用 HMAC 密钥，都未提供时匿名访问
+    fn build_auth(config: &ConnectionConfig) -> Result<GcsAuth, StorageError> {
+        if let Some(key_path) = config.private_key_path.as_ref().filter(|p| !p.is_empty()) {
+            let key_path = PathUtils::expand_home_dir(key_path)?;
+            let json = std::fs::read_to_string(&key_path).map_err(|e| {
+                StorageError::InvalidConfig(format!(
+                    "Failed to read service account key {}: {}",
+                    key_path, e
+                ))
+            })?;
+            return Ok(GcsAuth::ServiceAccount(Box::new(
+                ServiceAccount::from_json(&json)?,
+            )));
+        }
+
+        let secret_key = config.secret_key.as_ref().filter(|s| !s.is_empty());
+        if let Some(json) = secret_key.filter(|s| s.trim_start().starts_with('{')) {
+            return Ok(GcsAuth::ServiceAccount(Box::new(
+                ServiceAccount::from_json(json)?,
+            )));
+        }
+
+        match (
+            config.access_key.as_ref().filter(|a| !a.is_empty()),
+            secret_key,
+        ) {
+            (Some(access_key), Some(secret_key)) => Ok(GcsAuth::Hmac(Credentials::new(
+                access_key.as_str(),
+                secret_key.as_str(),
+            ))),
+            (None, None) => Ok(GcsAuth::Anonymous),
+            _ => Err(StorageError::InvalidConfig(
+                "GCS HMAC authentication requires both access key and secret key".to_string(),
+            )),
+        }
+    }
+
+    /// HMAC 密钥只能用于 XML API
+    fn uses_xml_api(&self) -> bool {
+        matches!(self.auth, GcsAuth::Hmac(_))
+    }
+
+    /// 端点的主机名（包含非默认端口）
+    fn host(&self) -> String {
+        Url::parse(&self.endpoint)
+            .ok()
+            .and_then(|url| {
+                let host = url.host_str()?.to_string();
+                Some(match url.port() {
+                    Some(port) => format!("{}:{}", host, port),
+                    None => host,
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    /// 逐段编码对象键，保留斜杠（XML API 和签名 URL 的路径格式）
+    fn encode_key_path(object_key: &str) -> String {
+        object_key
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// JSON API 的对象资源 URL，对象名中的斜杠也需要编码
+    fn json_object_url(&self, object_key: &str) -> String {
+        format!(
+            "{}/storage/v1/b/{}/o/{}",
+            self.endpoint,
+            urlencoding::encode(&self.bucket),
+            urlencoding::encode(object_key)
+        )
+    }
+
+    /// 获取服务账号的访问令牌，过期前一分钟刷新
+    async fn access_token(&self, account: &ServiceAccount) -> Result<String, StorageError> {
+        let mut cached = self.token.lock().await;
+        if let Some((token, expires_at)) = cached.as_ref() {
+            if Instant::now() + Duration::from_secs(60) < *expires_at {
+                return Ok(token.clone());
+            }
+        }
+
+        let assertion = account.jwt_assertion();
+        let response = self
+            .client
+            .post(&account.token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(StorageError::AuthenticationFailed(format!(
+                "GCS token request failed with status {}: {}",
+                status, body
+            )));
+        }
+
+        let token: TokenResponse = response.json().await.map_err(|e| {
+            StorageError::AuthenticationFailed(format!("Invalid GCS token response: {}", e))
+        })?;
+        let expires_at = Instant::now() + Duration::from_secs(token.expires_in.unwrap_or(3600));
+        *cached = Some((token.access_token.clone(), expires_at));
+
+        Ok(token.access_token)
+    }
+
+    /// JSON API 请求需要的认证头，匿名访问时为空
+    async fn auth_headers(&self) -> Result<HashMap<String, String>, StorageError> {
+        let mut headers = HashMap::new();
+        if let GcsAuth::ServiceAccount(account) = &self.auth {
+            let token = self.access_token(account).await?;
+            headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+        }
+        Ok(headers)
+    }
+
+    /// 构建 JSON API 请求
+    async fn json_request(
+        &self,
+        method: Method,
+        url: &str,
+    ) -> Result<RequestBuilder, StorageError> {
+        let mut req_builder = self.client.request(method, url);
+        for (key, value) in self.auth_headers().await? {
+            req_builder = req_builder.header(&key, &value);
+        }
+        Ok(req_builder)
+    }
+
+    /// 构建带 AWS V4 签名的 XML API 请求，object_key 为 None 时请求存储桶本身
+    fn xml_request(
+        &self,
+        method: Method,
+        object_key: Option<&str>,
+        query_string: &str,
+        extra_headers: &HashMap<String, String>,
+    ) -> RequestBuilder {
+        let uri = match object_key {
+            Some(object_key) => format!("/{}/{}", self.bucket, Self::encode_key_path(object_key)),
+            None => format!("/{}", self.bucket),
+        };
+        let url = if query_string.is_empty() {
+            format!("{}{}", self.endpoint, uri)
+        } else {
+            format!("{}{}?{}", self.endpoint, uri, query_string)
+        };
+
+        let mut req_builder = self.client.request(method.clone(), &url);
+        if let GcsAuth::Hmac(credentials) = &self.auth {
+            let region = self.config.region.as_deref().unwrap_or("auto");
+            let headers = build_aws_auth_headers(
+                method.as_str(),
+                &uri,
+                extra_headers,
+                Some(query_string),
+                credentials,
+                region,
+                &self.host(),
+            );
+            for (key, value) in headers {
+                req_builder = req_builder.header(&key, &value);
+            }
+        }
+        req_builder
+    }
+
+    /// 发送请求，非成功状态转换为对应的存储错误
+    async fn send(
+        req_builder: RequestBuilder,
+        context: &str,
+    ) -> Result<reqwest::Response, StorageError> {
+        let response = req_builder.send().await.map_err(|e| {
+            StorageError::NetworkError(format!("{} request failed: {}", context, e))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("{} failed with status {}: {}", context, status, body),
+            ));
+        }
+        Ok(response)
+    }
+
+    /// 解析路径为对象键（支持 gs:// 协议 URL 和相对路径）
+    fn resolve_object_key(&self, path: &str) -> Result<String, StorageError> {
+        if let Some(gs_path) = path.strip_prefix("gs://") {
+            let (bucket, object_key) = gs_path.split_once('/').unwrap_or((gs_path, ""));
+            if bucket != self.bucket {
+                return Err(StorageError::RequestFailed(format!(
+                    "Bucket mismatch: url='{}' != configured='{}'",
+                    bucket, self.bucket
+                )));
+            }
+            return Ok(object_key.to_string());
+        }
+
+        Ok(format!("{}{}", self.prefix, path.trim_start_matches('/')))
+    }
+
+    /// 解析目录路径为以斜杠结尾的列表前缀，根目录为空字符串
+    fn resolve_directory_prefix(&self, path: &str) -> Result<String, StorageError> {
+        let object_key = self.resolve_object_key(path)?;
+        let trimmed = object_key.trim_end_matches('/');
+        if trimmed.is_empty() {
+            Ok(String::new())
+        } else {
+            Ok(format!("{}/", trimmed))
+        }
+    }
+
+    /// 列出一页对象，recursive 为 false 时按斜杠分组返回子目录前缀
+    async fn list_page(
+        &self,
+        prefix: &str,
+        recursive: bool,
+        page_size: u32,
+        page_token: Option<&str>,
+    ) -> Result<GcsListPage, StorageError> {
+        let mut query_params = Vec::new();
+        if !prefix.is_empty() {
+            query_params.push(("prefix", prefix.to_string()));
+        }
+        if !recursive {
+            query_params.push(("delimiter", "/".to_string()));
+        }
+
+        if self.uses_xml_api() {
+            query_params.push(("list-type", "2".to_string()));
+            query_params.push(("max-keys", page_size.to_string()));
+            if let Some(token) = page_token {
+                query_params.push(("continuation-token", token.to_string()));
+            }
+        } else {
+            query_params.push(("maxResults", page_size.to_string()));
+            if let Some(token) = page_token {
+                query_params.push(("pageToken", token.to_string()));
+            }
+        }
+
+        let query_string = query_params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        if self.uses_xml_api() {
+            let req_builder = self.xml_request(Method::GET, None, &query_string, &HashMap::new());
+            let response = Self::send(req_builder, "List objects").await?;
+            let xml_content = response.text().await.map_err(StorageError::from_reqwest)?;
+            return Self::parse_xml_list(&xml_content);
+        }
+
+        let url = format!(
+            "{}/storage/v1/b/{}/o?{}",
+            self.endpoint,
+            urlencoding::encode(&self.bucket),
+            query_string
+        );
+        let req_builder = self.json_request(Method::GET, &url).await?;
+        let response = Self::send(req_builder, "List objects").await?;
+        response.json().await.map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to parse list response: {}", e))
+        })
+    }
+
+    /// 解析 XML API 的 ListObjectsV2 响应
+    fn parse_xml_list(xml_content: &str) -> Result<GcsListPage, StorageError> {
+        let mut reader = Reader::from_str(xml_content);
+        reader.trim_text(true);
+
+        let mut page = GcsListPage::default();
+        let mut buf = Vec::new();
+        let mut current_object: Option<GcsObject> = None;
+        let mut in_prefixes = false;
+        let mut current_text = String::new();
+        let mut is_truncated = false;
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => {
+                    match e.name().as_ref() {
+                        b"Contents" => current_object = Some(GcsObject::default()),
+                        b"CommonPrefixes" => in_prefixes = true,
+                        _ => {}
+                    }
+                    current_text.clear();
+                }
+                Ok(Event::Text(e)) => {
+                    current_text = e.unescape().unwrap_or_default().to_string();
+                }
+                Ok(Event::End(ref e)) => {
+                    let text = current_text.clone();
+                    match (e.name().as_ref(), current_object.as_mut()) {
+                        (b"Key", Some(object)) => object.name = text,
+                        (b"Size", Some(object)) => object.size = Some(text),
+                        (b"LastModified", Some(object)) => object.updated = Some(text),
+                        (b"ETag", Some(object)) => {
+                            object.etag = Some(text.trim_matches('"').to_string())
+                        }
+                        (b"StorageClass", Some(object)) => object.storage_class = Some(text),
+                        (b"Contents", _) => page.items.extend(current_object.take()),
+                        (b"Prefix", None) if in_prefixes => page.prefixes.push(text),
+                        (b"CommonPrefixes", _) => in_prefixes = false,
+                        (b"IsTruncated", _) => is_truncated = text == "true",
+                        (b"NextContinuationToken", _) => page.next_page_token = Some(text),
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    return Err(StorageError::RequestFailed(format!(
+                        "XML parsing error: {}",
+                        e
+                    )))
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        if !is_truncated {
+            page.next_page_token = None;
+        }
+        Ok(page)
+    }
+
+    /// 将一页对象列表转换为相对于 prefix 的目录结果
+    fn directory_result(page: GcsListPage, prefix: &str, recursive: bool) -> DirectoryResult {
+        let mut files = Vec::new();
+
+        for prefix_path in &page.prefixes {
+            let relative_path = prefix_path.strip_prefix(prefix).unwrap_or(prefix_path);
+            let dir_name = relative_path.trim_end_matches('/');
+            if dir_name.is_empty() || dir_name.contains('/') {
+                continue;
+            }
+            files.push(StorageFile {
+                filename: dir_name.to_string(),
+                basename: dir_name.to_string(),
+                lastmod: String::new(),
+                size: "0".to_string(),
+                file_type: "directory".to_string(),
+                mime: None,
+                etag: None,
+            });
+        }
+
+        for object in page.items {
+            let relative_path = object.name.strip_prefix(prefix).unwrap_or(&object.name);
+            // 以斜杠结尾的是目录占位对象
+            if relative_path.is_empty()
+                || relative_path.ends_with('/')
+                || (!recursive && relative_path.contains('/'))
+            {
+                continue;
+            }
+            files.push(StorageFile {
+                filename: relative_path.to_string(),
+                basename: object
+                    .name
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&object.name)
+                    .to_string(),
+                lastmod: object.updated.clone().unwrap_or_default(),
+                size: object.size.clone().unwrap_or_else(|| "0".to_string()),
+                file_type: "file".to_string(),
+                mime: object.content_type.clone(),
+                etag: object
+                    .etag
+                    .as_ref()
+                    .map(|v| v.trim_matches('"').to_string()),
+            });
+        }
+
+        DirectoryResult {
+            files,
+            has_more: page.next_page_token.is_some(),
+            next_marker: page.next_page_token,
+            total_count: None,
+            path: prefix.to_string(),
+        }
+    }
+
+    /// 获取对象元数据，对象不存在时返回 None
+    async fn object_metadata(&self, object_key: &str) -> Result<Option<GcsObject>, StorageError> {
+        let response = if self.uses_xml_api() {
+            self.xml_request(Method::HEAD, Some(object_key), "", &HashMap::new())
+                .send()
+                .await
+        } else {
+            self.json_request(Method::GET, &self.json_object_url(object_key))
+                .await?
+                .send()
+                .await
+        }
+        .map_err(|e| StorageError::NetworkError(format!("Get object request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!("Get object failed with status: {}", status),
+            ));
+        }
+
+        if self.uses_xml_api() {
+            return Ok(Some(Self::object_from_headers(
+                object_key,
+                response.headers(),
+            )));
+        }
+
+        response.json().await.map(Some).map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to parse object metadata: {}", e))
+        })
+    }
+
+    /// 根据 XML API 的 HEAD 响应头构建对象资源
+    fn object_from_headers(object_key: &str, headers: &reqwest::header::HeaderMap) -> GcsObject {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+
+        let mut object = GcsObject {
+            name: object_key.to_string(),
+            size: header("content-length"),
+            // Last-Modified 为 HTTP 日期格式，统一转换为 RFC3339
+            updated: header("last-modified").map(|v| {
+                chrono::DateTime::parse_from_rfc2822(&v)
+                    .map(|dt| dt.with_timezone(&Utc).to_rfc3339())
+                    .unwrap_or(v)
+            }),
+            content_type: header("content-type"),
+            etag: header("etag").map(|v| v.trim_matches('"').to_string()),
+            storage_class: header("x-goog-storage-class"),
+            generation: header("x-goog-generation"),
+            content_encoding: header("content-encoding"),
+            cache_control: header("cache-control"),
+            ..Default::default()
+        };
+
+        // x-goog-hash 可能出现多次，格式为 crc32c=...,md5=...
+        for value in headers.get_all("x-goog-hash") {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            for part in value.split(',') {
+                match part.trim().split_once('=') {
+                    Some(("crc32c", hash)) => object.crc32c = Some(hash.to_string()),
+                    Some(("md5", hash)) => object.md5_hash = Some(hash.to_string()),
+                    _ => {}
+                }
+            }
+        }
+
+        let metadata: HashMap<String, String> = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let key = name.as_str().strip_prefix("x-goog-meta-")?;
+                Some((key.to_string(), value.to_str().ok()?.to_string()))
+            })
+            .collect();
+        if !metadata.is_empty() {
+            object.metadata = Some(metadata);
+        }
+
+        object
+    }
+
+    /// 根据对象资源构建元数据
+    fn metadata_from_object(path: &str, object: GcsObject) -> FileMetadata {
+        let mut extra = HashMap::new();
+        for (key, value) in [
+            ("storageClass", &object.storage_class),
+            ("generation", &object.generation),
+            ("contentEncoding", &object.content_encoding),
+            ("cacheControl", &object.cache_control),
+        ] {
+            if let Some(value) = value {
+                extra.insert(key.to_string(), value.clone());
+            }
+        }
+        // 用户自定义元数据
+        for (key, value) in object.metadata.unwrap_or_default() {
+            extra.insert(format!("meta:{}", key), value);
+        }
+
+        // 校验和：优先使用 CRC32C，服务端提供的哈希值为 base64 编码
+        let checksum = object
+            .crc32c
+            .map(|v| format!("crc32c:{}", v))
+            .or_else(|| object.md5_hash.map(|v| format!("md5:{}", v)));
+
+        FileMetadata {
+            filename: path.to_string(),
+            basename: object
+                .name
+                .rsplit('/')
+                .next()
+                .unwrap_or(&object.name)
+                .to_string(),
+            lastmod: object.updated.unwrap_or_default(),
+            size: object.size.unwrap_or_else(|| "0".to_string()),
+            file_type: "file".to_string(),
+            mime: object.content_type,
+            etag: object.etag.map(|v| v.trim_matches('"').to_string()),
+            checksum,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra,
+        }
+    }
+
+    /// 读取对象内容的 URL 和请求头，范围读取、流式读取和下载共用
+    async fn media_target(
+        &self,
+        object_key: &str,
+    ) -> Result<(String, HashMap<String, String>), StorageError> {
+        match &self.auth {
+            // 预签名 URL 不需要额外的认证头
+            GcsAuth::Hmac(_) => Ok((
+                self.generate_download_url(object_key, 3600)?,
+                HashMap::new(),
+            )),
+            _ => Ok((
+                format!("{}?alt=media", self.json_object_url(object_key)),
+                self.auth_headers().await?,
+            )),
+        }
+    }
+
+    /// 生成下载 URL
+    /// 服务账号使用 V4 RSA 签名，HMAC 密钥使用 AWS V4 预签名，匿名访问返回公开 URL
+    fn generate_download_url(
+        &self,
+        object_key: &str,
+        expires_in_seconds: i64,
+    ) -> Result<String, StorageError> {
+        // V4 签名最长有效期为 7 天
+        let expires = expires_in_seconds.min(7 * 24 * 3600);
+
+        match &self.auth {
+            GcsAuth::ServiceAccount(account) => {
+                Ok(self.generate_rsa_signed_url(account, object_key, expires))
+            }
+            GcsAuth::Hmac(credentials) => generate_aws_presigned_url(
+                &self.endpoint,
+                object_key,
+                expires,
+                credentials,
+                self.config.region.as_deref().unwrap_or("auto"),
+                &self.bucket,
+                None,
+            )
+            .map_err(StorageError::RequestFailed),
+            GcsAuth::Anonymous => Ok(format!(
+                "{}/{}/{}",
+                self.endpoint,
+                self.bucket,
+                Self::encode_key_path(object_key)
+            )),
+        }
+    }
+
+    /// 使用服务账号私钥生成 V4 签名 URL（GOOG4-RSA-SHA256）
+    fn generate_rsa_signed_url(
+        &self,
+        account: &ServiceAccount,
+        object_key: &str,
+        expires: i64,
+    ) -> String {
+        let now = Utc::now();
+        let datetime = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let credential_scope = format!("{}/auto/storage/goog4_request", now.format("%Y%m%d"));
+        let credential = format!("{}/{}", account.client_email, credential_scope);
+
+        let canonical_uri = format!("/{}/{}", self.bucket, Self::encode_key_path(object_key));
+        let query_string = format!(
+            "X-Goog-Algorithm=GOOG4-RSA-SHA256&X-Goog-Credential={}&X-Goog-Date={}&X-Goog-Expires={}&X-Goog-SignedHeaders=host",
+            urlencoding::encode(&credential),
+            datetime,
+            expires
+        );
+
+        let canonical_request = format!(
+            "GET\n{}\n{}\nhost:{}\n\nhost\nUNSIGNED-PAYLOAD",
+            canonical_uri,
+            query_string,
+            self.host()
+        );
+        let string_to_sign = format!(
+            "GOOG4-RSA-SHA256\n{}\n{}\n{}",
+            datetime,
+            credential_scope,
+            sha256_hex(&canonical_request)
+        );
+        let signature = hex::encode(account.sign(string_to_sign.as_bytes()));
+
+        format!(
+            "{}{}?{}&X-Goog-Signature={}",
+            self.endpoint, canonical_uri, query_string, signature
+        )
+    }
+
+    /// 删除单个对象，对象不存在时视为成功
+    async fn delete_object(&self, object_key: &str) -> Result<(), StorageError> {
+        let req_builder = if self.uses_xml_api() {
+            self.xml_request(Method::DELETE, Some(object_key), "", &HashMap::new())
+        } else {
+            self.json_request(Method::DELETE, &self.json_object_url(object_key))
+                .await?
+        };
+
+        match Self::send(req_builder, "Delete object").await {
+            Ok(_) | Err(StorageError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 在服务端复制对象
+    async fn copy_object(&self, source_key: &str, dest_key: &str) -> Result<(), StorageError> {
+        if self.uses_xml_api() {
+            let mut headers = HashMap::new();
+            headers.insert(
+                "x-amz-copy-source".to_string(),
+                format!("/{}/{}", self.bucket, Self::encode_key_path(source_key)),
+            );
+            let req_builder = self
+                .xml_request(Method::PUT, Some(dest_key), "", &headers)
+                .header("Content-Length", "0");
+            Self::send(req_builder, "Copy object").await?;
+            return Ok(());
+        }
+
+        let base_url = format!(
+            "{}/rewriteTo/b/{}/o/{}",
+            self.json_object_url(source_key),
+            urlencoding::encode(&self.bucket),
+            urlencoding::encode(dest_key)
+        );
+        let mut rewrite_token: Option<String> = None;
+        loop {
+            let url = match &rewrite_token {
+                Some(token) => format!("{}?rewriteToken={}", base_url, urlencoding::encode(token)),
+                None => base_url.clone(),
+            };
+            let req_builder = self
+                .json_request(Method::POST, &url)
+                .await?
+                .header("Content-Length", "0");
+            let response = Self::send(req_builder, "Copy object").await?;
+            let result: RewriteResponse = response.json().await.map_err(|e| {
+                StorageError::RequestFailed(format!("Failed to parse rewrite response: {}", e))
+            })?;
+
+            if result.done {
+                return Ok(());
+            }
+            rewrite_token = result.rewrite_token;
+            if rewrite_token.is_none() {
+                return Err(StorageError::RequestFailed(
+                    "Rewrite response is missing rewriteToken".to_string(),
+                ));
+            }
+        }
+    }
+
+    /// 收集前缀下的所有对象键，包含目录占位对象
+    async fn collect_object_keys(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        let mut object_keys = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let page = self
+                .list_page(prefix, true, 1000, page_token.as_deref())
+                .await?;
+            object_keys.extend(page.items.into_iter().map(|object| object.name));
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(object_keys)
+    }
+
+    /// 上传对象内容，供 put_file、put_stream 和 create_directory 共用
+    async fn put_object_body(
+        &self,
+        object_key: &str,
+        body: reqwest::Body,
+        file_size: u64,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let req_builder = if self.uses_xml_api() {
+            // 流式请求体无法预先计算哈希
+            let mut headers = HashMap::new();
+            headers.insert(
+                "x-amz-content-sha256".to_string(),
+                "UNSIGNED-PAYLOAD".to_string(),
+            );
+            self.xml_request(Method::PUT, Some(object_key), "", &headers)
+        } else {
+            let url = format!(
+                "{}/upload/storage/v1/b/{}/o?uploadType=media&name={}",
+                self.endpoint,
+                urlencoding::encode(&self.bucket),
+                urlencoding::encode(object_key)
+            );
+            self.json_request(Method::POST, &url)
+                .await?
+                .header("Content-Type", "application/octet-stream")
+        };
+
+        let req_builder = req_builder
+            .header("Content-Length", file_size.to_string())
+            .body(body);
+        let response = HttpUploader::send_with_cancel(req_builder, cancel_rx).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let error_body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("Put object failed with status {}: {}", status, error_body),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 获取对象元数据，对象不存在时返回 NotFound
+    async fn require_object(
+        &self,
+        path: &str,
+        object_key: &str,
+    ) -> Result<GcsObject, StorageError> {
+        self.object_metadata(object_key)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(path.to_string()))
+    }
+}
+
+#[async_trait]
+impl StorageClient for GcsClient {
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        *self = Self::new(config.clone())?;
+
+        // 通过查询一个不存在的对象测试连接，只需要对象读取权限，不需要列出存储桶
+        // 服务账号会在此时换取访问令牌，404 表示认证有效
+        let test_object = format!("{}__connection_test__", self.prefix);
+        self.object_metadata(&test_object).await.map_err(|e| {
+            StorageError::ConnectionFailed(format!("GCS connection test failed: {}", e))
+        })?;
+
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// 不带 delimiter 的列表请求即可递归列出前缀下的所有对象
+    fn supports_recursive_listing(&self, _path: &str) -> bool {
+        true
+    }
+
+    /// 对象列表通过 maxResults 和 pageToken 原生分页
+    fn supports_native_paging(&self, _path: &str, _recursive: bool) -> bool {
+        true
+    }
+
+    fn canonical_path(&self, path: &str) -> String {
+        self.resolve_object_key(path)
+            .unwrap_or_else(|_| path.to_string())
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let options = options.cloned().unwrap_or_default();
+        let recursive = options.recursive.unwrap_or(false);
+        let prefix = self.resolve_directory_prefix(path)?;
+
+        let page = self
+            .list_page(
+                &prefix,
+                recursive,
+                options.page_size.unwrap_or(1000).min(1000),
+                options.marker.as_deref(),
+            )
+            .await?;
+
+        Ok(Self::directory_result(page, &prefix, recursive))
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_file_range_with_progress(path, start, length, None, None)
+            .await
+    }
+
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        use futures_util::StreamExt;
+
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        let (url, headers) = self.media_target(&object_key).await?;
+
+        let mut req_builder = self
+            .client
+            .get(&url)
+            .header("Range", format!("bytes={}-{}", start, start + length - 1));
+        for (key, value) in headers {
+            req_builder = req_builder.header(&key, &value);
+        }
+        let response = Self::send(req_builder, "Range request").await?;
+
+        // 使用流式读取以支持进度回调
+        let mut result = Vec::with_capacity(length as usize);
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk_result) = stream.next().await {
+            // 检查取消信号
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    return Err(StorageError::Cancelled);
+                }
+            }
+
+            let chunk = chunk_result
+                .map_err(|e| StorageError::RequestFailed(format!("Failed to read chunk: {}", e)))?;
+            result.extend_from_slice(&chunk);
+
+            if let Some(ref callback) = progress_callback {
+                callback(result.len() as u64, length);
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        let (url, headers) = self.media_target(&object_key).await?;
+
+        let mut req_builder = self.client.get(&url);
+        for (key, value) in headers {
+            req_builder = req_builder.header(&key, &value);
+        }
+        let response = Self::send(req_builder, "Get file").await?;
+
+        let bytes = response.bytes().await.map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to read file content: {}", e))
+        })?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        let (url, headers) = self.media_target(&object_key).await?;
+
+        let mut config = HttpDownloadConfig::new(url);
+        config.headers = headers;
+        HttpDownloader::open_stream(&self.client, config, start).await
+    }
+
+    /// 获取对象元数据，目录通过占位对象或前缀下的对象判断
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        let trimmed_key = object_key.trim_end_matches('/');
+
+        if !trimmed_key.is_empty() && !object_key.ends_with('/') {
+            if let Some(object) = self.object_metadata(&object_key).await? {
+                return Ok(Self::metadata_from_object(path, object));
+            }
+        }
+
+        let directory = FileMetadata {
+            filename: path.to_string(),
+            basename: trimmed_key
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            lastmod: String::new(),
+            size: "0".to_string(),
+            file_type: "directory".to_string(),
+            mime: None,
+            etag: None,
+            checksum: None,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra: HashMap::new(),
+        };
+
+        // 存储桶根目录
+        if trimmed_key.is_empty() {
+            return Ok(directory);
+        }
+
+        // 目录占位对象或前缀下的任意对象都表示目录存在
+        let page = self
+            .list_page(&format!("{}/", trimmed_key), false, 1, None)
+            .await?;
+        if !page.items.is_empty() || !page.prefixes.is_empty() {
+            return Ok(directory);
+        }
+
+        Err(StorageError::NotFound(path.to_string()))
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        let object = self.require_object(path, &object_key).await?;
+
+        object
+            .size
+            .and_then(|size| size.parse().ok())
+            .ok_or_else(|| StorageError::RequestFailed("No object size in response".to_string()))
+    }
+
+    fn protocol(&self) -> &str {
+        "gcs"
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        if config.bucket.as_deref().unwrap_or_default().is_empty() {
+            return Err(StorageError::InvalidConfig(
+                "GCS bucket is required".to_string(),
+            ));
+        }
+        Self::build_auth(config).map(|_| ())
+    }
+
+    fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
+        let object_key = self.resolve_object_key(path)?;
+
+        // 生成 1 小时有效期的签名下载 URL
+        self.generate_download_url(&object_key, 3600)
+    }
+
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        let (url, headers) = self.media_target(&object_key).await?;
+
+        let mut config = HttpDownloadConfig::new(url);
+        config.headers = headers;
+        HttpDownloader::download_stream(
+            &self.client,
+            config,
+            save_path,
+            progress_callback,
+            cancel_rx,
+        )
+        .await
+    }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        let (body, file_size) = HttpUploader::file_body(local_path, progress_callback).await?;
+
+        self.put_object_body(&object_key, body, file_size, cancel_rx)
+            .await
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        let body = HttpUploader::stream_body(stream, size, progress_callback);
+
+        self.put_object_body(&object_key, body, size, cancel_rx)
+            .await
+    }
+
+    /// 对象存储没有真正的目录，创建以斜杠结尾的空对象作为目录占位
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let directory_key = self.resolve_directory_prefix(path)?;
+        self.put_object_body(&directory_key, reqwest::Body::from(Vec::new()), 0, None)
+            .await
+    }
+
+    /// 通过服务端复制 + 删除实现重命名，目录会逐个对象处理
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let source_key = self.resolve_object_key(from)?;
+        let dest_key = self.resolve_object_key(to)?;
+
+        if !source_key.ends_with('/') && self.object_metadata(&source_key).await?.is_some() {
+            if self.object_metadata(&dest_key).await?.is_some() {
+                return Err(StorageError::Conflict(format!(
+                    "Destination already exists: {}",
+                    to
+                )));
+            }
+            self.copy_object(&source_key, &dest_key).await?;
+            return self.delete_object(&source_key).await;
+        }
+
+        // 按目录前缀处理，递归列表已包含目录占位对象
+        let source_prefix = format!("{}/", source_key.trim_end_matches('/'));
+        let dest_prefix = format!("{}/", dest_key.trim_end_matches('/'));
+        let object_keys = self.collect_object_keys(&source_prefix).await?;
+        if object_keys.is_empty() {
+            return Err(StorageError::NotFound(from.to_string()));
+        }
+
+        for key in &object_keys {
+            let relative = key.strip_prefix(&source_prefix).unwrap_or(key);
+            self.copy_object(key, &format!("{}{}", dest_prefix, relative))
+                .await?;
+            self.delete_object(key).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 删除对象，目录前缀下的所有对象会被一并删除
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let object_key = self.resolve_object_key(path)?;
+        if object_key.trim_end_matches('/') == self.prefix.trim_end_matches('/') {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the root directory".to_string(),
+            ));
+        }
+
+        if !object_key.ends_with('/') && self.object_metadata(&object_key).await?.is_some() {
+            return self.delete_object(&object_key).await;
+        }
+
+        let directory_prefix = format!("{}/", object_key.trim_end_matches('/'));
+        let object_keys = self.collect_object_keys(&directory_prefix).await?;
+        if object_keys.is_empty() {
+            return Err(StorageError::NotFound(path.to_string()));
+        }
+
+        for key in &object_keys {
+            self.delete_object(key).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+pub mod parser;
+
+// 重新导出解析相关功能
+pub use parser::{is_url_list, parse_html_index, parse_json_index, parse_url_list, IndexEntry};
@@ -0,0 +1,330 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// 目录索引页面中的条目
+#[derive(Debug, Clone)]
+pub struct IndexEntry {
+    pub name: String,
+    pub is_dir: bool,
+    /// 索引页面给出的大小，可读格式（如 1.2M）为近似值
+    pub size: Option<u64>,
+    /// RFC3339 格式的修改时间
+    pub modified: Option<String>,
+    /// URL 列表中条目的绝对地址，目录索引中的条目为 None（地址由所在目录推导）
+    pub url: Option<url::Url>,
+}
+
+// <a href="...">，兼容单引号、双引号和无引号的写法
+static HREF_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)<a\s[^>]*?href\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))[^>]*>"#).unwrap()
+});
+
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+
+static CLOSE_A_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)</a\s*>").unwrap());
+
+static ROW_END_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)</tr\s*>").unwrap());
+
+// Apache 表格格式 "2024-01-15 10:30"
+static ISO_DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d{4}-\d{2}-\d{2})[ T](\d{1,2}:\d{2}(?::\d{2})?)").unwrap());
+
+// nginx 和 Apache 预格式化文本 "15-Jan-2024 10:30"
+static DMY_DATE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d{1,2}-[A-Za-z]{3}-\d{4}) (\d{1,2}:\d{2}(?::\d{2})?)").unwrap()
+});
+
+// lighttpd 格式 "2024-Jan-15 10:30:00"
+static YMD_DATE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d{4}-[A-Za-z]{3}-\d{1,2}) (\d{1,2}:\d{2}(?::\d{2})?)").unwrap()
+});
+
+// 字节数或带单位的可读大小，目录显示为 "-"
+static SIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*(-|\d+(?:\.\d+)?)\s*([KMGTP]?)(?:i?B)?(?:\s|$)").unwrap()
+});
+
+/// 解析 HTML 目录索引（Apache、nginx、lighttpd 等 autoindex 页面）
+///
+/// 只保留指向 base_url 下一级的链接，排序链接、上级目录和外部链接会被忽略；
+/// 修改时间和大小从链接后到下一个链接之间的文本中提取
+pub fn parse_html_index(html: &str, base_url: &url::Url) -> Vec<IndexEntry> {
+    let matches: Vec<_> = HREF_REGEX.captures_iter(html).collect();
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (index, captures) in matches.iter().enumerate() {
+        let Some(href) = captures
+            .get(1)
+            .or_else(|| captures.get(2))
+            .or_else(|| captures.get(3))
+        else {
+            continue;
+        };
+        let href = decode_html_entities(href.as_str());
+        let Some((name, is_dir)) = child_name(&href, base_url) else {
+            continue;
+        };
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+
+        // 链接之后到下一个链接（或表格行结束）之间的文本
+        let tail_start = captures.get(0).map(|m| m.end()).unwrap_or(0);
+        let tail_end = matches
+            .get(index + 1)
+            .and_then(|next| next.get(0))
+            .map(|m| m.start())
+            .unwrap_or(html.len());
+        let tail = &html[tail_start..tail_end];
+        // 跳过链接文字本身，避免文件名中的日期和数字被误识别
+        let tail = CLOSE_A_REGEX
+            .find(tail)
+            .map(|m| &tail[m.end()..])
+            .unwrap_or(tail);
+        let tail = ROW_END_REGEX.split(tail).next().unwrap_or(tail);
+        let text = decode_html_entities(&TAG_REGEX.replace_all(tail, " "));
+
+        let (modified, after_date) = parse_index_date(&text);
+        let size = if is_dir {
+            None
+        } else {
+            after_date.and_then(parse_human_size)
+        };
+
+        entries.push(IndexEntry {
+            name,
+            is_dir,
+            size,
+            modified,
+            url: None,
+        });
+    }
+
+    entries
+}
+
+/// 解析 JSON 目录索引
+/// 支持 nginx autoindex_format json（name/type/mtime/size）和 Caddy browse（name/is_dir/mod_time/size）
+pub fn parse_json_index(body: &str) -> Option<Vec<IndexEntry>> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let items = match &value {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(object) => object
+            .get("files")
+            .or_else(|| object.get("entries"))
+            .and_then(|items| items.as_array())?,
+        _ => return None,
+    };
+
+    let entries = items
+        .iter()
+        .filter_map(|item| {
+            let name = item.get("name")?.as_str()?.trim_end_matches('/');
+            if name.is_empty() || name == "." || name == ".." {
+                return None;
+            }
+
+            let is_dir = item
+                .get("is_dir")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+                || item.get("type").and_then(|v| v.as_str()) == Some("directory")
+                || item.get("name")?.as_str()?.ends_with('/');
+
+            let modified = ["mtime", "mod_time", "modified"]
+                .iter()
+                .filter_map(|key| item.get(*key).and_then(|v| v.as_str()))
+                .find_map(|value| {
+                    DateTime::parse_from_rfc2822(value)
+                        .or_else(|_| DateTime::parse_from_rfc3339(value))
+                        .ok()
+                })
+                .map(|dt| dt.with_timezone(&Utc).to_rfc3339());
+
+            Some(IndexEntry {
+                name: name.to_string(),
+                is_dir,
+                size: item
+                    .get("size")
+                    .and_then(|v| v.as_u64())
+                    .filter(|_| !is_dir),
+                modified,
+                url: None,
+            })
+        })
+        .collect();
+
+    Some(entries)
+}
+
+/// 判断响应体是否为 URL 列表
+/// text/uri-list 直接视为列表；text/plain 要求每个非注释行都是绝对的 http(s) URL，避免把普通文本文件当作列表
+pub fn is_url_list(body: &str, content_type: &str) -> bool {
+    let mut lines = url_list_lines(body).peekable();
+    if lines.peek().is_none() {
+        return false;
+    }
+    if content_type.contains("text/uri-list") {
+        return true;
+    }
+
+    content_type.contains("text/plain")
+        && lines.all(|line| {
+            url::Url::parse(line).is_ok_and(|url| url.scheme() == "http" || url.scheme() == "https")
+        })
+}
+
+/// 解析纯文本 URL 列表，每行一个相对或绝对 URL
+///
+/// 相对链接基于 base_url 解析，跨域名的绝对 URL 同样保留；
+/// 条目名称取 URL 的最后一级路径，重名时追加序号，条目的绝对地址保存在 url 中
+pub fn parse_url_list(body: &str, base_url: &url::Url) -> Vec<IndexEntry> {
+    let mut seen_urls = HashSet::new();
+    let mut seen_names = HashSet::new();
+    let mut entries = Vec::new();
+
+    for line in url_list_lines(body) {
+        let Ok(mut target) = base_url.join(line) else {
+            continue;
+        };
+        if target.scheme() != "http" && target.scheme() != "https" {
+            continue;
+        }
+        target.set_fragment(None);
+        if !seen_urls.insert(target.to_string()) {
+            continue;
+        }
+
+        let is_dir = target.path().ends_with('/');
+        let segment = target
+            .path()
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+        let decoded = urlencoding::decode(segment)
+            .map(|name| name.into_owned())
+            .unwrap_or_else(|_| segment.to_string());
+        let base_name = match decoded.as_str() {
+            "" | "." | ".." => target.host_str().unwrap_or("index").to_string(),
+            _ => decoded,
+        };
+
+        let mut name = base_name.clone();
+        let mut counter = 2;
+        while !seen_names.insert(name.clone()) {
+            name = format!("{} ({})", base_name, counter);
+            counter += 1;
+        }
+
+        entries.push(IndexEntry {
+            name,
+            is_dir,
+            size: None,
+            modified: None,
+            url: Some(target),
+        });
+    }
+
+    entries
+}
+
+/// URL 列表中的有效行，跳过空行和 # 开头的注释
+fn url_list_lines(body: &str) -> impl Iterator<Item = &str> {
+    body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// 判断链接是否指向 base_url 的直接子项，返回解码后的名称和是否为目录
+fn child_name(href: &str, base_url: &url::Url) -> Option<(String, bool)> {
+    if href.is_empty() || href.starts_with('?') || href.starts_with('#') {
+        return None;
+    }
+
+    let mut target = base_url.join(href).ok()?;
+    target.set_query(None);
+    target.set_fragment(None);
+    if target.origin() != base_url.origin() {
+        return None;
+    }
+
+    let relative = target.path().strip_prefix(base_url.path())?;
+    let (segment, is_dir) = match relative.strip_suffix('/') {
+        Some(segment) => (segment, true),
+        None => (relative, false),
+    };
+    if segment.is_empty() || segment.contains('/') {
+        return None;
+    }
+
+    let name = urlencoding::decode(segment)
+        .map(|name| name.into_owned())
+        .unwrap_or_else(|_| segment.to_string());
+    if name == "." || name == ".." {
+        return None;
+    }
+    Some((name, is_dir))
+}
+
+/// 从文本中查找修改时间，返回时间和时间之后的剩余文本
+fn parse_index_date(text: &str) -> (Option<String>, Option<&str>) {
+    let patterns: [(&Regex, &[&str]); 3] = [
+        (&ISO_DATE_REGEX, &["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]),
+        (&DMY_DATE_REGEX, &["%d-%b-%Y %H:%M:%S", "%d-%b-%Y %H:%M"]),
+        (&YMD_DATE_REGEX, &["%Y-%b-%d %H:%M:%S", "%Y-%b-%d %H:%M"]),
+    ];
+
+    for (regex, formats) in patterns {
+        let Some(captures) = regex.captures(text) else {
+            continue;
+        };
+        let value = format!("{} {}", &captures[1], &captures[2]);
+        let modified = formats
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(&value, format).ok())
+            .map(|dt| Utc.from_utc_datetime(&dt).to_rfc3339());
+        let rest = captures.get(0).map(|m| &text[m.end()..]);
+        return (modified, rest);
+    }
+
+    (None, Some(text))
+}
+
+/// 解析 "1234"、"1.2M"、"512 KiB" 等格式的大小，目录的 "-" 返回 None
+fn parse_human_size(text: &str) -> Option<u64> {
+    let captures = SIZE_REGEX.captures(text)?;
+    let number = captures.get(1)?.as_str();
+    if number == "-" {
+        return None;
+    }
+
+    let value: f64 = number.parse().ok()?;
+    let exponent = match captures
+        .get(2)
+        .map(|m| m.as_str().to_ascii_uppercase())
+        .as_deref()
+    {
+        Some("K") => 1,
+        Some("M") => 2,
+        Some("G") => 3,
+        Some("T") => 4,
+        Some("P") => 5,
+        _ => 0,
+    };
+    Some((value * 1024f64.powi(exponent)) as u64)
+}
+
+/// 还原链接和文本中常见的 HTML 实体
+fn decode_html_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
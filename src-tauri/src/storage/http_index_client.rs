@@ -0,0 +1,673 @@
+use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
+use futures_util::StreamExt;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::storage::http_index::{
+    is_url_list, parse_html_index, parse_json_index, parse_url_list, IndexEntry,
+};
+use crate::storage::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile,
+};
+use crate::utils::http_downloader::{HttpDownloadConfig, HttpDownloader};
+
+/// HEAD 或 Range 探测得到的远程文件信息
+struct RemoteInfo {
+    /// 跟随重定向后的最终 URL
+    url: url::Url,
+    size: Option<u64>,
+    modified: Option<String>,
+    etag: Option<String>,
+    content_type: Option<String>,
+    accepts_ranges: bool,
+}
+
+/// 单个文件 URL 作为连接时的文件信息
+struct SingleFile {
+    name: String,
+    url: url::Url,
+}
+
+/// 连接地址为文本 URL 列表时，列表文件允许的最大大小
+const MAX_URL_LIST_SIZE: u64 = 8 * 1024 * 1024;
+
+/// 只读的 HTTP(S) 目录索引客户端
+/// 支持 Apache/nginx/lighttpd 的 HTML autoindex、JSON autoindex 和纯文本 URL 列表，
+/// 也可以把单个文件 URL 作为只包含该文件的连接
+pub struct HttpIndexClient {
+    client: Client,
+    download_client: Client, // 专门用于下载的客户端，配置更长超时
+    config: ConnectionConfig,
+    // 以 / 结尾的目录 URL；URL 列表连接时为列表文件本身的地址
+    base_url: url::Url,
+    single_file: Option<SingleFile>,
+    // URL 列表中条目的相对路径到绝对地址的映射，列表可以引用其他目录或域名下的文件
+    listed_urls: RwLock<HashMap<String, url::Url>>,
+    auth_header: Option<String>,
+    connected: AtomicBool,
+}
+
+impl HttpIndexClient {
+    pub fn new(config: ConnectionConfig) -> Result<Self, StorageError> {
+        let base_url = Self::parse_base_url(&config)?;
+
+        let auth_header = match (&config.username, &config.password) {
+            (Some(username), Some(password)) if !username.is_empty() => {
+                let credentials =
+                    general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+                Some(format!("Basic {}", credentials))
+            }
+            _ => None,
+        };
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .pool_max_idle_per_host(10)
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .map_err(|e| {
+                StorageError::InvalidConfig(format!("Failed to create HTTP client: {}", e))
+            })?;
+
+        let download_client = Client::builder()
+            .timeout(Duration::from_secs(600))
+            .connect_timeout(Duration::from_secs(10))
+            .pool_idle_timeout(Duration::from_secs(300))
+            .pool_max_idle_per_host(5)
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .map_err(|e| {
+                StorageError::InvalidConfig(format!("Failed to create download HTTP client: {}", e))
+            })?;
+
+        Ok(Self {
+            client,
+            download_client,
+            config,
+            base_url,
+            single_file: None,
+            listed_urls: RwLock::new(HashMap::new()),
+            auth_header,
+            connected: AtomicBool::new(false),
+        })
+    }
+
+    fn parse_base_url(config: &ConnectionConfig) -> Result<url::Url, StorageError> {
+        let url = config
+            .url
+            .as_deref()
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| StorageError::InvalidConfig("HTTP URL is required".to_string()))?;
+
+        let parsed = url::Url::parse(url)
+            .map_err(|e| StorageError::InvalidConfig(format!("Invalid HTTP URL: {}", e)))?;
+        if parsed.scheme() != "http" && parsed.scheme() != "https" {
+            return Err(StorageError::InvalidConfig(format!(
+                "Unsupported URL scheme: {}",
+                parsed.scheme()
+            )));
+        }
+        Ok(parsed)
+    }
+
+    /// 添加认证头
+    fn with_auth(&self, builder: RequestBuilder) -> RequestBuilder {
+        match self.auth_header {
+            Some(ref auth) => builder.header("Authorization", auth),
+            None => builder,
+        }
+    }
+
+    fn download_config(&self, url: &url::Url) -> HttpDownloadConfig {
+        let config = HttpDownloadConfig::new(url.to_string());
+        match self.auth_header {
+            Some(ref auth) => config.with_auth(auth.clone()),
+            None => config,
+        }
+    }
+
+    /// 将相对路径转换为 URL，每一级路径单独编码
+    fn url_for(&self, path: &str, is_dir: bool) -> Result<url::Url, StorageError> {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            return url::Url::parse(path)
+                .map_err(|e| StorageError::InvalidConfig(format!("Invalid URL: {}", e)));
+        }
+
+        let clean_path = path.trim_matches('/');
+        if let Some(ref file) = self.single_file {
+            if clean_path == file.name {
+                return Ok(file.url.clone());
+            }
+        }
+        if clean_path.is_empty() {
+            return Ok(self.base_url.clone());
+        }
+
+        // 路径或其上级目录来自 URL 列表时，以列表给出的绝对地址为基准
+        let segments: Vec<&str> = clean_path.split('/').collect();
+        let listed = self.listed_urls.read().unwrap();
+        let (base_url, rest) = (1..=segments.len())
+            .rev()
+            .find_map(|depth| {
+                let url = listed.get(&segments[..depth].join("/"))?;
+                if depth == segments.len() {
+                    return Some((url.clone(), &segments[depth..]));
+                }
+                let mut url = url.clone();
+                if !url.path().ends_with('/') {
+                    url.set_path(&format!("{}/", url.path()));
+                }
+                Some((url, &segments[depth..]))
+            })
+            .unwrap_or_else(|| (self.base_url.clone(), &segments[..]));
+        drop(listed);
+
+        if rest.is_empty() {
+            return Ok(base_url);
+        }
+
+        let mut relative = rest
+            .iter()
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        if is_dir {
+            relative.push('/');
+        }
+
+        base_url
+            .join(&relative)
+            .map_err(|e| StorageError::InvalidConfig(format!("Invalid path {}: {}", path, e)))
+    }
+
+    fn http_error(response: &reqwest::Response, url: &url::Url) -> StorageError {
+        StorageError::from_response(
+            response,
+            format!(
+                "HTTP {}: {}",
+                response.status(),
+                response.status().canonical_reason().unwrap_or(url.as_str())
+            ),
+        )
+    }
+
+    /// 获取文件信息，优先使用 HEAD；服务器不支持 HEAD 或未返回长度时改用单字节 Range 请求
+    async fn probe(&self, url: &url::Url) -> Result<RemoteInfo, StorageError> {
+        let response = self
+            .with_auth(self.client.head(url.clone()))
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+
+        let head_usable =
+            response.status().is_success() && Self::content_length(&response).is_some();
+        let head_rejected = matches!(
+            response.status(),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED | StatusCode::FORBIDDEN
+        );
+        if !head_usable && !head_rejected && !response.status().is_success() {
+            return Err(Self::http_error(&response, url));
+        }
+
+        let response = if head_usable {
+            response
+        } else {
+            let response = self
+                .with_auth(self.client.get(url.clone()))
+                .header("Range", "bytes=0-0")
+                .send()
+                .await
+                .map_err(StorageError::from_reqwest)?;
+            if !response.status().is_success()
+                && response.status() != StatusCode::RANGE_NOT_SATISFIABLE
+            {
+                return Err(Self::http_error(&response, url));
+            }
+            response
+        };
+
+        Ok(Self::remote_info(&response))
+    }
+
+    /// 直接读取 Content-Length 头，HEAD 响应没有响应体，不能使用 content_length()
+    fn content_length(response: &reqwest::Response) -> Option<u64> {
+        response
+            .headers()
+            .get(reqwest::header::CONTENT_LENGTH)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn remote_info(response: &reqwest::Response) -> RemoteInfo {
+        let header = |name: reqwest::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+
+        // 206 和 416 响应的总长度在 Content-Range 的 "/" 之后
+        let size = match response.status() {
+            StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE => {
+                header(reqwest::header::CONTENT_RANGE)
+                    .and_then(|range| range.rsplit_once('/').and_then(|(_, t)| t.parse().ok()))
+            }
+            _ => Self::content_length(response),
+        };
+
+        let modified = header(reqwest::header::LAST_MODIFIED)
+            .and_then(|value| chrono::DateTime::parse_from_rfc2822(&value).ok())
+            .map(|dt| dt.with_timezone(&chrono::Utc).to_rfc3339());
+
+        let accepts_ranges = response.status() == StatusCode::PARTIAL_CONTENT
+            || header(reqwest::header::ACCEPT_RANGES).as_deref() == Some("bytes");
+
+        RemoteInfo {
+            url: response.url().clone(),
+            size,
+            modified,
+            etag: header(reqwest::header::ETAG).map(|etag| etag.trim_matches('"').to_string()),
+            content_type: header(reqwest::header::CONTENT_TYPE),
+            accepts_ranges,
+        }
+    }
+
+    /// 获取并解析目录索引，按响应类型选择 JSON、HTML 或纯文本解析
+    /// 不以 / 结尾的地址只接受 URL 列表；列表中条目的绝对地址记录在 path 下供后续访问
+    async fn fetch_index(
+        &self,
+        path: &str,
+        url: &url::Url,
+    ) -> Result<Vec<IndexEntry>, StorageError> {
+        let response = self
+            .with_auth(self.client.get(url.clone()))
+            .header(
+                "Accept",
+                "application/json, text/html;q=0.9, text/plain;q=0.8, */*;q=0.5",
+            )
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+
+        if !response.status().is_success() {
+            return Err(Self::http_error(&response, url));
+        }
+
+        // 以重定向后的地址作为相对链接的基准
+        let final_url = response.url().clone();
+        let is_directory_url = final_url.path().ends_with('/');
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_lowercase();
+        let body = response.text().await.map_err(|e| {
+            StorageError::NetworkError(format!("Failed to read response body: {}", e))
+        })?;
+        let trimmed = body.trim_start();
+
+        if is_directory_url {
+            if content_type.contains("json") || trimmed.starts_with('[') {
+                if let Some(entries) = parse_json_index(&body) {
+                    return Ok(entries);
+                }
+            }
+            if content_type.contains("html") || trimmed.starts_with('<') {
+                return Ok(parse_html_index(&body, &final_url));
+            }
+        } else if !is_url_list(&body, &content_type) {
+            return Err(StorageError::RequestFailed(format!(
+                "Not a directory: {}",
+                final_url
+            )));
+        }
+
+        let entries = parse_url_list(&body, &final_url);
+        let parent = path.trim_matches('/');
+        let mut listed = self.listed_urls.write().unwrap();
+        for entry in &entries {
+            if let Some(ref entry_url) = entry.url {
+                let key = if parent.is_empty() {
+                    entry.name.clone()
+                } else {
+                    format!("{}/{}", parent, entry.name)
+                };
+                listed.insert(key, entry_url.clone());
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 只有纯文本且大小合理的文件才尝试按 URL 列表解析
+    fn is_url_list_candidate(info: &RemoteInfo) -> bool {
+        let content_type = info
+            .content_type
+            .as_deref()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        (content_type.starts_with("text/plain") || content_type.starts_with("text/uri-list"))
+            && info.size.is_none_or(|size| size <= MAX_URL_LIST_SIZE)
+    }
+
+    fn read_only_error() -> StorageError {
+        StorageError::ProtocolNotSupported("HTTP index connections are read-only".to_string())
+    }
+}
+
+#[async_trait]
+impl StorageClient for HttpIndexClient {
+    /// 连接时探测 URL：以 / 结尾或重定向到目录的视为目录索引，
+    /// 内容为 URL 列表的文本文件作为列表连接，否则作为单文件连接
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        *self = Self::new(config.clone())?;
+
+        let url = self.base_url.clone();
+        let info = self.probe(&url).await.map_err(|e| match e {
+            StorageError::NetworkError(message) => {
+                StorageError::ConnectionFailed(format!("HTTP connection test failed: {}", message))
+            }
+            other => other,
+        })?;
+
+        if url.path().ends_with('/') || info.url.path().ends_with('/') {
+            let mut base_url = info.url;
+            base_url.set_query(None);
+            base_url.set_fragment(None);
+            if !base_url.path().ends_with('/') {
+                base_url.set_path(&format!("{}/", base_url.path()));
+            }
+            self.base_url = base_url;
+        } else if Self::is_url_list_candidate(&info) && self.fetch_index("", &url).await.is_ok() {
+            // 列表中的相对链接基于列表文件地址解析
+            self.base_url = url;
+        } else {
+            let segment = url.path().rsplit('/').next().unwrap_or_default();
+            let name = urlencoding::decode(segment)
+                .map(|name| name.into_owned())
+                .unwrap_or_else(|_| segment.to_string());
+            self.base_url = url
+                .join("./")
+                .map_err(|e| StorageError::InvalidConfig(format!("Invalid HTTP URL: {}", e)))?;
+            self.single_file = Some(SingleFile { name, url });
+        }
+
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        _options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let entries = match self.single_file {
+            // 单文件连接只列出该文件
+            Some(ref file) if path.trim_matches('/').is_empty() => {
+                let info = self.probe(&file.url).await?;
+                vec![IndexEntry {
+                    name: file.name.clone(),
+                    is_dir: false,
+                    size: info.size,
+                    modified: info.modified,
+                    url: None,
+                }]
+            }
+            Some(_) => return Err(StorageError::NotFound(path.to_string())),
+            None => self.fetch_index(path, &self.url_for(path, true)?).await?,
+        };
+
+        let files = entries
+            .into_iter()
+            .map(|entry| StorageFile {
+                filename: entry.name.clone(),
+                basename: entry.name,
+                lastmod: entry.modified.unwrap_or_default(),
+                size: entry.size.unwrap_or(0).to_string(),
+                file_type: if entry.is_dir { "directory" } else { "file" }.to_string(),
+                mime: None,
+                etag: None,
+            })
+            .collect();
+
+        Ok(DirectoryResult {
+            files,
+            has_more: false,
+            next_marker: None,
+            total_count: None,
+            path: path.to_string(),
+        })
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_file_range_with_progress(path, start, length, None, None)
+            .await
+    }
+
+    /// 使用 Range 请求读取；服务器忽略 Range 返回完整内容时，跳过前面的字节后截取所需部分
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+
+        let url = self.url_for(path, false)?;
+        let response = self
+            .with_auth(self.client.get(url.clone()))
+            .header("Range", format!("bytes={}-{}", start, start + length - 1))
+            .timeout(Duration::from_secs(600))
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+
+        if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            return Err(Self::http_error(&response, &url));
+        }
+
+        let mut skip = if response.status() == StatusCode::PARTIAL_CONTENT {
+            0
+        } else {
+            start
+        };
+        let mut result = Vec::with_capacity(length.min(64 * 1024 * 1024) as usize);
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk_result) = stream.next().await {
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    return Err(StorageError::Cancelled);
+                }
+            }
+
+            let mut chunk = chunk_result
+                .map_err(|e| StorageError::NetworkError(format!("Stream error: {}", e)))?;
+            if skip > 0 {
+                let skipped = skip.min(chunk.len() as u64);
+                skip -= skipped;
+                chunk = chunk.slice(skipped as usize..);
+            }
+
+            let remaining = (length - result.len() as u64) as usize;
+            result.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+
+            if let Some(ref callback) = progress_callback {
+                callback(result.len() as u64, length);
+            }
+            if result.len() as u64 >= length {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        let url = self.url_for(path, false)?;
+        let response = self
+            .with_auth(self.download_client.get(url.clone()))
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+
+        if !response.status().is_success() {
+            return Err(Self::http_error(&response, &url));
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("Failed to read response: {}", e)))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        let url = self.url_for(path, false)?;
+        HttpDownloader::open_stream(&self.download_client, self.download_config(&url), start).await
+    }
+
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        let clean_path = path.trim_matches('/');
+        let basename = clean_path
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let directory = |lastmod: String| FileMetadata {
+            filename: path.to_string(),
+            basename: basename.clone(),
+            lastmod,
+            size: "0".to_string(),
+            file_type: "directory".to_string(),
+            mime: None,
+            etag: None,
+            checksum: None,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra: HashMap::new(),
+        };
+
+        if clean_path.is_empty() || path.ends_with('/') {
+            return Ok(directory(String::new()));
+        }
+
+        // 目录地址不带 / 时服务器通常会重定向到带 / 的地址
+        let info = self.probe(&self.url_for(path, false)?).await?;
+        if info.url.path().ends_with('/') {
+            return Ok(directory(info.modified.unwrap_or_default()));
+        }
+
+        let mut extra = HashMap::new();
+        extra.insert("url".to_string(), info.url.to_string());
+        extra.insert("acceptRanges".to_string(), info.accepts_ranges.to_string());
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename,
+            lastmod: info.modified.unwrap_or_default(),
+            size: info.size.unwrap_or(0).to_string(),
+            file_type: "file".to_string(),
+            mime: info.content_type,
+            etag: info.etag,
+            checksum: None,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra,
+        })
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        let url = self.url_for(path, false)?;
+        self.probe(&url).await?.size.ok_or_else(|| {
+            StorageError::RequestFailed(format!("Server did not report the size of {}", url))
+        })
+    }
+
+    fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
+        Ok(self.url_for(path, false)?.to_string())
+    }
+
+    fn protocol(&self) -> &str {
+        "http"
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        Self::parse_base_url(config).map(|_| ())
+    }
+
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let url = self.url_for(path, false)?;
+        HttpDownloader::download_stream(
+            &self.download_client,
+            self.download_config(&url),
+            save_path,
+            progress_callback,
+            cancel_rx,
+        )
+        .await
+    }
+
+    async fn put_file(
+        &self,
+        _local_path: &std::path::Path,
+        _path: &str,
+        _progress_callback: Option<ProgressCallback>,
+        _cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn create_directory(&self, _path: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn rename(&self, _from: &str, _to: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn delete(&self, _path: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+}
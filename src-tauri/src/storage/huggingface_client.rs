@@ -1,14 +1,17 @@
 use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
 use reqwest::Client;
 use serde::Deserialize;
-
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::storage::traits::{
-    ConnectionConfig, DirectoryResult, ListOptions, ProgressCallback, StorageClient, StorageError,
-    StorageFile,
+    ByteStream, ConnectionConfig, DirectoryResult, DirectoryUsage, FileMetadata, ListOptions,
+    ProgressCallback, StorageClient, StorageError, StorageFile,
 };
-use crate::utils::http_downloader::HttpDownloader;
+use crate::utils::http_downloader::{HttpDownloadConfig, HttpDownloader};
+use crate::utils::http_uploader::HttpUploader;
 
 /// HuggingFace 数据集信息
 #[derive(Debug, Deserialize)]
@@ -18,6 +21,13 @@ struct DatasetInfo {
     last_modified: Option<String>,
 }
 
+/// HuggingFace 数据集存储占用（来自 expand=usedStorage）
+#[derive(Debug, Deserialize)]
+struct DatasetStorageInfo {
+    #[serde(rename = "usedStorage")]
+    used_storage: Option<u64>,
+}
+
 /// HuggingFace 数据集文件信息（来自 tree API）
 #[derive(Debug, Clone, Deserialize)]
 pub struct DatasetFile {
@@ -28,6 +38,96 @@ pub struct DatasetFile {
     pub path: String, // 文件路径
 }
 
+/// HuggingFace 路径详情（来自 paths-info API）
+#[derive(Debug, Deserialize)]
+struct PathInfo {
+    #[serde(rename = "type")]
+    file_type: String,
+    oid: Option<String>,
+    size: Option<u64>,
+    lfs: Option<LfsInfo>,
+    #[serde(rename = "lastCommit")]
+    last_commit: Option<LastCommitInfo>,
+}
+
+/// LFS 文件信息
+#[derive(Debug, Deserialize)]
+struct LfsInfo {
+    oid: String,
+    size: u64,
+    #[serde(rename = "pointerSize")]
+    pointer_size: Option<u64>,
+}
+
+/// 最后一次修改该路径的提交
+#[derive(Debug, Deserialize)]
+struct LastCommitInfo {
+    id: String,
+    title: Option<String>,
+    date: Option<String>,
+}
+
+/// 预上传检查结果（来自 preupload API）
+#[derive(Debug, Deserialize)]
+struct PreuploadResponse {
+    files: Vec<PreuploadFile>,
+}
+
+/// 单个文件的上传方式："regular" 内联提交，"lfs" 走 LFS 存储
+#[derive(Debug, Deserialize)]
+struct PreuploadFile {
+    path: String,
+    #[serde(rename = "uploadMode")]
+    upload_mode: String,
+}
+
+/// LFS batch API 响应
+#[derive(Debug, Deserialize)]
+struct LfsBatchResponse {
+    objects: Vec<LfsBatchObject>,
+}
+
+/// LFS batch 中的单个对象，actions 为空表示服务端已有该对象
+#[derive(Debug, Deserialize)]
+struct LfsBatchObject {
+    oid: String,
+    actions: Option<LfsBatchActions>,
+    error: Option<LfsBatchError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LfsBatchActions {
+    upload: Option<LfsAction>,
+    verify: Option<LfsAction>,
+}
+
+/// LFS 传输动作，分片上传时 header 中包含 chunk_size 和各分片的上传地址
+#[derive(Debug, Deserialize)]
+struct LfsAction {
+    href: String,
+    #[serde(default)]
+    header: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LfsBatchError {
+    message: String,
+}
+
+/// 本地待上传文件的摘要，用于预上传检查和 LFS 上传
+struct LocalFileDigest {
+    size: u64,
+    sha256: String,
+    /// 文件开头的样本，服务端据此判断是否为文本文件
+    sample: Vec<u8>,
+}
+
+/// 预上传检查发送的文件样本大小
+const PREUPLOAD_SAMPLE_SIZE: usize = 512;
+
+/// 内联提交的文件大小上限，超过时即使服务端判定为 regular 也走 LFS
+const MAX_INLINE_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
 // HuggingFace API 直接返回数组，不需要包装结构体
 pub struct HuggingFaceClient {
     client: reqwest::Client,
@@ -80,50 +180,14 @@ impl HuggingFaceClient {
             .map_err(|e| StorageError::NetworkError(e.to_string()))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "Failed to fetch datasets: {}",
-                response.status()
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!("Failed to fetch datasets: {}", response.status()),
+            ));
         }
 
         // 提取 Link header 信息以及下一页的 cursor（在消耗 response 之前）
-        let (has_more, next_cursor) = if let Some(link_header) = response.headers().get("Link") {
-            if let Ok(link_str) = link_header.to_str() {
-                let has_more = link_str.contains("rel=\"next\"");
-
-                // 从 Link header 中提取 cursor 参数
-                let next_cursor = if has_more {
-                    // 提取形如 <https://huggingface.co/api/datasets?cursor=xxx&limit=20>; rel="next" 的链接
-                    link_str
-                        .split(',')
-                        .find(|part| part.contains("rel=\"next\""))
-                        .and_then(|next_part| {
-                            // 提取 URL 部分
-                            next_part
-                                .trim()
-                                .strip_prefix('<')
-                                .and_then(|s| s.split('>').next())
-                        })
-                        .and_then(|url| {
-                            // 从 URL 中提取 cursor 参数
-                            url.split('&')
-                                .find(|param| param.starts_with("cursor="))
-                                .and_then(|cursor_param| cursor_param.strip_prefix("cursor="))
-                                .map(|cursor| {
-                                    urlencoding::decode(cursor).unwrap_or_default().into_owned()
-                                })
-                        })
-                } else {
-                    None
-                };
-
-                (has_more, next_cursor)
-            } else {
-                (false, None)
-            }
-        } else {
-            (false, None)
-        };
+        let (has_more, next_cursor) = Self::next_page_cursor(response.headers());
 
         let datasets: Vec<DatasetInfo> = response
             .json()
@@ -194,50 +258,14 @@ impl HuggingFaceClient {
             .map_err(|e| StorageError::NetworkError(e.to_string()))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "Failed to search datasets: {}",
-                response.status()
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!("Failed to search datasets: {}", response.status()),
+            ));
         }
 
         // 提取 Link header 信息以及下一页的 cursor（在消耗 response 之前）
-        let (has_more, next_cursor) = if let Some(link_header) = response.headers().get("Link") {
-            if let Ok(link_str) = link_header.to_str() {
-                let has_more = link_str.contains("rel=\"next\"");
-
-                // 从 Link header 中提取 cursor 参数
-                let next_cursor = if has_more {
-                    // 提取形如 <https://huggingface.co/api/datasets?cursor=xxx&limit=20>; rel="next" 的链接
-                    link_str
-                        .split(',')
-                        .find(|part| part.contains("rel=\"next\""))
-                        .and_then(|next_part| {
-                            // 提取 URL 部分
-                            next_part
-                                .trim()
-                                .strip_prefix('<')
-                                .and_then(|s| s.split('>').next())
-                        })
-                        .and_then(|url| {
-                            // 从 URL 中提取 cursor 参数
-                            url.split('&')
-                                .find(|param| param.starts_with("cursor="))
-                                .and_then(|cursor_param| cursor_param.strip_prefix("cursor="))
-                                .map(|cursor| {
-                                    urlencoding::decode(cursor).unwrap_or_default().into_owned()
-                                })
-                        })
-                } else {
-                    None
-                };
-
-                (has_more, next_cursor)
-            } else {
-                (false, None)
-            }
-        } else {
-            (false, None)
-        };
+        let (has_more, next_cursor) = Self::next_page_cursor(response.headers());
 
         let datasets: Vec<DatasetInfo> = response
             .json()
@@ -308,50 +336,17 @@ impl HuggingFaceClient {
             .map_err(|e| StorageError::NetworkError(e.to_string()))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "Failed to fetch organization datasets: {}",
-                response.status()
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "Failed to fetch organization datasets: {}",
+                    response.status()
+                ),
+            ));
         }
 
         // 提取 Link header 信息以及下一页的 cursor（在消耗 response 之前）
-        let (has_more, next_cursor) = if let Some(link_header) = response.headers().get("link") {
-            if let Ok(link_str) = link_header.to_str() {
-                let has_more = link_str.contains("rel=\"next\"");
-
-                // 从 Link header 中提取 cursor 参数
-                let next_cursor = if has_more {
-                    // 提取形如 <https://huggingface.co/api/datasets?cursor=xxx&limit=20>; rel="next" 的链接
-                    link_str
-                        .split(',')
-                        .find(|part| part.contains("rel=\"next\""))
-                        .and_then(|next_part| {
-                            // 提取 URL 部分
-                            next_part
-                                .trim()
-                                .strip_prefix('<')
-                                .and_then(|s| s.split('>').next())
-                        })
-                        .and_then(|url| {
-                            // 从 URL 中提取 cursor 参数
-                            url.split('&')
-                                .find(|param| param.starts_with("cursor="))
-                                .and_then(|cursor_param| cursor_param.strip_prefix("cursor="))
-                                .map(|cursor| {
-                                    urlencoding::decode(cursor).unwrap_or_default().into_owned()
-                                })
-                        })
-                } else {
-                    None
-                };
-
-                (has_more, next_cursor)
-            } else {
-                (false, None)
-            }
-        } else {
-            (false, None)
-        };
+        let (has_more, next_cursor) = Self::next_page_cursor(response.headers());
 
         let datasets: Vec<DatasetInfo> = response
             .json()
@@ -388,8 +383,14 @@ impl HuggingFaceClient {
         owner: &str,
         dataset: &str,
         subpath: &str,
-        _options: Option<&ListOptions>,
+        options: Option<&ListOptions>,
     ) -> Result<DirectoryResult, StorageError> {
+        if options.and_then(|o| o.recursive).unwrap_or(false) {
+            return self
+                .list_dataset_files_recursive(owner, dataset, subpath, options)
+                .await;
+        }
+
         let dataset_id = format!("{}/{}", owner, dataset);
         // 使用 tree API 获取完整的文件信息
         let url = if subpath.is_empty() {
@@ -499,6 +500,132 @@ impl HuggingFaceClient {
         })
     }
 
+    /// 使用 tree API 的 recursive=true 递归列出数据集文件，filename 为相对于 subpath 的路径
+    async fn list_dataset_files_recursive(
+        &self,
+        owner: &str,
+        dataset: &str,
+        subpath: &str,
+        options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        let dataset_id = format!("{}/{}", owner, dataset);
+        let mut url = if subpath.is_empty() {
+            format!(
+                "{}/datasets/{}/tree/main?recursive=true",
+                self.api_url, dataset_id
+            )
+        } else {
+            format!(
+                "{}/datasets/{}/tree/main/{}?recursive=true",
+                self.api_url, dataset_id, subpath
+            )
+        };
+        if let Some(marker) = options.and_then(|o| o.marker.as_ref()) {
+            url.push_str(&format!("&cursor={}", urlencoding::encode(marker)));
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.get_reqwest_headers())
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "Failed to fetch dataset files for {}/{}: {}",
+                    dataset_id,
+                    subpath,
+                    response.status()
+                ),
+            ));
+        }
+
+        let (has_more, next_cursor) = Self::next_page_cursor(response.headers());
+
+        let files_data: Vec<DatasetFile> = response
+            .json()
+            .await
+            .map_err(|e| StorageError::RequestFailed(e.to_string()))?;
+
+        let prefix = if subpath.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", subpath)
+        };
+        let files: Vec<StorageFile> = files_data
+            .into_iter()
+            .filter(|file| file.file_type != "directory")
+            .filter_map(|file| {
+                let relative_path = file.path.strip_prefix(&prefix)?.to_string();
+                let basename = relative_path
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&relative_path)
+                    .to_string();
+                Some(StorageFile {
+                    mime: Some(self.get_mime_type(&basename)),
+                    filename: relative_path,
+                    basename,
+                    lastmod: "unknown".to_string(),
+                    size: file.size.to_string(),
+                    file_type: "file".to_string(),
+                    etag: Some(file.oid),
+                })
+            })
+            .collect();
+
+        let path = if subpath.is_empty() {
+            dataset_id.replace('/', ":")
+        } else {
+            format!("{}/{}", dataset_id.replace('/', ":"), subpath)
+        };
+
+        Ok(DirectoryResult {
+            files,
+            has_more,
+            next_marker: next_cursor,
+            total_count: None,
+            path,
+        })
+    }
+
+    /// 从 Link header 中提取是否有下一页以及下一页的 cursor
+    fn next_page_cursor(headers: &reqwest::header::HeaderMap) -> (bool, Option<String>) {
+        let Some(link_str) = headers.get("Link").and_then(|link| link.to_str().ok()) else {
+            return (false, None);
+        };
+
+        let has_more = link_str.contains("rel=\"next\"");
+        if !has_more {
+            return (false, None);
+        }
+
+        // 提取形如 <https://huggingface.co/api/datasets?cursor=xxx&limit=20>; rel="next" 的链接
+        let next_cursor = link_str
+            .split(',')
+            .find(|part| part.contains("rel=\"next\""))
+            .and_then(|next_part| {
+                // 提取 URL 部分
+                next_part
+                    .trim()
+                    .strip_prefix('<')
+                    .and_then(|s| s.split('>').next())
+            })
+            .and_then(|url| {
+                // 从 URL 中提取 cursor 参数
+                url.split(['?', '&'])
+                    .find(|param| param.starts_with("cursor="))
+                    .and_then(|cursor_param| cursor_param.strip_prefix("cursor="))
+                    .map(|cursor| urlencoding::decode(cursor).unwrap_or_default().into_owned())
+            });
+
+        (has_more, next_cursor)
+    }
+
     /// 获取 MIME 类型
     fn get_mime_type(&self, filename: &str) -> String {
         let ext = filename.split('.').last().unwrap_or("").to_lowercase();
@@ -611,6 +738,448 @@ impl HuggingFaceClient {
 
         headers
     }
+
+    /// 提交一次 commit，operations 为 NDJSON 格式的操作列表
+    async fn create_commit(
+        &self,
+        dataset_id: &str,
+        summary: &str,
+        operations: Vec<serde_json::Value>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        self.ensure_write_token()?;
+
+        let mut lines = vec![serde_json::json!({
+            "key": "header",
+            "value": { "summary": summary, "description": "" }
+        })
+        .to_string()];
+        lines.extend(operations.iter().map(|op| op.to_string()));
+
+        let mut headers = self.get_reqwest_headers();
+        headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/x-ndjson"),
+        );
+
+        let url = format!("{}/datasets/{}/commit/main", self.api_url, dataset_id);
+        let request = self
+            .client
+            .post(&url)
+            .headers(headers)
+            .body(lines.join("\n"));
+
+        let response = HttpUploader::send_with_cancel(request, cancel_rx).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!(
+                    "Commit to {} failed with status {}: {}",
+                    dataset_id, status, body
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 写操作必须提供 API token
+    fn ensure_write_token(&self) -> Result<(), StorageError> {
+        if self
+            .api_token
+            .as_ref()
+            .is_none_or(|token| token.trim().is_empty())
+        {
+            return Err(StorageError::AuthenticationFailed(
+                "HuggingFace API token is required for write operations".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 流式计算本地文件的大小和 SHA256，同时截取开头样本
+    async fn digest_local_file(
+        local_path: &std::path::Path,
+    ) -> Result<LocalFileDigest, StorageError> {
+        let local_path = local_path.to_path_buf();
+
+        // 大文件哈希耗时较长，放到阻塞线程执行
+        tokio::task::spawn_blocking(move || {
+            use sha2::Digest;
+            use std::io::Read;
+
+            let mut file = std::fs::File::open(&local_path)
+                .map_err(|e| StorageError::IoError(format!("Failed to open local file: {}", e)))?;
+            let mut hasher = sha2::Sha256::new();
+            let mut buffer = vec![0u8; 1024 * 1024];
+            let mut sample = Vec::with_capacity(PREUPLOAD_SAMPLE_SIZE);
+            let mut size = 0u64;
+
+            loop {
+                let bytes_read = file.read(&mut buffer).map_err(|e| {
+                    StorageError::IoError(format!("Failed to read local file: {}", e))
+                })?;
+                if bytes_read == 0 {
+                    break;
+                }
+
+                if sample.len() < PREUPLOAD_SAMPLE_SIZE {
+                    let take = (PREUPLOAD_SAMPLE_SIZE - sample.len()).min(bytes_read);
+                    sample.extend_from_slice(&buffer[..take]);
+                }
+                hasher.update(&buffer[..bytes_read]);
+                size += bytes_read as u64;
+            }
+
+            Ok(LocalFileDigest {
+                size,
+                sha256: hex::encode(hasher.finalize()),
+                sample,
+            })
+        })
+        .await
+        .map_err(|e| StorageError::IoError(format!("File hashing task failed: {}", e)))?
+    }
+
+    /// 调用 preupload API，由服务端根据大小、样本和 .gitattributes 决定上传方式
+    async fn preupload_mode(
+        &self,
+        dataset_id: &str,
+        file_path: &str,
+        digest: &LocalFileDigest,
+    ) -> Result<String, StorageError> {
+        let url = format!("{}/datasets/{}/preupload/main", self.api_url, dataset_id);
+        let body = serde_json::json!({
+            "files": [{
+                "path": file_path,
+                "sample": general_purpose::STANDARD.encode(&digest.sample),
+                "size": digest.size,
+            }]
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(self.get_reqwest_headers())
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "Preupload request failed with status: {}",
+                    response.status()
+                ),
+            ));
+        }
+
+        let result: PreuploadResponse = response.json().await.map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to parse preupload response: {}", e))
+        })?;
+
+        Ok(result
+            .files
+            .into_iter()
+            .find(|f| f.path == file_path)
+            .map(|f| f.upload_mode)
+            .unwrap_or_else(|| "lfs".to_string()))
+    }
+
+    /// 通过 LFS batch API 上传文件内容，服务端已有相同对象时直接跳过
+    async fn upload_lfs_object(
+        &self,
+        dataset_id: &str,
+        local_path: &std::path::Path,
+        digest: &LocalFileDigest,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let url = format!(
+            "{}/datasets/{}.git/info/lfs/objects/batch",
+            self.base_url, dataset_id
+        );
+        let body = serde_json::json!({
+            "operation": "upload",
+            "transfers": ["basic", "multipart"],
+            "objects": [{ "oid": digest.sha256, "size": digest.size }],
+            "hash_algo": "sha256",
+            "ref": { "name": "main" },
+        });
+
+        let mut headers = self.get_reqwest_headers();
+        let lfs_content_type =
+            reqwest::header::HeaderValue::from_static("application/vnd.git-lfs+json");
+        headers.insert(reqwest::header::CONTENT_TYPE, lfs_content_type.clone());
+        headers.insert(reqwest::header::ACCEPT, lfs_content_type);
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(headers)
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "LFS batch request failed with status: {}",
+                    response.status()
+                ),
+            ));
+        }
+
+        let batch: LfsBatchResponse = response.json().await.map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to parse LFS batch response: {}", e))
+        })?;
+        let object = batch
+            .objects
+            .into_iter()
+            .find(|o| o.oid == digest.sha256)
+            .ok_or_else(|| {
+                StorageError::RequestFailed("LFS batch response is missing the object".to_string())
+            })?;
+
+        if let Some(error) = object.error {
+            return Err(StorageError::RequestFailed(format!(
+                "LFS upload rejected: {}",
+                error.message
+            )));
+        }
+
+        let Some(upload) = object.actions.as_ref().and_then(|a| a.upload.as_ref()) else {
+            // 服务端已有该对象，无需重复上传
+            if let Some(callback) = progress_callback {
+                callback(digest.size, digest.size);
+            }
+            return Ok(());
+        };
+
+        if upload.header.contains_key("chunk_size") {
+            self.upload_lfs_multipart(
+                upload,
+                local_path,
+                digest,
+                progress_callback,
+                cancel_rx.as_deref_mut(),
+            )
+            .await?;
+        } else {
+            let (body, file_size) = HttpUploader::file_body(local_path, progress_callback).await?;
+            let mut request = self
+                .client
+                .put(&upload.href)
+                .header("Content-Length", file_size.to_string())
+                .body(body);
+            for (key, value) in &upload.header {
+                request = request.header(key, value);
+            }
+
+            let response = HttpUploader::send_with_cancel(request, cancel_rx).await?;
+            if !response.status().is_success() {
+                return Err(StorageError::from_response(
+                    &response,
+                    format!("LFS upload failed with status: {}", response.status()),
+                ));
+            }
+        }
+
+        if let Some(verify) = object.actions.as_ref().and_then(|a| a.verify.as_ref()) {
+            let mut request = self
+                .client
+                .post(&verify.href)
+                .header("Content-Type", "application/vnd.git-lfs+json")
+                .body(serde_json::json!({ "oid": digest.sha256, "size": digest.size }).to_string());
+            for (key, value) in &verify.header {
+                request = request.header(key, value);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| StorageError::NetworkError(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(StorageError::from_response(
+                    &response,
+                    format!("LFS verify failed with status: {}", response.status()),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// LFS 分片上传：按 chunk_size 逐片读取本地文件并上传到对应地址，最后提交分片列表
+    async fn upload_lfs_multipart(
+        &self,
+        upload: &LfsAction,
+        local_path: &std::path::Path,
+        digest: &LocalFileDigest,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+        let chunk_size = upload
+            .header
+            .get("chunk_size")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .ok_or_else(|| {
+                StorageError::RequestFailed("Invalid LFS multipart chunk size".to_string())
+            })?;
+
+        // 分片地址以零填充的分片序号为键
+        let mut part_urls: Vec<(u32, &String)> = upload
+            .header
+            .iter()
+            .filter_map(|(key, url)| key.parse::<u32>().ok().map(|n| (n, url)))
+            .collect();
+        part_urls.sort_by_key(|(n, _)| *n);
+
+        let mut file = tokio::fs::File::open(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to open local file: {}", e)))?;
+        let mut parts = Vec::with_capacity(part_urls.len());
+        let mut uploaded = 0u64;
+
+        for (part_number, part_url) in part_urls {
+            let offset = (part_number as u64 - 1) * chunk_size;
+            if offset >= digest.size {
+                break;
+            }
+            let length = chunk_size.min(digest.size - offset);
+
+            // 内存占用受分片大小限制
+            let mut buffer = vec![0u8; length as usize];
+            file.seek(std::io::SeekFrom::Start(offset))
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to seek local file: {}", e)))?;
+            file.read_exact(&mut buffer)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to read local file: {}", e)))?;
+
+            let request = self.client.put(part_url).body(buffer);
+            let response =
+                HttpUploader::send_with_cancel(request, cancel_rx.as_deref_mut()).await?;
+            if !response.status().is_success() {
+                return Err(StorageError::from_response(
+                    &response,
+                    format!(
+                        "LFS part {} upload failed with status: {}",
+                        part_number,
+                        response.status()
+                    ),
+                ));
+            }
+
+            let etag = response
+                .headers()
+                .get("etag")
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| {
+                    StorageError::RequestFailed(format!(
+                        "LFS part {} response is missing ETag",
+                        part_number
+                    ))
+                })?
+                .to_string();
+            parts.push(serde_json::json!({ "partNumber": part_number, "etag": etag }));
+
+            uploaded += length;
+            if let Some(callback) = &progress_callback {
+                callback(uploaded, digest.size);
+            }
+        }
+
+        let response = self
+            .client
+            .post(&upload.href)
+            .header("Content-Type", "application/vnd.git-lfs+json")
+            .body(serde_json::json!({ "oid": digest.sha256, "parts": parts }).to_string())
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "LFS multipart completion failed with status: {}",
+                    response.status()
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 查询单个路径的详情（paths-info API），路径不存在时返回 None
+    async fn fetch_path_info(
+        &self,
+        dataset_id: &str,
+        file_path: &str,
+    ) -> Result<Option<PathInfo>, StorageError> {
+        let url = format!("{}/datasets/{}/paths-info/main", self.api_url, dataset_id);
+        let mut request = self
+            .client
+            .post(&url)
+            .form(&[("paths", file_path), ("expand", "true")]);
+        if let Some(token) = self.api_token.as_ref().filter(|t| !t.trim().is_empty()) {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "Paths info request failed with status: {}",
+                    response.status()
+                ),
+            ));
+        }
+
+        let infos: Vec<PathInfo> = response.json().await.map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to parse paths info: {}", e))
+        })?;
+        Ok(infos.into_iter().next())
+    }
+
+    /// 查找路径在 tree API 中的类型（"file" 或 "directory"）
+    async fn find_entry_type(
+        &self,
+        dataset_id: &str,
+        file_path: &str,
+    ) -> Result<Option<String>, StorageError> {
+        let (owner, dataset) = dataset_id.split_once('/').ok_or_else(|| {
+            StorageError::InvalidConfig(format!("Invalid dataset ID: {}", dataset_id))
+        })?;
+        let (parent, name) = file_path.rsplit_once('/').unwrap_or(("", file_path));
+
+        let result = self
+            .list_dataset_files(owner, dataset, parent, None)
+            .await?;
+
+        Ok(result
+            .files
+            .into_iter()
+            .find(|f| f.filename == name)
+            .map(|f| f.file_type))
+    }
 }
 
 #[async_trait]
@@ -676,6 +1245,67 @@ impl StorageClient for HuggingFaceClient {
         }
     }
 
+    /// 数据集内的路径可以通过 tree API 的 recursive=true 递归列出
+    fn supports_recursive_listing(&self, path: &str) -> bool {
+        let path_trimmed = path.trim_start_matches('/');
+        !path.starts_with("/search/")
+            && (path_trimmed.contains('/') || path_trimmed.contains(':'))
+            && self.parse_path(path).is_ok()
+    }
+
+    /// 数据集列表通过 cursor 原生分页；数据集内只有递归列出分页，逐层列出一次返回整个目录
+    fn supports_native_paging(&self, path: &str, recursive: bool) -> bool {
+        !self.supports_recursive_listing(path) || recursive
+    }
+
+    /// 数据集根目录可以直接使用 usedStorage 获取总占用
+    /// usedStorage 只有总量且包含历史版本，因此只在不需要子目录明细时使用
+    async fn directory_usage(
+        &self,
+        path: &str,
+        max_depth: u32,
+    ) -> Result<Option<Vec<DirectoryUsage>>, StorageError> {
+        if max_depth > 0 || !self.supports_recursive_listing(path) {
+            return Ok(None);
+        }
+        let (dataset_id, file_path) = self.parse_path(path)?;
+        if !file_path.is_empty() {
+            return Ok(None);
+        }
+
+        let url = format!(
+            "{}/datasets/{}?expand=usedStorage",
+            self.api_url, dataset_id
+        );
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.get_reqwest_headers())
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!("Failed to fetch dataset storage: {}", response.status()),
+            ));
+        }
+
+        let info: DatasetStorageInfo = response
+            .json()
+            .await
+            .map_err(|e| StorageError::RequestFailed(e.to_string()))?;
+
+        Ok(info.used_storage.map(|size| {
+            vec![DirectoryUsage {
+                path: String::new(),
+                size,
+                file_count: None,
+            }]
+        }))
+    }
+
     async fn read_file_range(
         &self,
         path: &str,
@@ -718,14 +1348,17 @@ impl StorageClient for HuggingFaceClient {
             .map_err(|e| StorageError::NetworkError(format!("Request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .status()
-                    .canonical_reason()
-                    .unwrap_or("error.unknown")
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "HTTP {}: {}",
+                    response.status(),
+                    response
+                        .status()
+                        .canonical_reason()
+                        .unwrap_or("error.unknown")
+                ),
+            ));
         }
 
         // 使用流式读取以支持进度回调
@@ -737,9 +1370,7 @@ impl StorageClient for HuggingFaceClient {
             // 检查取消信号
             if let Some(ref mut cancel_rx) = cancel_rx {
                 if cancel_rx.try_recv().is_ok() {
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+                    return Err(StorageError::Cancelled);
                 }
             }
 
@@ -772,14 +1403,17 @@ impl StorageClient for HuggingFaceClient {
             .map_err(|e| StorageError::NetworkError(format!("Request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .status()
-                    .canonical_reason()
-                    .unwrap_or("error.unknown")
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "HTTP {}: {}",
+                    response.status(),
+                    response
+                        .status()
+                        .canonical_reason()
+                        .unwrap_or("error.unknown")
+                ),
+            ));
         }
 
         let bytes = response.bytes().await.map_err(|e| {
@@ -789,6 +1423,125 @@ impl StorageClient for HuggingFaceClient {
         Ok(bytes.to_vec())
     }
 
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        let (dataset_id, file_path) = self.parse_path(path)?;
+        let download_url = self.build_download_url(&dataset_id, &file_path);
+
+        let mut config = HttpDownloadConfig::new(download_url);
+        if let Some(token) = self.api_token.as_ref().filter(|t| !t.trim().is_empty()) {
+            config = config.with_auth(format!("Bearer {}", token));
+        }
+
+        HttpDownloader::open_stream(&self.client, config, start).await
+    }
+
+    /// 通过 paths-info API 获取文件元数据，包含 LFS oid 和最后提交信息
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        let (dataset_id, file_path) = self.parse_path(path)?;
+
+        let mut extra = HashMap::new();
+        extra.insert("datasetId".to_string(), dataset_id.clone());
+
+        // 数据集根目录：使用数据集信息
+        if file_path.is_empty() {
+            let url = format!("{}/datasets/{}", self.api_url, dataset_id);
+            let response = self
+                .client
+                .get(&url)
+                .headers(self.get_reqwest_headers())
+                .send()
+                .await
+                .map_err(|e| StorageError::NetworkError(format!("Request failed: {}", e)))?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Err(StorageError::NotFound(path.to_string()));
+            }
+            if !response.status().is_success() {
+                return Err(StorageError::from_response(
+                    &response,
+                    format!("HTTP {}", response.status()),
+                ));
+            }
+
+            let info: DatasetInfo = response.json().await.map_err(|e| {
+                StorageError::RequestFailed(format!("Failed to parse dataset info: {}", e))
+            })?;
+
+            return Ok(FileMetadata {
+                filename: path.to_string(),
+                basename: info.id.rsplit('/').next().unwrap_or_default().to_string(),
+                lastmod: info.last_modified.unwrap_or_default(),
+                size: "0".to_string(),
+                file_type: "directory".to_string(),
+                mime: None,
+                etag: None,
+                checksum: None,
+                permissions: None,
+                owner: dataset_id.split('/').next().map(|s| s.to_string()),
+                group: None,
+                extra,
+            });
+        }
+
+        let info = self
+            .fetch_path_info(&dataset_id, &file_path)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(path.to_string()))?;
+
+        let is_directory = info.file_type == "directory";
+        let mut lastmod = String::new();
+
+        if let Some(oid) = &info.oid {
+            extra.insert("oid".to_string(), oid.clone());
+        }
+        if let Some(lfs) = &info.lfs {
+            extra.insert("lfsOid".to_string(), lfs.oid.clone());
+            extra.insert("lfsSize".to_string(), lfs.size.to_string());
+            if let Some(pointer_size) = lfs.pointer_size {
+                extra.insert("lfsPointerSize".to_string(), pointer_size.to_string());
+            }
+        }
+        if let Some(commit) = info.last_commit {
+            extra.insert("lastCommitId".to_string(), commit.id);
+            if let Some(title) = commit.title {
+                extra.insert("lastCommitTitle".to_string(), title);
+            }
+            lastmod = commit.date.unwrap_or_default();
+        }
+
+        // LFS 文件的 size 为实际内容大小，普通文件的 oid 为 git blob 哈希
+        let size = info
+            .lfs
+            .as_ref()
+            .map(|lfs| lfs.size)
+            .or(info.size)
+            .unwrap_or(0);
+        let checksum = info
+            .lfs
+            .as_ref()
+            .map(|lfs| format!("sha256:{}", lfs.oid))
+            .or_else(|| info.oid.as_ref().map(|oid| format!("git-sha1:{}", oid)));
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename: file_path.rsplit('/').next().unwrap_or_default().to_string(),
+            lastmod,
+            size: if is_directory {
+                "0".to_string()
+            } else {
+                size.to_string()
+            },
+            file_type: if is_directory { "directory" } else { "file" }.to_string(),
+            mime: None,
+            etag: info.oid,
+            checksum: if is_directory { None } else { checksum },
+            permissions: None,
+            owner: dataset_id.split('/').next().map(|s| s.to_string()),
+            group: None,
+            extra,
+        })
+    }
+
     async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
         println!("[DEBUG] HuggingFace get_file_size called with:");
         println!("[DEBUG] - path: {}", path);
@@ -824,10 +1577,10 @@ impl StorageClient for HuggingFaceClient {
             .map_err(|e| StorageError::NetworkError(e.to_string()))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "Failed to fetch file info: {}",
-                response.status()
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!("Failed to fetch file info: {}", response.status()),
+            ));
         }
 
         let files: Vec<DatasetFile> = response
@@ -856,10 +1609,10 @@ impl StorageClient for HuggingFaceClient {
                 .map_err(|e| StorageError::NetworkError(e.to_string()))?;
 
             if !response.status().is_success() {
-                return Err(StorageError::RequestFailed(format!(
-                    "HEAD request failed: {}",
-                    response.status()
-                )));
+                return Err(StorageError::from_response(
+                    &response,
+                    format!("HEAD request failed: {}", response.status()),
+                ));
             }
 
             if let Some(content_length) = response.headers().get("content-length") {
@@ -881,6 +1634,12 @@ impl StorageClient for HuggingFaceClient {
         Ok(self.build_download_url(&dataset_id, &file_path))
     }
 
+    fn canonical_path(&self, path: &str) -> String {
+        path.strip_prefix("huggingface://")
+            .unwrap_or(path)
+            .to_string()
+    }
+
     fn protocol(&self) -> &str {
         "huggingface"
     }
@@ -924,4 +1683,173 @@ impl StorageClient for HuggingFaceClient {
         )
         .await
     }
+
+    /// 通过 preupload → LFS batch → commit 流程上传文件
+    /// 服务端判定为 regular 的小文件以 base64 内联提交，其余文件流式上传到 LFS 存储
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let (dataset_id, file_path) = self.parse_path(path)?;
+        if file_path.is_empty() {
+            return Err(StorageError::InvalidConfig(
+                "Target file path is required".to_string(),
+            ));
+        }
+        self.ensure_write_token()?;
+
+        let digest = Self::digest_local_file(local_path).await?;
+        let upload_mode = self
+            .preupload_mode(&dataset_id, &file_path, &digest)
+            .await?;
+
+        let inline = upload_mode == "regular" && digest.size <= MAX_INLINE_FILE_SIZE;
+        let operation = if inline {
+            let content = tokio::fs::read(local_path)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to read local file: {}", e)))?;
+            serde_json::json!({
+                "key": "file",
+                "value": {
+                    "path": file_path,
+                    "encoding": "base64",
+                    "content": general_purpose::STANDARD.encode(&content),
+                }
+            })
+        } else {
+            self.upload_lfs_object(
+                &dataset_id,
+                local_path,
+                &digest,
+                progress_callback.clone(),
+                cancel_rx.as_deref_mut(),
+            )
+            .await?;
+            serde_json::json!({
+                "key": "lfsFile",
+                "value": {
+                    "path": file_path,
+                    "algo": "sha256",
+                    "oid": digest.sha256,
+                    "size": digest.size,
+                }
+            })
+        };
+
+        self.create_commit(
+            &dataset_id,
+            &format!("Upload {}", file_path),
+            vec![operation],
+            cancel_rx,
+        )
+        .await?;
+
+        // 内联提交是单次请求，完成后一次性上报进度
+        if inline {
+            if let Some(callback) = progress_callback {
+                callback(digest.size, digest.size);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn create_directory(&self, _path: &str) -> Result<(), StorageError> {
+        // Git 仓库不跟踪空目录，目录会随文件上传自动出现
+        Err(StorageError::ProtocolNotSupported(
+            "HuggingFace datasets do not support empty directories".to_string(),
+        ))
+    }
+
+    /// 在同一次 commit 中写入新路径并删除旧路径
+    /// LFS 文件直接引用已有的 LFS 对象，只有普通小文件才会重新内联内容
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        let (dataset_id, from_path) = self.parse_path(from)?;
+        let (target_dataset_id, to_path) = self.parse_path(to)?;
+
+        if dataset_id != target_dataset_id {
+            return Err(StorageError::RequestFailed(
+                "Cannot move files between datasets".to_string(),
+            ));
+        }
+
+        let info = self
+            .fetch_path_info(&dataset_id, &from_path)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(from.to_string()))?;
+        if info.file_type != "file" {
+            return Err(StorageError::ProtocolNotSupported(
+                "Only files can be renamed in HuggingFace datasets".to_string(),
+            ));
+        }
+
+        let new_file = match &info.lfs {
+            Some(lfs) => serde_json::json!({
+                "key": "lfsFile",
+                "value": {
+                    "path": to_path,
+                    "algo": "sha256",
+                    "oid": lfs.oid,
+                    "size": lfs.size,
+                }
+            }),
+            None => {
+                let content = self.read_full_file(from).await?;
+                serde_json::json!({
+                    "key": "file",
+                    "value": {
+                        "path": to_path,
+                        "encoding": "base64",
+                        "content": general_purpose::STANDARD.encode(&content),
+                    }
+                })
+            }
+        };
+        let operations = vec![
+            new_file,
+            serde_json::json!({
+                "key": "deletedFile",
+                "value": { "path": from_path }
+            }),
+        ];
+
+        self.create_commit(
+            &dataset_id,
+            &format!("Rename {} to {}", from_path, to_path),
+            operations,
+            None,
+        )
+        .await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        let (dataset_id, file_path) = self.parse_path(path)?;
+        if file_path.is_empty() {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the dataset root".to_string(),
+            ));
+        }
+
+        let operation_key = match self.find_entry_type(&dataset_id, &file_path).await? {
+            Some(file_type) if file_type == "directory" => "deletedFolder",
+            Some(_) => "deletedFile",
+            None => return Err(StorageError::NotFound(path.to_string())),
+        };
+
+        let operation = serde_json::json!({
+            "key": operation_key,
+            "value": { "path": file_path }
+        });
+
+        self.create_commit(
+            &dataset_id,
+            &format!("Delete {}", file_path),
+            vec![operation],
+            None,
+        )
+        .await
+    }
 }
@@ -0,0 +1,418 @@
+use base64::engine::general_purpose;
+use base64::Engine;
+use std::cmp::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::caching_client::LruIndex;
+use super::search::parse_lastmod;
+use super::traits::{DirectoryResult, ListOptions, StorageClient, StorageError, StorageFile};
+
+// 每个连接保留的列表快照数
+const SNAPSHOT_CAPACITY: u64 = 8;
+// 列表快照的有效期，只在同一次翻页过程中复用
+const SNAPSHOT_TTL: Duration = Duration::from_secs(120);
+
+/// 排序字段
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortField {
+    Name,
+    Size,
+    Modified,
+}
+
+impl SortField {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SortField::Name => "name",
+            SortField::Size => "size",
+            SortField::Modified => "modified",
+        }
+    }
+}
+
+/// 条目的排序键，name 在目录内唯一，用作次级排序保证顺序稳定
+#[derive(Debug, Clone)]
+struct SortKey {
+    value: i64,
+    name: String,
+}
+
+/// 排序方式，同时负责分页游标的编码与解码
+struct SortOrder {
+    field: SortField,
+    descending: bool,
+    recursive: bool,
+}
+
+impl SortOrder {
+    fn new(options: &ListOptions) -> Result<Self, StorageError> {
+        let field = match options.sort_by.as_deref().unwrap_or("name") {
+            "name" => SortField::Name,
+            "size" => SortField::Size,
+            "modified" => SortField::Modified,
+            other => {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Unsupported sort field: {}",
+                    other
+                )))
+            }
+        };
+        let descending = match options.sort_order.as_deref().unwrap_or("asc") {
+            "asc" => false,
+            "desc" => true,
+            other => {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Unsupported sort order: {}",
+                    other
+                )))
+            }
+        };
+
+        Ok(Self {
+            field,
+            descending,
+            recursive: options.recursive.unwrap_or(false),
+        })
+    }
+
+    fn key(&self, file: &StorageFile) -> SortKey {
+        let value = match self.field {
+            SortField::Name => 0,
+            SortField::Size => file.size.parse::<i64>().unwrap_or(0),
+            // 无法解析修改时间的条目排在最前
+            SortField::Modified => parse_lastmod(&file.lastmod)
+                .map(|time| time.timestamp_millis())
+                .unwrap_or(i64::MIN),
+        };
+
+        SortKey {
+            value,
+            name: entry_name(file, self.recursive).to_string(),
+        }
+    }
+
+    fn compare(&self, a: &SortKey, b: &SortKey) -> Ordering {
+        let ordering = a.value.cmp(&b.value).then_with(|| a.name.cmp(&b.name));
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    fn order_str(&self) -> &'static str {
+        if self.descending {
+            "desc"
+        } else {
+            "asc"
+        }
+    }
+
+    /// 游标记录上一页最后一个条目的排序键，翻页期间目录有增删也不会重复或遗漏
+    fn encode_marker(&self, key: &SortKey) -> String {
+        let raw = format!(
+            "{}|{}|{}|{}",
+            self.field.as_str(),
+            self.order_str(),
+            key.value,
+            key.name
+        );
+        general_purpose::URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    fn decode_marker(&self, marker: &str) -> Result<SortKey, StorageError> {
+        let invalid = || StorageError::InvalidConfig(format!("Invalid listing marker: {}", marker));
+
+        let raw = general_purpose::URL_SAFE_NO_PAD
+            .decode(marker)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(invalid)?;
+        let mut parts = raw.splitn(4, '|');
+        let (Some(field), Some(order), Some(value), Some(name)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        if field != self.field.as_str() || order != self.order_str() {
+            return Err(StorageError::InvalidConfig(
+                "Listing marker does not match the current sort options".to_string(),
+            ));
+        }
+
+        Ok(SortKey {
+            value: value.parse().map_err(|_| invalid())?,
+            name: name.to_string(),
+        })
+    }
+}
+
+/// 编译后的列表过滤条件
+struct ListingFilter {
+    show_hidden: bool,
+    prefix: Option<String>,
+    pattern: Option<glob::Pattern>,
+    file_type: Option<String>,
+    recursive: bool,
+}
+
+impl ListingFilter {
+    fn new(options: &ListOptions) -> Result<Self, StorageError> {
+        if let Some(file_type) = options.file_type.as_deref() {
+            if file_type != "file" && file_type != "directory" {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Unsupported file type filter: {}",
+                    file_type
+                )));
+            }
+        }
+
+        Ok(Self {
+            show_hidden: options.show_hidden.unwrap_or(true),
+            prefix: options.prefix.clone().filter(|prefix| !prefix.is_empty()),
+            pattern: options
+                .pattern
+                .as_deref()
+                .filter(|pattern| !pattern.is_empty())
+                .map(glob::Pattern::new)
+                .transpose()
+                .map_err(|e| StorageError::InvalidConfig(format!("Invalid glob pattern: {}", e)))?,
+            file_type: options.file_type.clone(),
+            recursive: options.recursive.unwrap_or(false),
+        })
+    }
+
+    /// 原生分页的后端已在服务端处理前缀，这里只判断其余条件
+    fn is_active(&self) -> bool {
+        !self.show_hidden || self.pattern.is_some() || self.file_type.is_some()
+    }
+
+    fn matches(&self, file: &StorageFile, check_prefix: bool) -> bool {
+        if !self.show_hidden && file.basename.starts_with('.') {
+            return false;
+        }
+
+        if let Some(file_type) = &self.file_type {
+            if &file.file_type != file_type {
+                return false;
+            }
+        }
+
+        if check_prefix {
+            if let Some(prefix) = &self.prefix {
+                if !entry_name(file, self.recursive).starts_with(prefix.as_str()) {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(pattern) = &self.pattern {
+            let options = glob::MatchOptions {
+                case_sensitive: false,
+                ..Default::default()
+            };
+            if !pattern.matches_with(&file.basename, options) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// 递归列出时 filename 为相对路径，逐层列出时使用 basename
+fn entry_name(file: &StorageFile, recursive: bool) -> &str {
+    if recursive {
+        &file.filename
+    } else {
+        &file.basename
+    }
+}
+
+/// 列表快照的键：规范化路径 + 不含分页参数的列表选项
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SnapshotKey {
+    path: String,
+    options: String,
+}
+
+/// 过滤并排序后的完整列表
+struct ListingSnapshot {
+    entries: Vec<(SortKey, StorageFile)>,
+    created_at: Instant,
+}
+
+/// 统一的目录列表处理
+/// 原生分页的后端（OSS、HF 数据集列表）直接透传分页参数，只在当前页内过滤和排序；
+/// 其余后端取回完整列表后统一过滤、排序，并按排序键生成稳定的分页游标
+///
+/// 非原生分页的后端在第一页时列出整个目录并保存排序后的快照，后续页面复用快照，
+/// 翻页开销与列表缓存是否开启、目录大小是否超出列表缓存容量无关
+pub struct ListingPipeline {
+    snapshots: Mutex<LruIndex<SnapshotKey, Arc<ListingSnapshot>>>,
+}
+
+impl Default for ListingPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ListingPipeline {
+    pub fn new() -> Self {
+        Self {
+            snapshots: Mutex::new(LruIndex::new(SNAPSHOT_CAPACITY)),
+        }
+    }
+
+    /// 清除全部列表快照，下次翻页时重新列出
+    pub fn refresh(&self) {
+        self.snapshots.lock().unwrap().clear();
+    }
+
+    pub async fn list(
+        &self,
+        client: &(dyn StorageClient + Send + Sync),
+        path: &str,
+        options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        let Some(options) = options else {
+            return client.list_directory(path, None).await;
+        };
+
+        let filter = ListingFilter::new(options)?;
+        let order = SortOrder::new(options)?;
+        let recursive = options.recursive.unwrap_or(false);
+
+        if client.supports_native_paging(path, recursive) {
+            let mut result = client.list_directory(path, Some(options)).await?;
+            if filter.is_active() {
+                result.files.retain(|file| filter.matches(file, false));
+                result.total_count = None;
+            }
+            // 原生分页时只能在当前页内排序
+            if options.sort_by.is_some() {
+                result.files = Self::sort(std::mem::take(&mut result.files), &order)
+                    .into_iter()
+                    .map(|(_, file)| file)
+                    .collect();
+            }
+            return Ok(result);
+        }
+
+        let key = SnapshotKey {
+            path: client.canonical_path(path),
+            options: serde_json::to_string(&ListOptions {
+                page_size: None,
+                marker: None,
+                ..options.clone()
+            })
+            .unwrap_or_default(),
+        };
+        let marker = options
+            .marker
+            .as_deref()
+            .filter(|marker| !marker.is_empty());
+
+        // 第一页总是重新列出，后续页面复用同一次翻页过程中的快照
+        let snapshot = match marker.and_then(|_| self.snapshot(&key)) {
+            Some(snapshot) => snapshot,
+            None => {
+                let snapshot = Arc::new(ListingSnapshot {
+                    entries: Self::list_all(client, path, options, &filter, &order).await?,
+                    created_at: Instant::now(),
+                });
+                if options.page_size.is_some() {
+                    self.snapshots
+                        .lock()
+                        .unwrap()
+                        .insert(key, snapshot.clone(), 1);
+                }
+                snapshot
+            }
+        };
+        let entries = &snapshot.entries;
+
+        let total = entries.len();
+        let start = match marker {
+            Some(marker) => {
+                let after = order.decode_marker(marker)?;
+                entries.partition_point(|(key, _)| order.compare(key, &after) != Ordering::Greater)
+            }
+            None => 0,
+        };
+        let end = options
+            .page_size
+            .map(|page_size| start.saturating_add(page_size.max(1) as usize).min(total))
+            .unwrap_or(total);
+
+        let has_more = end < total;
+        let next_marker = if has_more {
+            Some(order.encode_marker(&entries[end - 1].0))
+        } else {
+            None
+        };
+
+        Ok(DirectoryResult {
+            files: entries[start..end]
+                .iter()
+                .map(|(_, file)| file.clone())
+                .collect(),
+            has_more,
+            next_marker,
+            total_count: Some(total.to_string()),
+            path: path.to_string(),
+        })
+    }
+
+    /// 返回未过期的列表快照
+    fn snapshot(&self, key: &SnapshotKey) -> Option<Arc<ListingSnapshot>> {
+        let mut snapshots = self.snapshots.lock().unwrap();
+        let snapshot = snapshots.get(key)?;
+        if snapshot.created_at.elapsed() < SNAPSHOT_TTL {
+            return Some(snapshot);
+        }
+        snapshots.remove(key);
+        None
+    }
+
+    /// 取回完整列表并过滤、排序，个别后端即使未分页也可能返回 has_more
+    async fn list_all(
+        client: &(dyn StorageClient + Send + Sync),
+        path: &str,
+        options: &ListOptions,
+        filter: &ListingFilter,
+        order: &SortOrder,
+    ) -> Result<Vec<(SortKey, StorageFile)>, StorageError> {
+        let mut backend_options = ListOptions {
+            recursive: options.recursive,
+            ..Default::default()
+        };
+        let mut files = Vec::new();
+        loop {
+            let result = client.list_directory(path, Some(&backend_options)).await?;
+            files.extend(result.files);
+
+            if result.has_more && result.next_marker.is_some() {
+                backend_options.marker = result.next_marker;
+            } else {
+                break;
+            }
+        }
+
+        files.retain(|file| filter.matches(file, true));
+        Ok(Self::sort(files, order))
+    }
+
+    /// 计算排序键并排序
+    fn sort(files: Vec<StorageFile>, order: &SortOrder) -> Vec<(SortKey, StorageFile)> {
+        let mut entries: Vec<(SortKey, StorageFile)> = files
+            .into_iter()
+            .map(|file| (order.key(&file), file))
+            .collect();
+        entries.sort_by(|a, b| order.compare(&a.0, &b.0));
+        entries
+    }
+}
@@ -0,0 +1,291 @@
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::caching_client::LruIndex;
+use super::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, DirectoryUsage, FileMetadata, ListOptions,
+    ListingCacheConfig, ObjectVersion, ProgressCallback, StorageClient, StorageError,
+};
+
+const DEFAULT_TTL_SECS: u32 = 30;
+const DEFAULT_MAX_ENTRIES: u32 = 200_000;
+
+/// 列表缓存的键：路径 + 序列化后的列表选项
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ListingKey {
+    path: String,
+    options: String,
+}
+
+/// 缓存的目录列表
+struct CachedListing {
+    result: DirectoryResult,
+    cached_at: Instant,
+}
+
+/// 目录列表缓存客户端
+/// 按路径和列表选项缓存 list_directory 的结果，超过有效期后重新列出，
+/// 经由本客户端的写操作会使受影响路径的缓存失效
+pub struct ListingCacheClient {
+    inner: Arc<dyn StorageClient + Send + Sync>,
+    ttl: Duration,
+    // 以缓存的条目数为容量，避免大目录占用过多内存
+    index: Mutex<LruIndex<ListingKey, Arc<CachedListing>>>,
+}
+
+impl ListingCacheClient {
+    /// 按配置创建缓存客户端，有效期为 0 时返回 None
+    pub fn new(
+        inner: Arc<dyn StorageClient + Send + Sync>,
+        config: Option<&ListingCacheConfig>,
+    ) -> Option<Self> {
+        let ttl_secs = config.and_then(|c| c.ttl_secs).unwrap_or(DEFAULT_TTL_SECS);
+        if ttl_secs == 0 {
+            return None;
+        }
+        let max_entries = config
+            .and_then(|c| c.max_entries)
+            .unwrap_or(DEFAULT_MAX_ENTRIES);
+
+        Some(Self {
+            inner,
+            ttl: Duration::from_secs(ttl_secs as u64),
+            index: Mutex::new(LruIndex::new(max_entries as u64)),
+        })
+    }
+
+    /// 使路径及其子路径的缓存失效，path 为空时清空全部缓存，返回失效的缓存数
+    pub fn refresh(&self, path: &str) -> usize {
+        if path.trim_matches('/').is_empty() {
+            return self.index.lock().unwrap().clear().len();
+        }
+        let path = self.normalize(path);
+        self.index
+            .lock()
+            .unwrap()
+            .remove_where(|key| Self::is_within(&key.path, &path))
+    }
+
+    /// 写操作后使受影响的缓存失效：路径本身、其子路径以及所有祖先目录的列表
+    fn invalidate(&self, path: &str) {
+        let path = self.normalize(path);
+        self.index.lock().unwrap().remove_where(|key| {
+            Self::is_within(&key.path, &path) || Self::is_within(&path, &key.path)
+        });
+    }
+
+    /// 经由内部客户端的路径解析得到缓存键，协议 URL 和相对路径指向同一位置时键相同
+    fn normalize(&self, path: &str) -> String {
+        self.inner
+            .canonical_path(path)
+            .trim_matches('/')
+            .to_string()
+    }
+
+    /// 判断 path 是否为 parent 本身或位于 parent 之下
+    fn is_within(path: &str, parent: &str) -> bool {
+        parent.is_empty()
+            || path == parent
+            || path
+                .strip_prefix(parent)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+#[async_trait]
+impl StorageClient for ListingCacheClient {
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        // 内部客户端通常在包装前已完成连接，仅在独占时转发
+        match Arc::get_mut(&mut self.inner) {
+            Some(inner) => inner.connect(config).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        let key = ListingKey {
+            path: self.normalize(path),
+            options: options
+                .and_then(|options| serde_json::to_string(options).ok())
+                .unwrap_or_default(),
+        };
+
+        {
+            let mut index = self.index.lock().unwrap();
+            if let Some(cached) = index.get(&key) {
+                if cached.cached_at.elapsed() < self.ttl {
+                    return Ok(cached.result.clone());
+                }
+                index.remove(&key);
+            }
+        }
+
+        let result = self.inner.list_directory(path, options).await?;
+        let size = result.files.len() as u64 + 1;
+        self.index.lock().unwrap().insert(
+            key,
+            Arc::new(CachedListing {
+                result: result.clone(),
+                cached_at: Instant::now(),
+            }),
+            size,
+        );
+        Ok(result)
+    }
+
+    fn supports_recursive_listing(&self, path: &str) -> bool {
+        self.inner.supports_recursive_listing(path)
+    }
+
+    fn supports_native_paging(&self, path: &str, recursive: bool) -> bool {
+        self.inner.supports_native_paging(path, recursive)
+    }
+
+    fn canonical_path(&self, path: &str) -> String {
+        self.inner.canonical_path(path)
+    }
+
+    async fn directory_usage(
+        &self,
+        path: &str,
+        max_depth: u32,
+    ) -> Result<Option<Vec<DirectoryUsage>>, StorageError> {
+        self.inner.directory_usage(path, max_depth).await
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.inner.read_file_range(path, start, length).await
+    }
+
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.inner
+            .read_file_range_with_progress(path, start, length, progress_callback, cancel_rx)
+            .await
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        self.inner.read_full_file(path).await
+    }
+
+    async fn read_full_file_with_progress(
+        &self,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.inner
+            .read_full_file_with_progress(path, progress_callback, cancel_rx)
+            .await
+    }
+
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        self.inner.open_read_stream(path, start).await
+    }
+
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        self.inner.stat(path).await
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        self.inner.get_file_size(path).await
+    }
+
+    fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
+        self.inner.get_download_url(path)
+    }
+
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        self.inner
+            .download_file(path, save_path, progress_callback, cancel_rx)
+            .await
+    }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let result = self
+            .inner
+            .put_file(local_path, path, progress_callback, cancel_rx)
+            .await;
+        self.invalidate(path);
+        result
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let result = self
+            .inner
+            .put_stream(stream, size, path, progress_callback, cancel_rx)
+            .await;
+        self.invalidate(path);
+        result
+    }
+
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        let result = self.inner.create_directory(path).await;
+        self.invalidate(path);
+        result
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        let result = self.inner.rename(from, to).await;
+        self.invalidate(from);
+        self.invalidate(to);
+        result
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        let result = self.inner.delete(path).await;
+        self.invalidate(path);
+        result
+    }
+
+    async fn list_versions(&self, path: &str) -> Result<Vec<ObjectVersion>, StorageError> {
+        self.inner.list_versions(path).await
+    }
+
+    fn protocol(&self) -> &str {
+        self.inner.protocol()
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        self.inner.validate_config(config)
+    }
+}
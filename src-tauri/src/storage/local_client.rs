@@ -1,15 +1,18 @@
 use async_trait::async_trait;
+use futures_util::StreamExt;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::fs;
 use tokio::io::AsyncReadExt;
 
 use super::traits::{
-    ConnectionConfig, DirectoryResult, ListOptions, ProgressCallback, StorageClient, StorageError,
-    StorageFile,
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile,
 };
 use crate::utils::chunk_size;
 use crate::utils::path_utils::PathUtils;
+use crate::utils::stream_io::StreamIo;
 
 /// 本机文件系统存储客户端
 pub struct LocalFileSystemClient {
@@ -69,6 +72,52 @@ impl LocalFileSystemClient {
         Ok(full_path)
     }
 
+    /// 解析写操作的目标路径，只允许连接根目录之下的路径
+    /// 已存在的祖先目录解析为真实路径后比较，避免通过绝对路径、~、.. 或符号链接写到根目录之外；
+    /// 最后一级不解析符号链接，删除或重命名链接时作用于链接本身
+    fn build_writable_path(&self, path: &str, allow_root: bool) -> Result<PathBuf, StorageError> {
+        let root = self.root_path.as_ref().ok_or(StorageError::NotConnected)?;
+        let root = std::fs::canonicalize(root)
+            .map_err(|e| StorageError::IoError(format!("Failed to resolve root path: {}", e)))?;
+        let outside_root = || {
+            StorageError::PermissionDenied(format!("Path is outside the connection root: {}", path))
+        };
+
+        let full_path = self.build_safe_path(path)?;
+        let resolved = match (full_path.parent(), full_path.file_name()) {
+            (Some(parent), Some(file_name)) => {
+                // 向上找到已存在的祖先目录，其余部分不能包含 ..
+                let mut existing = parent;
+                let mut missing = Vec::new();
+                while !existing.exists() {
+                    missing.push(existing.file_name().ok_or_else(outside_root)?);
+                    existing = existing.parent().ok_or_else(outside_root)?;
+                }
+                let mut resolved = std::fs::canonicalize(existing)
+                    .map_err(|e| StorageError::IoError(format!("Failed to resolve path: {}", e)))?;
+                resolved.extend(missing.into_iter().rev());
+                resolved.push(file_name);
+                resolved
+            }
+            // 以 .. 结尾或文件系统根目录，直接解析
+            _ => std::fs::canonicalize(&full_path).map_err(|_| outside_root())?,
+        };
+
+        if resolved == root {
+            return if allow_root {
+                Ok(resolved)
+            } else {
+                Err(StorageError::PermissionDenied(
+                    "Cannot modify the root directory".to_string(),
+                ))
+            };
+        }
+        if !resolved.starts_with(&root) {
+            return Err(outside_root());
+        }
+        Ok(resolved)
+    }
+
     /// 获取文件的 MIME 类型
     fn get_mime_type(path: &Path) -> Option<String> {
         path.extension()
@@ -294,9 +343,7 @@ impl StorageClient for LocalFileSystemClient {
             // 检查取消信号
             if let Some(ref mut cancel_rx) = cancel_rx {
                 if cancel_rx.try_recv().is_ok() {
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+                    return Err(StorageError::Cancelled);
                 }
             }
 
@@ -349,6 +396,121 @@ impl StorageClient for LocalFileSystemClient {
             .map_err(|e| StorageError::IoError(format!("Failed to read file: {}", e)))
     }
 
+    /// 流式读取文件，从指定偏移量开始按块产出
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let file_path = self.build_safe_path(path)?;
+
+        if !file_path.exists() {
+            return Err(StorageError::RequestFailed("File not found".to_string()));
+        }
+
+        let mut file = fs::File::open(&file_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to open file: {}", e)))?;
+
+        let file_size = file
+            .metadata()
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to get file metadata: {}", e)))?
+            .len();
+
+        use tokio::io::AsyncSeekExt;
+
+        file.seek(std::io::SeekFrom::Start(start))
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to seek in file: {}", e)))?;
+
+        let chunk_size = chunk_size::calculate_local_read_chunk_size(file_size);
+        let stream = tokio_util::io::ReaderStream::with_capacity(file, chunk_size).map(|chunk| {
+            chunk.map_err(|e| StorageError::IoError(format!("Failed to read file: {}", e)))
+        });
+
+        Ok(Box::pin(stream))
+    }
+
+    /// 获取文件元数据，Unix 平台上包含权限位和 uid/gid
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let file_path = self.build_safe_path(path)?;
+
+        let metadata = fs::metadata(&file_path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                StorageError::NotFound(path.to_string())
+            } else {
+                StorageError::IoError(format!("Failed to get file metadata: {}", e))
+            }
+        })?;
+
+        let is_directory = metadata.is_dir();
+        let basename = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let mut extra = HashMap::new();
+        if let Some(created) = metadata
+            .created()
+            .ok()
+            .map(chrono::DateTime::<chrono::Utc>::from)
+        {
+            extra.insert("created".to_string(), created.to_rfc3339());
+        }
+
+        #[cfg(unix)]
+        let (permissions, owner, group) = {
+            use std::os::unix::fs::MetadataExt;
+            (
+                Some(format!("{:o}", metadata.mode() & 0o7777)),
+                Some(metadata.uid().to_string()),
+                Some(metadata.gid().to_string()),
+            )
+        };
+        #[cfg(not(unix))]
+        let (permissions, owner, group) = (
+            Some(
+                if metadata.permissions().readonly() {
+                    "readonly"
+                } else {
+                    "readwrite"
+                }
+                .to_string(),
+            ),
+            None,
+            None,
+        );
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename,
+            lastmod: Self::format_modification_time(&metadata),
+            size: if is_directory {
+                "0".to_string()
+            } else {
+                metadata.len().to_string()
+            },
+            file_type: if is_directory { "directory" } else { "file" }.to_string(),
+            mime: if is_directory {
+                None
+            } else {
+                Self::get_mime_type(&file_path)
+            },
+            etag: None,
+            checksum: None,
+            permissions,
+            owner,
+            group,
+            extra,
+        })
+    }
+
     /// 获取文件大小
     async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
         if !self.connected.load(Ordering::Relaxed) {
@@ -374,6 +536,12 @@ impl StorageClient for LocalFileSystemClient {
         Ok(metadata.len())
     }
 
+    fn canonical_path(&self, path: &str) -> String {
+        self.build_safe_path(path)
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.to_string())
+    }
+
     fn protocol(&self) -> &str {
         "local"
     }
@@ -463,9 +631,7 @@ impl StorageClient for LocalFileSystemClient {
             if let Some(ref mut cancel_rx) = cancel_rx {
                 if cancel_rx.try_recv().is_ok() {
                     let _ = fs::remove_file(save_path).await;
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+                    return Err(StorageError::Cancelled);
                 }
             }
 
@@ -498,4 +664,176 @@ impl StorageClient for LocalFileSystemClient {
 
         Ok(())
     }
+
+    /// 本地文件上传实现，使用流式复制
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let dest_path = self.build_writable_path(path, false)?;
+
+        let file_size = fs::metadata(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to get file metadata: {}", e)))?
+            .len();
+
+        // 确保目标目录存在
+        if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                StorageError::IoError(format!("Failed to create parent directory: {}", e))
+            })?;
+        }
+
+        let mut source_file = fs::File::open(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to open source file: {}", e)))?;
+
+        let mut dest_file = fs::File::create(&dest_path).await.map_err(|e| {
+            StorageError::IoError(format!("Failed to create destination file: {}", e))
+        })?;
+
+        let chunk_size = chunk_size::calculate_optimal_chunk_size(file_size);
+        let mut buffer = vec![0u8; chunk_size];
+        let mut copied = 0u64;
+
+        loop {
+            // 检查取消信号
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    drop(dest_file);
+                    let _ = fs::remove_file(&dest_path).await;
+                    return Err(StorageError::Cancelled);
+                }
+            }
+
+            let bytes_read = source_file.read(&mut buffer).await.map_err(|e| {
+                StorageError::IoError(format!("Failed to read from source file: {}", e))
+            })?;
+
+            if bytes_read == 0 {
+                break; // EOF
+            }
+
+            dest_file
+                .write_all(&buffer[..bytes_read])
+                .await
+                .map_err(|e| {
+                    StorageError::IoError(format!("Failed to write to destination file: {}", e))
+                })?;
+
+            copied += bytes_read as u64;
+
+            // 调用进度回调
+            if let Some(ref callback) = progress_callback {
+                callback(copied, file_size);
+            }
+        }
+
+        dest_file.flush().await.map_err(|e| {
+            StorageError::IoError(format!("Failed to flush destination file: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// 本地文件系统直接将数据流写入目标文件
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let dest_path = self.build_writable_path(path, false)?;
+
+        // 确保目标目录存在
+        if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                StorageError::IoError(format!("Failed to create parent directory: {}", e))
+            })?;
+        }
+
+        StreamIo::save_to_file(stream, &dest_path, size, progress_callback, cancel_rx).await
+    }
+
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let dir_path = self.build_writable_path(path, true)?;
+
+        fs::create_dir_all(&dir_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to create directory: {}", e)))
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let source_path = self.build_writable_path(from, false)?;
+        let dest_path = self.build_writable_path(to, false)?;
+
+        if !source_path.exists() {
+            return Err(StorageError::NotFound(format!(
+                "Source path does not exist: {}",
+                source_path.display()
+            )));
+        }
+
+        // 不覆盖已存在的目标
+        if dest_path.exists() {
+            return Err(StorageError::Conflict(format!(
+                "Destination already exists: {}",
+                dest_path.display()
+            )));
+        }
+
+        fs::rename(&source_path, &dest_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to rename: {}", e)))
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        // 禁止删除连接的根目录及根目录之外的路径
+        let target_path = self.build_writable_path(path, false)?;
+
+        let metadata = fs::symlink_metadata(&target_path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                StorageError::NotFound(format!("Path does not exist: {}", target_path.display()))
+            } else {
+                StorageError::IoError(format!("Failed to get metadata: {}", e))
+            }
+        })?;
+
+        if metadata.is_dir() {
+            fs::remove_dir_all(&target_path)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to delete directory: {}", e)))
+        } else {
+            fs::remove_file(&target_path)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to delete file: {}", e)))
+        }
+    }
 }
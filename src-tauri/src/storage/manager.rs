@@ -1,19 +1,60 @@
+use super::archive_client::ArchiveStorageClient;
+use super::azure_blob_client::AzureBlobClient;
+use super::caching_client::{CacheStats, CachingStorageClient};
+use super::ftp_client::FtpClient;
+use super::gcs_client::GcsClient;
+use super::http_index_client::HttpIndexClient;
 use super::huggingface_client::HuggingFaceClient;
+use super::listing::ListingPipeline;
+use super::listing_cache::ListingCacheClient;
 use super::local_client::LocalFileSystemClient;
+use super::oci_registry_client::OciRegistryClient;
 use super::oss_client::OSSClient;
+use super::retrying_client::RetryingStorageClient;
 use super::smb_client::SMBClient;
 use super::ssh_client::SSHClient;
 use super::traits::{ConnectionConfig, DirectoryResult, ListOptions, StorageClient, StorageError};
 use super::webdav_client::WebDAVClient;
+use super::webhdfs_client::WebHdfsClient;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{RwLock, Semaphore};
 
+/// 已建立的连接信息
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionInfo {
+    pub id: String,
+    pub protocol: String,
+    pub url: Option<String>,
+    pub read_only: bool,
+    pub block_cache: bool,
+    pub connected_at: String,
+    /// 绑定到该连接的窗口
+    pub windows: Vec<String>,
+}
+
+/// 已建立的连接
+pub struct StorageConnection {
+    client: Arc<dyn StorageClient + Send + Sync>,
+    protocol: String,
+    url: Option<String>,
+    // 只读连接禁止上传、创建、重命名和删除等写操作
+    read_only: bool,
+    // 连接启用的块缓存
+    block_cache: Option<Arc<CachingStorageClient>>,
+    // 连接启用的目录列表缓存
+    listing_cache: Option<Arc<ListingCacheClient>>,
+    // 排序、过滤和分页处理，保存翻页过程中的列表快照
+    listing: ListingPipeline,
+    connected_at: String,
+}
+
 pub struct StorageManager {
-    clients: HashMap<String, Arc<dyn StorageClient + Send + Sync>>,
-    active_client: Option<String>,
-    // 缓存的活跃客户端引用，减少HashMap查找
-    cached_client: Option<Arc<dyn StorageClient + Send + Sync>>,
+    connections: HashMap<String, StorageConnection>,
+    // 窗口与连接的绑定关系（窗口 label -> 连接 ID），未显式指定连接的命令使用窗口绑定的连接
+    window_bindings: HashMap<String, String>,
     // 并发控制：限制同时进行的请求数量
     request_semaphore: Arc<Semaphore>,
 }
@@ -21,14 +62,17 @@ pub struct StorageManager {
 impl StorageManager {
     pub fn new() -> Self {
         Self {
-            clients: HashMap::new(),
-            active_client: None,
-            cached_client: None,
+            connections: HashMap::new(),
+            window_bindings: HashMap::new(),
             request_semaphore: Arc::new(Semaphore::new(10)), // 限制最多10个并发请求
         }
     }
 
-    pub async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+    /// 根据配置创建并连接存储客户端
+    /// 不需要持有管理器的锁，避免慢速连接阻塞其他连接上的操作
+    pub async fn create_connection(
+        config: &ConnectionConfig,
+    ) -> Result<StorageConnection, StorageError> {
         let client: Arc<dyn StorageClient + Send + Sync> = match config.protocol.as_str() {
             "webdav" => {
                 let mut client = WebDAVClient::new(config.clone())?;
@@ -60,39 +104,193 @@ impl StorageManager {
                 client.connect(config).await?;
                 Arc::new(client)
             }
+            "gcs" => {
+                let mut client = GcsClient::new(config.clone())?;
+                client.connect(config).await?;
+                Arc::new(client)
+            }
+            "azblob" => {
+                let mut client = AzureBlobClient::new(config.clone())?;
+                client.connect(config).await?;
+                Arc::new(client)
+            }
+            "ftp" | "ftps" => {
+                let mut client = FtpClient::new(config.clone())?;
+                client.connect(config).await?;
+                Arc::new(client)
+            }
+            "http" => {
+                let mut client = HttpIndexClient::new(config.clone())?;
+                client.connect(config).await?;
+                Arc::new(client)
+            }
+            "webhdfs" => {
+                let mut client = WebHdfsClient::new(config.clone())?;
+                client.connect(config).await?;
+                Arc::new(client)
+            }
+            "oci" => {
+                let mut client = OciRegistryClient::new(config.clone())?;
+                client.connect(config).await?;
+                Arc::new(client)
+            }
             _ => return Err(StorageError::UnsupportedProtocol(config.protocol.clone())),
         };
 
-        let client_id = format!("{}_{}", config.protocol, chrono::Utc::now().timestamp());
+        // 所有连接都经过重试层，块缓存位于重试层之上，缓存未命中时的读取同样会重试
+        let client: Arc<dyn StorageClient + Send + Sync> =
+            Arc::new(RetryingStorageClient::new(client, config.retry.as_ref()));
 
-        self.clients.insert(client_id.clone(), client.clone());
-        self.active_client = Some(client_id);
+        // 目录列表缓存位于重试层之上，经由上层的写操作同样会使列表缓存失效
+        let listing_cache =
+            ListingCacheClient::new(client.clone(), config.listing_cache.as_ref()).map(Arc::new);
+        let client: Arc<dyn StorageClient + Send + Sync> = match &listing_cache {
+            Some(cache_client) => cache_client.clone(),
+            None => client,
+        };
 
-        // 更新缓存的客户端引用
-        self.cached_client = Some(client.clone());
+        // 按连接配置决定是否包装块缓存
+        let block_cache = config.block_cache.as_ref().map(|cache_config| {
+            Arc::new(CachingStorageClient::new(
+                client.clone(),
+                config,
+                cache_config,
+            ))
+        });
+        let client: Arc<dyn StorageClient + Send + Sync> = match &block_cache {
+            Some(caching_client) => caching_client.clone(),
+            None => client,
+        };
+
+        Ok(StorageConnection {
+            client,
+            protocol: config.protocol.clone(),
+            url: config.url.clone(),
+            read_only: config.read_only.unwrap_or(false),
+            block_cache,
+            listing_cache,
+            listing: ListingPipeline::new(),
+            connected_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// 把上层连接中的压缩包打开为只读连接
+    /// 分析压缩包可能需要读取整个文件，同样不持有管理器的锁
+    pub async fn open_archive(
+        parent: Arc<dyn StorageClient + Send + Sync>,
+        archive_path: &str,
+    ) -> Result<StorageConnection, StorageError> {
+        let client = ArchiveStorageClient::open(parent, archive_path).await?;
+
+        Ok(StorageConnection {
+            client: Arc::new(client),
+            protocol: "archive".to_string(),
+            url: Some(archive_path.to_string()),
+            read_only: true,
+            block_cache: None,
+            listing_cache: None,
+            listing: ListingPipeline::new(),
+            connected_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// 登记已建立的连接，返回连接 ID
+    pub fn add_connection(&mut self, connection: StorageConnection) -> String {
+        let connection_id = format!("{}_{}", connection.protocol, uuid::Uuid::new_v4().simple());
+        self.connections.insert(connection_id.clone(), connection);
+        connection_id
+    }
+
+    pub async fn disconnect(&mut self, connection_id: &str) -> Result<(), StorageError> {
+        // 注意：由于 StorageClient trait 的 disconnect 方法需要 &mut self，
+        // 而我们现在使用 Arc<dyn StorageClient> 无法获得可变引用，
+        // 所以我们依赖 Drop trait 来进行资源清理。
+        // 这是合理的，因为大多数网络连接会在 Drop 时自动清理。
+        self.connections
+            .remove(connection_id)
+            .ok_or(StorageError::NotConnected)?;
+
+        // 解除绑定到该连接的窗口
+        self.window_bindings
+            .retain(|_, bound| bound != connection_id);
 
         Ok(())
     }
 
-    pub async fn disconnect(&mut self) -> Result<(), StorageError> {
-        if let Some(client_id) = &self.active_client {
-            if let Some(_client) = self.clients.remove(client_id) {
-                // 注意：由于 StorageClient trait 的 disconnect 方法需要 &mut self，
-                // 而我们现在使用 Arc<dyn StorageClient> 无法获得可变引用，
-                // 所以我们依赖 Drop trait 来进行资源清理。
-                // 这是合理的，因为大多数网络连接会在 Drop 时自动清理。
-            }
-        }
-        self.active_client = None;
+    /// 列出所有已建立的连接
+    pub fn list_connections(&self) -> Vec<ConnectionInfo> {
+        let mut connections: Vec<ConnectionInfo> = self
+            .connections
+            .iter()
+            .map(|(id, connection)| {
+                let mut windows: Vec<String> = self
+                    .window_bindings
+                    .iter()
+                    .filter(|(_, bound)| *bound == id)
+                    .map(|(window, _)| window.clone())
+                    .collect();
+                windows.sort();
 
-        // 清空缓存的客户端引用
-        self.cached_client = None;
+                ConnectionInfo {
+                    id: id.clone(),
+                    protocol: connection.protocol.clone(),
+                    url: connection.url.clone(),
+                    read_only: connection.read_only,
+                    block_cache: connection.block_cache.is_some(),
+                    connected_at: connection.connected_at.clone(),
+                    windows,
+                }
+            })
+            .collect();
+        connections.sort_by(|a, b| a.connected_at.cmp(&b.connected_at));
+        connections
+    }
 
+    /// 将窗口绑定到指定连接
+    pub fn bind_window(&mut self, window: &str, connection_id: &str) -> Result<(), StorageError> {
+        if !self.connections.contains_key(connection_id) {
+            return Err(StorageError::NotConnected);
+        }
+        self.window_bindings
+            .insert(window.to_string(), connection_id.to_string());
         Ok(())
     }
 
+    /// 解除窗口的连接绑定
+    pub fn unbind_window(&mut self, window: &str) {
+        self.window_bindings.remove(window);
+    }
+
+    /// 确定命令使用的连接：优先使用显式指定的连接 ID，否则使用窗口绑定的连接
+    pub fn resolve_connection_id(
+        &self,
+        connection_id: Option<&str>,
+        window: &str,
+    ) -> Result<String, StorageError> {
+        let connection_id = match connection_id {
+            Some(connection_id) => connection_id,
+            None => self
+                .window_bindings
+                .get(window)
+                .ok_or(StorageError::NotConnected)?,
+        };
+
+        if !self.connections.contains_key(connection_id) {
+            return Err(StorageError::NotConnected);
+        }
+
+        Ok(connection_id.to_string())
+    }
+
+    fn get_connection(&self, connection_id: &str) -> Result<&StorageConnection, StorageError> {
+        self.connections
+            .get(connection_id)
+            .ok_or(StorageError::NotConnected)
+    }
+
     pub async fn list_directory(
         &self,
+        connection_id: &str,
         path: &str,
         options: Option<&ListOptions>,
     ) -> Result<DirectoryResult, StorageError> {
@@ -101,28 +299,109 @@ impl StorageManager {
             StorageError::ConnectionFailed("Request semaphore acquisition failed".to_string())
         })?;
 
-        // 快速获取缓存的客户端引用
-        let client = if let Some(ref client) = self.cached_client {
-            client.clone()
-        } else {
-            return Err(StorageError::NotConnected);
-        };
+        let connection = self.get_connection(connection_id)?;
 
-        // 直接执行请求，client 本身就是线程安全的
-        client.list_directory(path, options).await
+        // 统一处理排序、过滤和分页，client 本身就是线程安全的
+        connection
+            .listing
+            .list(connection.client.as_ref(), path, options)
+            .await
     }
 
-    pub fn get_current_client(&self) -> Option<Arc<dyn StorageClient + Send + Sync>> {
-        self.cached_client.clone()
+    pub fn get_client(
+        &self,
+        connection_id: &str,
+    ) -> Result<Arc<dyn StorageClient + Send + Sync>, StorageError> {
+        Ok(self.get_connection(connection_id)?.client.clone())
+    }
+
+    /// 获取允许写操作的客户端，只读连接会返回错误
+    pub fn get_writable_client(
+        &self,
+        connection_id: &str,
+    ) -> Result<Arc<dyn StorageClient + Send + Sync>, StorageError> {
+        let connection = self.get_connection(connection_id)?;
+
+        if connection.read_only {
+            return Err(StorageError::PermissionDenied("storage.read.only".to_string()));
+        }
+
+        Ok(connection.client.clone())
     }
 
-    pub async fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
-        let client = self
-            .cached_client
+    pub async fn create_directory(
+        &self,
+        connection_id: &str,
+        path: &str,
+    ) -> Result<(), StorageError> {
+        let _permit = self.request_semaphore.acquire().await.map_err(|_| {
+            StorageError::ConnectionFailed("Request semaphore acquisition failed".to_string())
+        })?;
+
+        self.get_writable_client(connection_id)?
+            .create_directory(path)
+            .await
+    }
+
+    pub async fn rename(
+        &self,
+        connection_id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<(), StorageError> {
+        let _permit = self.request_semaphore.acquire().await.map_err(|_| {
+            StorageError::ConnectionFailed("Request semaphore acquisition failed".to_string())
+        })?;
+
+        self.get_writable_client(connection_id)?
+            .rename(from, to)
+            .await
+    }
+
+    pub async fn delete(&self, connection_id: &str, path: &str) -> Result<(), StorageError> {
+        let _permit = self.request_semaphore.acquire().await.map_err(|_| {
+            StorageError::ConnectionFailed("Request semaphore acquisition failed".to_string())
+        })?;
+
+        self.get_writable_client(connection_id)?.delete(path).await
+    }
+
+    /// 获取连接的块缓存统计信息，未启用缓存时返回 None
+    pub fn cache_stats(&self, connection_id: &str) -> Result<Option<CacheStats>, StorageError> {
+        Ok(self
+            .get_connection(connection_id)?
+            .block_cache
             .as_ref()
-            .ok_or(StorageError::NotConnected)?;
+            .map(|cache| cache.stats()))
+    }
+
+    /// 清空连接的块缓存
+    pub async fn clear_cache(&self, connection_id: &str) -> Result<(), StorageError> {
+        if let Some(cache) = &self.get_connection(connection_id)?.block_cache {
+            cache.clear().await;
+        }
+        Ok(())
+    }
+
+    /// 使连接中指定路径及其子路径的列表缓存失效，返回失效的缓存数
+    pub fn refresh_listing(&self, connection_id: &str, path: &str) -> Result<usize, StorageError> {
+        let connection = self.get_connection(connection_id)?;
+        connection.listing.refresh();
+        Ok(connection
+            .listing_cache
+            .as_ref()
+            .map(|cache| cache.refresh(path))
+            .unwrap_or(0))
+    }
 
-        client.get_download_url(path)
+    pub async fn get_download_url(
+        &self,
+        connection_id: &str,
+        path: &str,
+    ) -> Result<String, StorageError> {
+        self.get_connection(connection_id)?
+            .client
+            .get_download_url(path)
     }
 }
 
@@ -140,3 +419,25 @@ pub async fn get_storage_manager() -> Arc<RwLock<StorageManager>> {
         .clone();
     result
 }
+
+/// 获取命令使用的存储客户端：优先使用显式指定的连接 ID，否则使用窗口绑定的连接
+pub async fn resolve_client(
+    connection_id: Option<&str>,
+    window: &str,
+) -> Result<Arc<dyn StorageClient + Send + Sync>, StorageError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+    let connection_id = manager.resolve_connection_id(connection_id, window)?;
+    manager.get_client(&connection_id)
+}
+
+/// 获取命令使用的可写存储客户端，只读连接会返回错误
+pub async fn resolve_writable_client(
+    connection_id: Option<&str>,
+    window: &str,
+) -> Result<Arc<dyn StorageClient + Send + Sync>, StorageError> {
+    let manager_arc = get_storage_manager().await;
+    let manager = manager_arc.read().await;
+    let connection_id = manager.resolve_connection_id(connection_id, window)?;
+    manager.get_writable_client(&connection_id)
+}
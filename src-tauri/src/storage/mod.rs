@@ -1,13 +1,33 @@
+pub mod archive_client;
+pub mod azure_blob_client;
+pub mod caching_client;
+pub mod config_import;
+pub mod disk_usage;
+pub mod ftp;
+pub mod ftp_client;
+pub mod gcs_client;
+pub mod http_index;
+pub mod http_index_client;
 pub mod huggingface_client;
+pub mod listing;
+pub mod listing_cache;
 pub mod local_client;
 pub mod manager;
+pub mod oci_registry_client;
 pub mod oss;
 pub mod oss_client;
+pub mod profile_store;
+pub mod retrying_client;
+pub mod search;
 pub mod smb_client;
 pub mod ssh_client;
 pub mod traits;
 pub mod webdav_client;
+pub mod webhdfs_client;
 
 pub use manager::get_storage_manager;
 #[allow(unused_imports)] // 这些类型通过Serde序列化在Tauri命令中使用
-pub use traits::{ConnectionConfig, DirectoryResult, ListOptions, StorageFile, StorageRequest};
+pub use traits::{
+    ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ObjectVersion, StorageFile,
+    StorageRequest,
+};
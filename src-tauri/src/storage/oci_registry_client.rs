@@ -0,0 +1,959 @@
+use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
+use futures_util::StreamExt;
+use regex::Regex;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::storage::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile,
+};
+use crate::utils::http_downloader::{HttpDownloadConfig, HttpDownloader};
+use crate::utils::stream_io::StreamIo;
+
+// 请求清单时接受的媒体类型，覆盖 OCI 和 Docker 的单平台清单与多平台索引
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.index.v1+json, \
+application/vnd.oci.image.manifest.v1+json, \
+application/vnd.docker.distribution.manifest.list.v2+json, \
+application/vnd.docker.distribution.manifest.v2+json";
+
+// 按标签解析的清单缓存时间，按摘要解析的清单内容不可变，不会过期
+const TAG_MANIFEST_TTL: Duration = Duration::from_secs(300);
+
+// ORAS 等工具在层注解中记录的原始文件名
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+// WWW-Authenticate 中的 key="value" 参数
+static CHALLENGE_PARAM_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(\w+)="([^"]*)""#).unwrap());
+
+/// 清单中引用的内容描述符
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    #[serde(default)]
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+    platform: Option<Platform>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Platform {
+    #[serde(default)]
+    os: String,
+    #[serde(default)]
+    architecture: String,
+    variant: Option<String>,
+}
+
+/// 单平台清单或多平台索引，两者通过 manifests 字段区分
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    #[serde(default)]
+    media_type: Option<String>,
+    config: Option<Descriptor>,
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+    manifests: Option<Vec<Descriptor>>,
+}
+
+/// 获取到的清单及其原始内容
+#[derive(Debug, Clone)]
+struct ManifestDocument {
+    manifest: Manifest,
+    raw: Vec<u8>,
+    media_type: String,
+    digest: Option<String>,
+}
+
+struct CachedManifest {
+    document: ManifestDocument,
+    fetched_at: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagList {
+    #[serde(default)]
+    tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+/// 路径解析后的目标
+enum OciTarget {
+    /// 虚拟目录及其子项
+    Directory(Vec<StorageFile>),
+    /// 清单文档，内容已在内存中
+    Document(ManifestDocument),
+    /// 配置或层的 blob
+    Blob(Descriptor),
+}
+
+/// 可读取的文件目标
+enum OciFile {
+    Document(ManifestDocument),
+    Blob(Descriptor),
+}
+
+/// OCI 镜像仓库客户端（Distribution v2 API），以只读目录的形式浏览镜像和 ORAS 制品
+///
+/// 目录结构：根目录列出标签（连接 URL 中指定了标签或摘要时直接进入该镜像），
+/// 镜像目录包含 manifest.json、config.json 和 layers/，多平台索引为每个平台生成一个子目录；
+/// 层按媒体类型命名为 .tar.gz 等扩展名，可以直接交给压缩包处理器通过范围读取浏览
+pub struct OciRegistryClient {
+    client: Client,
+    download_client: Client, // 专门用于下载的客户端，配置更长超时
+    config: ConnectionConfig,
+    // 如 https://registry-1.docker.io
+    registry_url: String,
+    repository: String,
+    // 连接 URL 中指定的标签或摘要，设置后根目录即为该镜像
+    reference: Option<String>,
+    basic_auth: Option<String>,
+    // 当前使用的 Authorization 头，收到 401 时根据质询重新获取
+    auth_header: RwLock<Option<String>>,
+    manifests: Mutex<HashMap<String, CachedManifest>>,
+    connected: AtomicBool,
+}
+
+impl OciRegistryClient {
+    pub fn new(config: ConnectionConfig) -> Result<Self, StorageError> {
+        let (registry_url, repository, reference) = Self::parse_reference(&config)?;
+
+        let basic_auth = match (&config.username, &config.password) {
+            (Some(username), Some(password)) if !username.is_empty() => {
+                let credentials =
+                    general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+                Some(format!("Basic {}", credentials))
+            }
+            _ => None,
+        };
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .map_err(|e| {
+                StorageError::InvalidConfig(format!("Failed to create HTTP client: {}", e))
+            })?;
+
+        let download_client = Client::builder()
+            .timeout(Duration::from_secs(600))
+            .connect_timeout(Duration::from_secs(10))
+            .pool_idle_timeout(Duration::from_secs(300))
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .map_err(|e| {
+                StorageError::InvalidConfig(format!("Failed to create download HTTP client: {}", e))
+            })?;
+
+        Ok(Self {
+            client,
+            download_client,
+            config,
+            registry_url,
+            repository,
+            reference,
+            basic_auth,
+            auth_header: RwLock::new(None),
+            manifests: Mutex::new(HashMap::new()),
+            connected: AtomicBool::new(false),
+        })
+    }
+
+    /// 解析 "registry/repo[:tag|@digest]"，仓库名也可以通过 bucket 单独指定
+    /// 未指定协议时使用 HTTPS，localhost 或 extraOptions.insecure 为 true 时使用 HTTP
+    fn parse_reference(
+        config: &ConnectionConfig,
+    ) -> Result<(String, String, Option<String>), StorageError> {
+        let url = config
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| StorageError::InvalidConfig("Registry URL is required".to_string()))?;
+
+        let (scheme, rest) = match url.split_once("://") {
+            Some((scheme, rest)) => (Some(scheme.to_lowercase()), rest),
+            None => (None, url),
+        };
+        let rest = rest.trim_end_matches('/');
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if host.is_empty() {
+            return Err(StorageError::InvalidConfig(
+                "Registry host is required".to_string(),
+            ));
+        }
+
+        let insecure = config
+            .extra_options
+            .as_ref()
+            .and_then(|options| options.get("insecure"))
+            .is_some_and(|value| value == "true");
+        let is_local = host.starts_with("localhost") || host.starts_with("127.0.0.1");
+        let scheme = match scheme.as_deref() {
+            Some("http") => "http",
+            Some("https") => "https",
+            Some("oci") | None if insecure || is_local => "http",
+            Some("oci") | None => "https",
+            Some(other) => {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Unsupported URL scheme: {}",
+                    other
+                )))
+            }
+        };
+
+        // Docker Hub 的 API 地址和官方镜像的 library/ 前缀
+        let is_docker_hub = host == "docker.io" || host == "index.docker.io";
+        let host = if is_docker_hub {
+            "registry-1.docker.io"
+        } else {
+            host
+        };
+
+        let path = match path {
+            "" => config.bucket.clone().unwrap_or_default(),
+            path => path.to_string(),
+        };
+        let (repository, reference) = if let Some((repository, digest)) = path.split_once('@') {
+            (repository.to_string(), Some(digest.to_string()))
+        } else {
+            // 标签分隔符只能出现在最后一级路径中，避免与端口号混淆
+            let last_slash = path.rfind('/').map(|i| i + 1).unwrap_or(0);
+            match path[last_slash..].rfind(':') {
+                Some(colon) => (
+                    path[..last_slash + colon].to_string(),
+                    Some(path[last_slash + colon + 1..].to_string()),
+                ),
+                None => (path.clone(), None),
+            }
+        };
+
+        let repository = repository.trim_matches('/').to_string();
+        if repository.is_empty() {
+            return Err(StorageError::InvalidConfig(
+                "Repository is required (registry/repository[:tag])".to_string(),
+            ));
+        }
+        let repository =
+            if (is_docker_hub || host == "registry-1.docker.io") && !repository.contains('/') {
+                format!("library/{}", repository)
+            } else {
+                repository
+            };
+
+        Ok((
+            format!("{}://{}", scheme, host),
+            repository,
+            reference.filter(|reference| !reference.is_empty()),
+        ))
+    }
+
+    fn api_url(&self, suffix: &str) -> String {
+        format!("{}/v2/{}/{}", self.registry_url, self.repository, suffix)
+    }
+
+    /// 根据 WWW-Authenticate 质询获取认证头
+    /// Bearer 质询向令牌服务换取令牌（有账号时附带 Basic 认证），Basic 质询直接使用账号密码
+    async fn authenticate(&self, challenge: &str) -> Result<String, StorageError> {
+        let (scheme, params) = challenge.split_once(' ').unwrap_or((challenge, ""));
+        if scheme.eq_ignore_ascii_case("basic") {
+            return self.basic_auth.clone().ok_or_else(|| {
+                StorageError::AuthenticationFailed("Registry requires credentials".to_string())
+            });
+        }
+
+        let params: HashMap<String, String> = CHALLENGE_PARAM_REGEX
+            .captures_iter(params)
+            .map(|captures| (captures[1].to_lowercase(), captures[2].to_string()))
+            .collect();
+        let realm = params.get("realm").ok_or_else(|| {
+            StorageError::AuthenticationFailed(format!("Invalid auth challenge: {}", challenge))
+        })?;
+
+        let mut query = Vec::new();
+        if let Some(service) = params.get("service") {
+            query.push(("service", service.clone()));
+        }
+        let scope = params
+            .get("scope")
+            .cloned()
+            .unwrap_or_else(|| format!("repository:{}:pull", self.repository));
+        query.push(("scope", scope));
+
+        let mut request = self.client.get(realm).query(&query);
+        if let Some(ref basic_auth) = self.basic_auth {
+            request = request.header("Authorization", basic_auth);
+        }
+        let response = request.send().await.map_err(StorageError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!("Registry token request failed: {}", response.status()),
+            ));
+        }
+
+        let token: TokenResponse = response.json().await.map_err(|e| {
+            StorageError::AuthenticationFailed(format!("Invalid token response: {}", e))
+        })?;
+        token
+            .token
+            .or(token.access_token)
+            .map(|token| format!("Bearer {}", token))
+            .ok_or_else(|| {
+                StorageError::AuthenticationFailed("Token response has no token".to_string())
+            })
+    }
+
+    /// 发送 GET 请求，收到 401 时按质询认证后重试一次
+    async fn get(
+        &self,
+        client: &Client,
+        url: &str,
+        headers: &[(&str, String)],
+    ) -> Result<reqwest::Response, StorageError> {
+        let build = |auth: Option<String>| {
+            let mut request = client.get(url);
+            for (key, value) in headers {
+                request = request.header(*key, value);
+            }
+            if let Some(auth) = auth {
+                request = request.header("Authorization", auth);
+            }
+            request
+        };
+
+        let auth = self.auth_header.read().await.clone();
+        let response = build(auth)
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let challenge = response
+            .headers()
+            .get(reqwest::header::WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+            .ok_or_else(|| StorageError::from_response(&response, "Unauthorized".to_string()))?;
+        let auth = self.authenticate(&challenge).await?;
+        *self.auth_header.write().await = Some(auth.clone());
+
+        build(Some(auth))
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)
+    }
+
+    async fn error_from_response(response: reqwest::Response, context: &str) -> StorageError {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+        StorageError::from_http_status(
+            status,
+            &headers,
+            format!("{} failed with status {}: {}", context, status, body),
+        )
+    }
+
+    /// 获取清单，按摘要引用的清单永久缓存，按标签引用的清单缓存一段时间
+    async fn manifest(&self, reference: &str) -> Result<ManifestDocument, StorageError> {
+        let is_digest = reference.contains(':');
+        if let Some(cached) = self.manifests.lock().await.get(reference) {
+            if is_digest || cached.fetched_at.elapsed() < TAG_MANIFEST_TTL {
+                return Ok(cached.document.clone());
+            }
+        }
+
+        let url = self.api_url(&format!("manifests/{}", reference));
+        let response = self
+            .get(
+                &self.client,
+                &url,
+                &[("Accept", MANIFEST_ACCEPT.to_string())],
+            )
+            .await?;
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response, "Manifest request").await);
+        }
+
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+        let content_type = header("content-type");
+        let digest = header("docker-content-digest");
+
+        let raw = response
+            .bytes()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("Failed to read manifest: {}", e)))?
+            .to_vec();
+        let manifest: Manifest = serde_json::from_slice(&raw).map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to parse manifest {}: {}", reference, e))
+        })?;
+
+        let media_type = manifest
+            .media_type
+            .clone()
+            .or(content_type)
+            .unwrap_or_else(|| "application/json".to_string());
+        let document = ManifestDocument {
+            manifest,
+            raw,
+            media_type,
+            digest: digest.or_else(|| is_digest.then(|| reference.to_string())),
+        };
+
+        self.manifests.lock().await.insert(
+            reference.to_string(),
+            CachedManifest {
+                document: document.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(document)
+    }
+
+    /// 列出仓库的全部标签，按 last 参数翻页
+    async fn list_tags(&self) -> Result<Vec<String>, StorageError> {
+        let mut tags: Vec<String> = Vec::new();
+        loop {
+            let mut url = self.api_url("tags/list?n=1000");
+            if let Some(last) = tags.last() {
+                url.push_str(&format!("&last={}", urlencoding::encode(last)));
+            }
+
+            let response = self.get(&self.client, &url, &[]).await?;
+            if !response.status().is_success() {
+                return Err(Self::error_from_response(response, "Tag list request").await);
+            }
+            let has_next = response.headers().contains_key(reqwest::header::LINK);
+            let page: TagList = response.json().await.map_err(|e| {
+                StorageError::RequestFailed(format!("Failed to parse tag list: {}", e))
+            })?;
+
+            let page = page.tags.unwrap_or_default();
+            if page.is_empty() || !has_next {
+                tags.extend(page);
+                break;
+            }
+            tags.extend(page);
+        }
+        Ok(tags)
+    }
+
+    /// 层的文件名：优先使用 ORAS 标题注解，否则按序号、摘要前缀和媒体类型生成
+    fn layer_names(layers: &[Descriptor]) -> Vec<String> {
+        let mut names: Vec<String> = Vec::with_capacity(layers.len());
+        for (index, layer) in layers.iter().enumerate() {
+            let title = layer
+                .annotations
+                .get(TITLE_ANNOTATION)
+                .filter(|title| !title.is_empty() && !title.contains('/'))
+                .filter(|title| !names.contains(title));
+            let name = match title {
+                Some(title) => title.clone(),
+                None => format!(
+                    "{}-{}.{}",
+                    index,
+                    Self::short_digest(&layer.digest),
+                    Self::layer_extension(&layer.media_type)
+                ),
+            };
+            names.push(name);
+        }
+        names
+    }
+
+    fn short_digest(digest: &str) -> &str {
+        let hex = digest.split_once(':').map(|(_, hex)| hex).unwrap_or(digest);
+        &hex[..hex.len().min(12)]
+    }
+
+    fn layer_extension(media_type: &str) -> &'static str {
+        let media_type = media_type.to_lowercase();
+        if media_type.ends_with("gzip") {
+            "tar.gz"
+        } else if media_type.ends_with("zstd") {
+            "tar.zst"
+        } else if media_type.ends_with(".tar") || media_type.ends_with("tar") {
+            "tar"
+        } else if media_type.ends_with("json") {
+            "json"
+        } else {
+            "bin"
+        }
+    }
+
+    /// 多平台索引中子清单的目录名，如 linux-arm64-v8，缺少平台信息时使用摘要前缀
+    fn platform_names(manifests: &[Descriptor]) -> Vec<String> {
+        let mut names: Vec<String> = Vec::with_capacity(manifests.len());
+        for descriptor in manifests {
+            let name = descriptor
+                .platform
+                .as_ref()
+                .filter(|platform| !platform.os.is_empty())
+                .map(|platform| {
+                    let mut name = format!("{}-{}", platform.os, platform.architecture);
+                    if let Some(ref variant) = platform.variant {
+                        name = format!("{}-{}", name, variant);
+                    }
+                    name
+                })
+                .filter(|name| !names.contains(name) && name != "unknown-unknown")
+                .unwrap_or_else(|| Self::short_digest(&descriptor.digest).to_string());
+            names.push(name);
+        }
+        names
+    }
+
+    fn file_entry(name: &str, size: u64, media_type: Option<&str>) -> StorageFile {
+        StorageFile {
+            filename: name.to_string(),
+            basename: name.to_string(),
+            lastmod: String::new(),
+            size: size.to_string(),
+            file_type: "file".to_string(),
+            mime: media_type.map(|media_type| media_type.to_string()),
+            etag: None,
+        }
+    }
+
+    fn directory_entry(name: &str) -> StorageFile {
+        StorageFile {
+            filename: name.to_string(),
+            basename: name.to_string(),
+            lastmod: String::new(),
+            size: "0".to_string(),
+            file_type: "directory".to_string(),
+            mime: None,
+            etag: None,
+        }
+    }
+
+    /// 将路径解析为目录、清单文档或 blob
+    async fn resolve(&self, path: &str) -> Result<OciTarget, StorageError> {
+        let segments: Vec<&str> = path
+            .trim_matches('/')
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        let (mut reference, mut rest) = match self.reference {
+            Some(ref reference) => (reference.clone(), segments.as_slice()),
+            None => match segments.split_first() {
+                Some((tag, rest)) => (tag.to_string(), rest),
+                None => {
+                    let tags = self.list_tags().await?;
+                    return Ok(OciTarget::Directory(
+                        tags.iter().map(|tag| Self::directory_entry(tag)).collect(),
+                    ));
+                }
+            },
+        };
+
+        loop {
+            let document = self.manifest(&reference).await?;
+            let manifest = &document.manifest;
+
+            let Some((name, tail)) = rest.split_first() else {
+                let mut entries = vec![Self::file_entry(
+                    "manifest.json",
+                    document.raw.len() as u64,
+                    Some(&document.media_type),
+                )];
+                if let Some(ref manifests) = manifest.manifests {
+                    entries.extend(
+                        Self::platform_names(manifests)
+                            .iter()
+                            .map(|name| Self::directory_entry(name)),
+                    );
+                } else {
+                    if let Some(ref config) = manifest.config {
+                        entries.push(Self::file_entry(
+                            "config.json",
+                            config.size,
+                            Some(&config.media_type),
+                        ));
+                    }
+                    entries.push(Self::directory_entry("layers"));
+                }
+                return Ok(OciTarget::Directory(entries));
+            };
+
+            match (*name, manifest.manifests.as_ref()) {
+                ("manifest.json", _) if tail.is_empty() => {
+                    return Ok(OciTarget::Document(document))
+                }
+                (name, Some(manifests)) => {
+                    let index = Self::platform_names(manifests)
+                        .iter()
+                        .position(|platform| platform == name)
+                        .ok_or_else(|| StorageError::NotFound(path.to_string()))?;
+                    reference = manifests[index].digest.clone();
+                    rest = tail;
+                }
+                ("config.json", None) if tail.is_empty() => {
+                    return manifest
+                        .config
+                        .clone()
+                        .map(OciTarget::Blob)
+                        .ok_or_else(|| StorageError::NotFound(path.to_string()))
+                }
+                ("layers", None) => {
+                    let names = Self::layer_names(&manifest.layers);
+                    return match tail {
+                        [] => Ok(OciTarget::Directory(
+                            names
+                                .iter()
+                                .zip(&manifest.layers)
+                                .map(|(name, layer)| {
+                                    Self::file_entry(name, layer.size, Some(&layer.media_type))
+                                })
+                                .collect(),
+                        )),
+                        [layer_name] => names
+                            .iter()
+                            .position(|name| name == layer_name)
+                            .map(|index| OciTarget::Blob(manifest.layers[index].clone()))
+                            .ok_or_else(|| StorageError::NotFound(path.to_string())),
+                        _ => Err(StorageError::NotFound(path.to_string())),
+                    };
+                }
+                _ => return Err(StorageError::NotFound(path.to_string())),
+            }
+        }
+    }
+
+    async fn resolve_file(&self, path: &str) -> Result<OciFile, StorageError> {
+        match self.resolve(path).await? {
+            OciTarget::Directory(_) => {
+                Err(StorageError::RequestFailed(format!("Not a file: {}", path)))
+            }
+            OciTarget::Document(document) => Ok(OciFile::Document(document)),
+            OciTarget::Blob(descriptor) => Ok(OciFile::Blob(descriptor)),
+        }
+    }
+
+    fn blob_url(&self, descriptor: &Descriptor) -> String {
+        self.api_url(&format!("blobs/{}", descriptor.digest))
+    }
+
+    fn read_only_error() -> StorageError {
+        StorageError::ProtocolNotSupported("OCI registry connections are read-only".to_string())
+    }
+}
+
+#[async_trait]
+impl StorageClient for OciRegistryClient {
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        *self = Self::new(config.clone())?;
+
+        // 指定了标签时获取清单，否则获取标签列表，同时完成令牌认证
+        let result = match self.reference.clone() {
+            Some(reference) => self.manifest(&reference).await.map(|_| ()),
+            None => self.list_tags().await.map(|_| ()),
+        };
+        result.map_err(|e| match e {
+            StorageError::NetworkError(message) => StorageError::ConnectionFailed(format!(
+                "Registry connection test failed: {}",
+                message
+            )),
+            other => other,
+        })?;
+
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        _options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let files = match self.resolve(path).await? {
+            OciTarget::Directory(files) => files,
+            _ => {
+                return Err(StorageError::RequestFailed(format!(
+                    "Not a directory: {}",
+                    path
+                )))
+            }
+        };
+
+        Ok(DirectoryResult {
+            files,
+            has_more: false,
+            next_marker: None,
+            total_count: None,
+            path: path.to_string(),
+        })
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_file_range_with_progress(path, start, length, None, None)
+            .await
+    }
+
+    /// blob 使用 Range 请求读取，仓库通常会重定向到对象存储，reqwest 跨域重定向时不会转发认证头
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+
+        let descriptor = match self.resolve_file(path).await? {
+            OciFile::Document(document) => {
+                let start = (start as usize).min(document.raw.len());
+                let end = start
+                    .saturating_add(length as usize)
+                    .min(document.raw.len());
+                return Ok(document.raw[start..end].to_vec());
+            }
+            OciFile::Blob(descriptor) => descriptor,
+        };
+
+        let end = start + length - 1;
+        let response = self
+            .get(
+                &self.download_client,
+                &self.blob_url(&descriptor),
+                &[("Range", format!("bytes={}-{}", start, end))],
+            )
+            .await?;
+
+        if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response, "Blob request").await);
+        }
+
+        // 不支持 Range 时返回完整内容，跳过前面的字节
+        let mut skip = if response.status() == StatusCode::PARTIAL_CONTENT {
+            0
+        } else {
+            start
+        };
+        let mut result = Vec::with_capacity(length.min(64 * 1024 * 1024) as usize);
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk_result) = stream.next().await {
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    return Err(StorageError::Cancelled);
+                }
+            }
+
+            let mut chunk = chunk_result
+                .map_err(|e| StorageError::NetworkError(format!("Stream error: {}", e)))?;
+            if skip > 0 {
+                let skipped = skip.min(chunk.len() as u64);
+                skip -= skipped;
+                chunk = chunk.slice(skipped as usize..);
+            }
+
+            let remaining = (length - result.len() as u64) as usize;
+            result.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+
+            if let Some(ref callback) = progress_callback {
+                callback(result.len() as u64, length);
+            }
+            if result.len() as u64 >= length {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        let descriptor = match self.resolve_file(path).await? {
+            OciFile::Document(document) => return Ok(document.raw),
+            OciFile::Blob(descriptor) => descriptor,
+        };
+
+        let response = self
+            .get(&self.download_client, &self.blob_url(&descriptor), &[])
+            .await?;
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response, "Blob request").await);
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("Failed to read blob: {}", e)))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        let descriptor = match self.resolve_file(path).await? {
+            OciFile::Document(document) => {
+                let start = (start as usize).min(document.raw.len());
+                let bytes = bytes::Bytes::from(document.raw).slice(start..);
+                return Ok(Box::pin(futures_util::stream::once(
+                    async move { Ok(bytes) },
+                )));
+            }
+            OciFile::Blob(descriptor) => descriptor,
+        };
+
+        // 先探测一次以完成认证，再把认证头交给下载工具
+        let url = self.blob_url(&descriptor);
+        let probe = self
+            .get(&self.client, &url, &[("Range", "bytes=0-0".to_string())])
+            .await?;
+        if !probe.status().is_success() && probe.status() != StatusCode::RANGE_NOT_SATISFIABLE {
+            return Err(Self::error_from_response(probe, "Blob request").await);
+        }
+        drop(probe);
+
+        let mut config = HttpDownloadConfig::new(url);
+        if let Some(auth) = self.auth_header.read().await.clone() {
+            config = config.with_auth(auth);
+        }
+        HttpDownloader::open_stream(&self.download_client, config, start).await
+    }
+
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        let basename = path
+            .trim_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let mut extra = HashMap::new();
+
+        let (size, file_type, mime, checksum) = match self.resolve(path).await? {
+            OciTarget::Directory(_) => (0, "directory", None, None),
+            OciTarget::Document(document) => (
+                document.raw.len() as u64,
+                "file",
+                Some(document.media_type),
+                document.digest,
+            ),
+            OciTarget::Blob(descriptor) => {
+                for (key, value) in descriptor.annotations {
+                    extra.insert(format!("annotation:{}", key), value);
+                }
+                (
+                    descriptor.size,
+                    "file",
+                    Some(descriptor.media_type),
+                    Some(descriptor.digest),
+                )
+            }
+        };
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename,
+            lastmod: String::new(),
+            size: size.to_string(),
+            file_type: file_type.to_string(),
+            mime,
+            etag: checksum.clone(),
+            checksum,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra,
+        })
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        match self.resolve_file(path).await? {
+            OciFile::Document(document) => Ok(document.raw.len() as u64),
+            OciFile::Blob(descriptor) => Ok(descriptor.size),
+        }
+    }
+
+    fn protocol(&self) -> &str {
+        "oci"
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        Self::parse_reference(config).map(|_| ())
+    }
+
+    /// 下载失败时由重试层通过 open_read_stream 的 Range 请求从已下载的部分继续
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let total_size = self.get_file_size(path).await?;
+        let stream = self.open_read_stream(path, 0).await?;
+
+        StreamIo::save_to_file(stream, save_path, total_size, progress_callback, cancel_rx).await
+    }
+
+    async fn put_file(
+        &self,
+        _local_path: &std::path::Path,
+        _path: &str,
+        _progress_callback: Option<ProgressCallback>,
+        _cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn create_directory(&self, _path: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn rename(&self, _from: &str, _to: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+
+    async fn delete(&self, _path: &str) -> Result<(), StorageError> {
+        Err(Self::read_only_error())
+    }
+}
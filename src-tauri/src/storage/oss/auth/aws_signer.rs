@@ -1,3 +1,4 @@
+use crate::storage::oss::credentials::Credentials;
 use crate::utils::crypto::{hmac_sha256, hmac_sha256_bytes, sha256_hex};
 use chrono::Utc;
 use std::collections::HashMap;
@@ -8,22 +9,49 @@ pub fn build_aws_auth_headers(
     uri: &str,
     extra_headers: &HashMap<String, String>,
     query_string: Option<&str>,
-    access_key: &str,
-    secret_key: &str,
+    credentials: &Credentials,
     region: &str,
     host: &str,
+) -> HashMap<String, String> {
+    build_sigv4_headers(
+        method,
+        uri,
+        extra_headers,
+        query_string,
+        credentials,
+        (region, "s3"),
+        host,
+    )
+}
+
+/// 按 AWS Signature V4 构建指定服务的认证头，scope 为 (区域, 服务名)
+/// 使用 STS 临时凭证时附带 X-Amz-Security-Token 并参与签名
+pub fn build_sigv4_headers(
+    method: &str,
+    uri: &str,
+    extra_headers: &HashMap<String, String>,
+    query_string: Option<&str>,
+    credentials: &Credentials,
+    (region, service): (&str, &str),
+    host: &str,
 ) -> HashMap<String, String> {
     let now = Utc::now();
     let date_stamp = now.format("%Y%m%d").to_string();
     let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
 
-    // 计算请求体的SHA256哈希（空请求体）
-    let payload_hash = sha256_hex("");
+    // 计算请求体的SHA256哈希（空请求体），流式上传时由调用方传入 UNSIGNED-PAYLOAD
+    let payload_hash = extra_headers
+        .get("x-amz-content-sha256")
+        .cloned()
+        .unwrap_or_else(|| sha256_hex(""));
 
     let mut headers = extra_headers.clone();
     headers.insert("Host".to_string(), host.to_string());
     headers.insert("X-Amz-Date".to_string(), amz_date.clone());
     headers.insert("x-amz-content-sha256".to_string(), payload_hash.clone());
+    if let Some(token) = &credentials.session_token {
+        headers.insert("X-Amz-Security-Token".to_string(), token.clone());
+    }
 
     // 构建规范请求
     let canonical_request = build_canonical_request_with_payload(
@@ -35,7 +63,7 @@ pub fn build_aws_auth_headers(
     );
 
     // 构建待签名字符串
-    let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, region);
+    let credential_scope = format!("{}/{}/{}/aws4_request", date_stamp, region, service);
     let string_to_sign = format!(
         "AWS4-HMAC-SHA256\n{}\n{}\n{}",
         amz_date,
@@ -44,12 +72,17 @@ pub fn build_aws_auth_headers(
     );
 
     // 计算签名
-    let signature = calculate_aws_signature(&string_to_sign, &date_stamp, region, secret_key);
+    let signature = calculate_aws_signature(
+        &string_to_sign,
+        &date_stamp,
+        (region, service),
+        &credentials.secret_access_key,
+    );
 
     // 构建Authorization头
     let authorization = format!(
         "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
-        access_key,
+        credentials.access_key_id,
         credential_scope,
         get_signed_headers(&headers),
         signature
@@ -118,13 +151,13 @@ fn get_signed_headers(headers: &HashMap<String, String>) -> String {
 fn calculate_aws_signature(
     string_to_sign: &str,
     date_stamp: &str,
-    region: &str,
+    (region, service): (&str, &str),
     secret_key: &str,
 ) -> String {
     // AWS4 签名密钥派生
     let k_date = hmac_sha256(&format!("AWS4{}", secret_key), date_stamp);
     let k_region = hmac_sha256_bytes(&k_date, region);
-    let k_service = hmac_sha256_bytes(&k_region, "s3");
+    let k_service = hmac_sha256_bytes(&k_region, service);
     let k_signing = hmac_sha256_bytes(&k_service, "aws4_request");
 
     // 计算最终签名
@@ -137,10 +170,10 @@ pub fn generate_aws_presigned_url(
     endpoint: &str,
     object_key: &str,
     expires_in_seconds: i64,
-    access_key: &str,
-    secret_key: &str,
+    credentials: &Credentials,
     region: &str,
     bucket: &str,
+    version_id: Option<&str>,
 ) -> Result<String, String> {
     let now = Utc::now();
     let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
@@ -156,7 +189,7 @@ pub fn generate_aws_presigned_url(
 
     // 构建查询参数
     let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, region);
-    let credential = format!("{}/{}", access_key, credential_scope);
+    let credential = format!("{}/{}", credentials.access_key_id, credential_scope);
 
     let mut query_params = vec![
         (
@@ -171,6 +204,19 @@ pub fn generate_aws_presigned_url(
         ("X-Amz-Expires".to_string(), expires.to_string()),
         ("X-Amz-SignedHeaders".to_string(), "host".to_string()),
     ];
+    if let Some(token) = &credentials.session_token {
+        query_params.push((
+            "X-Amz-Security-Token".to_string(),
+            urlencoding::encode(token).to_string(),
+        ));
+    }
+    // 历史版本的 versionId 属于规范查询字符串，参与签名
+    if let Some(version_id) = version_id {
+        query_params.push((
+            "versionId".to_string(),
+            urlencoding::encode(version_id).to_string(),
+        ));
+    }
 
     // 排序查询参数
     query_params.sort_by(|a, b| a.0.cmp(&b.0));
@@ -226,7 +272,12 @@ pub fn generate_aws_presigned_url(
     );
 
     // 计算签名
-    let signature = calculate_aws_signature(&string_to_sign, &date_stamp, region, secret_key);
+    let signature = calculate_aws_signature(
+        &string_to_sign,
+        &date_stamp,
+        (region, "s3"),
+        &credentials.secret_access_key,
+    );
 
     // 构建最终URL
     Ok(format!(
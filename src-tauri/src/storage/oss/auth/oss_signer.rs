@@ -1,3 +1,4 @@
+use crate::storage::oss::credentials::Credentials;
 use crate::utils::crypto::hmac_sha1_base64;
 use chrono::Utc;
 use std::collections::HashMap;
@@ -44,7 +45,10 @@ pub fn generate_oss_signature(
     // 根据OSS文档，签名中的URI应该是解码后的UTF-8形式
     let normalized_uri = normalize_uri_for_signing(uri);
 
-    let canonicalized_resource = if normalized_uri == "/" {
+    // ListBuckets 等服务级请求不属于任何存储桶，资源为 "/"
+    let canonicalized_resource = if bucket.is_empty() {
+        normalized_uri
+    } else if normalized_uri == "/" {
         format!("/{}/", bucket)
     } else {
         format!("/{}{}", bucket, normalized_uri)
@@ -77,8 +81,7 @@ pub fn build_oss_auth_headers(
     method: &str,
     uri: &str,
     extra_headers: &HashMap<String, String>,
-    access_key: &str,
-    secret_key: &str,
+    credentials: &Credentials,
     bucket: &str,
     host: &str,
 ) -> HashMap<String, String> {
@@ -88,9 +91,20 @@ pub fn build_oss_auth_headers(
     let mut headers = extra_headers.clone();
     headers.insert("Date".to_string(), date.clone());
     headers.insert("Host".to_string(), host.to_string());
+    // STS 临时凭证的令牌属于 x-oss- 头，会参与签名
+    if let Some(token) = &credentials.session_token {
+        headers.insert("x-oss-security-token".to_string(), token.clone());
+    }
 
-    let signature = generate_oss_signature(method, uri, &headers, &date, secret_key, bucket);
-    let authorization = format!("OSS {}:{}", access_key, signature);
+    let signature = generate_oss_signature(
+        method,
+        uri,
+        &headers,
+        &date,
+        &credentials.secret_access_key,
+        bucket,
+    );
+    let authorization = format!("OSS {}:{}", credentials.access_key_id, signature);
 
     headers.insert("Authorization".to_string(), authorization);
     headers
@@ -116,9 +130,9 @@ pub fn generate_oss_presigned_url(
     endpoint: &str,
     object_key: &str,
     expires_in_seconds: i64,
-    access_key: &str,
-    secret_key: &str,
+    credentials: &Credentials,
     bucket: &str,
+    version_id: Option<&str>,
 ) -> Result<String, String> {
     // 计算过期时间戳
     let now = Utc::now().timestamp();
@@ -133,7 +147,10 @@ pub fn generate_oss_presigned_url(
 
     // 构建查询参数 - 使用OSS格式
     let mut query_params = HashMap::new();
-    query_params.insert("OSSAccessKeyId".to_string(), access_key.to_string());
+    query_params.insert(
+        "OSSAccessKeyId".to_string(),
+        credentials.access_key_id.clone(),
+    );
     query_params.insert("Expires".to_string(), expires.to_string());
 
     // 构建待签名字符串
@@ -142,8 +159,20 @@ pub fn generate_oss_presigned_url(
     let content_md5 = "";
     let content_type = "";
 
-    // 构建 Canonicalized Resource
-    let canonicalized_resource = format!("/{}{}", bucket, uri);
+    // 构建 Canonicalized Resource，STS 令牌和对象版本作为子资源按名称顺序参与签名
+    let mut canonicalized_resource = format!("/{}{}", bucket, uri);
+    let mut sub_resources = Vec::new();
+    if let Some(token) = &credentials.session_token {
+        sub_resources.push(format!("security-token={}", token));
+        query_params.insert("security-token".to_string(), token.clone());
+    }
+    if let Some(version_id) = version_id {
+        sub_resources.push(format!("versionId={}", version_id));
+        query_params.insert("versionId".to_string(), version_id.to_string());
+    }
+    if !sub_resources.is_empty() {
+        canonicalized_resource.push_str(&format!("?{}", sub_resources.join("&")));
+    }
 
     // 构建签名字符串
     let string_to_sign = format!(
@@ -152,7 +181,7 @@ pub fn generate_oss_presigned_url(
     );
 
     // 生成签名
-    let signature = hmac_sha1_base64(secret_key, &string_to_sign);
+    let signature = hmac_sha1_base64(&credentials.secret_access_key, &string_to_sign);
     query_params.insert("Signature".to_string(), signature);
 
     // 构建最终 URL
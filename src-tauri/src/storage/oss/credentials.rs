@@ -0,0 +1,764 @@
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::storage::config_import::{
+    aws_shared_config_paths, parse_ini, read_optional, IniSection,
+};
+use crate::storage::oss::auth::aws_signer::build_sigv4_headers;
+use crate::storage::traits::{ConnectionConfig, StorageError};
+
+// 临时凭证在过期前多少秒开始刷新
+const REFRESH_WINDOW_SECS: i64 = 5 * 60;
+// AssumeRole 换取的会话时长
+const SESSION_DURATION_SECS: u32 = 3600;
+const DEFAULT_SESSION_NAME: &str = "dataset-viewer";
+// ECS 容器凭证端点，AWS_CONTAINER_CREDENTIALS_RELATIVE_URI 相对于该地址
+const ECS_CREDENTIALS_HOST: &str = "http://169.254.170.2";
+const IMDS_ENDPOINT: &str = "http://169.254.169.254";
+// 不在 EC2 上运行时实例元数据服务不可达，使用较短的超时避免拖慢连接
+const IMDS_TIMEOUT: Duration = Duration::from_secs(1);
+// 凭证来源的最大嵌套深度，避免 profile 之间循环引用
+const MAX_SOURCE_DEPTH: usize = 8;
+
+/// 访问 OSS/S3 使用的凭证，STS 临时凭证附带会话令牌和过期时间
+#[derive(Clone)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+    pub expiration: Option<DateTime<Utc>>,
+}
+
+impl Credentials {
+    pub fn new(access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            expiration: None,
+        }
+    }
+
+    fn with_session_token(mut self, session_token: Option<String>) -> Self {
+        self.session_token = session_token.filter(|token| !token.is_empty());
+        self
+    }
+
+    /// 是否会在 seconds 秒内过期，固定密钥永不过期
+    fn expires_within(&self, seconds: i64) -> bool {
+        self.expiration
+            .is_some_and(|expiration| expiration - chrono::Duration::seconds(seconds) <= Utc::now())
+    }
+}
+
+/// 容器凭证端点和实例元数据服务返回的凭证
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MetadataCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    token: Option<String>,
+    expiration: Option<String>,
+}
+
+impl From<MetadataCredentials> for Credentials {
+    fn from(credentials: MetadataCredentials) -> Self {
+        Self {
+            access_key_id: credentials.access_key_id,
+            secret_access_key: credentials.secret_access_key,
+            session_token: credentials.token.filter(|token| !token.is_empty()),
+            expiration: credentials.expiration.as_deref().and_then(parse_expiration),
+        }
+    }
+}
+
+/// 凭证来源
+#[derive(Clone)]
+enum CredentialSource {
+    /// 连接配置中的密钥，可带 STS 会话令牌
+    Static(Credentials),
+    /// AWS_ACCESS_KEY_ID、AWS_SECRET_ACCESS_KEY、AWS_SESSION_TOKEN 环境变量
+    Environment,
+    /// 共享凭证文件和配置文件中的 profile
+    Profile(String),
+    /// 以 OIDC 令牌文件调用 AssumeRoleWithWebIdentity
+    WebIdentity {
+        role_arn: String,
+        token_file: PathBuf,
+        session_name: String,
+    },
+    /// ECS 容器凭证端点，也可以指向本地的兼容端点
+    Container { url: String },
+    /// EC2 实例元数据服务（IMDSv2，不支持时退回 IMDSv1）
+    InstanceMetadata { endpoint: String },
+    /// 以其他来源的凭证调用 AssumeRole
+    AssumeRole {
+        source: Box<CredentialSource>,
+        role_arn: String,
+        session_name: String,
+        external_id: Option<String>,
+    },
+    /// 依次尝试，使用第一个提供凭证的来源
+    Chain(Vec<CredentialSource>),
+}
+
+/// OSS/S3 凭证提供者
+/// 按连接配置确定凭证来源，缓存获取到的凭证并在临时凭证过期前自动刷新
+pub struct CredentialProvider {
+    source: CredentialSource,
+    region: String,
+    sts_endpoint: Option<String>,
+    client: Client,
+    cached: RwLock<Option<Credentials>>,
+    // 同一时间只发起一次刷新
+    refresh_lock: tokio::sync::Mutex<()>,
+}
+
+impl CredentialProvider {
+    /// 根据连接配置确定凭证来源
+    ///
+    /// 配置了密钥时直接使用（可带 sessionToken）；extra_options 中的 profile 按共享配置文件解析，
+    /// webIdentityTokenFile、credentialsEndpoint 分别使用 web identity 和容器凭证端点；
+    /// 都没有时依次尝试环境变量、web identity、默认 profile、容器凭证端点和实例元数据。
+    /// 配置了 roleArn 时以上述凭证调用 AssumeRole
+    pub fn from_config(config: &ConnectionConfig) -> Self {
+        let option = |key: &str| {
+            config
+                .extra_options
+                .as_ref()
+                .and_then(|options| options.get(key))
+                .map(String::as_str)
+                .filter(|value| !value.is_empty())
+        };
+        let role_arn = option("roleArn");
+        let session_name = option("roleSessionName")
+            .unwrap_or(DEFAULT_SESSION_NAME)
+            .to_string();
+
+        let static_credentials = match (config.access_key.as_deref(), config.secret_key.as_deref())
+        {
+            (Some(access_key), Some(secret_key))
+                if !access_key.is_empty() && !secret_key.is_empty() =>
+            {
+                Some(
+                    Credentials::new(access_key, secret_key)
+                        .with_session_token(config.session_token.clone()),
+                )
+            }
+            _ => None,
+        };
+
+        let source = if let Some(credentials) = static_credentials {
+            Self::with_role(
+                CredentialSource::Static(credentials),
+                role_arn,
+                &session_name,
+                option("externalId"),
+            )
+        } else if let Some(profile) = option("profile") {
+            // profile 中的 role_arn 在解析 profile 时处理
+            CredentialSource::Profile(profile.to_string())
+        } else if let (Some(token_file), Some(role_arn)) =
+            (option("webIdentityTokenFile"), role_arn)
+        {
+            CredentialSource::WebIdentity {
+                role_arn: role_arn.to_string(),
+                token_file: PathBuf::from(token_file),
+                session_name,
+            }
+        } else {
+            let base = if let Some(profile) = option("sourceProfile") {
+                CredentialSource::Profile(profile.to_string())
+            } else if let Some(url) = option("credentialsEndpoint") {
+                CredentialSource::Container {
+                    url: url.to_string(),
+                }
+            } else {
+                Self::default_chain(option("imdsEndpoint"))
+            };
+            Self::with_role(base, role_arn, &session_name, option("externalId"))
+        };
+
+        Self {
+            source,
+            region: config
+                .region
+                .clone()
+                .filter(|region| !region.is_empty())
+                .unwrap_or_else(|| "us-east-1".to_string()),
+            sts_endpoint: option("stsEndpoint")
+                .map(|endpoint| endpoint.trim_end_matches('/').to_string()),
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .connect_timeout(Duration::from_secs(5))
+                .build()
+                .unwrap_or_default(),
+            cached: RwLock::new(None),
+            refresh_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn with_role(
+        source: CredentialSource,
+        role_arn: Option<&str>,
+        session_name: &str,
+        external_id: Option<&str>,
+    ) -> CredentialSource {
+        match role_arn {
+            Some(role_arn) => CredentialSource::AssumeRole {
+                source: Box::new(source),
+                role_arn: role_arn.to_string(),
+                session_name: session_name.to_string(),
+                external_id: external_id.map(str::to_string),
+            },
+            None => source,
+        }
+    }
+
+    /// 与 AWS SDK 默认凭证链相同的查找顺序
+    fn default_chain(imds_endpoint: Option<&str>) -> CredentialSource {
+        let mut chain = vec![CredentialSource::Environment];
+
+        if let (Some(token_file), Some(role_arn)) =
+            (env("AWS_WEB_IDENTITY_TOKEN_FILE"), env("AWS_ROLE_ARN"))
+        {
+            chain.push(CredentialSource::WebIdentity {
+                role_arn,
+                token_file: PathBuf::from(token_file),
+                session_name: env("AWS_ROLE_SESSION_NAME")
+                    .unwrap_or_else(|| DEFAULT_SESSION_NAME.to_string()),
+            });
+        }
+
+        chain.push(CredentialSource::Profile(
+            env("AWS_PROFILE").unwrap_or_else(|| "default".to_string()),
+        ));
+
+        if let Some(url) = container_url() {
+            chain.push(CredentialSource::Container { url });
+        }
+
+        if !env("AWS_EC2_METADATA_DISABLED").is_some_and(|value| value.eq_ignore_ascii_case("true"))
+        {
+            chain.push(CredentialSource::InstanceMetadata {
+                endpoint: imds_endpoint
+                    .map(str::to_string)
+                    .or_else(|| env("AWS_EC2_METADATA_SERVICE_ENDPOINT"))
+                    .unwrap_or_else(|| IMDS_ENDPOINT.to_string()),
+            });
+        }
+
+        CredentialSource::Chain(chain)
+    }
+
+    /// 获取当前有效的凭证，临时凭证在过期前自动刷新
+    pub async fn credentials(&self) -> Result<Credentials, StorageError> {
+        if let Some(credentials) = self.fresh() {
+            return Ok(credentials);
+        }
+
+        let _guard = self.refresh_lock.lock().await;
+        // 等待期间其他请求可能已经完成刷新
+        if let Some(credentials) = self.fresh() {
+            return Ok(credentials);
+        }
+
+        match self.fetch(&self.source, 0).await {
+            Ok(Some(credentials)) => {
+                *self.cached.write().unwrap() = Some(credentials.clone());
+                Ok(credentials)
+            }
+            Ok(None) => Err(StorageError::AuthenticationFailed(
+                "No credentials found in the connection config, environment, shared profiles, container endpoint or instance metadata".to_string(),
+            )),
+            Err(e) => match self.cached() {
+                // 提前刷新失败时继续使用尚未过期的凭证，下次请求再重试
+                Some(credentials) if !credentials.expires_within(0) => {
+                    log::warn!("Failed to refresh credentials, using cached credentials: {}", e);
+                    Ok(credentials)
+                }
+                _ => Err(e),
+            },
+        }
+    }
+
+    /// 最近一次获取的凭证，用于无法等待刷新的同步场景（如生成预签名 URL）
+    pub fn cached(&self) -> Option<Credentials> {
+        self.cached.read().unwrap().clone()
+    }
+
+    fn fresh(&self) -> Option<Credentials> {
+        self.cached()
+            .filter(|credentials| !credentials.expires_within(REFRESH_WINDOW_SECS))
+    }
+
+    /// 从指定来源获取凭证，来源未配置时返回 None
+    fn fetch<'a>(
+        &'a self,
+        source: &'a CredentialSource,
+        depth: usize,
+    ) -> BoxFuture<'a, Result<Option<Credentials>, StorageError>> {
+        async move {
+            if depth > MAX_SOURCE_DEPTH {
+                return Err(StorageError::InvalidConfig(
+                    "Credential source chain is too deep; check source_profile for cycles"
+                        .to_string(),
+                ));
+            }
+
+            match source {
+                CredentialSource::Static(credentials) => Ok(Some(credentials.clone())),
+                CredentialSource::Environment => Ok(Self::environment()),
+                CredentialSource::Profile(name) => match Self::profile_source(name)? {
+                    Some(source) => self.fetch(&source, depth + 1).await,
+                    None => Ok(None),
+                },
+                CredentialSource::WebIdentity {
+                    role_arn,
+                    token_file,
+                    session_name,
+                } => self
+                    .assume_role_with_web_identity(role_arn, token_file, session_name)
+                    .await
+                    .map(Some),
+                CredentialSource::Container { url } => self.fetch_container(url).await.map(Some),
+                CredentialSource::InstanceMetadata { endpoint } => {
+                    self.fetch_instance_metadata(endpoint).await
+                }
+                CredentialSource::AssumeRole {
+                    source,
+                    role_arn,
+                    session_name,
+                    external_id,
+                } => {
+                    let base = self.fetch(source, depth + 1).await?.ok_or_else(|| {
+                        StorageError::AuthenticationFailed(format!(
+                            "No source credentials to assume role {}",
+                            role_arn
+                        ))
+                    })?;
+                    self.assume_role(&base, role_arn, session_name, external_id.as_deref())
+                        .await
+                        .map(Some)
+                }
+                CredentialSource::Chain(sources) => {
+                    // 单个来源出错时继续尝试后续来源，全部失败时返回最后一个错误
+                    let mut last_error = None;
+                    for source in sources {
+                        match self.fetch(source, depth + 1).await {
+                            Ok(Some(credentials)) => return Ok(Some(credentials)),
+                            Ok(None) => {}
+                            Err(e) => {
+                                log::warn!("Credential source failed, trying next: {}", e);
+                                last_error = Some(e);
+                            }
+                        }
+                    }
+                    match last_error {
+                        Some(e) => Err(e),
+                        None => Ok(None),
+                    }
+                }
+            }
+        }
+        .boxed()
+    }
+
+    fn environment() -> Option<Credentials> {
+        let access_key = env("AWS_ACCESS_KEY_ID")?;
+        let secret_key = env("AWS_SECRET_ACCESS_KEY")?;
+        Some(Credentials::new(access_key, secret_key).with_session_token(env("AWS_SESSION_TOKEN")))
+    }
+
+    /// 解析共享配置文件中的 profile，profile 不存在时返回 None
+    fn profile_source(name: &str) -> Result<Option<CredentialSource>, StorageError> {
+        let (credentials_path, config_path) = aws_shared_config_paths()?;
+        let credentials = read_optional(&credentials_path)?
+            .map(|text| parse_ini(&text))
+            .unwrap_or_default();
+        let config = read_optional(&config_path)?
+            .map(|text| parse_ini(&text))
+            .unwrap_or_default();
+
+        // config 中除 default 外的 profile 以 "profile " 为前缀
+        let config_name = if name == "default" {
+            name.to_string()
+        } else {
+            format!("profile {}", name)
+        };
+        let sections: Vec<&IniSection> = credentials
+            .iter()
+            .filter(|section| section.name == name)
+            .chain(config.iter().filter(|section| section.name == config_name))
+            .collect();
+        if sections.is_empty() {
+            return Ok(None);
+        }
+        let get = |key: &str| sections.iter().find_map(|section| section.get(key));
+
+        let static_credentials = match (get("aws_access_key_id"), get("aws_secret_access_key")) {
+            (Some(access_key), Some(secret_key)) => Some(
+                Credentials::new(access_key, secret_key)
+                    .with_session_token(get("aws_session_token").map(str::to_string)),
+            ),
+            _ => None,
+        };
+
+        let Some(role_arn) = get("role_arn") else {
+            if let Some(credentials) = static_credentials {
+                return Ok(Some(CredentialSource::Static(credentials)));
+            }
+            if get("credential_process").is_some()
+                || get("sso_session").is_some()
+                || get("sso_start_url").is_some()
+            {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Profile {} uses credential_process or SSO, which is not supported",
+                    name
+                )));
+            }
+            return Ok(None);
+        };
+
+        let session_name = get("role_session_name")
+            .unwrap_or(DEFAULT_SESSION_NAME)
+            .to_string();
+        if let Some(token_file) = get("web_identity_token_file") {
+            return Ok(Some(CredentialSource::WebIdentity {
+                role_arn: role_arn.to_string(),
+                token_file: PathBuf::from(token_file),
+                session_name,
+            }));
+        }
+
+        let source = match (get("source_profile"), get("credential_source")) {
+            // source_profile 指向自身时使用该 profile 的密钥
+            (Some(source_profile), _) if source_profile == name => static_credentials
+                .map(CredentialSource::Static)
+                .ok_or_else(|| {
+                    StorageError::InvalidConfig(format!(
+                        "Profile {} references itself but has no access key",
+                        name
+                    ))
+                })?,
+            (Some(source_profile), _) => CredentialSource::Profile(source_profile.to_string()),
+            (None, Some("Environment")) => CredentialSource::Environment,
+            (None, Some("Ec2InstanceMetadata")) => CredentialSource::InstanceMetadata {
+                endpoint: env("AWS_EC2_METADATA_SERVICE_ENDPOINT")
+                    .unwrap_or_else(|| IMDS_ENDPOINT.to_string()),
+            },
+            (None, Some("EcsContainer")) => CredentialSource::Container {
+                url: container_url().ok_or_else(|| {
+                    StorageError::InvalidConfig(
+                        "credential_source EcsContainer requires AWS_CONTAINER_CREDENTIALS_RELATIVE_URI or AWS_CONTAINER_CREDENTIALS_FULL_URI".to_string(),
+                    )
+                })?,
+            },
+            (None, Some(other)) => {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Unsupported credential_source {} in profile {}",
+                    other, name
+                )))
+            }
+            (None, None) => {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Profile {} has role_arn but no source_profile or credential_source",
+                    name
+                )))
+            }
+        };
+
+        Ok(Some(CredentialSource::AssumeRole {
+            source: Box::new(source),
+            role_arn: role_arn.to_string(),
+            session_name,
+            external_id: get("external_id").map(str::to_string),
+        }))
+    }
+
+    fn sts_endpoint(&self) -> String {
+        self.sts_endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://sts.{}.amazonaws.com", self.region))
+    }
+
+    /// 按键名排序并编码 STS 查询参数
+    fn sts_query(params: &[(&str, &str)]) -> String {
+        let mut pairs: Vec<String> = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+            .collect();
+        pairs.sort();
+        pairs.join("&")
+    }
+
+    async fn assume_role(
+        &self,
+        base: &Credentials,
+        role_arn: &str,
+        session_name: &str,
+        external_id: Option<&str>,
+    ) -> Result<Credentials, StorageError> {
+        let duration = SESSION_DURATION_SECS.to_string();
+        let mut params = vec![
+            ("Action", "AssumeRole"),
+            ("Version", "2011-06-15"),
+            ("RoleArn", role_arn),
+            ("RoleSessionName", session_name),
+            ("DurationSeconds", duration.as_str()),
+        ];
+        if let Some(external_id) = external_id {
+            params.push(("ExternalId", external_id));
+        }
+        let query = Self::sts_query(&params);
+
+        let endpoint = url::Url::parse(&self.sts_endpoint())
+            .map_err(|e| StorageError::InvalidConfig(format!("Invalid STS endpoint: {}", e)))?;
+        let host = match (endpoint.host_str(), endpoint.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => {
+                return Err(StorageError::InvalidConfig(
+                    "Invalid STS endpoint: missing host".to_string(),
+                ))
+            }
+        };
+
+        let headers = build_sigv4_headers(
+            "GET",
+            endpoint.path(),
+            &HashMap::new(),
+            Some(&query),
+            base,
+            (&self.region, "sts"),
+            &host,
+        );
+        let mut request = self.client.get(format!("{}?{}", endpoint, query));
+        for (key, value) in headers {
+            request = request.header(&key, &value);
+        }
+
+        let response = request.send().await.map_err(StorageError::from_reqwest)?;
+        Self::parse_sts_response(response).await
+    }
+
+    async fn assume_role_with_web_identity(
+        &self,
+        role_arn: &str,
+        token_file: &Path,
+        session_name: &str,
+    ) -> Result<Credentials, StorageError> {
+        // 令牌文件由平台定期轮换，每次刷新时重新读取
+        let token = tokio::fs::read_to_string(token_file).await.map_err(|e| {
+            StorageError::IoError(format!(
+                "Failed to read web identity token {}: {}",
+                token_file.display(),
+                e
+            ))
+        })?;
+
+        let duration = SESSION_DURATION_SECS.to_string();
+        let query = Self::sts_query(&[
+            ("Action", "AssumeRoleWithWebIdentity"),
+            ("Version", "2011-06-15"),
+            ("RoleArn", role_arn),
+            ("RoleSessionName", session_name),
+            ("WebIdentityToken", token.trim()),
+            ("DurationSeconds", duration.as_str()),
+        ]);
+
+        // AssumeRoleWithWebIdentity 以令牌本身认证，不需要签名
+        let response = self
+            .client
+            .get(format!("{}/?{}", self.sts_endpoint(), query))
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+        Self::parse_sts_response(response).await
+    }
+
+    async fn parse_sts_response(response: reqwest::Response) -> Result<Credentials, StorageError> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await.map_err(StorageError::from_reqwest)?;
+        let values = xml_values(&body);
+
+        if !status.is_success() {
+            let message = values.get("Message").cloned().unwrap_or(body);
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("STS request failed: {}", message),
+            ));
+        }
+
+        let field = |name: &str| {
+            values.get(name).cloned().ok_or_else(|| {
+                StorageError::RequestFailed(format!("STS response is missing {}", name))
+            })
+        };
+        Ok(Credentials {
+            access_key_id: field("AccessKeyId")?,
+            secret_access_key: field("SecretAccessKey")?,
+            session_token: Some(field("SessionToken")?),
+            expiration: values
+                .get("Expiration")
+                .and_then(|value| parse_expiration(value)),
+        })
+    }
+
+    async fn fetch_container(&self, url: &str) -> Result<Credentials, StorageError> {
+        let mut request = self.client.get(url);
+        if let Some(token) = container_authorization()? {
+            request = request.header("Authorization", token);
+        }
+
+        let response = request.send().await.map_err(StorageError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!("Container credentials request to {} failed", url),
+            ));
+        }
+
+        let credentials: MetadataCredentials = response.json().await.map_err(|e| {
+            StorageError::RequestFailed(format!("Invalid container credentials response: {}", e))
+        })?;
+        Ok(credentials.into())
+    }
+
+    /// 从实例元数据服务获取实例角色的凭证，服务不可达或未绑定角色时返回 None
+    async fn fetch_instance_metadata(
+        &self,
+        endpoint: &str,
+    ) -> Result<Option<Credentials>, StorageError> {
+        let endpoint = endpoint.trim_end_matches('/');
+
+        let token = match self
+            .client
+            .put(format!("{}/latest/api/token", endpoint))
+            .header("X-aws-ec2-metadata-token-ttl-seconds", "21600")
+            .timeout(IMDS_TIMEOUT)
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => response.text().await.ok(),
+            // 不支持 IMDSv2 时退回 IMDSv1
+            Ok(_) => None,
+            Err(_) => return Ok(None),
+        };
+
+        let get = |path: &str| {
+            let mut request = self
+                .client
+                .get(format!(
+                    "{}/latest/meta-data/iam/security-credentials/{}",
+                    endpoint, path
+                ))
+                .timeout(IMDS_TIMEOUT);
+            if let Some(ref token) = token {
+                request = request.header("X-aws-ec2-metadata-token", token);
+            }
+            request
+        };
+
+        let response = get("").send().await.map_err(StorageError::from_reqwest)?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                "Instance metadata role request failed".to_string(),
+            ));
+        }
+        let roles = response.text().await.map_err(StorageError::from_reqwest)?;
+        let Some(role) = roles.lines().map(str::trim).find(|role| !role.is_empty()) else {
+            return Ok(None);
+        };
+
+        let response = get(role).send().await.map_err(StorageError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!("Instance metadata credentials request for {} failed", role),
+            ));
+        }
+        let credentials: MetadataCredentials = response.json().await.map_err(|e| {
+            StorageError::RequestFailed(format!("Invalid instance metadata credentials: {}", e))
+        })?;
+        Ok(Some(credentials.into()))
+    }
+}
+
+fn env(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+/// ECS 容器凭证端点地址
+fn container_url() -> Option<String> {
+    env("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI")
+        .map(|relative| format!("{}{}", ECS_CREDENTIALS_HOST, relative))
+        .or_else(|| env("AWS_CONTAINER_CREDENTIALS_FULL_URI"))
+}
+
+/// 容器凭证端点的授权令牌，令牌文件优先
+fn container_authorization() -> Result<Option<String>, StorageError> {
+    if let Some(path) = env("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE") {
+        return std::fs::read_to_string(&path)
+            .map(|token| Some(token.trim().to_string()))
+            .map_err(|e| {
+                StorageError::IoError(format!(
+                    "Failed to read container authorization token {}: {}",
+                    path, e
+                ))
+            });
+    }
+    Ok(env("AWS_CONTAINER_AUTHORIZATION_TOKEN"))
+}
+
+fn parse_expiration(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|expiration| expiration.with_timezone(&Utc))
+}
+
+/// 收集 XML 中叶子元素的文本，STS 响应和错误中的字段名不重复
+fn xml_values(xml_content: &str) -> HashMap<String, String> {
+    let mut reader = Reader::from_str(xml_content);
+    reader.trim_text(true);
+
+    let mut values = HashMap::new();
+    let mut buf = Vec::new();
+    let mut current: Option<String> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                current = Some(String::from_utf8_lossy(e.local_name().as_ref()).to_string());
+            }
+            Ok(Event::Text(e)) => {
+                if let Some(name) = current.take() {
+                    values
+                        .entry(name)
+                        .or_insert_with(|| e.unescape().unwrap_or_default().to_string());
+                }
+            }
+            Ok(Event::End(_)) => current = None,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    values
+}
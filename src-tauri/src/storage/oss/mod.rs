@@ -1,4 +1,5 @@
 pub mod auth;
+pub mod credentials;
 pub mod parser;
 
 // 重新导出认证相关功能
@@ -7,8 +8,11 @@ pub use auth::{
     generate_oss_presigned_url,
 };
 
+pub use credentials::{CredentialProvider, Credentials};
+
 // 重新导出解析相关功能
 pub use parser::{
     build_full_path, build_object_url, extract_object_key, normalize_uri_for_signing,
-    parse_list_objects_response,
+    parse_list_buckets_response, parse_list_objects_response, parse_list_versions_response,
+    split_version_id, BucketInfo,
 };
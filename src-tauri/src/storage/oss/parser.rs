@@ -3,7 +3,10 @@ use quick_xml::events::Event;
 use quick_xml::Reader;
 use urlencoding;
 
-use crate::storage::traits::{DirectoryResult, StorageError, StorageFile};
+use crate::storage::traits::{DirectoryResult, ObjectVersion, StorageError, StorageFile};
+
+/// 路径中指定对象历史版本的后缀，如 "data/labels.json?versionId=xxx"
+const VERSION_ID_SUFFIX: &str = "?versionId=";
 
 /// 解析 OSS 协议 URL 并返回对象键和实际 URL
 ///
@@ -116,9 +119,11 @@ pub fn normalize_uri_for_signing(uri: &str) -> String {
 }
 
 /// 解析 XML 列表响应
+/// recursive 为 true 时对应不带 delimiter 的请求，保留前缀下所有层级的对象，filename 为相对路径
 pub fn parse_list_objects_response(
     xml_content: &str,
     prefix: &str,
+    recursive: bool,
 ) -> Result<DirectoryResult, StorageError> {
     let mut reader = Reader::from_str(xml_content);
     reader.trim_text(true);
@@ -130,6 +135,7 @@ pub fn parse_list_objects_response(
     let mut current_text = String::new();
     let mut is_truncated = false;
     let mut next_marker: Option<String> = None;
+    let mut last_key: Option<String> = None;
 
     loop {
         match reader.read_event_into(&mut buf) {
@@ -169,6 +175,7 @@ pub fn parse_list_objects_response(
                                 .next()
                                 .unwrap_or(&current_text)
                                 .to_string();
+                            last_key = Some(current_text.clone());
                         }
                         "LastModified" => {
                             obj.lastmod = current_text.clone();
@@ -184,7 +191,13 @@ pub fn parse_list_objects_response(
                                 // 只添加当前前缀下的直接子项
                                 let relative_path =
                                     obj.filename.strip_prefix(prefix).unwrap_or(&obj.filename);
-                                if !relative_path.is_empty() && !relative_path.contains('/') {
+                                let is_child = if recursive {
+                                    // 以斜杠结尾的是目录占位对象
+                                    !relative_path.ends_with('/')
+                                } else {
+                                    !relative_path.contains('/')
+                                };
+                                if !relative_path.is_empty() && is_child {
                                     files.push(obj);
                                 }
                             }
@@ -250,6 +263,11 @@ pub fn parse_list_objects_response(
         buf.clear();
     }
 
+    // 不带 delimiter 的 V1 请求不返回 NextMarker，使用最后一个对象键继续分页
+    if is_truncated && next_marker.is_none() {
+        next_marker = last_key;
+    }
+
     Ok(DirectoryResult {
         files,
         has_more: is_truncated,
@@ -258,3 +276,223 @@ pub fn parse_list_objects_response(
         path: prefix.to_string(),
     })
 }
+
+/// ListBuckets 返回的存储桶信息
+#[derive(Debug, Clone, Default)]
+pub struct BucketInfo {
+    pub name: String,
+    pub creation_date: Option<String>,
+    /// 存储桶所在区域，S3 为 BucketRegion，阿里云为 Region（或去掉 oss- 前缀的 Location）
+    pub region: Option<String>,
+    /// 阿里云返回的外网访问域名，如 oss-cn-hangzhou.aliyuncs.com
+    pub extranet_endpoint: Option<String>,
+}
+
+/// 解析 ListBuckets（S3 `GET /`）响应，返回存储桶列表和下一页的标记
+pub fn parse_list_buckets_response(
+    xml_content: &str,
+) -> Result<(Vec<BucketInfo>, Option<String>), StorageError> {
+    let mut reader = Reader::from_str(xml_content);
+    reader.trim_text(true);
+
+    let mut buckets = Vec::new();
+    let mut buf = Vec::new();
+    let mut current_bucket: Option<BucketInfo> = None;
+    let mut current_text = String::new();
+    let mut is_truncated: Option<bool> = None;
+    let mut next_marker: Option<String> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                if e.name().as_ref() == b"Bucket" {
+                    current_bucket = Some(BucketInfo::default());
+                }
+                current_text.clear();
+            }
+            Ok(Event::Text(e)) => {
+                current_text = e.unescape().unwrap_or_default().to_string();
+            }
+            Ok(Event::End(ref e)) => {
+                let element_name_bytes = e.name();
+                let element_name = String::from_utf8_lossy(element_name_bytes.as_ref());
+
+                if let Some(ref mut bucket) = current_bucket {
+                    match element_name.as_ref() {
+                        "Name" => bucket.name = current_text.clone(),
+                        "CreationDate" => bucket.creation_date = Some(current_text.clone()),
+                        "Region" | "BucketRegion" => bucket.region = Some(current_text.clone()),
+                        "Location" if bucket.region.is_none() => {
+                            bucket.region = Some(
+                                current_text
+                                    .strip_prefix("oss-")
+                                    .unwrap_or(&current_text)
+                                    .to_string(),
+                            );
+                        }
+                        "ExtranetEndpoint" => bucket.extranet_endpoint = Some(current_text.clone()),
+                        "Bucket" => {
+                            if let Some(bucket) = current_bucket.take() {
+                                if !bucket.name.is_empty() {
+                                    buckets.push(bucket);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                } else {
+                    match element_name.as_ref() {
+                        "IsTruncated" => is_truncated = Some(current_text == "true"),
+                        // 阿里云、腾讯云使用 NextMarker，S3 使用 ContinuationToken
+                        "NextMarker" | "ContinuationToken" => {
+                            next_marker = Some(current_text.clone())
+                        }
+                        _ => {}
+                    }
+                }
+                current_text.clear();
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(StorageError::RequestFailed(format!(
+                    "XML parsing error: {}",
+                    e
+                )))
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    // S3 不返回 IsTruncated，仅在还有更多结果时返回 ContinuationToken
+    if is_truncated == Some(false) {
+        next_marker = None;
+    }
+
+    Ok((buckets, next_marker.filter(|marker| !marker.is_empty())))
+}
+
+/// 拆分带版本后缀的路径，返回 (对象路径, versionId)
+pub fn split_version_id(path: &str) -> (&str, Option<String>) {
+    match path.rsplit_once(VERSION_ID_SUFFIX) {
+        Some((object_path, version_id)) if !version_id.is_empty() => {
+            let version_id = urlencoding::decode(version_id)
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| version_id.to_string());
+            (object_path, Some(version_id))
+        }
+        _ => (path, None),
+    }
+}
+
+/// 构建指向对象指定版本的路径
+pub fn versioned_path(path: &str, version_id: &str) -> String {
+    format!(
+        "{}{}{}",
+        path,
+        VERSION_ID_SUFFIX,
+        urlencoding::encode(version_id)
+    )
+}
+
+/// ListObjectVersions 的分页标记 (NextKeyMarker, NextVersionIdMarker)
+pub type VersionMarkers = (String, String);
+
+/// 解析 ListObjectVersions（`GET /?versions`）响应，只保留 object_key 的版本和删除标记
+/// 返回的版本路径基于 path 构建；还有更多结果时同时返回下一页的分页标记
+pub fn parse_list_versions_response(
+    xml_content: &str,
+    object_key: &str,
+    path: &str,
+) -> Result<(Vec<ObjectVersion>, Option<VersionMarkers>), StorageError> {
+    let mut reader = Reader::from_str(xml_content);
+    reader.trim_text(true);
+
+    let mut versions = Vec::new();
+    let mut buf = Vec::new();
+    let mut current_version: Option<(String, ObjectVersion)> = None;
+    let mut current_text = String::new();
+    let mut is_truncated = false;
+    let mut next_key_marker = String::new();
+    let mut next_version_id_marker = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let element_name = e.name();
+                if element_name.as_ref() == b"Version" || element_name.as_ref() == b"DeleteMarker" {
+                    current_version = Some((
+                        String::new(),
+                        ObjectVersion {
+                            path: String::new(),
+                            version_id: String::new(),
+                            is_latest: false,
+                            is_delete_marker: element_name.as_ref() == b"DeleteMarker",
+                            lastmod: String::new(),
+                            size: "0".to_string(),
+                            etag: None,
+                            storage_class: None,
+                        },
+                    ));
+                }
+                current_text.clear();
+            }
+            Ok(Event::Text(e)) => {
+                current_text = e.unescape().unwrap_or_default().to_string();
+            }
+            Ok(Event::End(ref e)) => {
+                let element_name_bytes = e.name();
+                let element_name = String::from_utf8_lossy(element_name_bytes.as_ref());
+
+                if let Some((ref mut key, ref mut version)) = current_version {
+                    match element_name.as_ref() {
+                        "Key" => *key = current_text.clone(),
+                        "VersionId" => version.version_id = current_text.clone(),
+                        "IsLatest" => version.is_latest = current_text == "true",
+                        "LastModified" => version.lastmod = current_text.clone(),
+                        "Size" => {
+                            version.size = current_text.parse::<u64>().unwrap_or(0).to_string()
+                        }
+                        "ETag" => version.etag = Some(current_text.trim_matches('"').to_string()),
+                        "StorageClass" => version.storage_class = Some(current_text.clone()),
+                        "Version" | "DeleteMarker" => {
+                            if let Some((key, mut version)) = current_version.take() {
+                                // prefix 查询会返回以该键开头的其他对象
+                                if key == object_key {
+                                    version.path = versioned_path(path, &version.version_id);
+                                    versions.push(version);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                } else {
+                    match element_name.as_ref() {
+                        "IsTruncated" => is_truncated = current_text == "true",
+                        "NextKeyMarker" => next_key_marker = current_text.clone(),
+                        "NextVersionIdMarker" => next_version_id_marker = current_text.clone(),
+                        _ => {}
+                    }
+                }
+                current_text.clear();
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(StorageError::RequestFailed(format!(
+                    "XML parsing error: {}",
+                    e
+                )))
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    let next_markers = if is_truncated && !next_key_marker.is_empty() {
+        Some((next_key_marker, next_version_id_marker))
+    } else {
+        None
+    };
+
+    Ok((versions, next_markers))
+}
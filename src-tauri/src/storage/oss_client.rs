@@ -2,18 +2,22 @@ use async_trait::async_trait;
 use reqwest::Client;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use url::Url;
 use urlencoding;
 
 use crate::storage::oss::{
     build_aws_auth_headers, build_full_path, build_object_url, build_oss_auth_headers,
     extract_object_key, generate_aws_presigned_url, generate_oss_presigned_url,
-    normalize_uri_for_signing, parse_list_objects_response,
+    normalize_uri_for_signing, parse_list_buckets_response, parse_list_objects_response,
+    parse_list_versions_response, split_version_id, BucketInfo, CredentialProvider, Credentials,
 };
 use crate::storage::traits::{
-    ConnectionConfig, DirectoryResult, ListOptions, ProgressCallback, StorageClient, StorageError,
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ObjectVersion,
+    ProgressCallback, StorageClient, StorageError, StorageFile,
 };
-use crate::utils::http_downloader::HttpDownloader;
+use crate::utils::http_downloader::{HttpDownloadConfig, HttpDownloader};
+use crate::utils::http_uploader::HttpUploader;
 
 #[derive(Debug, Clone, PartialEq)]
 enum OSSPlatform {
@@ -30,12 +34,18 @@ pub struct OSSClient {
     config: ConnectionConfig,
     connected: AtomicBool,
     endpoint: String,
-    access_key: String,
-    secret_key: String,
-    bucket: String,
+    // 固定密钥或凭证链提供的凭证，临时凭证在过期前自动刷新
+    credentials: Arc<CredentialProvider>,
+    // 匿名访问公开存储桶，请求不签名
+    anonymous: bool,
+    bucket: String, // 为空时列出所有可访问的存储桶，路径第一级为存储桶名称
     prefix: String, // 从 bucket 字段解析出的路径前缀
     region: Option<String>,
     platform: OSSPlatform,
+    // 未配置 bucket 时最近一次 ListBuckets 的结果
+    buckets: Mutex<HashMap<String, BucketInfo>>,
+    // 未配置 bucket 时按存储桶创建的子客户端，共享凭证和 HTTP 客户端
+    bucket_clients: Mutex<HashMap<String, Arc<OSSClient>>>,
 }
 
 impl OSSClient {
@@ -45,36 +55,16 @@ impl OSSClient {
             .clone()
             .ok_or_else(|| StorageError::InvalidConfig("OSS endpoint is required".to_string()))?;
 
-        let access_key = config
-            .access_key
-            .clone()
-            .ok_or_else(|| StorageError::InvalidConfig("OSS access key is required".to_string()))?;
-
-        let secret_key = config
-            .secret_key
-            .clone()
-            .ok_or_else(|| StorageError::InvalidConfig("OSS secret key is required".to_string()))?;
-
-        let bucket_input = config
-            .bucket
-            .clone()
-            .ok_or_else(|| StorageError::InvalidConfig("OSS bucket is required".to_string()))?;
+        // 未配置密钥时通过凭证链获取（环境变量、共享配置文件、容器或实例角色等）
+        let credentials = Arc::new(CredentialProvider::from_config(&config));
+        let anonymous = Self::is_anonymous(&config);
 
-        // 解析 bucket 字段，支持 "bucket/path/prefix" 格式
-        let (bucket, prefix) = if let Some(slash_pos) = bucket_input.find('/') {
-            let bucket = bucket_input[..slash_pos].to_string();
-            let prefix = bucket_input[slash_pos + 1..].to_string();
-            (
-                bucket,
-                if prefix.ends_with('/') {
-                    prefix
-                } else {
-                    format!("{}/", prefix)
-                },
-            )
-        } else {
-            (bucket_input, String::new())
-        };
+        let (bucket, prefix) = Self::parse_bucket_input(config.bucket.as_deref().unwrap_or(""));
+        if anonymous && bucket.is_empty() {
+            return Err(StorageError::InvalidConfig(
+                "OSS bucket is required for anonymous access".to_string(),
+            ));
+        }
 
         let region = config.region.clone();
         let platform = Self::detect_platform(&endpoint);
@@ -84,15 +74,38 @@ impl OSSClient {
             config,
             connected: AtomicBool::new(false),
             endpoint,
-            access_key,
-            secret_key,
+            credentials,
+            anonymous,
             bucket,
             prefix,
             region,
             platform,
+            buckets: Mutex::new(HashMap::new()),
+            bucket_clients: Mutex::new(HashMap::new()),
         })
     }
 
+    /// 解析 bucket 字段，支持 "bucket/path/prefix" 格式
+    fn parse_bucket_input(bucket_input: &str) -> (String, String) {
+        let bucket_input = bucket_input.trim_matches('/');
+        if let Some(slash_pos) = bucket_input.find('/') {
+            let bucket = bucket_input[..slash_pos].to_string();
+            let prefix = bucket_input[slash_pos + 1..].to_string();
+            (bucket, format!("{}/", prefix))
+        } else {
+            (bucket_input.to_string(), String::new())
+        }
+    }
+
+    /// extra_options 中 anonymous 为 true 时匿名访问（公开数据集）
+    fn is_anonymous(config: &ConnectionConfig) -> bool {
+        config
+            .extra_options
+            .as_ref()
+            .and_then(|options| options.get("anonymous"))
+            .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+    }
+
     /// 根据端点检测OSS平台类型
     fn detect_platform(endpoint: &str) -> OSSPlatform {
         let endpoint_lower = endpoint.to_lowercase();
@@ -112,17 +125,22 @@ impl OSSClient {
         }
     }
 
-    /// 构建认证头
-    fn build_auth_headers(
+    /// 构建认证头，使用的临时凭证即将过期时先刷新
+    async fn build_auth_headers(
         &self,
         method: &str,
         uri: &str,
         extra_headers: &HashMap<String, String>,
         query_string: Option<&str>,
-    ) -> HashMap<String, String> {
+    ) -> Result<HashMap<String, String>, StorageError> {
+        if self.anonymous {
+            return Ok(extra_headers.clone());
+        }
+
         let host = self.get_host();
+        let credentials = self.credentials.credentials().await?;
 
-        match self.platform {
+        Ok(match self.platform {
             OSSPlatform::AwsS3 => {
                 let region = self
                     .region
@@ -134,8 +152,7 @@ impl OSSClient {
                     uri,
                     extra_headers,
                     query_string,
-                    &self.access_key,
-                    &self.secret_key,
+                    &credentials,
                     &region,
                     &host,
                 )
@@ -145,12 +162,11 @@ impl OSSClient {
                 method,
                 uri,
                 extra_headers,
-                &self.access_key,
-                &self.secret_key,
+                &credentials,
                 &self.bucket,
                 &host,
             ),
-        }
+        })
     }
 
     /// 从 endpoint 提取 region（仅用于AWS S3）
@@ -180,16 +196,26 @@ impl OSSClient {
         }
     }
 
-    /// 生成预签名下载 URL
+    /// 生成预签名下载 URL，使用临时凭证时有效期不超过凭证的剩余时间
+    /// 指定 version_id 时 URL 指向对象的历史版本，versionId 参与签名
     fn generate_download_url(
         &self,
         object_key: &str,
         expires_in_seconds: i64,
+        credentials: &Credentials,
+        version_id: Option<&str>,
     ) -> Result<String, StorageError> {
         if !self.connected.load(Ordering::Relaxed) {
             return Err(StorageError::NotConnected);
         }
 
+        let expires_in_seconds = match credentials.expiration {
+            Some(expiration) => {
+                expires_in_seconds.min((expiration - chrono::Utc::now()).num_seconds().max(1))
+            }
+            None => expires_in_seconds,
+        };
+
         // 根据平台选择不同的预签名URL算法
         if self.platform == OSSPlatform::AwsS3 {
             let region = if let Some(region) = &self.region {
@@ -204,10 +230,10 @@ impl OSSClient {
                 &self.endpoint,
                 object_key,
                 expires_in_seconds,
-                &self.access_key,
-                &self.secret_key,
+                credentials,
                 &region,
                 &self.bucket,
+                version_id,
             )
             .map_err(|e| StorageError::RequestFailed(e))
         } else {
@@ -216,21 +242,275 @@ impl OSSClient {
                 &self.endpoint,
                 object_key,
                 expires_in_seconds,
-                &self.access_key,
-                &self.secret_key,
+                credentials,
                 &self.bucket,
+                version_id,
             )
             .map_err(|e| StorageError::RequestFailed(e))
         }
     }
 
+    /// 获取对象的下载 URL，匿名访问时直接使用不签名的对象 URL
+    async fn download_url(
+        &self,
+        object_key: &str,
+        expires_in_seconds: i64,
+        version_id: Option<&str>,
+    ) -> Result<String, StorageError> {
+        if self.anonymous {
+            return Ok(self.build_object_request_target(object_key, version_id).0);
+        }
+
+        let credentials = self.credentials.credentials().await?;
+        self.generate_download_url(object_key, expires_in_seconds, &credentials, version_id)
+    }
+
+    /// 列出当前凭证可访问的所有存储桶（S3 `GET /`，阿里云 ListBuckets）
+    async fn list_buckets(&self) -> Result<Vec<BucketInfo>, StorageError> {
+        let mut buckets = Vec::new();
+        let mut marker: Option<String> = None;
+
+        loop {
+            let mut query_params = Vec::new();
+            if self.platform == OSSPlatform::AliyunOSS {
+                query_params.push(("max-keys".to_string(), "1000".to_string()));
+            }
+            if let Some(marker) = &marker {
+                let param_name = if self.platform == OSSPlatform::AwsS3 {
+                    "continuation-token"
+                } else {
+                    "marker"
+                };
+                query_params.push((param_name.to_string(), marker.clone()));
+            }
+
+            let query_string = query_params
+                .iter()
+                .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+                .collect::<Vec<_>>()
+                .join("&");
+            let url = if query_string.is_empty() {
+                format!("{}/", self.endpoint.trim_end_matches('/'))
+            } else {
+                format!("{}/?{}", self.endpoint.trim_end_matches('/'), query_string)
+            };
+
+            let headers = self
+                .build_auth_headers("GET", "/", &HashMap::new(), Some(&query_string))
+                .await?;
+            let mut req_builder = self.client.get(&url);
+            for (key, value) in headers {
+                req_builder = req_builder.header(&key, &value);
+            }
+
+            let response = req_builder.send().await.map_err(|e| {
+                StorageError::NetworkError(format!("List buckets request failed: {}", e))
+            })?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let headers = response.headers().clone();
+                let body = response.text().await.unwrap_or_default();
+                return Err(StorageError::from_http_status(
+                    status,
+                    &headers,
+                    format!("List buckets failed with status {}: {}", status, body),
+                ));
+            }
+
+            let xml_content = response.text().await.map_err(|e| {
+                StorageError::NetworkError(format!("Failed to read response body: {}", e))
+            })?;
+            let (page, next_marker) = parse_list_buckets_response(&xml_content)?;
+            buckets.extend(page);
+
+            match next_marker {
+                Some(next_marker) => marker = Some(next_marker),
+                None => break,
+            }
+        }
+
+        // 记录存储桶信息，访问存储桶时据此确定其区域和端点
+        let mut index = self.buckets.lock().unwrap();
+        index.clear();
+        for bucket in &buckets {
+            index.insert(bucket.name.clone(), bucket.clone());
+        }
+
+        Ok(buckets)
+    }
+
+    /// 查询 S3 存储桶所在区域，HEAD 存储桶的响应头 x-amz-bucket-region 无需权限即可获得
+    async fn resolve_bucket_region(&self, bucket: &str) -> Option<String> {
+        let response = self
+            .client
+            .head(format!("https://{}.s3.amazonaws.com", bucket))
+            .send()
+            .await
+            .ok()?;
+
+        response
+            .headers()
+            .get("x-amz-bucket-region")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    }
+
+    /// 根据存储桶所在区域构建虚拟主机风格的端点，返回 (端点, 区域)
+    fn bucket_endpoint(&self, info: &BucketInfo) -> (String, Option<String>) {
+        let scheme = Url::parse(&self.endpoint)
+            .map(|url| url.scheme().to_string())
+            .unwrap_or_else(|_| "https".to_string());
+        let region = info.region.clone().or_else(|| self.region.clone());
+        let bucket = &info.name;
+
+        let host = match (&self.platform, region.as_deref()) {
+            (OSSPlatform::AliyunOSS, _) => {
+                let service_host = info
+                    .extranet_endpoint
+                    .clone()
+                    .or_else(|| {
+                        info.region
+                            .as_ref()
+                            .map(|region| format!("oss-{}.aliyuncs.com", region))
+                    })
+                    .unwrap_or_else(|| self.get_host());
+                format!("{}.{}", bucket, service_host)
+            }
+            (OSSPlatform::AwsS3, None | Some("us-east-1")) => {
+                format!("{}.s3.amazonaws.com", bucket)
+            }
+            (OSSPlatform::AwsS3, Some(region)) => {
+                format!("{}.s3.{}.amazonaws.com", bucket, region)
+            }
+            (OSSPlatform::TencentCOS, Some(region)) => {
+                format!("{}.cos.{}.myqcloud.com", bucket, region)
+            }
+            (OSSPlatform::HuaweiOBS, Some(region)) => {
+                format!("{}.obs.{}.myhuaweicloud.com", bucket, region)
+            }
+            (OSSPlatform::TencentCOS | OSSPlatform::HuaweiOBS, None) => {
+                format!("{}.{}", bucket, self.get_host())
+            }
+            // MinIO 等自定义平台使用路径风格，沿用原端点
+            (OSSPlatform::MinIO | OSSPlatform::Custom, _) => {
+                return (self.endpoint.clone(), region)
+            }
+        };
+
+        (format!("{}://{}", scheme, host), region)
+    }
+
+    /// 获取存储桶的子客户端，首次访问时确定区域并创建
+    async fn bucket_client(&self, bucket: &str) -> Result<Arc<OSSClient>, StorageError> {
+        if bucket.is_empty() {
+            return Err(StorageError::RequestFailed(
+                "Path must start with a bucket name".to_string(),
+            ));
+        }
+        if let Some(client) = self.bucket_clients.lock().unwrap().get(bucket) {
+            return Ok(client.clone());
+        }
+
+        // 不在列表中的存储桶（如直接打开的 oss:// 链接）按名称访问
+        let mut info = self
+            .buckets
+            .lock()
+            .unwrap()
+            .get(bucket)
+            .cloned()
+            .unwrap_or_else(|| BucketInfo {
+                name: bucket.to_string(),
+                ..Default::default()
+            });
+        if self.platform == OSSPlatform::AwsS3 && info.region.is_none() {
+            info.region = self.resolve_bucket_region(bucket).await;
+        }
+
+        let (endpoint, region) = self.bucket_endpoint(&info);
+        let mut config = self.config.clone();
+        config.url = Some(endpoint);
+        config.bucket = Some(bucket.to_string());
+        config.region = region;
+
+        let mut client = OSSClient::new(config)?;
+        client.client = self.client.clone();
+        client.credentials = self.credentials.clone();
+        client.connected.store(true, Ordering::Relaxed);
+
+        let client = Arc::new(client);
+        self.bucket_clients
+            .lock()
+            .unwrap()
+            .insert(bucket.to_string(), client.clone());
+        Ok(client)
+    }
+
+    /// 将路径拆分为存储桶名称和桶内路径（仅在未配置 bucket 时使用）
+    /// 子客户端没有路径前缀，oss://bucket/key 与 bucket/key 等价
+    fn split_bucket_path(path: &str) -> (String, String) {
+        let path = path.strip_prefix("oss://").unwrap_or(path);
+        let trimmed = path.trim_start_matches('/');
+        match trimmed.split_once('/') {
+            Some((bucket, rest)) => (bucket.to_string(), rest.to_string()),
+            None => (trimmed.to_string(), String::new()),
+        }
+    }
+
+    /// 解析路径对应的存储桶子客户端和桶内路径
+    async fn route(&self, path: &str) -> Result<(Arc<OSSClient>, String), StorageError> {
+        let (bucket, rest) = Self::split_bucket_path(path);
+        Ok((self.bucket_client(&bucket).await?, rest))
+    }
+
+    /// 写操作的目标必须是存储桶内的对象，不支持创建或覆盖存储桶
+    async fn route_object(&self, path: &str) -> Result<(Arc<OSSClient>, String), StorageError> {
+        let (client, rest) = self.route(path).await?;
+        if rest.trim_matches('/').is_empty() {
+            return Err(StorageError::ProtocolNotSupported(
+                "Creating or modifying buckets is not supported".to_string(),
+            ));
+        }
+        Ok((client, rest))
+    }
+
+    /// 以目录形式列出所有存储桶
+    async fn list_buckets_as_directory(&self) -> Result<DirectoryResult, StorageError> {
+        let buckets = self.list_buckets().await?;
+        let files = buckets
+            .into_iter()
+            .map(|bucket| StorageFile {
+                filename: bucket.name.clone(),
+                basename: bucket.name,
+                lastmod: bucket.creation_date.unwrap_or_default(),
+                size: "0".to_string(),
+                file_type: "directory".to_string(),
+                mime: None,
+                etag: None,
+            })
+            .collect();
+
+        Ok(DirectoryResult {
+            files,
+            has_more: false,
+            next_marker: None,
+            total_count: None,
+            path: String::new(),
+        })
+    }
+
     /// 使用 HTTP 请求列出目录内容
     async fn list_directory_with_http(
         &self,
         prefix: &str,
         options: &ListOptions,
     ) -> Result<DirectoryResult, StorageError> {
-        let mut query_params = vec![("delimiter".to_string(), "/".to_string())];
+        // 递归列出时不使用 delimiter，一次返回前缀下所有层级的对象
+        let recursive = options.recursive.unwrap_or(false);
+        let mut query_params = Vec::new();
+        if !recursive {
+            query_params.push(("delimiter".to_string(), "/".to_string()));
+        }
 
         // 只对 AWS S3 使用 list-type=2
         if self.platform == OSSPlatform::AwsS3 {
@@ -260,6 +540,93 @@ impl OSSClient {
             .collect::<Vec<_>>()
             .join("&");
 
+        let (bucket_url, signing_uri) = self.build_bucket_request_target();
+        let url = format!("{}?{}", bucket_url, query_string);
+
+        let headers = self
+            .build_auth_headers("GET", &signing_uri, &HashMap::new(), Some(&query_string))
+            .await?;
+        let mut req_builder = self.client.get(&url);
+
+        for (key, value) in headers {
+            req_builder = req_builder.header(&key, &value);
+        }
+
+        let response = req_builder.send().await.map_err(|e| {
+            StorageError::NetworkError(format!("List directory request failed: {}", e))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("List directory failed with status {}: {}", status, body),
+            ));
+        }
+
+        let xml_content = response.text().await.map_err(|e| {
+            StorageError::NetworkError(format!("Failed to read response body: {}", e))
+        })?;
+
+        parse_list_objects_response(&xml_content, prefix, recursive)
+    }
+
+    /// 历史版本只读，写操作只能作用于对象的当前版本
+    fn ensure_current_version(path: &str) -> Result<(), StorageError> {
+        if split_version_id(path).1.is_some() {
+            return Err(StorageError::ProtocolNotSupported(
+                "Object versions are read-only".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 解析路径为对象键（支持 oss:// 协议 URL 和相对路径）
+    fn resolve_object_key(&self, path: &str) -> Result<String, StorageError> {
+        extract_object_key(
+            path,
+            &self.endpoint,
+            self.config.bucket.as_ref().unwrap_or(&String::new()),
+            &self.prefix,
+        )
+    }
+
+    /// 构建对象请求的 URL、签名 URI 和查询字符串
+    /// 指定 version_id 时请求对象的历史版本：S3 的 versionId 在规范查询字符串中签名，
+    /// 其他平台作为子资源附加在签名 URI 上
+    fn build_object_request_target(
+        &self,
+        object_key: &str,
+        version_id: Option<&str>,
+    ) -> (String, String, Option<String>) {
+        let mut url = build_object_url(&self.endpoint, object_key);
+        let uri = if let Ok(parsed_url) = Url::parse(&url) {
+            parsed_url.path().to_string()
+        } else {
+            // 如果无法解析URL，则直接使用编码后的路径
+            format!("/{}", urlencoding::encode(object_key))
+        };
+
+        // 对于签名，使用解码后的URI（OSS签名需要原始的未编码路径）
+        let mut signing_uri = normalize_uri_for_signing(&uri);
+        let query_string = version_id.map(|version_id| {
+            if self.platform != OSSPlatform::AwsS3 {
+                signing_uri.push_str(&format!("?versionId={}", version_id));
+            }
+            format!("versionId={}", urlencoding::encode(version_id))
+        });
+        if let Some(query_string) = &query_string {
+            url.push_str(&format!("?{}", query_string));
+        }
+
+        (url, signing_uri, query_string)
+    }
+
+    /// 构建存储桶级请求的 URL（不含查询字符串）和签名 URI
+    fn build_bucket_request_target(&self) -> (String, String) {
         // 获取实际的 bucket 名称（不包含路径前缀）
         let actual_bucket = if let Some(slash_pos) = self.config.bucket.as_ref().unwrap().find('/')
         {
@@ -281,11 +648,10 @@ impl OSSClient {
             false
         };
 
-        let (signing_uri, url) = if is_virtual_hosted {
+        if is_virtual_hosted {
             // 虚拟主机风格 - AWS S3
-            let signing_uri = "/".to_string();
-            let list_url = format!("{}/?{}", self.endpoint.trim_end_matches('/'), query_string);
-            (signing_uri, list_url)
+            let url = format!("{}/", self.endpoint.trim_end_matches('/'));
+            (url, "/".to_string())
         } else {
             // 路径风格 - 对于 AWS S3，签名 URI 应该包含 bucket 名称
             let signing_uri = if self.platform == OSSPlatform::AwsS3 {
@@ -293,41 +659,278 @@ impl OSSClient {
             } else {
                 "/".to_string()
             };
-            let list_url = format!(
-                "{}/{}?{}",
-                self.endpoint.trim_end_matches('/'),
-                actual_bucket,
-                query_string
-            );
-            (signing_uri, list_url)
+            let url = format!("{}/{}", self.endpoint.trim_end_matches('/'), actual_bucket);
+            (url, signing_uri)
+        }
+    }
+
+    /// 发送不带请求体的签名对象请求
+    async fn send_object_request(
+        &self,
+        method: &str,
+        object_key: &str,
+        version_id: Option<&str>,
+        extra_headers: &HashMap<String, String>,
+    ) -> Result<reqwest::Response, StorageError> {
+        let (url, signing_uri, query_string) =
+            self.build_object_request_target(object_key, version_id);
+        let auth_headers = self
+            .build_auth_headers(method, &signing_uri, extra_headers, query_string.as_deref())
+            .await?;
+
+        let mut req_builder = match method {
+            "PUT" => self.client.put(&url).header("Content-Length", "0"),
+            "DELETE" => self.client.delete(&url),
+            "HEAD" => self.client.head(&url),
+            _ => self.client.get(&url),
         };
+        for (key, value) in auth_headers {
+            req_builder = req_builder.header(&key, &value);
+        }
 
-        let headers =
-            self.build_auth_headers("GET", &signing_uri, &HashMap::new(), Some(&query_string));
-        let mut req_builder = self.client.get(&url);
+        req_builder
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("{} request failed: {}", method, e)))
+    }
 
-        for (key, value) in headers {
-            req_builder = req_builder.header(&key, &value);
+    /// 根据 HEAD 响应头构建对象元数据
+    fn metadata_from_headers(
+        path: &str,
+        object_key: &str,
+        headers: &reqwest::header::HeaderMap,
+    ) -> FileMetadata {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+
+        let mut extra = HashMap::new();
+        for (name, value) in headers {
+            let name = name.as_str();
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+
+            // 用户自定义元数据
+            if let Some(meta_key) = name
+                .strip_prefix("x-oss-meta-")
+                .or_else(|| name.strip_prefix("x-amz-meta-"))
+            {
+                extra.insert(format!("meta:{}", meta_key), value.to_string());
+                continue;
+            }
+
+            let key = match name {
+                "x-oss-storage-class" | "x-amz-storage-class" => "storageClass",
+                "x-oss-object-type" => "objectType",
+                "x-oss-version-id" | "x-amz-version-id" => "versionId",
+                "x-oss-server-side-encryption" | "x-amz-server-side-encryption" => {
+                    "serverSideEncryption"
+                }
+                "x-oss-restore" | "x-amz-restore" => "restore",
+                "cache-control" => "cacheControl",
+                "content-encoding" => "contentEncoding",
+                "content-disposition" => "contentDisposition",
+                _ => continue,
+            };
+            extra.insert(key.to_string(), value.to_string());
         }
 
-        let response = req_builder.send().await.map_err(|e| {
-            StorageError::NetworkError(format!("List directory request failed: {}", e))
-        })?;
+        // 校验和：优先使用服务端提供的哈希值
+        let checksum = header("x-oss-hash-crc64ecma")
+            .map(|v| format!("crc64ecma:{}", v))
+            .or_else(|| header("x-amz-checksum-sha256").map(|v| format!("sha256:{}", v)))
+            .or_else(|| header("x-amz-checksum-crc32").map(|v| format!("crc32:{}", v)))
+            .or_else(|| header("content-md5").map(|v| format!("md5:{}", v)));
+
+        // Last-Modified 为 HTTP 日期格式，统一转换为 RFC3339
+        let lastmod = header("last-modified")
+            .map(|v| {
+                chrono::DateTime::parse_from_rfc2822(&v)
+                    .map(|dt| dt.with_timezone(&chrono::Utc).to_rfc3339())
+                    .unwrap_or(v)
+            })
+            .unwrap_or_default();
+
+        FileMetadata {
+            filename: path.to_string(),
+            basename: object_key
+                .rsplit('/')
+                .next()
+                .unwrap_or(object_key)
+                .to_string(),
+            lastmod,
+            size: header("content-length").unwrap_or_else(|| "0".to_string()),
+            file_type: "file".to_string(),
+            mime: header("content-type"),
+            etag: header("etag").map(|v| v.trim_matches('"').to_string()),
+            checksum,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra,
+        }
+    }
+
+    /// 检查对象是否存在
+    async fn object_exists(&self, object_key: &str) -> Result<bool, StorageError> {
+        let response = self
+            .send_object_request("HEAD", object_key, None, &HashMap::new())
+            .await?;
+
+        if response.status().is_success() {
+            Ok(true)
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Ok(false)
+        } else {
+            Err(StorageError::RequestFailed(format!(
+                "Head request failed with status: {}",
+                response.status()
+            )))
+        }
+    }
+
+    /// 删除单个对象，对象不存在时视为成功
+    async fn delete_object(&self, object_key: &str) -> Result<(), StorageError> {
+        let response = self
+            .send_object_request("DELETE", object_key, None, &HashMap::new())
+            .await?;
 
         let status = response.status();
-        if !status.is_success() {
+        if status.is_success() || status == reqwest::StatusCode::NOT_FOUND {
+            Ok(())
+        } else {
             let body = response.text().await.unwrap_or_default();
-            return Err(StorageError::RequestFailed(format!(
-                "List directory failed with status {}: {}",
+            Err(StorageError::RequestFailed(format!(
+                "Delete object failed with status {}: {}",
                 status, body
-            )));
+            )))
         }
+    }
 
-        let xml_content = response.text().await.map_err(|e| {
-            StorageError::NetworkError(format!("Failed to read response body: {}", e))
-        })?;
+    /// 在服务端复制对象
+    async fn copy_object(&self, source_key: &str, dest_key: &str) -> Result<(), StorageError> {
+        let copy_source = format!("/{}/{}", self.bucket, urlencoding::encode(source_key));
+        let header_name = if self.platform == OSSPlatform::AwsS3 {
+            "x-amz-copy-source"
+        } else {
+            "x-oss-copy-source"
+        };
+
+        let mut headers = HashMap::new();
+        headers.insert(header_name.to_string(), copy_source);
+
+        let response = self
+            .send_object_request("PUT", dest_key, None, &headers)
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("Copy object failed with status {}: {}", status, body),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 收集前缀下的所有对象键，返回 (文件对象键, 目录前缀)
+    async fn collect_object_keys(
+        &self,
+        prefix: &str,
+    ) -> Result<(Vec<String>, Vec<String>), StorageError> {
+        let mut object_keys = Vec::new();
+        let mut directory_prefixes = Vec::new();
+        let mut pending = vec![prefix.to_string()];
+
+        while let Some(current_prefix) = pending.pop() {
+            let mut marker: Option<String> = None;
+            loop {
+                let options = ListOptions {
+                    page_size: Some(1000),
+                    marker: marker.clone(),
+                    prefix: None,
+                    recursive: Some(false),
+                    ..Default::default()
+                };
+                let result = self
+                    .list_directory_with_http(&current_prefix, &options)
+                    .await?;
+
+                for file in result.files {
+                    if file.file_type == "directory" {
+                        pending.push(format!("{}{}/", current_prefix, file.filename));
+                    } else {
+                        object_keys.push(format!("{}{}", current_prefix, file.filename));
+                    }
+                }
+
+                if result.has_more && result.next_marker.is_some() {
+                    marker = result.next_marker;
+                } else {
+                    break;
+                }
+            }
+            directory_prefixes.push(current_prefix);
+        }
+
+        Ok((object_keys, directory_prefixes))
+    }
+
+    /// 以 PUT 请求上传对象内容，供 put_file 和 put_stream 共用
+    async fn put_object_body(
+        &self,
+        path: &str,
+        body: reqwest::Body,
+        file_size: u64,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        Self::ensure_current_version(path)?;
+        let object_key = self.resolve_object_key(path)?;
+        let (url, signing_uri, _) = self.build_object_request_target(&object_key, None);
+
+        let mut headers = HashMap::new();
+        if self.platform == OSSPlatform::AwsS3 {
+            // 流式请求体无法预先计算哈希
+            headers.insert(
+                "x-amz-content-sha256".to_string(),
+                "UNSIGNED-PAYLOAD".to_string(),
+            );
+        }
+        let auth_headers = self
+            .build_auth_headers("PUT", &signing_uri, &headers, None)
+            .await?;
+
+        let mut req_builder = self
+            .client
+            .put(&url)
+            .header("Content-Length", file_size.to_string())
+            .body(body);
+        for (key, value) in auth_headers {
+            req_builder = req_builder.header(&key, &value);
+        }
+
+        let response = HttpUploader::send_with_cancel(req_builder, cancel_rx).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let error_body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("Put object failed with status {}: {}", status, error_body),
+            ));
+        }
 
-        parse_list_objects_response(&xml_content, prefix)
+        Ok(())
     }
 }
 
@@ -341,32 +944,27 @@ impl StorageClient for OSSClient {
             self.endpoint = endpoint.clone();
             self.platform = Self::detect_platform(&endpoint);
         }
-        if let Some(access_key) = &config.access_key {
-            self.access_key = access_key.clone();
-        }
-        if let Some(secret_key) = &config.secret_key {
-            self.secret_key = secret_key.clone();
-        }
-        if let Some(bucket_input) = &config.bucket {
-            // 重新解析 bucket 路径
-            let (bucket, prefix) = if let Some(slash_pos) = bucket_input.find('/') {
-                let bucket = bucket_input[..slash_pos].to_string();
-                let prefix = bucket_input[slash_pos + 1..].to_string();
-                (
-                    bucket,
-                    if prefix.ends_with('/') {
-                        prefix
-                    } else {
-                        format!("{}/", prefix)
-                    },
-                )
-            } else {
-                (bucket_input.clone(), String::new())
-            };
-            self.bucket = bucket;
-            self.prefix = prefix;
-        }
+        self.credentials = Arc::new(CredentialProvider::from_config(config));
+        self.anonymous = Self::is_anonymous(config);
+        // 重新解析 bucket 路径
+        let (bucket, prefix) = Self::parse_bucket_input(config.bucket.as_deref().unwrap_or(""));
+        self.bucket = bucket;
+        self.prefix = prefix;
         self.region = config.region.clone();
+        self.buckets.get_mut().unwrap().clear();
+        self.bucket_clients.get_mut().unwrap().clear();
+
+        // 未指定 bucket 时列出可访问的存储桶，同时验证凭证
+        if self.bucket.is_empty() {
+            if self.anonymous {
+                return Err(StorageError::InvalidConfig(
+                    "OSS bucket is required for anonymous access".to_string(),
+                ));
+            }
+            self.list_buckets().await?;
+            self.connected.store(true, Ordering::Relaxed);
+            return Ok(());
+        }
 
         // 简化配置：统一使用HTTP方式，避免AWS SDK的复杂性和兼容性问题
         println!("使用统一的HTTP客户端，支持所有S3兼容服务");
@@ -432,7 +1030,9 @@ impl StorageClient for OSSClient {
         println!("  test_uri: {}", uri);
         println!("  test_url: {}", url);
 
-        let headers = self.build_auth_headers("HEAD", &uri, &HashMap::new(), None);
+        let headers = self
+            .build_auth_headers("HEAD", &uri, &HashMap::new(), None)
+            .await?;
         let mut req_builder = self.client.head(&url);
 
         for (key, value) in headers {
@@ -450,7 +1050,11 @@ impl StorageClient for OSSClient {
 
         // 对于连接测试，200/404都表示认证成功
         // 404表示对象不存在但认证有效，这正是我们想要的
-        if status.is_success() || status == reqwest::StatusCode::NOT_FOUND {
+        // 匿名访问没有 ListBucket 权限的公开存储桶时，不存在的对象返回 403
+        if status.is_success()
+            || status == reqwest::StatusCode::NOT_FOUND
+            || (self.anonymous && status == reqwest::StatusCode::FORBIDDEN)
+        {
             self.connected.store(true, Ordering::Relaxed);
             println!("连接测试成功");
             Ok(())
@@ -468,6 +1072,26 @@ impl StorageClient for OSSClient {
         self.connected.load(Ordering::Relaxed)
     }
 
+    /// 不带 delimiter 的 ListObjects 请求即可递归列出前缀下的所有对象
+    fn supports_recursive_listing(&self, path: &str) -> bool {
+        !self.bucket.is_empty() || !Self::split_bucket_path(path).0.is_empty()
+    }
+
+    /// ListObjects 通过 max-keys 和 marker 原生分页，存储桶列表一次返回
+    fn supports_native_paging(&self, path: &str, _recursive: bool) -> bool {
+        !self.bucket.is_empty() || !Self::split_bucket_path(path).0.is_empty()
+    }
+
+    /// 未配置 bucket 时为 bucket/key，否则为包含路径前缀的对象键
+    fn canonical_path(&self, path: &str) -> String {
+        if self.bucket.is_empty() {
+            let (bucket, rest) = Self::split_bucket_path(path);
+            return format!("{}/{}", bucket, rest);
+        }
+        self.resolve_object_key(path)
+            .unwrap_or_else(|_| path.to_string())
+    }
+
     async fn read_file_range(
         &self,
         path: &str,
@@ -492,33 +1116,26 @@ impl StorageClient for OSSClient {
             return Err(StorageError::NotConnected);
         }
 
+        if self.bucket.is_empty() {
+            let (client, path) = self.route(path).await?;
+            return client
+                .read_file_range_with_progress(&path, start, length, progress_callback, cancel_rx)
+                .await;
+        }
+
         println!(
             "OSS读取文件范围: path={}, start={}, length={}",
             path, start, length
         );
 
-        // 处理 oss:// 协议 URL
-        let object_key = extract_object_key(
-            path,
-            &self.endpoint,
-            &self.config.bucket.as_ref().unwrap_or(&String::new()),
-            &self.prefix,
-        )?;
-
-        let url = build_object_url(&self.endpoint, &object_key);
+        // 处理 oss:// 协议 URL，带版本后缀的路径读取对象的历史版本
+        let (path, version_id) = split_version_id(path);
+        let object_key = self.resolve_object_key(path)?;
+        let (url, signing_uri, query_string) =
+            self.build_object_request_target(&object_key, version_id.as_deref());
 
         println!("构建的URL: {}", url);
 
-        let uri = if let Ok(parsed_url) = Url::parse(&url) {
-            parsed_url.path().to_string()
-        } else {
-            // 如果无法解析URL，则直接使用编码后的路径
-            format!("/{}", urlencoding::encode(&object_key))
-        };
-
-        // 对于签名，使用解码后的URI（OSS签名需要原始的未编码路径）
-        let signing_uri = normalize_uri_for_signing(&uri);
-
         let mut headers = HashMap::new();
         // 添加范围请求头
         let end = start + length - 1;
@@ -527,7 +1144,9 @@ impl StorageClient for OSSClient {
 
         println!("Range请求头: {}", range_header);
 
-        let auth_headers = self.build_auth_headers("GET", &signing_uri, &headers, None);
+        let auth_headers = self
+            .build_auth_headers("GET", &signing_uri, &headers, query_string.as_deref())
+            .await?;
 
         let mut req_builder = self.client.get(&url);
         for (key, value) in auth_headers {
@@ -543,12 +1162,17 @@ impl StorageClient for OSSClient {
         println!("OSS Range请求响应状态: {}", status);
 
         if !status.is_success() {
+            let headers = response.headers().clone();
             let error_body = response.text().await.unwrap_or_default();
             println!("OSS Range请求失败，响应体: {}", error_body);
-            return Err(StorageError::RequestFailed(format!(
-                "Range request failed with status {}: {}",
-                status, error_body
-            )));
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!(
+                    "Range request failed with status {}: {}",
+                    status, error_body
+                ),
+            ));
         }
 
         let content_length = response
@@ -572,9 +1196,7 @@ impl StorageClient for OSSClient {
             // 检查取消信号
             if let Some(ref mut cancel_rx) = cancel_rx {
                 if cancel_rx.try_recv().is_ok() {
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+                    return Err(StorageError::Cancelled);
                 }
             }
 
@@ -611,8 +1233,20 @@ impl StorageClient for OSSClient {
             recursive: Some(false),
             sort_by: None,
             sort_order: None,
+            show_hidden: None,
+            pattern: None,
+            file_type: None,
         });
 
+        if self.bucket.is_empty() {
+            let (bucket, _) = Self::split_bucket_path(path);
+            if bucket.is_empty() {
+                return self.list_buckets_as_directory().await;
+            }
+            let (client, path) = self.route(path).await?;
+            return client.list_directory(&path, Some(options)).await;
+        }
+
         // 处理路径：如果是协议URL，直接解析；如果是相对路径，则添加前缀
         let full_prefix = if path.starts_with("oss://") {
             // 协议URL包含完整路径，直接解析对象键
@@ -654,29 +1288,27 @@ impl StorageClient for OSSClient {
             return Err(StorageError::NotConnected);
         }
 
-        // 处理 oss:// 协议 URL
-        let object_key = extract_object_key(
-            path,
-            &self.endpoint,
-            &self.config.bucket.as_ref().unwrap_or(&String::new()),
-            &self.prefix,
-        )?;
+        if self.bucket.is_empty() {
+            let (client, path) = self.route(path).await?;
+            return client.read_full_file(&path).await;
+        }
 
-        let url = build_object_url(&self.endpoint, &object_key);
+        // 处理 oss:// 协议 URL，带版本后缀的路径读取对象的历史版本
+        let (path, version_id) = split_version_id(path);
+        let object_key = self.resolve_object_key(path)?;
+        let (url, signing_uri, query_string) =
+            self.build_object_request_target(&object_key, version_id.as_deref());
 
         println!("构建的URL: {}", url);
 
-        let uri = if let Ok(parsed_url) = Url::parse(&url) {
-            parsed_url.path().to_string()
-        } else {
-            // 如果无法解析URL，则直接使用编码后的路径
-            format!("/{}", urlencoding::encode(&object_key))
-        };
-
-        // 对于签名，使用解码后的URI（OSS签名需要原始的未编码路径）
-        let signing_uri = normalize_uri_for_signing(&uri);
-
-        let auth_headers = self.build_auth_headers("GET", &signing_uri, &HashMap::new(), None);
+        let auth_headers = self
+            .build_auth_headers(
+                "GET",
+                &signing_uri,
+                &HashMap::new(),
+                query_string.as_deref(),
+            )
+            .await?;
 
         let mut req_builder = self.client.get(&url);
         for (key, value) in auth_headers {
@@ -692,12 +1324,14 @@ impl StorageClient for OSSClient {
         println!("OSS文件请求响应状态: {}", status);
 
         if !status.is_success() {
+            let headers = response.headers().clone();
             let error_body = response.text().await.unwrap_or_default();
             println!("OSS文件请求失败，响应体: {}", error_body);
-            return Err(StorageError::RequestFailed(format!(
-                "Get file failed with status {}: {}",
-                status, error_body
-            )));
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!("Get file failed with status {}: {}", status, error_body),
+            ));
         }
 
         let content_length = response
@@ -718,31 +1352,163 @@ impl StorageClient for OSSClient {
         Ok(bytes.to_vec())
     }
 
-    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+    /// 使用预签名 URL 流式读取对象
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
         if !self.is_connected().await {
             return Err(StorageError::NotConnected);
         }
 
-        // 处理 oss:// 协议 URL
-        let object_key = extract_object_key(
-            path,
-            &self.endpoint,
-            &self.config.bucket.as_ref().unwrap_or(&String::new()),
-            &self.prefix,
-        )?;
+        if self.bucket.is_empty() {
+            let (client, path) = self.route(path).await?;
+            return client.open_read_stream(&path, start).await;
+        }
 
-        let url = build_object_url(&self.endpoint, &object_key);
-        let uri = if let Ok(parsed_url) = Url::parse(&url) {
-            parsed_url.path().to_string()
-        } else {
-            // 如果无法解析URL，则直接使用编码后的路径
-            format!("/{}", urlencoding::encode(&object_key))
+        let (path, version_id) = split_version_id(path);
+        let object_key = self.resolve_object_key(path)?;
+        let download_url = self
+            .download_url(&object_key, 3600, version_id.as_deref())
+            .await?;
+
+        HttpDownloader::open_stream(&self.client, HttpDownloadConfig::new(download_url), start)
+            .await
+    }
+
+    /// 通过 HEAD 请求获取对象元数据，目录通过占位对象或前缀判断
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        if self.bucket.is_empty() {
+            let (bucket, _) = Self::split_bucket_path(path);
+            if bucket.is_empty() {
+                // 存储桶列表根目录
+                return Ok(FileMetadata {
+                    filename: path.to_string(),
+                    basename: String::new(),
+                    lastmod: String::new(),
+                    size: "0".to_string(),
+                    file_type: "directory".to_string(),
+                    mime: None,
+                    etag: None,
+                    checksum: None,
+                    permissions: None,
+                    owner: None,
+                    group: None,
+                    extra: HashMap::new(),
+                });
+            }
+            let (client, bucket_path) = self.route(path).await?;
+            let mut metadata = client.stat(&bucket_path).await?;
+            metadata.filename = path.to_string();
+            if bucket_path.trim_matches('/').is_empty() {
+                metadata.basename = bucket;
+            }
+            return Ok(metadata);
+        }
+
+        let (object_path, version_id) = split_version_id(path);
+        let object_key = self.resolve_object_key(object_path)?;
+        let trimmed_key = object_key.trim_end_matches('/');
+
+        if !trimmed_key.is_empty() && !object_key.ends_with('/') {
+            let response = self
+                .send_object_request("HEAD", &object_key, version_id.as_deref(), &HashMap::new())
+                .await?;
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(Self::metadata_from_headers(
+                    path,
+                    &object_key,
+                    response.headers(),
+                ));
+            }
+            if status != reqwest::StatusCode::NOT_FOUND {
+                return Err(StorageError::RequestFailed(format!(
+                    "Head request failed with status: {}",
+                    status
+                )));
+            }
+        }
+
+        // 指定的历史版本不存在
+        if version_id.is_some() {
+            return Err(StorageError::NotFound(path.to_string()));
+        }
+
+        let directory = FileMetadata {
+            filename: path.to_string(),
+            basename: trimmed_key
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            lastmod: String::new(),
+            size: "0".to_string(),
+            file_type: "directory".to_string(),
+            mime: None,
+            etag: None,
+            checksum: None,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra: HashMap::new(),
         };
 
-        // 对于签名，使用解码后的URI（OSS签名需要原始的未编码路径）
-        let signing_uri = normalize_uri_for_signing(&uri);
+        // 存储桶根目录
+        if trimmed_key.is_empty() {
+            return Ok(directory);
+        }
+
+        // 显式创建的目录占位对象
+        let directory_key = format!("{}/", trimmed_key);
+        if self.object_exists(&directory_key).await? {
+            return Ok(directory);
+        }
+
+        // 没有占位对象的虚拟目录：前缀下至少存在一个对象
+        let options = ListOptions {
+            page_size: Some(1),
+            marker: None,
+            prefix: None,
+            recursive: Some(false),
+            ..Default::default()
+        };
+        let result = self
+            .list_directory_with_http(&directory_key, &options)
+            .await?;
+        if !result.files.is_empty() {
+            return Ok(directory);
+        }
+
+        Err(StorageError::NotFound(path.to_string()))
+    }
 
-        let auth_headers = self.build_auth_headers("HEAD", &signing_uri, &HashMap::new(), None);
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        if self.bucket.is_empty() {
+            let (client, path) = self.route(path).await?;
+            return client.get_file_size(&path).await;
+        }
+
+        // 处理 oss:// 协议 URL，带版本后缀的路径读取对象的历史版本
+        let (path, version_id) = split_version_id(path);
+        let object_key = self.resolve_object_key(path)?;
+        let (url, signing_uri, query_string) =
+            self.build_object_request_target(&object_key, version_id.as_deref());
+
+        let auth_headers = self
+            .build_auth_headers(
+                "HEAD",
+                &signing_uri,
+                &HashMap::new(),
+                query_string.as_deref(),
+            )
+            .await?;
 
         let mut req_builder = self.client.head(&url);
         for (key, value) in auth_headers {
@@ -755,10 +1521,10 @@ impl StorageClient for OSSClient {
             .map_err(|e| StorageError::NetworkError(format!("Head request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "Head request failed with status: {}",
-                response.status()
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!("Head request failed with status: {}", response.status()),
+            ));
         }
 
         response
@@ -779,26 +1545,30 @@ impl StorageClient for OSSClient {
                 "OSS endpoint is required".to_string(),
             ));
         }
-        if config.access_key.is_none() {
-            return Err(StorageError::InvalidConfig(
-                "OSS access key is required".to_string(),
-            ));
-        }
-        if config.secret_key.is_none() {
-            return Err(StorageError::InvalidConfig(
-                "OSS secret key is required".to_string(),
-            ));
-        }
-        if config.bucket.is_none() {
+        if Self::is_anonymous(config) && config.bucket.as_deref().unwrap_or("").is_empty() {
             return Err(StorageError::InvalidConfig(
-                "OSS bucket is required".to_string(),
+                "OSS bucket is required for anonymous access".to_string(),
             ));
         }
         Ok(())
     }
 
     fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
-        // 从传入的路径/URL 中提取对象键
+        if self.bucket.is_empty() {
+            // 同步接口无法创建子客户端，存储桶需已被访问过
+            let (bucket, path) = Self::split_bucket_path(path);
+            let client = self
+                .bucket_clients
+                .lock()
+                .unwrap()
+                .get(&bucket)
+                .cloned()
+                .ok_or(StorageError::NotConnected)?;
+            return client.get_download_url(&path);
+        }
+
+        // 从传入的路径/URL 中提取对象键和版本
+        let (path, version_id) = split_version_id(path);
         let object_key = extract_object_key(
             path,
             &self.endpoint,
@@ -806,8 +1576,18 @@ impl StorageClient for OSSClient {
             &self.prefix,
         )?;
 
-        // 生成 1 小时有效期的预签名下载 URL
-        self.generate_download_url(&object_key, 3600)
+        if self.anonymous {
+            return Ok(self
+                .build_object_request_target(&object_key, version_id.as_deref())
+                .0);
+        }
+
+        // 生成 1 小时有效期的预签名下载 URL，同步接口使用最近一次获取的凭证
+        let credentials = self
+            .credentials
+            .cached()
+            .ok_or(StorageError::NotConnected)?;
+        self.generate_download_url(&object_key, 3600, &credentials, version_id.as_deref())
     }
 
     /// 高效的 OSS 文件下载实现，使用 HTTP 流式下载
@@ -818,7 +1598,15 @@ impl StorageClient for OSSClient {
         progress_callback: Option<ProgressCallback>,
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
     ) -> Result<(), StorageError> {
-        // 从路径中提取对象键
+        if self.bucket.is_empty() {
+            let (client, path) = self.route(path).await?;
+            return client
+                .download_file(&path, save_path, progress_callback, cancel_rx)
+                .await;
+        }
+
+        // 从路径中提取对象键和版本
+        let (path, version_id) = split_version_id(path);
         let object_key = extract_object_key(
             path,
             &self.endpoint,
@@ -827,7 +1615,9 @@ impl StorageClient for OSSClient {
         )?;
 
         // 构建下载 URL
-        let download_url = self.generate_download_url(&object_key, 3600)?;
+        let download_url = self
+            .download_url(&object_key, 3600, version_id.as_deref())
+            .await?;
 
         // 使用通用HTTP下载工具
         HttpDownloader::download_with_auth(
@@ -840,4 +1630,285 @@ impl StorageClient for OSSClient {
         )
         .await
     }
+
+    /// OSS 文件上传实现，使用 HTTP 流式上传
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        if self.bucket.is_empty() {
+            let (client, path) = self.route_object(path).await?;
+            return client
+                .put_file(local_path, &path, progress_callback, cancel_rx)
+                .await;
+        }
+
+        let (body, file_size) = HttpUploader::file_body(local_path, progress_callback).await?;
+
+        self.put_object_body(path, body, file_size, cancel_rx).await
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        if self.bucket.is_empty() {
+            let (client, path) = self.route_object(path).await?;
+            return client
+                .put_stream(stream, size, &path, progress_callback, cancel_rx)
+                .await;
+        }
+
+        let body = HttpUploader::stream_body(stream, size, progress_callback);
+
+        self.put_object_body(path, body, size, cancel_rx).await
+    }
+
+    /// 对象存储没有真正的目录，创建以斜杠结尾的空对象作为目录占位
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        if self.bucket.is_empty() {
+            let (client, path) = self.route_object(path).await?;
+            return client.create_directory(&path).await;
+        }
+        Self::ensure_current_version(path)?;
+
+        let object_key = self.resolve_object_key(path)?;
+        let directory_key = format!("{}/", object_key.trim_end_matches('/'));
+
+        let response = self
+            .send_object_request("PUT", &directory_key, None, &HashMap::new())
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let error_body = response.text().await.unwrap_or_default();
+            return Err(StorageError::from_http_status(
+                status,
+                &headers,
+                format!(
+                    "Create directory failed with status {}: {}",
+                    status, error_body
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 通过服务端复制 + 删除实现重命名，目录会逐个对象处理
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        if self.bucket.is_empty() {
+            if Self::split_bucket_path(from).0 != Self::split_bucket_path(to).0 {
+                return Err(StorageError::ProtocolNotSupported(
+                    "Renaming across buckets is not supported".to_string(),
+                ));
+            }
+            let (client, from) = self.route_object(from).await?;
+            let (_, to) = self.route_object(to).await?;
+            return client.rename(&from, &to).await;
+        }
+        Self::ensure_current_version(from)?;
+        Self::ensure_current_version(to)?;
+
+        let source_key = self.resolve_object_key(from)?;
+        let dest_key = self.resolve_object_key(to)?;
+
+        if !source_key.ends_with('/') && self.object_exists(&source_key).await? {
+            if self.object_exists(&dest_key).await? {
+                return Err(StorageError::Conflict(format!(
+                    "Destination already exists: {}",
+                    to
+                )));
+            }
+            self.copy_object(&source_key, &dest_key).await?;
+            return self.delete_object(&source_key).await;
+        }
+
+        // 按目录前缀处理
+        let source_prefix = format!("{}/", source_key.trim_end_matches('/'));
+        let dest_prefix = format!("{}/", dest_key.trim_end_matches('/'));
+        let (object_keys, directory_prefixes) = self.collect_object_keys(&source_prefix).await?;
+
+        if object_keys.is_empty() && !self.object_exists(&source_prefix).await? {
+            return Err(StorageError::NotFound(from.to_string()));
+        }
+
+        for key in &object_keys {
+            let relative = key.strip_prefix(&source_prefix).unwrap_or(key);
+            self.copy_object(key, &format!("{}{}", dest_prefix, relative))
+                .await?;
+            self.delete_object(key).await?;
+        }
+
+        // 迁移目录占位对象
+        for directory_prefix in &directory_prefixes {
+            if self.object_exists(directory_prefix).await? {
+                let relative = directory_prefix
+                    .strip_prefix(&source_prefix)
+                    .unwrap_or(directory_prefix);
+                self.copy_object(directory_prefix, &format!("{}{}", dest_prefix, relative))
+                    .await?;
+                self.delete_object(directory_prefix).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 删除对象，目录前缀下的所有对象会被一并删除
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        if self.bucket.is_empty() {
+            let (client, path) = self.route_object(path).await?;
+            return client.delete(&path).await;
+        }
+        Self::ensure_current_version(path)?;
+
+        let object_key = self.resolve_object_key(path)?;
+        if object_key.trim_end_matches('/') == self.prefix.trim_end_matches('/') {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the root directory".to_string(),
+            ));
+        }
+
+        if !object_key.ends_with('/') && self.object_exists(&object_key).await? {
+            return self.delete_object(&object_key).await;
+        }
+
+        let directory_prefix = format!("{}/", object_key.trim_end_matches('/'));
+        let (object_keys, directory_prefixes) = self.collect_object_keys(&directory_prefix).await?;
+
+        for key in &object_keys {
+            self.delete_object(key).await?;
+        }
+
+        // 子目录占位对象先于父目录删除
+        for prefix in directory_prefixes.iter().rev() {
+            self.delete_object(prefix).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 通过 ListObjectVersions 列出对象的历史版本和删除标记
+    async fn list_versions(&self, path: &str) -> Result<Vec<ObjectVersion>, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        if self.bucket.is_empty() {
+            let (client, path) = self.route_object(path).await?;
+            return client.list_versions(&path).await;
+        }
+
+        let (path, _) = split_version_id(path);
+        let object_key = self.resolve_object_key(path)?;
+        if object_key.is_empty() || object_key.ends_with('/') {
+            return Err(StorageError::RequestFailed(format!(
+                "Versions can only be listed for files: {}",
+                path
+            )));
+        }
+
+        let (bucket_url, bucket_signing_uri) = self.build_bucket_request_target();
+        // versions 是子资源：S3 在规范查询字符串中签名，其他平台附加在签名 URI 上
+        let signing_uri = if self.platform == OSSPlatform::AwsS3 {
+            bucket_signing_uri
+        } else {
+            format!("{}?versions", bucket_signing_uri)
+        };
+
+        let mut versions = Vec::new();
+        let mut markers: Option<(String, String)> = None;
+        loop {
+            let mut query_params = vec![
+                ("prefix".to_string(), object_key.clone()),
+                ("versions".to_string(), String::new()),
+            ];
+            if let Some((key_marker, version_id_marker)) = &markers {
+                query_params.push(("key-marker".to_string(), key_marker.clone()));
+                if !version_id_marker.is_empty() {
+                    query_params.push(("version-id-marker".to_string(), version_id_marker.clone()));
+                }
+            }
+
+            let query_string = query_params
+                .iter()
+                .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+                .collect::<Vec<_>>()
+                .join("&");
+            let url = format!("{}?{}", bucket_url, query_string);
+
+            let headers = self
+                .build_auth_headers("GET", &signing_uri, &HashMap::new(), Some(&query_string))
+                .await?;
+            let mut req_builder = self.client.get(&url);
+            for (key, value) in headers {
+                req_builder = req_builder.header(&key, &value);
+            }
+
+            let response = req_builder.send().await.map_err(|e| {
+                StorageError::NetworkError(format!("List versions request failed: {}", e))
+            })?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let headers = response.headers().clone();
+                let body = response.text().await.unwrap_or_default();
+                return Err(StorageError::from_http_status(
+                    status,
+                    &headers,
+                    format!("List versions failed with status {}: {}", status, body),
+                ));
+            }
+
+            let xml_content = response.text().await.map_err(|e| {
+                StorageError::NetworkError(format!("Failed to read response body: {}", e))
+            })?;
+            let (page, next_markers) =
+                parse_list_versions_response(&xml_content, &object_key, path)?;
+            versions.extend(page);
+
+            // 结果按键排序，下一页从其他键开始时该对象的版本已全部列出
+            match next_markers {
+                Some((key_marker, version_id_marker)) if key_marker == object_key => {
+                    markers = Some((key_marker, version_id_marker));
+                }
+                _ => break,
+            }
+        }
+
+        if versions.is_empty() {
+            return Err(StorageError::NotFound(path.to_string()));
+        }
+
+        Ok(versions)
+    }
 }
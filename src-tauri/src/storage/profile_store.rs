@@ -0,0 +1,567 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose;
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::traits::ConnectionConfig;
+use crate::error::{AppError, ErrorCode};
+
+/// 加密文件格式版本
+const STORE_VERSION: u32 = 1;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// 密钥派生参数，随加密文件一同保存，调整默认值不影响已有文件的解密
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KdfParams {
+    algorithm: String,
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl KdfParams {
+    /// 使用随机盐和 argon2id 默认参数
+    fn generate() -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+
+        Self {
+            algorithm: "argon2id".to_string(),
+            salt: general_purpose::STANDARD.encode(salt),
+            memory_kib: argon2::Params::DEFAULT_M_COST,
+            iterations: argon2::Params::DEFAULT_T_COST,
+            parallelism: argon2::Params::DEFAULT_P_COST,
+        }
+    }
+
+    fn derive_key(&self, password: &str) -> Result<Key<Aes256Gcm>, AppError> {
+        if self.algorithm != "argon2id" {
+            return Err(AppError::new(
+                ErrorCode::Unsupported,
+                format!("Unsupported key derivation: {}", self.algorithm),
+            ));
+        }
+
+        let salt = decode_base64(&self.salt)?;
+        let params = argon2::Params::new(
+            self.memory_kib,
+            self.iterations,
+            self.parallelism,
+            Some(KEY_LEN),
+        )
+        .map_err(|e| AppError::internal(format!("Invalid key derivation parameters: {}", e)))?;
+
+        let mut key = [0u8; KEY_LEN];
+        argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), &salt, &mut key)
+            .map_err(|e| AppError::internal(format!("Key derivation failed: {}", e)))?;
+
+        Ok(*Key::<Aes256Gcm>::from_slice(&key))
+    }
+}
+
+/// 加密文件，配置库和导出文件共用此格式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedFile {
+    version: u32,
+    kdf: KdfParams,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl EncryptedFile {
+    /// 每次写入都使用新的随机 nonce
+    fn seal(kdf: &KdfParams, key: &Key<Aes256Gcm>, plaintext: &[u8]) -> Result<Self, AppError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let ciphertext = Aes256Gcm::new(key)
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| AppError::internal("Failed to encrypt connection profiles"))?;
+
+        Ok(Self {
+            version: STORE_VERSION,
+            kdf: kdf.clone(),
+            nonce: general_purpose::STANDARD.encode(nonce),
+            ciphertext: general_purpose::STANDARD.encode(ciphertext),
+        })
+    }
+
+    /// 认证标签校验失败即视为密码错误
+    fn open(&self, key: &Key<Aes256Gcm>) -> Result<Vec<u8>, AppError> {
+        let nonce = decode_base64(&self.nonce)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(AppError::internal("Corrupted profile store: invalid nonce"));
+        }
+        let ciphertext = decode_base64(&self.ciphertext)?;
+
+        Aes256Gcm::new(key)
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| {
+                AppError::i18n(ErrorCode::AuthenticationFailed, "profile.password.invalid")
+            })
+    }
+
+    fn read(path: &Path) -> Result<Self, AppError> {
+        let data = std::fs::read(path)
+            .map_err(|e| AppError::from(e).context("Failed to read profile store"))?;
+        let file: Self = serde_json::from_slice(&data)
+            .map_err(|e| AppError::internal(format!("Corrupted profile store: {}", e)))?;
+
+        if file.version > STORE_VERSION {
+            return Err(AppError::new(
+                ErrorCode::Unsupported,
+                format!("Unsupported profile store version: {}", file.version),
+            ));
+        }
+        Ok(file)
+    }
+
+    /// 先写临时文件再替换，避免写入中断损坏已有文件
+    fn write(&self, path: &Path) -> Result<(), AppError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| AppError::from(e).context("Failed to create profile directory"))?;
+        }
+
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|e| AppError::internal(format!("Failed to serialize profiles: {}", e)))?;
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, data)
+            .map_err(|e| AppError::from(e).context("Failed to write profile store"))?;
+        std::fs::rename(&temp_path, path)
+            .map_err(|e| AppError::from(e).context("Failed to write profile store"))
+    }
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, AppError> {
+    general_purpose::STANDARD
+        .decode(value)
+        .map_err(|e| AppError::internal(format!("Corrupted profile store: {}", e)))
+}
+
+/// 完整的连接配置，包含密钥，只在 Rust 侧使用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredProfile {
+    id: String,
+    name: String,
+    config: ConnectionConfig,
+    created_at: String,
+    updated_at: String,
+    last_connected: Option<String>,
+}
+
+/// 返回给前端的连接配置，密钥字段已清空
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionProfile {
+    pub id: String,
+    pub name: String,
+    pub config: ConnectionConfig,
+    /// 已保存但未返回的密钥字段（secretKey、password、passphrase）
+    pub stored_secrets: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub last_connected: Option<String>,
+}
+
+impl From<&StoredProfile> for ConnectionProfile {
+    fn from(profile: &StoredProfile) -> Self {
+        let mut config = profile.config.clone();
+        let stored_secrets = redact_secrets(&mut config);
+
+        Self {
+            id: profile.id.clone(),
+            name: profile.name.clone(),
+            config,
+            stored_secrets,
+            created_at: profile.created_at.clone(),
+            updated_at: profile.updated_at.clone(),
+            last_connected: profile.last_connected.clone(),
+        }
+    }
+}
+
+/// 连接配置中的密钥字段
+fn secret_fields(config: &mut ConnectionConfig) -> [(&'static str, &mut Option<String>); 4] {
+    [
+        ("secretKey", &mut config.secret_key),
+        ("sessionToken", &mut config.session_token),
+        ("password", &mut config.password),
+        ("passphrase", &mut config.passphrase),
+    ]
+}
+
+/// 清空配置中的密钥字段，返回被清空的字段名
+pub fn redact_secrets(config: &mut ConnectionConfig) -> Vec<String> {
+    secret_fields(config)
+        .into_iter()
+        .filter_map(|(name, value)| value.take().map(|_| name.to_string()))
+        .collect()
+}
+
+/// 配置库状态
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileStoreStatus {
+    /// 加密文件是否已创建
+    pub exists: bool,
+    pub unlocked: bool,
+    /// 当前平台是否支持系统钥匙串
+    pub keyring_available: bool,
+    /// 密钥是否已保存到系统钥匙串
+    pub keyring_enabled: bool,
+}
+
+/// 系统钥匙串后端，保存派生后的密钥，解锁时无需再次输入主密码
+struct KeyringBackend;
+
+#[cfg(feature = "os-keyring")]
+impl KeyringBackend {
+    const SERVICE: &'static str = "dataset-viewer";
+    const ACCOUNT: &'static str = "profile-store-key";
+
+    fn entry() -> Option<keyring::Entry> {
+        keyring::Entry::new(Self::SERVICE, Self::ACCOUNT).ok()
+    }
+
+    fn available() -> bool {
+        Self::entry().is_some()
+    }
+
+    fn load() -> Option<String> {
+        Self::entry()?.get_password().ok()
+    }
+
+    fn store(value: &str) -> Result<(), AppError> {
+        Self::entry()
+            .ok_or_else(|| AppError::new(ErrorCode::Unsupported, "OS keyring is not available"))?
+            .set_password(value)
+            .map_err(|e| AppError::internal(format!("Failed to save key to OS keyring: {}", e)))
+    }
+
+    fn remove() {
+        if let Some(entry) = Self::entry() {
+            let _ = entry.delete_credential();
+        }
+    }
+}
+
+#[cfg(not(feature = "os-keyring"))]
+impl KeyringBackend {
+    fn available() -> bool {
+        false
+    }
+
+    fn load() -> Option<String> {
+        None
+    }
+
+    fn store(_value: &str) -> Result<(), AppError> {
+        Err(AppError::new(
+            ErrorCode::Unsupported,
+            "OS keyring is not available",
+        ))
+    }
+
+    fn remove() {}
+}
+
+/// 解锁后的配置库内容
+struct UnlockedStore {
+    kdf: KdfParams,
+    key: Key<Aes256Gcm>,
+    profiles: Vec<StoredProfile>,
+}
+
+/// 加密的连接配置库
+/// 配置以 JSON 序列化后使用 AES-256-GCM 加密保存，密钥由主密码经 argon2id 派生，
+/// 可选地将派生后的密钥保存到系统钥匙串；密钥字段不会返回给前端
+pub struct ProfileStore {
+    path: PathBuf,
+    unlocked: Option<UnlockedStore>,
+}
+
+impl ProfileStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            unlocked: None,
+        }
+    }
+
+    /// 默认保存在系统数据目录下
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default()
+            .join("dataset-viewer")
+            .join("profiles.json")
+    }
+
+    pub fn status(&self) -> ProfileStoreStatus {
+        ProfileStoreStatus {
+            exists: self.path.exists(),
+            unlocked: self.unlocked.is_some(),
+            keyring_available: KeyringBackend::available(),
+            keyring_enabled: KeyringBackend::load().is_some(),
+        }
+    }
+
+    /// 解锁配置库，文件不存在时以该主密码创建
+    /// 未提供密码时尝试使用系统钥匙串中的密钥；remember 为 true 时保存密钥到钥匙串，为 false 时移除
+    pub fn unlock(
+        &mut self,
+        password: Option<&str>,
+        remember: Option<bool>,
+    ) -> Result<(), AppError> {
+        let unlocked = if self.path.exists() {
+            let file = EncryptedFile::read(&self.path)?;
+            let key = match password {
+                Some(password) => file.kdf.derive_key(password)?,
+                None => Self::keyring_key()?,
+            };
+            let profiles = serde_json::from_slice(&file.open(&key)?)
+                .map_err(|e| AppError::internal(format!("Corrupted profile store: {}", e)))?;
+
+            UnlockedStore {
+                kdf: file.kdf,
+                key,
+                profiles,
+            }
+        } else {
+            let password = password
+                .filter(|password| !password.is_empty())
+                .ok_or_else(|| AppError::invalid_argument("A master password is required"))?;
+            let kdf = KdfParams::generate();
+            let key = kdf.derive_key(password)?;
+
+            let store = UnlockedStore {
+                kdf,
+                key,
+                profiles: Vec::new(),
+            };
+            Self::save_to(&self.path, &store)?;
+            store
+        };
+
+        match remember {
+            Some(true) => KeyringBackend::store(&general_purpose::STANDARD.encode(unlocked.key))?,
+            Some(false) => KeyringBackend::remove(),
+            None => {}
+        }
+
+        self.unlocked = Some(unlocked);
+        Ok(())
+    }
+
+    /// 锁定配置库，清除内存中的密钥和配置
+    pub fn lock(&mut self) {
+        self.unlocked = None;
+    }
+
+    /// 修改主密码，使用新的盐重新加密，钥匙串中已保存的密钥同步更新
+    pub fn change_password(&mut self, new_password: &str) -> Result<(), AppError> {
+        if new_password.is_empty() {
+            return Err(AppError::invalid_argument("A master password is required"));
+        }
+
+        let store = self.unlocked_mut()?;
+        let kdf = KdfParams::generate();
+        store.key = kdf.derive_key(new_password)?;
+        store.kdf = kdf;
+
+        let key = store.key;
+        self.save()?;
+        if KeyringBackend::load().is_some() {
+            KeyringBackend::store(&general_purpose::STANDARD.encode(key))?;
+        }
+        Ok(())
+    }
+
+    pub fn list(&self) -> Result<Vec<ConnectionProfile>, AppError> {
+        Ok(self
+            .unlocked()?
+            .profiles
+            .iter()
+            .map(ConnectionProfile::from)
+            .collect())
+    }
+
+    pub fn create(
+        &mut self,
+        name: String,
+        config: ConnectionConfig,
+    ) -> Result<ConnectionProfile, AppError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let profile = StoredProfile {
+            id: format!("profile_{}", uuid::Uuid::new_v4().simple()),
+            name,
+            config,
+            created_at: now.clone(),
+            updated_at: now,
+            last_connected: None,
+        };
+        let result = ConnectionProfile::from(&profile);
+
+        self.unlocked_mut()?.profiles.push(profile);
+        self.save()?;
+        Ok(result)
+    }
+
+    /// 更新配置，新配置中为空的密钥字段保留原值，设为空字符串时清除
+    pub fn update(
+        &mut self,
+        id: &str,
+        name: Option<String>,
+        config: Option<ConnectionConfig>,
+    ) -> Result<ConnectionProfile, AppError> {
+        let profile = self.find_mut(id)?;
+
+        if let Some(name) = name {
+            profile.name = name;
+        }
+        if let Some(mut config) = config {
+            for ((_, new), (_, old)) in secret_fields(&mut config)
+                .into_iter()
+                .zip(secret_fields(&mut profile.config))
+            {
+                match new.as_deref() {
+                    None => *new = old.take(),
+                    Some("") => *new = None,
+                    Some(_) => {}
+                }
+            }
+            profile.config = config;
+        }
+        profile.updated_at = chrono::Utc::now().to_rfc3339();
+
+        let result = ConnectionProfile::from(&*profile);
+        self.save()?;
+        Ok(result)
+    }
+
+    pub fn delete(&mut self, id: &str) -> Result<bool, AppError> {
+        let profiles = &mut self.unlocked_mut()?.profiles;
+        let count = profiles.len();
+        profiles.retain(|profile| profile.id != id);
+        if profiles.len() == count {
+            return Ok(false);
+        }
+
+        self.save()?;
+        Ok(true)
+    }
+
+    /// 取出包含密钥的完整连接配置用于建立连接，并记录连接时间
+    pub fn connection_config(&mut self, id: &str) -> Result<ConnectionConfig, AppError> {
+        let profile = self.find_mut(id)?;
+        profile.last_connected = Some(chrono::Utc::now().to_rfc3339());
+        let config = profile.config.clone();
+
+        self.save()?;
+        Ok(config)
+    }
+
+    /// 导出配置到单独的加密文件，使用独立的导出密码，未指定 ID 时导出全部
+    pub fn export(
+        &self,
+        path: &Path,
+        passphrase: &str,
+        ids: Option<&[String]>,
+    ) -> Result<usize, AppError> {
+        if passphrase.is_empty() {
+            return Err(AppError::invalid_argument("An export password is required"));
+        }
+
+        let profiles: Vec<&StoredProfile> = self
+            .unlocked()?
+            .profiles
+            .iter()
+            .filter(|profile| ids.is_none_or(|ids| ids.contains(&profile.id)))
+            .collect();
+
+        let kdf = KdfParams::generate();
+        let key = kdf.derive_key(passphrase)?;
+        let plaintext = serde_json::to_vec(&profiles)
+            .map_err(|e| AppError::internal(format!("Failed to serialize profiles: {}", e)))?;
+        EncryptedFile::seal(&kdf, &key, &plaintext)?.write(path)?;
+
+        Ok(profiles.len())
+    }
+
+    /// 从导出文件导入配置，ID 相同的配置会被覆盖，返回导入的数量
+    pub fn import(&mut self, path: &Path, passphrase: &str) -> Result<usize, AppError> {
+        let file = EncryptedFile::read(path)?;
+        let key = file.kdf.derive_key(passphrase)?;
+        let imported: Vec<StoredProfile> = serde_json::from_slice(&file.open(&key)?)
+            .map_err(|e| AppError::internal(format!("Invalid profile export: {}", e)))?;
+
+        let profiles = &mut self.unlocked_mut()?.profiles;
+        let count = imported.len();
+        for profile in imported {
+            match profiles
+                .iter_mut()
+                .find(|existing| existing.id == profile.id)
+            {
+                Some(existing) => *existing = profile,
+                None => profiles.push(profile),
+            }
+        }
+
+        self.save()?;
+        Ok(count)
+    }
+
+    // === 私有辅助方法 ===
+
+    fn keyring_key() -> Result<Key<Aes256Gcm>, AppError> {
+        let encoded = KeyringBackend::load().ok_or_else(|| {
+            AppError::i18n(ErrorCode::AuthenticationFailed, "profile.password.required")
+        })?;
+        let key = decode_base64(&encoded)?;
+        if key.len() != KEY_LEN {
+            return Err(AppError::internal("Invalid key in OS keyring"));
+        }
+        Ok(*Key::<Aes256Gcm>::from_slice(&key))
+    }
+
+    fn unlocked(&self) -> Result<&UnlockedStore, AppError> {
+        self.unlocked
+            .as_ref()
+            .ok_or_else(|| AppError::i18n(ErrorCode::Locked, "profile.store.locked"))
+    }
+
+    fn unlocked_mut(&mut self) -> Result<&mut UnlockedStore, AppError> {
+        self.unlocked
+            .as_mut()
+            .ok_or_else(|| AppError::i18n(ErrorCode::Locked, "profile.store.locked"))
+    }
+
+    fn find_mut(&mut self, id: &str) -> Result<&mut StoredProfile, AppError> {
+        self.unlocked_mut()?
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.id == id)
+            .ok_or_else(|| AppError::not_found(format!("Connection profile not found: {}", id)))
+    }
+
+    fn save(&self) -> Result<(), AppError> {
+        Self::save_to(&self.path, self.unlocked()?)
+    }
+
+    fn save_to(path: &Path, store: &UnlockedStore) -> Result<(), AppError> {
+        let plaintext = serde_json::to_vec(&store.profiles)
+            .map_err(|e| AppError::internal(format!("Failed to serialize profiles: {}", e)))?;
+        EncryptedFile::seal(&store.kdf, &store.key, &plaintext)?.write(path)
+    }
+}
@@ -0,0 +1,484 @@
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, DirectoryUsage, FileMetadata, ListOptions,
+    ObjectVersion, ProgressCallback, RetryConfig, StorageClient, StorageError,
+};
+use crate::utils::stream_io::StreamIo;
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_INITIAL_BACKOFF_MS: u32 = 500;
+const DEFAULT_MAX_BACKOFF_MS: u32 = 10_000;
+// 服务端要求的等待时间超过该值时不再重试，直接返回错误
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+// 范围读取按该大小分段请求，失败时只重新获取未完成的分段
+const RESUME_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// 重试策略：指数退避 + 可选抖动，并遵循服务端的 Retry-After
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+}
+
+impl RetryPolicy {
+    pub fn new(config: Option<&RetryConfig>) -> Self {
+        let max_attempts = config.and_then(|c| c.max_attempts);
+        let initial_backoff_ms = config.and_then(|c| c.initial_backoff_ms);
+        let max_backoff_ms = config.and_then(|c| c.max_backoff_ms);
+        let jitter = config.and_then(|c| c.jitter);
+
+        Self {
+            max_attempts: max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1),
+            initial_backoff: Duration::from_millis(
+                initial_backoff_ms.unwrap_or(DEFAULT_INITIAL_BACKOFF_MS) as u64,
+            ),
+            max_backoff: Duration::from_millis(
+                max_backoff_ms.unwrap_or(DEFAULT_MAX_BACKOFF_MS) as u64
+            ),
+            jitter: jitter.unwrap_or(true),
+        }
+    }
+
+    /// 计算第 attempt 次尝试失败后的等待时间，不应重试时返回 None
+    pub fn retry_delay(&self, attempt: u32, error: &StorageError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !error.is_retryable() {
+            return None;
+        }
+
+        if let Some(retry_after) = error.retry_after() {
+            return (retry_after <= MAX_RETRY_AFTER).then_some(retry_after);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(16);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+
+        if !self.jitter {
+            return Some(backoff);
+        }
+
+        // 保留一半的基础等待时间，另一半随机化，避免多个请求同时重试
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let half = backoff / 2;
+        Some(half + half.mul_f64(nanos as f64 / 1_000_000_000.0))
+    }
+
+    /// 失败后等待并判断是否继续重试
+    async fn backoff(
+        &self,
+        operation: &str,
+        path: &str,
+        attempt: u32,
+        error: &StorageError,
+    ) -> bool {
+        match self.retry_delay(attempt, error) {
+            Some(delay) => {
+                log::warn!(
+                    "{} {} failed (attempt {}/{}), retrying in {:?}: {}",
+                    operation,
+                    path,
+                    attempt,
+                    self.max_attempts,
+                    delay,
+                    error
+                );
+                tokio::time::sleep(delay).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 按策略执行操作，遇到可重试错误时自动重试
+    pub async fn run<T, F, Fut>(
+        &self,
+        operation: &str,
+        path: &str,
+        mut f: F,
+    ) -> Result<T, StorageError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, StorageError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match f().await {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    if !self.backoff(operation, path, attempt, &e).await {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// 带重试的存储客户端
+/// 包装任意存储客户端，对幂等操作按重试策略自动重试；
+/// 范围读取和下载失败时从已完成的位置继续，而不是从头开始。
+/// 重命名和删除不是幂等操作，直接转发给内部客户端
+pub struct RetryingStorageClient {
+    inner: Arc<dyn StorageClient + Send + Sync>,
+    policy: RetryPolicy,
+}
+
+impl RetryingStorageClient {
+    pub fn new(inner: Arc<dyn StorageClient + Send + Sync>, config: Option<&RetryConfig>) -> Self {
+        Self {
+            inner,
+            policy: RetryPolicy::new(config),
+        }
+    }
+
+    /// 分段读取文件范围，每个分段独立重试，已读取的数据不会重复获取
+    async fn read_range_resumable(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        let mut result = Vec::with_capacity(length.min(RESUME_CHUNK_SIZE) as usize);
+        let mut attempt = 1;
+
+        loop {
+            let received = result.len() as u64;
+            let chunk_length = (length - received).min(RESUME_CHUNK_SIZE);
+
+            // 将分段内的进度换算为整个范围的进度
+            let chunk_callback = progress_callback.clone().map(|callback| {
+                Arc::new(move |done: u64, _total: u64| callback(received + done, length))
+                    as ProgressCallback
+            });
+
+            match self
+                .inner
+                .read_file_range_with_progress(
+                    path,
+                    start + received,
+                    chunk_length,
+                    chunk_callback,
+                    cancel_rx.as_deref_mut(),
+                )
+                .await
+            {
+                Ok(data) => {
+                    let reached_end = (data.len() as u64) < chunk_length;
+                    result.extend_from_slice(&data);
+                    attempt = 1;
+
+                    if reached_end || result.len() as u64 >= length {
+                        return Ok(result);
+                    }
+                }
+                Err(e) => {
+                    if !self
+                        .policy
+                        .backoff("read_file_range", path, attempt, &e)
+                        .await
+                    {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// 从本地已下载的部分继续下载
+    async fn resume_download(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let total_size = self.inner.get_file_size(path).await?;
+        let offset = match tokio::fs::metadata(save_path).await {
+            Ok(metadata) if metadata.len() <= total_size => metadata.len(),
+            _ => 0,
+        };
+
+        log::info!("Resuming download of {} from byte {}", path, offset);
+
+        let stream = self.inner.open_read_stream(path, offset).await?;
+        StreamIo::append_to_file(
+            stream,
+            save_path,
+            offset,
+            total_size,
+            progress_callback,
+            cancel_rx,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl StorageClient for RetryingStorageClient {
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        // 内部客户端通常在包装前已完成连接，仅在独占时转发
+        match Arc::get_mut(&mut self.inner) {
+            Some(inner) => inner.connect(config).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    async fn list_directory(
+        &self,
+        path: &str,
+        options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        self.policy
+            .run("list_directory", path, || {
+                self.inner.list_directory(path, options)
+            })
+            .await
+    }
+
+    fn supports_recursive_listing(&self, path: &str) -> bool {
+        self.inner.supports_recursive_listing(path)
+    }
+
+    fn supports_native_paging(&self, path: &str, recursive: bool) -> bool {
+        self.inner.supports_native_paging(path, recursive)
+    }
+
+    fn canonical_path(&self, path: &str) -> String {
+        self.inner.canonical_path(path)
+    }
+
+    async fn directory_usage(
+        &self,
+        path: &str,
+        max_depth: u32,
+    ) -> Result<Option<Vec<DirectoryUsage>>, StorageError> {
+        self.policy
+            .run("directory_usage", path, || {
+                self.inner.directory_usage(path, max_depth)
+            })
+            .await
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_range_resumable(path, start, length, None, None)
+            .await
+    }
+
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_range_resumable(path, start, length, progress_callback, cancel_rx)
+            .await
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        self.policy
+            .run("read_full_file", path, || self.inner.read_full_file(path))
+            .await
+    }
+
+    async fn read_full_file_with_progress(
+        &self,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        let mut attempt = 1;
+        loop {
+            match self
+                .inner
+                .read_full_file_with_progress(
+                    path,
+                    progress_callback.clone(),
+                    cancel_rx.as_deref_mut(),
+                )
+                .await
+            {
+                Ok(data) => return Ok(data),
+                Err(e) => {
+                    if !self
+                        .policy
+                        .backoff("read_full_file", path, attempt, &e)
+                        .await
+                    {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        self.policy
+            .run("open_read_stream", path, || {
+                self.inner.open_read_stream(path, start)
+            })
+            .await
+    }
+
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        self.policy
+            .run("stat", path, || self.inner.stat(path))
+            .await
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        self.policy
+            .run("get_file_size", path, || self.inner.get_file_size(path))
+            .await
+    }
+
+    fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
+        self.inner.get_download_url(path)
+    }
+
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        // 首次使用客户端自身的下载实现，失败后基于已写入的部分续传
+        let mut result = self
+            .inner
+            .download_file(
+                path,
+                save_path,
+                progress_callback.clone(),
+                cancel_rx.as_deref_mut(),
+            )
+            .await;
+        let mut attempt = 1;
+
+        loop {
+            let error = match result {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            if !self
+                .policy
+                .backoff("download_file", path, attempt, &error)
+                .await
+            {
+                return Err(error);
+            }
+            attempt += 1;
+
+            result = self
+                .resume_download(
+                    path,
+                    save_path,
+                    progress_callback.clone(),
+                    cancel_rx.as_deref_mut(),
+                )
+                .await;
+        }
+    }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        // 上传以覆盖方式写入，失败后重新上传整个文件
+        let mut attempt = 1;
+        loop {
+            match self
+                .inner
+                .put_file(
+                    local_path,
+                    path,
+                    progress_callback.clone(),
+                    cancel_rx.as_deref_mut(),
+                )
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    if !self.policy.backoff("put_file", path, attempt, &e).await {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        // 数据流只能消费一次，由调用方决定是否重新打开源文件重试
+        self.inner
+            .put_stream(stream, size, path, progress_callback, cancel_rx)
+            .await
+    }
+
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        self.policy
+            .run("create_directory", path, || {
+                self.inner.create_directory(path)
+            })
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        self.inner.delete(path).await
+    }
+
+    async fn list_versions(&self, path: &str) -> Result<Vec<ObjectVersion>, StorageError> {
+        self.policy
+            .run("list_versions", path, || self.inner.list_versions(path))
+            .await
+    }
+
+    fn protocol(&self) -> &str {
+        self.inner.protocol()
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        self.inner.validate_config(config)
+    }
+}
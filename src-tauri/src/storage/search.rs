@@ -0,0 +1,413 @@
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::traits::{ListOptions, StorageClient, StorageError, StorageFile};
+use crate::utils::path_utils::PathUtils;
+
+/// 遍历目录时每页的条目数
+const LIST_PAGE_SIZE: u32 = 1000;
+/// 默认最多返回的匹配数
+const DEFAULT_MAX_RESULTS: u32 = 10000;
+/// 默认同时列出的目录数
+const DEFAULT_CONCURRENCY: u32 = 8;
+/// 同时列出的目录数上限
+const MAX_CONCURRENCY: u32 = 32;
+
+/// 搜索选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchOptions {
+    /// 搜索 ID，未指定时自动生成，可用于 storage_search_cancel
+    pub search_id: Option<String>,
+    /// glob 模式，如 "*.parquet"；包含 / 时匹配相对路径，否则匹配文件名
+    pub pattern: Option<String>,
+    /// 正则表达式，匹配相对于搜索根目录的路径
+    pub regex: Option<String>,
+    pub min_size: Option<String>, // 使用字符串表示大数字
+    pub max_size: Option<String>, // 使用字符串表示大数字
+    /// 修改时间下限（RFC 3339）
+    pub modified_after: Option<String>,
+    /// 修改时间上限（RFC 3339）
+    pub modified_before: Option<String>,
+    /// 最大目录深度，0 表示只搜索根目录
+    pub max_depth: Option<u32>,
+    /// 最多返回的匹配数，默认 10000
+    pub max_results: Option<u32>,
+    /// 回退到逐层遍历时同时列出的目录数，默认 8
+    pub concurrency: Option<u32>,
+}
+
+/// 搜索汇总
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSummary {
+    pub search_id: String,
+    pub matched: u32,
+    pub scanned: u32,
+    /// 列出失败而被跳过的子目录数
+    pub errors: u32,
+    /// 是否因达到 max_results 而提前结束
+    pub truncated: bool,
+    pub cancelled: bool,
+    /// 是否使用了后端原生的递归列出
+    pub native: bool,
+    pub duration_ms: String,
+}
+
+/// 搜索过程中分批推送的结果，filename 为可直接用于其他命令的完整路径
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub search_id: String,
+    pub files: Vec<StorageFile>,
+}
+
+/// 编译后的搜索过滤条件
+struct SearchFilter {
+    glob: Option<glob::Pattern>,
+    regex: Option<regex::Regex>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    modified_after: Option<chrono::DateTime<chrono::Utc>>,
+    modified_before: Option<chrono::DateTime<chrono::Utc>>,
+    max_depth: Option<u32>,
+}
+
+impl SearchFilter {
+    fn new(options: &SearchOptions) -> Result<Self, StorageError> {
+        let parse_size = |value: &Option<String>, name: &str| {
+            value
+                .as_deref()
+                .map(|v| {
+                    v.parse::<u64>().map_err(|_| {
+                        StorageError::InvalidConfig(format!("Invalid {}: {}", name, v))
+                    })
+                })
+                .transpose()
+        };
+        let parse_time = |value: &Option<String>, name: &str| {
+            value
+                .as_deref()
+                .map(|v| {
+                    parse_lastmod(v).ok_or_else(|| {
+                        StorageError::InvalidConfig(format!("Invalid {}: {}", name, v))
+                    })
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            glob: options
+                .pattern
+                .as_deref()
+                .map(glob::Pattern::new)
+                .transpose()
+                .map_err(|e| StorageError::InvalidConfig(format!("Invalid glob pattern: {}", e)))?,
+            regex: options
+                .regex
+                .as_deref()
+                .map(regex::Regex::new)
+                .transpose()
+                .map_err(|e| StorageError::InvalidConfig(format!("Invalid regex: {}", e)))?,
+            min_size: parse_size(&options.min_size, "minSize")?,
+            max_size: parse_size(&options.max_size, "maxSize")?,
+            modified_after: parse_time(&options.modified_after, "modifiedAfter")?,
+            modified_before: parse_time(&options.modified_before, "modifiedBefore")?,
+            max_depth: options.max_depth,
+        })
+    }
+
+    /// 判断文件是否满足所有过滤条件，relative_path 为相对于搜索根目录的路径
+    fn matches(&self, relative_path: &str, file: &StorageFile) -> bool {
+        if file.file_type != "file" {
+            return false;
+        }
+
+        if let Some(max_depth) = self.max_depth {
+            if relative_path.matches('/').count() as u32 > max_depth {
+                return false;
+            }
+        }
+
+        if let Some(glob) = &self.glob {
+            let target = if glob.as_str().contains('/') {
+                relative_path
+            } else {
+                file.basename.as_str()
+            };
+            if !glob.matches(target) {
+                return false;
+            }
+        }
+
+        if let Some(regex) = &self.regex {
+            if !regex.is_match(relative_path) {
+                return false;
+            }
+        }
+
+        if self.min_size.is_some() || self.max_size.is_some() {
+            let size = file.size.parse::<u64>().unwrap_or(0);
+            if self.min_size.is_some_and(|min| size < min)
+                || self.max_size.is_some_and(|max| size > max)
+            {
+                return false;
+            }
+        }
+
+        if self.modified_after.is_some() || self.modified_before.is_some() {
+            // 无法确定修改时间的文件不满足时间条件
+            let Some(lastmod) = parse_lastmod(&file.lastmod) else {
+                return false;
+            };
+            if self.modified_after.is_some_and(|after| lastmod < after)
+                || self.modified_before.is_some_and(|before| lastmod > before)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// 搜索进度与结果计数
+struct SearchState<'a> {
+    root: &'a str,
+    filter: SearchFilter,
+    max_results: u32,
+    matched: u32,
+    scanned: u32,
+    errors: u32,
+    truncated: bool,
+    on_batch: &'a (dyn Fn(Vec<StorageFile>) + Send + Sync),
+}
+
+impl SearchState<'_> {
+    /// 过滤一批条目并推送匹配结果，达到结果上限时返回 false
+    fn consume(&mut self, entries: Vec<(String, StorageFile)>) -> bool {
+        let mut batch = Vec::new();
+
+        for (relative_path, mut file) in entries {
+            self.scanned += 1;
+            if !self.filter.matches(&relative_path, &file) {
+                continue;
+            }
+
+            file.filename = PathUtils::join_storage_path(self.root, &relative_path);
+            batch.push(file);
+            self.matched += 1;
+
+            if self.matched >= self.max_results {
+                self.truncated = true;
+                break;
+            }
+        }
+
+        if !batch.is_empty() {
+            (self.on_batch)(batch);
+        }
+
+        !self.truncated
+    }
+}
+
+/// 存储搜索器
+/// 优先使用后端原生的递归列出（OSS 不带 delimiter、HF tree recursive、WebDAV Depth: infinity），
+/// 不支持时回退到并发的逐层遍历
+pub struct StorageSearcher;
+
+impl StorageSearcher {
+    /// 在 root 下递归搜索，匹配结果通过 on_batch 分批推送
+    pub async fn search(
+        client: &(dyn StorageClient + Send + Sync),
+        root: &str,
+        search_id: &str,
+        options: &SearchOptions,
+        cancelled: &AtomicBool,
+        on_batch: &(dyn Fn(Vec<StorageFile>) + Send + Sync),
+    ) -> Result<SearchSummary, StorageError> {
+        let started_at = std::time::Instant::now();
+        let mut state = SearchState {
+            root,
+            filter: SearchFilter::new(options)?,
+            max_results: options.max_results.unwrap_or(DEFAULT_MAX_RESULTS).max(1),
+            matched: 0,
+            scanned: 0,
+            errors: 0,
+            truncated: false,
+            on_batch,
+        };
+        let concurrency = options
+            .concurrency
+            .unwrap_or(DEFAULT_CONCURRENCY)
+            .clamp(1, MAX_CONCURRENCY) as usize;
+
+        let mut native = client.supports_recursive_listing(root);
+        if native {
+            match Self::search_native(client, root, cancelled, &mut state).await {
+                Ok(()) => {}
+                // 服务器拒绝原生递归列出时回退到逐层遍历
+                Err(StorageError::ProtocolNotSupported(reason)) if state.scanned == 0 => {
+                    log::info!(
+                        "Native recursive listing unavailable for {}: {}",
+                        root,
+                        reason
+                    );
+                    native = false;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if !native {
+            Self::search_parallel(client, root, concurrency, cancelled, &mut state).await?;
+        }
+
+        Ok(SearchSummary {
+            search_id: search_id.to_string(),
+            matched: state.matched,
+            scanned: state.scanned,
+            errors: state.errors,
+            truncated: state.truncated,
+            cancelled: cancelled.load(Ordering::SeqCst),
+            native,
+            duration_ms: started_at.elapsed().as_millis().to_string(),
+        })
+    }
+
+    /// 使用原生递归列出，逐页过滤并推送
+    async fn search_native(
+        client: &(dyn StorageClient + Send + Sync),
+        root: &str,
+        cancelled: &AtomicBool,
+        state: &mut SearchState<'_>,
+    ) -> Result<(), StorageError> {
+        let mut marker: Option<String> = None;
+
+        while !cancelled.load(Ordering::SeqCst) {
+            let options = ListOptions {
+                page_size: Some(LIST_PAGE_SIZE),
+                marker: marker.clone(),
+                prefix: None,
+                recursive: Some(true),
+                ..Default::default()
+            };
+            let result = client.list_directory(root, Some(&options)).await?;
+
+            // 原生递归列出的 filename 为相对于 root 的路径
+            let entries = result
+                .files
+                .into_iter()
+                .map(|file| (file.filename.clone(), file))
+                .collect();
+            if !state.consume(entries) {
+                break;
+            }
+
+            if result.has_more && result.next_marker.is_some() {
+                marker = result.next_marker;
+            } else {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 逐层遍历目录，每层内的目录并发列出，每个目录列出完成后立即推送结果
+    async fn search_parallel(
+        client: &(dyn StorageClient + Send + Sync),
+        root: &str,
+        concurrency: usize,
+        cancelled: &AtomicBool,
+        state: &mut SearchState<'_>,
+    ) -> Result<(), StorageError> {
+        let max_depth = state.filter.max_depth;
+        let mut frontier = vec![(String::new(), 0u32)];
+
+        while !frontier.is_empty() && !state.truncated && !cancelled.load(Ordering::SeqCst) {
+            let mut next_frontier = Vec::new();
+            let mut listings = futures_util::stream::iter(std::mem::take(&mut frontier))
+                .map(|(relative_dir, depth)| async move {
+                    let directory = PathUtils::join_storage_path(root, &relative_dir);
+                    let result = Self::list_all(client, &directory, cancelled).await;
+                    (result, relative_dir, depth)
+                })
+                .buffer_unordered(concurrency);
+
+            while let Some((result, relative_dir, depth)) = listings.next().await {
+                let files = match result {
+                    Ok(files) => files,
+                    // 根目录无法列出时整个搜索失败，子目录失败只记录并跳过
+                    Err(e) if relative_dir.is_empty() => return Err(e),
+                    Err(e) => {
+                        log::warn!("Search skipped {}: {}", relative_dir, e);
+                        state.errors += 1;
+                        continue;
+                    }
+                };
+
+                let mut entries = Vec::with_capacity(files.len());
+                for file in files {
+                    let relative_path = PathUtils::join_storage_path(&relative_dir, &file.basename);
+                    if file.file_type == "directory" && max_depth.is_none_or(|max| depth < max) {
+                        next_frontier.push((relative_path.clone(), depth + 1));
+                    }
+                    entries.push((relative_path, file));
+                }
+
+                if !state.consume(entries) {
+                    break;
+                }
+            }
+
+            frontier = next_frontier;
+        }
+
+        Ok(())
+    }
+
+    /// 列出目录的所有分页，收到取消时返回已列出的部分
+    pub async fn list_all(
+        client: &(dyn StorageClient + Send + Sync),
+        directory: &str,
+        cancelled: &AtomicBool,
+    ) -> Result<Vec<StorageFile>, StorageError> {
+        let mut files = Vec::new();
+        let mut marker: Option<String> = None;
+
+        while !cancelled.load(Ordering::SeqCst) {
+            let options = ListOptions {
+                page_size: Some(LIST_PAGE_SIZE),
+                marker: marker.clone(),
+                prefix: None,
+                recursive: Some(false),
+                ..Default::default()
+            };
+            let result = client.list_directory(directory, Some(&options)).await?;
+            files.extend(result.files);
+
+            if result.has_more && result.next_marker.is_some() {
+                marker = result.next_marker;
+            } else {
+                break;
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+/// 解析各协议返回的修改时间（RFC 3339、RFC 2822 或不带时区的日期时间）
+pub fn parse_lastmod(lastmod: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(lastmod) {
+        return Some(time.with_timezone(&chrono::Utc));
+    }
+    if let Ok(time) = chrono::DateTime::parse_from_rfc2822(lastmod) {
+        return Some(time.with_timezone(&chrono::Utc));
+    }
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(lastmod, format).ok())
+        .map(|time| time.and_utc())
+}
@@ -1,7 +1,12 @@
 use async_trait::async_trait;
-use smb::packets::fscc::FileAccessMask;
+use smb::packets::fscc::{
+    FileAccessMask, FileAttributes, FileDispositionInformation, FileNetworkOpenInformation,
+    FileRenameInformation2,
+};
+use smb::packets::smb2::{CreateDisposition, CreateOptions};
 use smb::resource::Resource;
 use smb::{Client, ClientConfig, FileCreateArgs, UncPath};
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
@@ -10,8 +15,10 @@ use std::sync::{
 use tokio::task::spawn_blocking;
 
 use crate::storage::traits::{
-    ConnectionConfig, DirectoryResult, ListOptions, ProgressCallback, StorageClient, StorageError,
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError,
 };
+use crate::utils::stream_io::StreamIo;
 
 pub struct SMBClient {
     config: ConnectionConfig,
@@ -91,6 +98,135 @@ impl SMBClient {
             StorageError::InvalidConfig(format!("Invalid UNC path '{}': {}", full_path, e))
         })
     }
+
+    /// 写入文件的一个数据块，truncate 为 true 时创建或覆盖文件
+    async fn write_file_block(
+        &self,
+        path: &str,
+        data: Vec<u8>,
+        offset: u64,
+        truncate: bool,
+    ) -> Result<(), StorageError> {
+        let unc_path = self.build_unc_path(path)?;
+        let client_arc = self.client.clone();
+        let path_clone = path.to_string();
+
+        spawn_blocking(move || -> Result<(), StorageError> {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut client_guard = client_arc.lock().unwrap();
+                if let Some(ref mut client) = *client_guard {
+                    let file_args = if truncate {
+                        FileCreateArgs::make_overwrite(FileAttributes::new(), CreateOptions::new())
+                    } else {
+                        FileCreateArgs::make_open_existing(
+                            FileAccessMask::new().with_generic_write(true),
+                        )
+                    };
+                    let resource =
+                        client
+                            .create_file(&unc_path, &file_args)
+                            .await
+                            .map_err(|e| {
+                                StorageError::IoError(format!(
+                                    "Failed to open file {}: {}",
+                                    path_clone, e
+                                ))
+                            })?;
+
+                    if let Resource::File(file) = resource {
+                        let mut written = 0usize;
+                        while written < data.len() {
+                            let bytes_written = file
+                                .write_block(&data[written..], offset + written as u64)
+                                .await
+                                .map_err(|e| {
+                                    StorageError::IoError(format!("Failed to write file: {}", e))
+                                })?;
+                            if bytes_written == 0 {
+                                return Err(StorageError::IoError(
+                                    "Failed to write file: no bytes written".to_string(),
+                                ));
+                            }
+                            written += bytes_written;
+                        }
+                        Ok(())
+                    } else {
+                        Err(StorageError::IoError("Expected file resource".to_string()))
+                    }
+                } else {
+                    Err(StorageError::NotConnected)
+                }
+            })
+        })
+        .await
+        .map_err(|e| StorageError::IoError(format!("Tokio join error: {}", e)))?
+    }
+
+    /// 打开文件或目录并设置删除/重命名信息
+    async fn set_path_info(
+        &self,
+        path: &str,
+        rename_to: Option<String>,
+    ) -> Result<(), StorageError> {
+        let unc_path = self.build_unc_path(path)?;
+        let client_arc = self.client.clone();
+        let path_clone = path.to_string();
+
+        spawn_blocking(move || -> Result<(), StorageError> {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut client_guard = client_arc.lock().unwrap();
+                if let Some(ref mut client) = *client_guard {
+                    let file_args = FileCreateArgs::make_open_existing(
+                        FileAccessMask::new()
+                            .with_delete(true)
+                            .with_generic_read(true),
+                    );
+                    let resource =
+                        client
+                            .create_file(&unc_path, &file_args)
+                            .await
+                            .map_err(|e| {
+                                StorageError::IoError(format!(
+                                    "Failed to open {}: {}",
+                                    path_clone, e
+                                ))
+                            })?;
+
+                    let handle = match &resource {
+                        Resource::File(file) => &**file,
+                        Resource::Directory(dir) => &**dir,
+                        Resource::Pipe(_) => {
+                            return Err(StorageError::IoError(
+                                "Unexpected pipe resource".to_string(),
+                            ))
+                        }
+                    };
+
+                    let result = if let Some(new_name) = rename_to {
+                        handle
+                            .set_file_info(FileRenameInformation2 {
+                                replace_if_exists: false.into(),
+                                root_directory: 0,
+                                file_name: new_name.into(),
+                            })
+                            .await
+                    } else {
+                        handle
+                            .set_file_info(FileDispositionInformation::default())
+                            .await
+                    };
+
+                    result.map_err(|e| {
+                        StorageError::IoError(format!("Failed to update {}: {}", path_clone, e))
+                    })
+                } else {
+                    Err(StorageError::NotConnected)
+                }
+            })
+        })
+        .await
+        .map_err(|e| StorageError::IoError(format!("Tokio join error: {}", e)))?
+    }
 }
 
 #[async_trait]
@@ -134,6 +270,224 @@ impl StorageClient for SMBClient {
         })
     }
 
+    /// SMB 流式读取：在独立线程中打开文件并按块读取，通过有界通道传递数据
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        self.establish_connection_internal().await?;
+
+        let unc_path = self.build_unc_path(path)?;
+        let client_arc = self.client.clone();
+        let path_clone = path.to_string();
+
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel::<Result<(), StorageError>>();
+        let (chunk_tx, mut chunk_rx) =
+            tokio::sync::mpsc::channel::<Result<bytes::Bytes, StorageError>>(4);
+
+        spawn_blocking(move || {
+            tokio::runtime::Handle::current().block_on(async {
+                // 仅在打开文件时持有客户端锁
+                let opened = {
+                    let mut client_guard = client_arc.lock().unwrap();
+                    if let Some(ref mut client) = *client_guard {
+                        let file_args = FileCreateArgs::make_open_existing(
+                            FileAccessMask::new().with_generic_read(true),
+                        );
+                        client
+                            .create_file(&unc_path, &file_args)
+                            .await
+                            .map_err(|e| {
+                                StorageError::IoError(format!(
+                                    "Failed to open file {}: {}",
+                                    path_clone, e
+                                ))
+                            })
+                    } else {
+                        Err(StorageError::NotConnected)
+                    }
+                };
+
+                let file = match opened {
+                    Ok(Resource::File(file)) => file,
+                    Ok(_) => {
+                        let _ = ready_tx.send(Err(StorageError::IoError(
+                            "Expected file resource".to_string(),
+                        )));
+                        return;
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+
+                use smb::resource::file_util::GetLen;
+                let file_size = match file.get_len().await {
+                    Ok(size) => size,
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(StorageError::IoError(format!(
+                            "Failed to get file size: {}",
+                            e
+                        ))));
+                        return;
+                    }
+                };
+
+                if ready_tx.send(Ok(())).is_err() {
+                    return;
+                }
+
+                let chunk_size = std::cmp::max(
+                    crate::utils::chunk_size::calculate_optimal_chunk_size(file_size),
+                    1024 * 1024, // 至少 1MB
+                ) as u64;
+                let mut offset = start;
+
+                while offset < file_size {
+                    let current_chunk_size = std::cmp::min(chunk_size, file_size - offset);
+                    let mut buffer = vec![0u8; current_chunk_size as usize];
+
+                    let chunk = match file.read_block(&mut buffer, offset, false).await {
+                        Ok(0) => break,
+                        Ok(bytes_read) => {
+                            buffer.truncate(bytes_read);
+                            offset += bytes_read as u64;
+                            Ok(bytes::Bytes::from(buffer))
+                        }
+                        Err(e) => Err(StorageError::IoError(format!("Failed to read file: {}", e))),
+                    };
+
+                    let is_err = chunk.is_err();
+                    // 接收端被丢弃说明调用方已停止读取
+                    if chunk_tx.send(chunk).await.is_err() || is_err {
+                        break;
+                    }
+                }
+            })
+        });
+
+        ready_rx.await.map_err(|_| {
+            StorageError::IoError("SMB read task exited unexpectedly".to_string())
+        })??;
+
+        Ok(Box::pin(async_stream::stream! {
+            while let Some(chunk) = chunk_rx.recv().await {
+                yield chunk;
+            }
+        }))
+    }
+
+    /// 通过 SMB 查询文件信息获取元数据，文件属性作为扩展信息返回
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        self.establish_connection_internal().await?;
+
+        let unc_path = self.build_unc_path(path)?;
+        let client_arc = self.client.clone();
+        let path_clone = path.to_string();
+
+        let info = spawn_blocking(
+            move || -> Result<FileNetworkOpenInformation, StorageError> {
+                tokio::runtime::Handle::current().block_on(async {
+                    let mut client_guard = client_arc.lock().unwrap();
+                    if let Some(ref mut client) = *client_guard {
+                        let file_args = FileCreateArgs::make_open_existing(
+                            FileAccessMask::new().with_generic_read(true),
+                        );
+                        let resource =
+                            client
+                                .create_file(&unc_path, &file_args)
+                                .await
+                                .map_err(|e| {
+                                    StorageError::IoError(format!(
+                                        "Failed to open {}: {}",
+                                        path_clone, e
+                                    ))
+                                })?;
+
+                        let handle = match &resource {
+                            Resource::File(file) => &**file,
+                            Resource::Directory(dir) => &**dir,
+                            Resource::Pipe(_) => {
+                                return Err(StorageError::IoError(
+                                    "Unexpected pipe resource".to_string(),
+                                ))
+                            }
+                        };
+
+                        handle
+                            .query_info::<FileNetworkOpenInformation>()
+                            .await
+                            .map_err(|e| {
+                                StorageError::IoError(format!("Failed to query file info: {}", e))
+                            })
+                    } else {
+                        Err(StorageError::NotConnected)
+                    }
+                })
+            },
+        )
+        .await
+        .map_err(|e| StorageError::IoError(format!("Tokio join error: {}", e)))??;
+
+        let format_time = |time: smb::packets::binrw_util::file_time::FileTime| {
+            chrono::DateTime::<chrono::Utc>::from(std::time::SystemTime::from(time)).to_rfc3339()
+        };
+
+        let attributes = info.file_attributes;
+        let is_directory = attributes.directory();
+
+        let mut extra = HashMap::new();
+        extra.insert("created".to_string(), format_time(info.creation_time));
+        extra.insert("accessed".to_string(), format_time(info.last_access_time));
+        extra.insert("changed".to_string(), format_time(info.change_time));
+        extra.insert(
+            "allocationSize".to_string(),
+            info.allocation_size.to_string(),
+        );
+        for (name, enabled) in [
+            ("hidden", attributes.hidden()),
+            ("system", attributes.system()),
+            ("archive", attributes.archive()),
+            ("compressed", attributes.compressed()),
+            ("encrypted", attributes.encrypted()),
+            ("sparse", attributes.sparse_file()),
+            ("offline", attributes.offline()),
+        ] {
+            if enabled {
+                extra.insert(name.to_string(), "true".to_string());
+            }
+        }
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename: path
+                .trim_end_matches(['/', '\\'])
+                .rsplit(['/', '\\'])
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            lastmod: format_time(info.last_write_time),
+            size: if is_directory {
+                "0".to_string()
+            } else {
+                info.end_of_file.to_string()
+            },
+            file_type: if is_directory { "directory" } else { "file" }.to_string(),
+            mime: None,
+            etag: None,
+            checksum: None,
+            permissions: Some(
+                if attributes.readonly() {
+                    "readonly"
+                } else {
+                    "readwrite"
+                }
+                .to_string(),
+            ),
+            owner: None,
+            group: None,
+            extra,
+        })
+    }
+
     async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
         self.establish_connection_internal().await?;
 
@@ -266,70 +620,146 @@ impl StorageClient for SMBClient {
         Ok(full_path)
     }
 
-    /// SMB 文件下载实现，使用分块读取（待实现完整 SMB 流式支持）
+    /// SMB 文件下载实现，基于流式读取
     async fn download_file(
         &self,
         path: &str,
         save_path: &std::path::Path,
         progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let file_size = self.get_file_size(path).await?;
+        let stream = self.open_read_stream(path, 0).await?;
+
+        StreamIo::save_to_file(stream, save_path, file_size, progress_callback, cancel_rx).await
+    }
+
+    /// SMB 文件上传实现，使用分块写入
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
         mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
     ) -> Result<(), StorageError> {
-        use tokio::io::AsyncWriteExt;
+        use tokio::io::AsyncReadExt;
 
-        // 获取文件大小
-        let file_size = self.get_file_size(path).await?;
+        self.establish_connection_internal().await?;
+
+        let file_size = tokio::fs::metadata(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to get file metadata: {}", e)))?
+            .len();
 
-        // 创建本地文件
-        let mut file = tokio::fs::File::create(save_path)
+        let mut local_file = tokio::fs::File::open(local_path)
             .await
-            .map_err(|e| StorageError::IoError(format!("Failed to create file: {}", e)))?;
+            .map_err(|e| StorageError::IoError(format!("Failed to open local file: {}", e)))?;
 
-        // 使用较大的块大小减少网络请求次数
+        // 与下载保持一致，使用较大的块大小减少网络请求次数
         let chunk_size = std::cmp::max(
             crate::utils::chunk_size::calculate_optimal_chunk_size(file_size),
             1024 * 1024, // 至少 1MB
         );
         let mut written = 0u64;
 
-        while written < file_size {
+        // 先创建（或清空）目标文件，保证空文件也能上传
+        self.write_file_block(path, Vec::new(), 0, true).await?;
+
+        loop {
             // 检查取消信号
             if let Some(ref mut cancel_rx) = cancel_rx {
                 if cancel_rx.try_recv().is_ok() {
-                    let _ = tokio::fs::remove_file(save_path).await;
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+                    let _ = self.set_path_info(path, None).await;
+                    return Err(StorageError::Cancelled);
                 }
             }
 
-            let remaining = file_size - written;
-            let current_chunk_size = std::cmp::min(chunk_size as u64, remaining);
-
-            let chunk_data = self
-                .read_file_range(path, written, current_chunk_size)
-                .await?;
-
-            file.write_all(&chunk_data)
+            let mut buffer = vec![0u8; chunk_size];
+            let bytes_read = local_file
+                .read(&mut buffer)
                 .await
-                .map_err(|e| StorageError::IoError(format!("Failed to write data: {}", e)))?;
+                .map_err(|e| StorageError::IoError(format!("Failed to read local file: {}", e)))?;
+
+            if bytes_read == 0 {
+                break;
+            }
 
-            written += chunk_data.len() as u64;
+            buffer.truncate(bytes_read);
+            self.write_file_block(path, buffer, written, false).await?;
+            written += bytes_read as u64;
 
             // 调用进度回调
             if let Some(ref callback) = progress_callback {
                 callback(written, file_size);
             }
-
-            // 如果读取的数据少于请求的数据，说明文件结束了
-            if chunk_data.len() < current_chunk_size as usize {
-                break;
-            }
         }
 
-        file.flush()
+        Ok(())
+    }
+
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        self.establish_connection_internal().await?;
+
+        // 逐级创建缺失的目录
+        let mut current = String::new();
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            current = format!("{}/{}", current, segment);
+
+            let unc_path = self.build_unc_path(&current)?;
+            let client_arc = self.client.clone();
+            let path_clone = current.clone();
+
+            spawn_blocking(move || -> Result<(), StorageError> {
+                tokio::runtime::Handle::current().block_on(async {
+                    let mut client_guard = client_arc.lock().unwrap();
+                    if let Some(ref mut client) = *client_guard {
+                        // OpenIf：目录已存在时直接打开
+                        let dir_args = FileCreateArgs {
+                            disposition: CreateDisposition::OpenIf,
+                            attributes: FileAttributes::new().with_directory(true),
+                            options: CreateOptions::new().with_directory_file(true),
+                            desired_access: FileAccessMask::new().with_generic_read(true),
+                        };
+                        client
+                            .create_file(&unc_path, &dir_args)
+                            .await
+                            .map(|_| ())
+                            .map_err(|e| {
+                                StorageError::IoError(format!(
+                                    "Failed to create directory {}: {}",
+                                    path_clone, e
+                                ))
+                            })
+                    } else {
+                        Err(StorageError::NotConnected)
+                    }
+                })
+            })
             .await
-            .map_err(|e| StorageError::IoError(format!("Failed to flush file: {}", e)))?;
+            .map_err(|e| StorageError::IoError(format!("Tokio join error: {}", e)))??;
+        }
 
         Ok(())
     }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        self.establish_connection_internal().await?;
+
+        // 新名称是相对于共享根目录的路径
+        let new_name = to.trim_start_matches('/').replace('/', "\\");
+        self.set_path_info(from, Some(new_name)).await
+    }
+
+    /// 删除文件或空目录（目录列举尚未实现，暂不支持递归删除）
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        self.establish_connection_internal().await?;
+
+        if path.trim_matches('/').is_empty() {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the root directory".to_string(),
+            ));
+        }
+
+        self.set_path_info(path, None).await
+    }
 }
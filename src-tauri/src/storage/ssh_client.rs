@@ -1,7 +1,9 @@
 use async_trait::async_trait;
+use futures_util::StreamExt;
 use russh::client::{self, Handle};
 use russh_keys;
 use russh_sftp::client::SftpSession;
+use std::collections::HashMap;
 use std::io::SeekFrom;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -9,14 +11,17 @@ use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tokio::sync::Mutex;
 
 use crate::storage::traits::{
-    ConnectionConfig, DirectoryResult, ListOptions, ProgressCallback, StorageClient, StorageError,
-    StorageFile,
+    ByteStream, ConnectionConfig, DirectoryResult, DirectoryUsage, FileMetadata, ListOptions,
+    ProgressCallback, StorageClient, StorageError, StorageFile,
 };
 use crate::utils::path_utils::PathUtils;
+use crate::utils::stream_io::StreamIo;
 
 pub struct SSHClient {
     config: ConnectionConfig,
     handle: Arc<Mutex<Option<Handle<Client>>>>,
+    // ProxyJump 跳板机的会话，目标主机的连接经由它们转发，需要与连接一同保留
+    jumps: Arc<Mutex<Vec<Handle<Client>>>>,
     sftp: Arc<Mutex<Option<SftpSession>>>,
     connected: Arc<std::sync::atomic::AtomicBool>,
 }
@@ -48,9 +53,9 @@ impl SSHClient {
         let error_lower = error_msg.to_lowercase();
 
         if error_lower.contains("permission") {
-            StorageError::RequestFailed(format!("Permission denied {} file: {}", operation, path))
+            StorageError::PermissionDenied(format!("{} file: {}", operation, path))
         } else if error_lower.contains("not found") || error_lower.contains("no such file") {
-            StorageError::RequestFailed(format!("File not found: {}", path))
+            StorageError::NotFound(path.to_string())
         } else if error_lower.contains("not a directory") {
             StorageError::RequestFailed(format!("Path is not a directory: {}", path))
         } else if error_lower.contains("directory") && operation == "accessing" {
@@ -71,9 +76,7 @@ impl SSHClient {
             std::io::ErrorKind::NotFound => {
                 StorageError::NotFound(format!("File not found: {}", path))
             }
-            std::io::ErrorKind::PermissionDenied => {
-                StorageError::RequestFailed(format!("Permission denied: {}", path))
-            }
+            std::io::ErrorKind::PermissionDenied => StorageError::PermissionDenied(path.to_string()),
             _ => {
                 StorageError::RequestFailed(format!("Failed to {} {}: {}", operation, path, error))
             }
@@ -84,6 +87,7 @@ impl SSHClient {
         Ok(SSHClient {
             config,
             handle: Arc::new(Mutex::new(None)),
+            jumps: Arc::new(Mutex::new(Vec::new())),
             sftp: Arc::new(Mutex::new(None)),
             connected: Arc::new(std::sync::atomic::AtomicBool::new(false)),
         })
@@ -104,14 +108,79 @@ impl SSHClient {
 
         // 创建SSH配置
         let ssh_config = Arc::new(client::Config::default());
-        let sh = Client {};
 
-        // 建立连接 - 修正类型问题
-        let mut handle = client::connect(ssh_config, (server.as_str(), port), sh)
-            .await
-            .map_err(|e| StorageError::ConnectionFailed(format!("SSH connect failed: {}", e)))?;
+        // 经由 extraOptions.proxyJump 中的跳板机逐级转发，跳板机使用与目标主机相同的认证方式
+        let mut jumps: Vec<Handle<Client>> = Vec::new();
+        for (jump_user, jump_host, jump_port) in Self::jump_hosts(config, username)? {
+            let mut jump =
+                Self::open_session(ssh_config.clone(), jumps.last(), &jump_host, jump_port).await?;
+            Self::authenticate(&mut jump, &jump_user, config).await?;
+            jumps.push(jump);
+        }
+
+        let mut handle = Self::open_session(ssh_config, jumps.last(), server, port).await?;
+        Self::authenticate(&mut handle, username, config).await?;
+
+        // 创建SFTP会话
+        let channel = handle.channel_open_session().await.map_err(|e| {
+            StorageError::ConnectionFailed(format!("Failed to open channel: {}", e))
+        })?;
+
+        channel.request_subsystem(true, "sftp").await.map_err(|e| {
+            StorageError::ConnectionFailed(format!("Failed to request SFTP subsystem: {}", e))
+        })?;
+
+        let sftp = SftpSession::new(channel.into_stream()).await.map_err(|e| {
+            StorageError::ConnectionFailed(format!("Failed to create SFTP session: {}", e))
+        })?;
+
+        // 保存连接
+        *self.handle.lock().await = Some(handle);
+        *self.jumps.lock().await = jumps;
+        *self.sftp.lock().await = Some(sftp);
+        self.connected
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// 连接到 SSH 服务器，via 不为空时通过该会话的 direct-tcpip 通道转发
+    async fn open_session(
+        ssh_config: Arc<client::Config>,
+        via: Option<&Handle<Client>>,
+        host: &str,
+        port: u16,
+    ) -> Result<Handle<Client>, StorageError> {
+        let result = match via {
+            Some(via) => {
+                let channel = via
+                    .channel_open_direct_tcpip(host, port as u32, "127.0.0.1", 0)
+                    .await
+                    .map_err(|e| {
+                        StorageError::ConnectionFailed(format!(
+                            "Failed to open tunnel to {}:{}: {}",
+                            host, port, e
+                        ))
+                    })?;
+                client::connect_stream(ssh_config, channel.into_stream(), Client {}).await
+            }
+            None => client::connect(ssh_config, (host, port), Client {}).await,
+        };
 
-        // 认证
+        result.map_err(|e| {
+            StorageError::ConnectionFailed(format!(
+                "SSH connect to {}:{} failed: {}",
+                host, port, e
+            ))
+        })
+    }
+
+    /// 使用配置中的密码或私钥认证
+    async fn authenticate(
+        handle: &mut Handle<Client>,
+        username: &str,
+        config: &ConnectionConfig,
+    ) -> Result<(), StorageError> {
         let auth_result = if let Some(password) = &config.password {
             // 密码认证
             handle
@@ -152,31 +221,60 @@ impl SSHClient {
         }?;
 
         if !auth_result {
-            return Err(StorageError::AuthenticationFailed(
-                "SSH authentication failed".to_string(),
-            ));
+            return Err(StorageError::AuthenticationFailed(format!(
+                "SSH authentication failed for {}",
+                username
+            )));
         }
+        Ok(())
+    }
 
-        // 创建SFTP会话
-        let channel = handle.channel_open_session().await.map_err(|e| {
-            StorageError::ConnectionFailed(format!("Failed to open channel: {}", e))
-        })?;
-
-        channel.request_subsystem(true, "sftp").await.map_err(|e| {
-            StorageError::ConnectionFailed(format!("Failed to request SFTP subsystem: {}", e))
-        })?;
-
-        let sftp = SftpSession::new(channel.into_stream()).await.map_err(|e| {
-            StorageError::ConnectionFailed(format!("Failed to create SFTP session: {}", e))
-        })?;
-
-        // 保存连接
-        *self.handle.lock().await = Some(handle);
-        *self.sftp.lock().await = Some(sftp);
-        self.connected
-            .store(true, std::sync::atomic::Ordering::Relaxed);
+    /// 解析 extraOptions.proxyJump，格式与 ssh 的 ProxyJump 相同：逗号分隔的 [user@]host[:port]
+    /// 返回按连接顺序排列的（用户名、主机、端口），未指定用户名时使用目标主机的用户名
+    fn jump_hosts(
+        config: &ConnectionConfig,
+        default_user: &str,
+    ) -> Result<Vec<(String, String, u16)>, StorageError> {
+        let Some(proxy_jump) = config
+            .extra_options
+            .as_ref()
+            .and_then(|options| options.get("proxyJump"))
+            .filter(|value| !value.is_empty() && value.as_str() != "none")
+        else {
+            return Ok(Vec::new());
+        };
 
-        Ok(())
+        proxy_jump
+            .split(',')
+            .map(|hop| {
+                let hop = hop.trim().trim_start_matches("ssh://");
+                let invalid =
+                    || StorageError::InvalidConfig(format!("Invalid ProxyJump host: {}", hop));
+                let (user, address) = match hop.rsplit_once('@') {
+                    Some((user, address)) => (user, address),
+                    None => (default_user, hop),
+                };
+                // IPv6 地址写作 [addr]:port
+                let (host, port) = match address.strip_prefix('[') {
+                    Some(rest) => {
+                        let (host, port) = rest.split_once(']').ok_or_else(invalid)?;
+                        (host, port.strip_prefix(':'))
+                    }
+                    None => match address.rsplit_once(':') {
+                        Some((host, port)) => (host, Some(port)),
+                        None => (address, None),
+                    },
+                };
+                let port = match port {
+                    Some(port) => port.parse().map_err(|_| invalid())?,
+                    None => 22,
+                };
+                if host.is_empty() || user.is_empty() {
+                    return Err(invalid());
+                }
+                Ok((user.to_string(), host.to_string(), port))
+            })
+            .collect()
     }
 
     /// 获取文件的完整路径
@@ -219,6 +317,39 @@ impl SSHClient {
         }
     }
 
+    /// 在远程主机上执行命令，返回退出码和标准输出
+    async fn exec_command(&self, command: &str) -> Result<(u32, Vec<u8>), StorageError> {
+        let handle_guard = self.handle.lock().await;
+        let handle = handle_guard.as_ref().ok_or(StorageError::NotConnected)?;
+
+        let mut channel = handle.channel_open_session().await.map_err(|e| {
+            StorageError::ConnectionFailed(format!("Failed to open channel: {}", e))
+        })?;
+        drop(handle_guard);
+
+        channel.exec(true, command).await.map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to execute remote command: {}", e))
+        })?;
+
+        let mut output = Vec::new();
+        let mut exit_status = None;
+        while let Some(message) = channel.wait().await {
+            match message {
+                russh::ChannelMsg::Data { data } => output.extend_from_slice(&data),
+                russh::ChannelMsg::ExitStatus { exit_status: code } => exit_status = Some(code),
+                _ => {}
+            }
+        }
+
+        // 服务器未返回退出码时视为执行失败
+        Ok((exit_status.unwrap_or(u32::MAX), output))
+    }
+
+    /// 将路径转义为单引号包裹的 shell 参数
+    fn shell_quote(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+
     /// 格式化文件大小
     fn format_file_size(size: u64) -> String {
         size.to_string()
@@ -335,6 +466,57 @@ impl StorageClient for SSHClient {
         })
     }
 
+    /// 使用远程的 du 统计目录占用，du 不可用或不支持 -b（如 BSD）时返回 None
+    async fn directory_usage(
+        &self,
+        path: &str,
+        max_depth: u32,
+    ) -> Result<Option<Vec<DirectoryUsage>>, StorageError> {
+        if !self.connected.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let full_path = self.get_full_path(path);
+        let root = full_path.trim_end_matches('/');
+        let command = format!(
+            "du -b -d {} -- {} 2>/dev/null",
+            max_depth,
+            Self::shell_quote(if root.is_empty() { "/" } else { root })
+        );
+        let (exit_status, output) = self.exec_command(&command).await?;
+
+        // 部分子目录无权限读取时 du 返回 1，但其余结果仍然可用
+        if exit_status > 1 || output.is_empty() {
+            log::info!(
+                "du unavailable for {} (exit status {})",
+                full_path,
+                exit_status
+            );
+            return Ok(None);
+        }
+
+        let mut usage = Vec::new();
+        for line in String::from_utf8_lossy(&output).lines() {
+            let Some((size, entry_path)) = line.split_once('\t') else {
+                continue;
+            };
+            let Ok(size) = size.trim().parse::<u64>() else {
+                continue;
+            };
+            let Some(relative) = entry_path.strip_prefix(root) else {
+                continue;
+            };
+
+            usage.push(DirectoryUsage {
+                path: relative.trim_matches('/').to_string(),
+                size,
+                file_count: None,
+            });
+        }
+
+        Ok(if usage.is_empty() { None } else { Some(usage) })
+    }
+
     async fn read_file_range(
         &self,
         path: &str,
@@ -359,9 +541,7 @@ impl StorageClient for SSHClient {
 
         if let Some(ref mut cancel_rx) = cancel_rx {
             if cancel_rx.try_recv().is_ok() {
-                return Err(StorageError::RequestFailed(
-                    "download.cancelled".to_string(),
-                ));
+                return Err(StorageError::Cancelled);
             }
         }
 
@@ -414,9 +594,7 @@ impl StorageClient for SSHClient {
             // 检查取消信号
             if let Some(ref mut cancel_rx) = cancel_rx {
                 if cancel_rx.try_recv().is_ok() {
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+                    return Err(StorageError::Cancelled);
                 }
             }
         }
@@ -448,6 +626,100 @@ impl StorageClient for SSHClient {
         Ok(buffer)
     }
 
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        if !self.connected.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let full_path = self.get_full_path(path);
+        let mut file = {
+            let mut sftp_guard = self.sftp.lock().await;
+            let sftp = sftp_guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+            sftp.open(&full_path)
+                .await
+                .map_err(|e| Self::parse_ssh_error(&e, "open", &full_path))?
+        };
+
+        file.seek(SeekFrom::Start(start)).await.map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to seek to position {}: {}", start, e))
+        })?;
+
+        // 文件句柄独立于会话锁，读取过程中不阻塞其他 SFTP 操作
+        let stream = tokio_util::io::ReaderStream::with_capacity(file, 64 * 1024)
+            .map(move |chunk| chunk.map_err(|e| Self::parse_io_error(&e, "read", &full_path)));
+
+        Ok(Box::pin(stream))
+    }
+
+    /// 通过 SFTP stat 获取元数据，包含 uid/gid 和权限位
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        if !self.connected.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let full_path = self.get_full_path(path);
+        let mut sftp_guard = self.sftp.lock().await;
+        let sftp = sftp_guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let metadata = sftp
+            .metadata(&full_path)
+            .await
+            .map_err(|e| Self::parse_ssh_error(&e, "stat", &full_path))?;
+
+        let is_dir = metadata.is_dir();
+        let basename = full_path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let mut extra = HashMap::new();
+        if let Some(uid) = metadata.uid {
+            extra.insert("uid".to_string(), uid.to_string());
+        }
+        if let Some(gid) = metadata.gid {
+            extra.insert("gid".to_string(), gid.to_string());
+        }
+        if let Some(mode) = metadata.permissions {
+            extra.insert("mode".to_string(), format!("{:o}", mode));
+        }
+        if let Some(atime) = metadata.atime {
+            extra.insert(
+                "accessed".to_string(),
+                Self::format_mtime(UNIX_EPOCH + std::time::Duration::from_secs(atime as u64)),
+            );
+        }
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename,
+            lastmod: Self::format_mtime(metadata.modified().unwrap_or(UNIX_EPOCH)),
+            size: Self::format_file_size(if is_dir { 0 } else { metadata.len() }),
+            file_type: if is_dir { "directory" } else { "file" }.to_string(),
+            mime: if is_dir {
+                None
+            } else {
+                Some("application/octet-stream".to_string())
+            },
+            etag: None,
+            checksum: None,
+            permissions: metadata
+                .permissions
+                .map(|mode| format!("{:o}", mode & 0o7777)),
+            owner: metadata
+                .user
+                .clone()
+                .or_else(|| metadata.uid.map(|uid| uid.to_string())),
+            group: metadata
+                .group
+                .clone()
+                .or_else(|| metadata.gid.map(|gid| gid.to_string())),
+            extra,
+        })
+    }
+
     async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
         if !self.connected.load(std::sync::atomic::Ordering::Relaxed) {
             return Err(StorageError::NotConnected);
@@ -469,6 +741,10 @@ impl StorageClient for SSHClient {
         Ok(metadata.len())
     }
 
+    fn canonical_path(&self, path: &str) -> String {
+        self.get_full_path(path)
+    }
+
     fn protocol(&self) -> &str {
         "ssh"
     }
@@ -530,56 +806,191 @@ impl StorageClient for SSHClient {
         path: &str,
         save_path: &std::path::Path,
         progress_callback: Option<ProgressCallback>,
-        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
     ) -> Result<(), StorageError> {
         let file_size = self.get_file_size(path).await?;
-        let mut local_file = tokio::fs::File::create(save_path)
+        let stream = self.open_read_stream(path, 0).await?;
+
+        StreamIo::save_to_file(stream, save_path, file_size, progress_callback, cancel_rx).await
+    }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.connected.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let file_size = tokio::fs::metadata(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to get file metadata: {}", e)))?
+            .len();
+
+        let mut local_file = tokio::fs::File::open(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to open local file: {}", e)))?;
+
+        let full_path = self.get_full_path(path);
+        let mut sftp_guard = self.sftp.lock().await;
+        let sftp = sftp_guard.as_mut().ok_or(StorageError::NotConnected)?;
+
+        let mut remote_file = sftp
+            .create(&full_path)
             .await
-            .map_err(|e| StorageError::IoError(format!("Failed to create local file: {}", e)))?;
-
-        let chunk_size = std::cmp::min(64 * 1024, file_size / 100).max(8 * 1024);
-        let mut downloaded = 0u64;
-
-        while downloaded < file_size {
-            let remaining = file_size - downloaded;
-            let current_chunk_size = std::cmp::min(chunk_size, remaining);
-
-            // 避免所有权问题，每次传递 None 而不是 cancel_rx 的引用
-            let chunk_data = if let Some(ref mut rx) = cancel_rx {
-                if rx.try_recv().is_ok() {
-                    let _ = tokio::fs::remove_file(save_path).await;
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+            .map_err(|e| Self::parse_ssh_error(&e, "create", &full_path))?;
+
+        let chunk_size = std::cmp::min(64 * 1024, file_size / 100).max(8 * 1024) as usize;
+        let mut buffer = vec![0u8; chunk_size];
+        let mut uploaded = 0u64;
+
+        loop {
+            // 检查取消信号
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    drop(remote_file);
+                    let _ = sftp.remove_file(&full_path).await;
+                    return Err(StorageError::Cancelled);
                 }
-                self.read_file_range(path, downloaded, current_chunk_size)
-                    .await?
-            } else {
-                self.read_file_range(path, downloaded, current_chunk_size)
-                    .await?
-            };
+            }
+
+            let bytes_read = local_file
+                .read(&mut buffer)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to read local file: {}", e)))?;
+
+            if bytes_read == 0 {
+                break;
+            }
 
-            local_file
-                .write_all(&chunk_data)
+            remote_file
+                .write_all(&buffer[..bytes_read])
                 .await
-                .map_err(|e| StorageError::IoError(format!("Failed to write data: {}", e)))?;
+                .map_err(|e| Self::parse_io_error(&e, "write", &full_path))?;
 
-            downloaded += chunk_data.len() as u64;
+            uploaded += bytes_read as u64;
 
             // 调用进度回调
             if let Some(ref callback) = progress_callback {
-                callback(downloaded, file_size);
+                callback(uploaded, file_size);
             }
+        }
 
-            if chunk_data.len() < current_chunk_size as usize {
-                break;
+        remote_file
+            .shutdown()
+            .await
+            .map_err(|e| Self::parse_io_error(&e, "close", &full_path))?;
+
+        Ok(())
+    }
+
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        if !self.connected.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let full_path = self.get_full_path(path);
+        let sftp_guard = self.sftp.lock().await;
+        let sftp = sftp_guard.as_ref().ok_or(StorageError::NotConnected)?;
+
+        // 逐级创建缺失的目录
+        let mut current = if full_path.starts_with('/') {
+            String::new()
+        } else {
+            ".".to_string()
+        };
+        for segment in full_path.split('/').filter(|s| !s.is_empty()) {
+            current = format!("{}/{}", current, segment);
+            let exists = sftp
+                .try_exists(&current)
+                .await
+                .map_err(|e| Self::parse_ssh_error(&e, "stat", &current))?;
+            if !exists {
+                sftp.create_dir(&current)
+                    .await
+                    .map_err(|e| Self::parse_ssh_error(&e, "create directory", &current))?;
             }
         }
 
-        local_file
-            .flush()
+        Ok(())
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        if !self.connected.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let from_path = self.get_full_path(from);
+        let to_path = self.get_full_path(to);
+        let sftp_guard = self.sftp.lock().await;
+        let sftp = sftp_guard.as_ref().ok_or(StorageError::NotConnected)?;
+
+        sftp.rename(&from_path, &to_path)
             .await
-            .map_err(|e| StorageError::IoError(format!("Failed to flush local file: {}", e)))?;
+            .map_err(|e| Self::parse_ssh_error(&e, "rename", &from_path))
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        if !self.connected.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let full_path = self.get_full_path(path);
+        let root_path = self.config.root_path.as_deref().unwrap_or("/");
+        if full_path.trim_end_matches('/') == root_path.trim_end_matches('/') {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the root directory".to_string(),
+            ));
+        }
+
+        let sftp_guard = self.sftp.lock().await;
+        let sftp = sftp_guard.as_ref().ok_or(StorageError::NotConnected)?;
+
+        let metadata = sftp
+            .symlink_metadata(&full_path)
+            .await
+            .map_err(|e| Self::parse_ssh_error(&e, "stat", &full_path))?;
+
+        if !metadata.is_dir() {
+            return sftp
+                .remove_file(&full_path)
+                .await
+                .map_err(|e| Self::parse_ssh_error(&e, "delete", &full_path));
+        }
+
+        // 目录：先删除所有文件，再按深度倒序删除目录
+        let mut pending = vec![full_path.clone()];
+        let mut directories = Vec::new();
+        while let Some(dir) = pending.pop() {
+            let entries = sftp
+                .read_dir(&dir)
+                .await
+                .map_err(|e| Self::parse_ssh_error(&e, "read directory", &dir))?;
+            for entry in entries {
+                let name = entry.file_name();
+                if name == "." || name == ".." {
+                    continue;
+                }
+                let child = format!("{}/{}", dir.trim_end_matches('/'), name);
+                if entry.metadata().is_dir() {
+                    pending.push(child);
+                } else {
+                    sftp.remove_file(&child)
+                        .await
+                        .map_err(|e| Self::parse_ssh_error(&e, "delete", &child))?;
+                }
+            }
+            directories.push(dir);
+        }
+
+        for dir in directories.iter().rev() {
+            sftp.remove_dir(dir)
+                .await
+                .map_err(|e| Self::parse_ssh_error(&e, "delete directory", dir))?;
+        }
 
         Ok(())
     }
@@ -1,11 +1,17 @@
 use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::Stream;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::pin::Pin;
 use std::sync::Arc;
 
 /// 进度回调函数类型
 pub type ProgressCallback = Arc<dyn Fn(u64, u64) + Send + Sync>;
 
+/// 流式读取的数据块流，按块产出文件内容，内存占用与文件大小无关
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, StorageError>> + Send>>;
+
 /// 统一的文件信息
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct StorageFile {
@@ -19,6 +25,27 @@ pub struct StorageFile {
     pub etag: Option<String>,
 }
 
+/// 统一的文件元数据（stat 结果）
+/// 在 StorageFile 的基础上补充校验和、权限、所有者以及各后端特有的扩展信息
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetadata {
+    pub filename: String,
+    pub basename: String,
+    pub lastmod: String,
+    pub size: String, // 使用字符串表示大数字
+    #[serde(rename = "type")]
+    pub file_type: String, // "file" or "directory"
+    pub mime: Option<String>,
+    pub etag: Option<String>,
+    pub checksum: Option<String>,
+    pub permissions: Option<String>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    /// 后端特有的扩展信息，如 OSS 存储类型、HF LFS oid 等
+    pub extra: HashMap<String, String>,
+}
+
 /// 统一的目录列表结果
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
@@ -31,7 +58,7 @@ pub struct DirectoryResult {
 }
 
 /// 统一的列表选项
-#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct ListOptions {
     pub page_size: Option<u32>,
@@ -40,6 +67,38 @@ pub struct ListOptions {
     pub recursive: Option<bool>,
     pub sort_by: Option<String>,    // "name", "size", "modified"
     pub sort_order: Option<String>, // "asc", "desc"
+    /// 是否显示以 . 开头的隐藏文件，默认显示
+    pub show_hidden: Option<bool>,
+    /// 文件名 glob 模式，如 "*.parquet"，不区分大小写
+    pub pattern: Option<String>,
+    /// 只返回指定类型的条目："file" 或 "directory"
+    pub file_type: Option<String>,
+}
+
+/// 服务端统计的目录占用
+#[derive(Debug, Clone)]
+pub struct DirectoryUsage {
+    /// 相对于统计根目录的路径，根目录为空字符串
+    pub path: String,
+    pub size: u64,
+    /// 服务端不提供文件数时为 None
+    pub file_count: Option<u64>,
+}
+
+/// 对象的一个历史版本或删除标记
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectVersion {
+    /// 读取该版本时使用的路径，可直接用于读取、下载等接口
+    pub path: String,
+    pub version_id: String,
+    pub is_latest: bool,
+    /// 删除标记没有内容，不能读取
+    pub is_delete_marker: bool,
+    pub lastmod: String,
+    pub size: String, // 使用字符串表示大数字
+    pub etag: Option<String>,
+    pub storage_class: Option<String>,
 }
 
 /// 统一的存储响应结构
@@ -61,13 +120,15 @@ pub struct StorageRequest {
 }
 
 /// 连接配置
-#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct ConnectionConfig {
     pub protocol: String,
     pub url: Option<String>,
     pub access_key: Option<String>,
     pub secret_key: Option<String>,
+    // OSS/S3 STS 临时凭证的会话令牌
+    pub session_token: Option<String>,
     pub region: Option<String>,
     pub bucket: Option<String>,
     pub endpoint: Option<String>,
@@ -82,6 +143,43 @@ pub struct ConnectionConfig {
     pub share: Option<String>,
     pub domain: Option<String>,
     pub extra_options: Option<HashMap<String, String>>,
+    // 只读模式，开启后禁止上传、创建、重命名和删除等写操作
+    pub read_only: Option<bool>,
+    // 块缓存配置，设置后范围读取会经过内存（可选磁盘）缓存
+    pub block_cache: Option<BlockCacheConfig>,
+    // 重试策略配置，未设置时使用默认策略
+    pub retry: Option<RetryConfig>,
+    // 目录列表缓存配置，未设置时使用默认配置
+    pub listing_cache: Option<ListingCacheConfig>,
+}
+
+/// 重试策略配置
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryConfig {
+    pub max_attempts: Option<u32>, // 最大尝试次数（含首次），设为 1 时关闭重试，默认 3
+    pub initial_backoff_ms: Option<u32>, // 首次重试前的等待时间，默认 500 毫秒
+    pub max_backoff_ms: Option<u32>, // 单次等待时间上限，默认 10 秒
+    pub jitter: Option<bool>,      // 是否为等待时间加入随机抖动，默认开启
+}
+
+/// 块缓存配置
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockCacheConfig {
+    pub block_size_kb: Option<u32>,      // 缓存块大小，默认 256 KB
+    pub memory_capacity_mb: Option<u32>, // 内存缓存容量，默认 64 MB
+    pub disk_cache: Option<bool>,        // 是否启用磁盘缓存层
+    pub disk_dir: Option<String>,        // 磁盘缓存目录，默认为系统缓存目录
+    pub disk_capacity_mb: Option<u32>,   // 磁盘缓存容量，默认 512 MB
+}
+
+/// 目录列表缓存配置
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingCacheConfig {
+    pub ttl_secs: Option<u32>,    // 缓存有效期，设为 0 时关闭缓存，默认 30 秒
+    pub max_entries: Option<u32>, // 缓存的条目总数上限，默认 200000
 }
 
 /// 存储客户端错误类型
@@ -116,6 +214,95 @@ pub enum StorageError {
 
     #[error("Network error: {0}")]
     NetworkError(String),
+
+    /// 服务端限流或暂时不可用，附带 Retry-After 指定的等待秒数
+    #[error("Rate limited: {0}")]
+    RateLimited(String, Option<u64>),
+
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// 用户取消，消息保持为前端识别的 download.cancelled
+    #[error("download.cancelled")]
+    Cancelled,
+
+    /// 目标已存在或版本不匹配（HTTP 409/412）
+    #[error("Conflict: {0}")]
+    Conflict(String),
+}
+
+impl StorageError {
+    /// 根据 HTTP 响应状态构造错误，限流和服务端临时故障会被归类为可重试错误
+    pub fn from_http_status(
+        status: reqwest::StatusCode,
+        headers: &reqwest::header::HeaderMap,
+        message: String,
+    ) -> Self {
+        match status.as_u16() {
+            401 => StorageError::AuthenticationFailed(message),
+            403 => StorageError::PermissionDenied(message),
+            404 => StorageError::NotFound(message),
+            409 | 412 => StorageError::Conflict(message),
+            429 | 503 => StorageError::RateLimited(message, Self::parse_retry_after(headers)),
+            408 | 504 => StorageError::Timeout(message),
+            500 | 502 => StorageError::NetworkError(message),
+            _ => StorageError::RequestFailed(message),
+        }
+    }
+
+    /// 根据尚未读取响应体的 HTTP 响应构造错误
+    pub fn from_response(response: &reqwest::Response, message: String) -> Self {
+        Self::from_http_status(response.status(), response.headers(), message)
+    }
+
+    /// 解析 Retry-After 响应头（秒数或 HTTP 日期）
+    fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+        let value = headers
+            .get(reqwest::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim();
+
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(seconds);
+        }
+
+        let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        let seconds = (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds();
+        Some(seconds.max(0) as u64)
+    }
+
+    /// 是否为可重试的临时错误
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            StorageError::NetworkError(_)
+                | StorageError::RateLimited(_, _)
+                | StorageError::Timeout(_)
+        )
+    }
+
+    /// 根据 reqwest 错误构造网络错误，超时单独归类
+    pub fn from_reqwest(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            StorageError::Timeout(error.to_string())
+        } else {
+            StorageError::NetworkError(error.to_string())
+        }
+    }
+
+    /// 服务端要求的重试等待时间
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            StorageError::RateLimited(_, Some(seconds)) => {
+                Some(std::time::Duration::from_secs(*seconds))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// 统一存储客户端接口
@@ -134,6 +321,37 @@ pub trait StorageClient: Send + Sync {
         options: Option<&ListOptions>,
     ) -> Result<DirectoryResult, StorageError>;
 
+    /// 是否支持原生递归列出（list_directory 的 recursive 选项）
+    /// 支持时返回的 filename 为相对于 path 的路径，且只包含文件
+    fn supports_recursive_listing(&self, path: &str) -> bool {
+        let _ = path;
+        false
+    }
+
+    /// 服务端统计目录占用的快捷方式（如 SSH 的 du、HF 的 usedStorage）
+    /// 返回 path 及其 max_depth 层以内各子目录的占用，不支持时返回 None，由调用方逐层遍历
+    async fn directory_usage(
+        &self,
+        path: &str,
+        max_depth: u32,
+    ) -> Result<Option<Vec<DirectoryUsage>>, StorageError> {
+        let _ = (path, max_depth);
+        Ok(None)
+    }
+
+    /// list_directory 是否由后端原生分页（page_size 和 marker 直接交给服务端）
+    /// 不支持时由 StorageManager 取回完整列表后统一排序和分页
+    fn supports_native_paging(&self, path: &str, recursive: bool) -> bool {
+        let _ = (path, recursive);
+        false
+    }
+
+    /// 将路径解析为连接内的规范路径，协议 URL 和相对路径指向同一位置时结果相同
+    /// 用于目录列表缓存等按路径比较的场景，比较时忽略首尾斜杠；默认原样返回
+    fn canonical_path(&self, path: &str) -> String {
+        path.to_string()
+    }
+
     /// 读取文件的指定范围（用于压缩包等需要随机访问的场景）
     async fn read_file_range(
         &self,
@@ -180,6 +398,12 @@ pub trait StorageClient: Send + Sync {
         Ok(result)
     }
 
+    /// 从指定偏移量开始流式读取文件直到末尾（用于大文件的增量处理）
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError>;
+
+    /// 获取文件或目录的元数据
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError>;
+
     /// 获取文件大小
     async fn get_file_size(&self, path: &str) -> Result<u64, StorageError>;
 
@@ -200,6 +424,79 @@ pub trait StorageClient: Send + Sync {
         cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
     ) -> Result<(), StorageError>;
 
+    /// 从本地路径上传文件到指定路径，支持进度回调和取消
+    /// 各个存储客户端应该以流式方式读取本地文件，避免一次性载入内存
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError>;
+
+    /// 将数据流写入到指定路径，用于跨存储传输，支持进度回调和取消
+    /// 默认实现先把数据流暂存到本地临时文件再调用 put_file，支持流式上传的客户端应直接写入
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let temp_path = std::env::temp_dir().join(format!(
+            "dataset-viewer-transfer-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+
+        // 暂存和上传各占一半进度
+        let spool_callback = progress_callback.clone().map(|callback| {
+            Arc::new(move |written: u64, total: u64| callback(written / 2, total))
+                as ProgressCallback
+        });
+        let upload_callback = progress_callback.map(|callback| {
+            Arc::new(move |uploaded: u64, total: u64| callback((total + uploaded) / 2, total))
+                as ProgressCallback
+        });
+
+        let result = match crate::utils::stream_io::StreamIo::save_to_file(
+            stream,
+            &temp_path,
+            size,
+            spool_callback,
+            cancel_rx.as_deref_mut(),
+        )
+        .await
+        {
+            Ok(()) => {
+                self.put_file(&temp_path, path, upload_callback, cancel_rx)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        result
+    }
+
+    /// 创建目录（包括缺失的父目录）
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError>;
+
+    /// 重命名或移动文件/目录
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError>;
+
+    /// 删除文件或目录（目录会被递归删除）
+    async fn delete(&self, path: &str) -> Result<(), StorageError>;
+
+    /// 列出文件的所有历史版本和删除标记，按时间从新到旧排列
+    async fn list_versions(&self, path: &str) -> Result<Vec<ObjectVersion>, StorageError> {
+        let _ = path;
+        Err(StorageError::ProtocolNotSupported(format!(
+            "Object versions are not supported by {}",
+            self.protocol()
+        )))
+    }
+
     /// 获取协议名称
     fn protocol(&self) -> &str;
 
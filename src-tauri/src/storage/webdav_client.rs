@@ -9,10 +9,11 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use crate::storage::traits::{
-    ConnectionConfig, DirectoryResult, ListOptions, ProgressCallback, StorageClient, StorageError,
-    StorageFile, StorageRequest, StorageResponse,
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile, StorageRequest, StorageResponse,
 };
-use crate::utils::http_downloader::HttpDownloader;
+use crate::utils::http_downloader::{HttpDownloadConfig, HttpDownloader};
+use crate::utils::http_uploader::HttpUploader;
 
 pub struct WebDAVClient {
     client: Client,
@@ -89,6 +90,12 @@ impl WebDAVClient {
                 reqwest::Method::from_bytes(b"PROPFIND").unwrap(),
                 &actual_url,
             ),
+            "MKCOL" => self
+                .client
+                .request(reqwest::Method::from_bytes(b"MKCOL").unwrap(), &actual_url),
+            "MOVE" => self
+                .client
+                .request(reqwest::Method::from_bytes(b"MOVE").unwrap(), &actual_url),
             _ => {
                 return Err(StorageError::RequestFailed(format!(
                     "Unsupported method: {}",
@@ -214,6 +221,8 @@ impl StorageClient for WebDAVClient {
         }
 
         let actual_url = self.parse_path_to_url(path)?;
+        // 递归列出时使用 Depth: infinity，部分服务器会拒绝此类请求
+        let recursive = options.and_then(|o| o.recursive).unwrap_or(false);
 
         let propfind_body = r#"<?xml version="1.0" encoding="utf-8" ?>
 <D:propfind xmlns:D="DAV:">
@@ -231,7 +240,10 @@ impl StorageClient for WebDAVClient {
             url: actual_url.clone(),
             headers: {
                 let mut headers = HashMap::new();
-                headers.insert("Depth".to_string(), "1".to_string());
+                headers.insert(
+                    "Depth".to_string(),
+                    if recursive { "infinity" } else { "1" }.to_string(),
+                );
                 headers.insert("Content-Type".to_string(), "application/xml".to_string());
                 headers
             },
@@ -240,6 +252,12 @@ impl StorageClient for WebDAVClient {
 
         let response = self.execute_request_internal(&request).await?;
 
+        if recursive && response.status == 403 {
+            return Err(StorageError::ProtocolNotSupported(
+                "PROPFIND with Depth: infinity is disabled on this server".to_string(),
+            ));
+        }
+
         if response.status < 200 || response.status >= 300 {
             return Err(StorageError::RequestFailed(format!(
                 "PROPFIND failed with status {}: {}",
@@ -270,7 +288,11 @@ impl StorageClient for WebDAVClient {
             ));
         }
 
-        let files = self.parse_webdav_xml(&response.body, &actual_url)?;
+        let files = if recursive {
+            self.parse_webdav_xml_recursive(&response.body, &actual_url)?
+        } else {
+            self.parse_webdav_xml(&response.body, &actual_url)?
+        };
 
         // 应用列表选项
         let result_files = if let Some(opts) = options {
@@ -288,10 +310,21 @@ impl StorageClient for WebDAVClient {
         })
     }
 
+    /// 以完整的资源 URL 比较路径
+    fn canonical_path(&self, path: &str) -> String {
+        self.parse_path_to_url_with_type(path, false)
+            .unwrap_or_else(|_| path.to_string())
+    }
+
     fn protocol(&self) -> &str {
         "webdav"
     }
 
+    /// 通过 Depth: infinity 的 PROPFIND 递归列出，服务器拒绝时返回 ProtocolNotSupported
+    fn supports_recursive_listing(&self, _path: &str) -> bool {
+        true
+    }
+
     async fn read_file_range(
         &self,
         path: &str,
@@ -335,11 +368,14 @@ impl StorageClient for WebDAVClient {
             .map_err(|e| StorageError::NetworkError(format!("Request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response.status().canonical_reason().unwrap_or("Unknown")
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "HTTP {}: {}",
+                    response.status(),
+                    response.status().canonical_reason().unwrap_or("Unknown")
+                ),
+            ));
         }
 
         // 使用流式读取以支持进度回调
@@ -351,9 +387,7 @@ impl StorageClient for WebDAVClient {
             // 检查取消信号
             if let Some(ref mut cancel_rx) = cancel_rx {
                 if cancel_rx.try_recv().is_ok() {
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+                    return Err(StorageError::Cancelled);
                 }
             }
 
@@ -391,11 +425,14 @@ impl StorageClient for WebDAVClient {
             .map_err(|e| StorageError::NetworkError(format!("Request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response.status().canonical_reason().unwrap_or("Unknown")
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "HTTP {}: {}",
+                    response.status(),
+                    response.status().canonical_reason().unwrap_or("Unknown")
+                ),
+            ));
         }
 
         let bytes = response.bytes().await.map_err(|e| {
@@ -405,6 +442,119 @@ impl StorageClient for WebDAVClient {
         Ok(bytes.to_vec())
     }
 
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        // 处理协议URL格式 - 文件操作，不添加尾部斜杠
+        let actual_url = self.parse_path_to_url_with_type(path, false)?;
+
+        let mut config = HttpDownloadConfig::new(actual_url);
+        if let Some(auth) = &self.auth_header {
+            config = config.with_auth(auth.clone());
+        }
+
+        // 使用下载专用客户端（更长超时）进行流式读取
+        HttpDownloader::open_stream(&self.download_client, config, start).await
+    }
+
+    /// 通过 PROPFIND Depth:0 获取单个资源的属性
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let actual_url = self.parse_path_to_url_with_type(path, false)?;
+
+        let propfind_body = r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:propfind xmlns:D="DAV:">
+  <D:prop>
+    <D:resourcetype/>
+    <D:getcontentlength/>
+    <D:getlastmodified/>
+    <D:getcontenttype/>
+    <D:getetag/>
+    <D:creationdate/>
+    <D:displayname/>
+  </D:prop>
+</D:propfind>"#;
+
+        let request = StorageRequest {
+            method: "PROPFIND".to_string(),
+            url: actual_url,
+            headers: {
+                let mut headers = HashMap::new();
+                headers.insert("Depth".to_string(), "0".to_string());
+                headers.insert("Content-Type".to_string(), "application/xml".to_string());
+                headers
+            },
+            body: Some(propfind_body.to_string()),
+        };
+
+        let response = self.execute_request_internal(&request).await?;
+
+        if response.status == 404 {
+            return Err(StorageError::NotFound(path.to_string()));
+        }
+        if response.status < 200 || response.status >= 300 {
+            return Err(StorageError::RequestFailed(format!(
+                "PROPFIND failed with status {}: {}",
+                response.status, response.body
+            )));
+        }
+
+        let resp = Self::parse_webdav_responses(&response.body)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| StorageError::RequestFailed("Empty PROPFIND response".to_string()))?;
+
+        let decoded_href = urlencoding::decode(&resp.href)
+            .map(|href| href.to_string())
+            .unwrap_or_else(|_| resp.href.clone());
+        let is_directory = resp.is_directory
+            || resp.content_type.as_deref() == Some("httpd/unix-directory")
+            || decoded_href.ends_with('/');
+        let basename = decoded_href
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let mut extra = HashMap::new();
+        extra.insert("href".to_string(), resp.href.clone());
+        if let Some(creation_date) = resp.creation_date {
+            extra.insert("creationDate".to_string(), creation_date);
+        }
+        if let Some(display_name) = resp.display_name {
+            extra.insert("displayName".to_string(), display_name);
+        }
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename,
+            lastmod: resp.lastmod,
+            size: if is_directory {
+                "0".to_string()
+            } else {
+                resp.size.to_string()
+            },
+            file_type: if is_directory { "directory" } else { "file" }.to_string(),
+            mime: if is_directory {
+                None
+            } else {
+                resp.content_type
+            },
+            etag: resp.etag,
+            checksum: None,
+            permissions: None,
+            owner: None,
+            group: None,
+            extra,
+        })
+    }
+
     async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
         if !self.connected.load(Ordering::Relaxed) {
             return Err(StorageError::NotConnected);
@@ -424,11 +574,14 @@ impl StorageClient for WebDAVClient {
             .map_err(|e| StorageError::NetworkError(format!("Request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response.status().canonical_reason().unwrap_or("Unknown")
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "HTTP {}: {}",
+                    response.status(),
+                    response.status().canonical_reason().unwrap_or("Unknown")
+                ),
+            ));
         }
 
         // 尝试从 Content-Length 头获取文件大小
@@ -508,14 +661,250 @@ impl StorageClient for WebDAVClient {
         )
         .await
     }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let (body, file_size) = HttpUploader::file_body(local_path, progress_callback).await?;
+
+        self.put_body(path, body, file_size, cancel_rx).await
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let body = HttpUploader::stream_body(stream, size, progress_callback);
+
+        self.put_body(path, body, size, cancel_rx).await
+    }
+
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        // MKCOL 不会自动创建父目录，相对路径需要逐级创建
+        let directory_urls = if path.starts_with("webdav://") || path.starts_with("webdavs://") {
+            vec![self.parse_path_to_url_with_type(path, true)?]
+        } else {
+            let clean_path = path.trim_start_matches('/').trim_end_matches('/');
+            let mut urls = Vec::new();
+            let mut current = String::new();
+            for segment in clean_path.split('/').filter(|s| !s.is_empty()) {
+                if !current.is_empty() {
+                    current.push('/');
+                }
+                current.push_str(segment);
+                urls.push(self.parse_path_to_url_with_type(&current, true)?);
+            }
+            urls
+        };
+
+        for url in directory_urls {
+            let request = StorageRequest {
+                method: "MKCOL".to_string(),
+                url,
+                headers: HashMap::new(),
+                body: None,
+            };
+
+            let response = self.execute_request_internal(&request).await?;
+
+            // 405 表示目录已存在
+            if !(200..300).contains(&response.status) && response.status != 405 {
+                return Err(StorageError::RequestFailed(format!(
+                    "MKCOL failed with status {}: {}",
+                    response.status, response.body
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        let source_url = self.parse_path_to_url_with_type(from, false)?;
+        let destination_url = self.parse_path_to_url_with_type(to, false)?;
+
+        let request = StorageRequest {
+            method: "MOVE".to_string(),
+            url: source_url,
+            headers: {
+                let mut headers = HashMap::new();
+                headers.insert("Destination".to_string(), destination_url);
+                // 不覆盖已存在的目标
+                headers.insert("Overwrite".to_string(), "F".to_string());
+                headers
+            },
+            body: None,
+        };
+
+        let response = self.execute_request_internal(&request).await?;
+
+        if response.status == 412 {
+            return Err(StorageError::Conflict(format!(
+                "Destination already exists: {}",
+                to
+            )));
+        }
+
+        if !(200..300).contains(&response.status) {
+            return Err(StorageError::RequestFailed(format!(
+                "MOVE failed with status {}: {}",
+                response.status, response.body
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return Err(StorageError::NotConnected);
+        }
+
+        if path.trim_matches('/').is_empty() {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the root directory".to_string(),
+            ));
+        }
+
+        // WebDAV 的 DELETE 对集合会递归删除
+        let request = StorageRequest {
+            method: "DELETE".to_string(),
+            url: self.parse_path_to_url_with_type(path, false)?,
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        let response = self.execute_request_internal(&request).await?;
+
+        if response.status == 404 {
+            return Err(StorageError::NotFound(path.to_string()));
+        }
+
+        if !(200..300).contains(&response.status) {
+            return Err(StorageError::RequestFailed(format!(
+                "DELETE failed with status {}: {}",
+                response.status, response.body
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 impl WebDAVClient {
+    /// 以 PUT 请求上传请求体，供 put_file 和 put_stream 共用
+    async fn put_body(
+        &self,
+        path: &str,
+        body: reqwest::Body,
+        file_size: u64,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        // 处理协议URL格式 - 文件操作，不添加尾部斜杠
+        let actual_url = self.parse_path_to_url_with_type(path, false)?;
+
+        // 使用下载专用客户端（更长超时）进行上传
+        let mut request = self
+            .download_client
+            .put(&actual_url)
+            .header("Content-Length", file_size.to_string())
+            .body(body);
+        if let Some(auth) = &self.auth_header {
+            request = request.header("Authorization", auth);
+        }
+
+        let response = HttpUploader::send_with_cancel(request, cancel_rx).await?;
+
+        if !response.status().is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!(
+                    "PUT failed with status {}: {}",
+                    response.status(),
+                    response.status().canonical_reason().unwrap_or("Unknown")
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 解析 Depth: infinity 的响应，只保留文件，filename 为相对于当前目录的路径
+    fn parse_webdav_xml_recursive(
+        &self,
+        xml_body: &str,
+        current_url: &str,
+    ) -> Result<Vec<StorageFile>, StorageError> {
+        let base_path = format!("{}/", Self::href_path(current_url).trim_end_matches('/'));
+
+        let files = Self::parse_webdav_responses(xml_body)?
+            .into_iter()
+            .filter_map(|resp| {
+                let relative_path = Self::href_path(&resp.href)
+                    .strip_prefix(&base_path)?
+                    .to_string();
+                let mut file = self.webdav_response_to_storage_file(resp, current_url)?;
+                if file.file_type == "directory" || relative_path.is_empty() {
+                    return None;
+                }
+                file.filename = relative_path;
+                Some(file)
+            })
+            .collect();
+
+        Ok(files)
+    }
+
+    /// 提取 href 或 URL 中解码后的路径部分
+    fn href_path(href: &str) -> String {
+        let path = match url::Url::parse(href) {
+            Ok(url) => url.path().to_string(),
+            Err(_) => href.to_string(),
+        };
+        urlencoding::decode(&path)
+            .map(|decoded| decoded.into_owned())
+            .unwrap_or(path)
+    }
+
     fn parse_webdav_xml(
         &self,
         xml_body: &str,
         current_path: &str,
     ) -> Result<Vec<StorageFile>, StorageError> {
+        let files = Self::parse_webdav_responses(xml_body)?
+            .into_iter()
+            .filter_map(|resp| self.webdav_response_to_storage_file(resp, current_path))
+            .collect();
+
+        Ok(files)
+    }
+
+    /// 解析 PROPFIND 响应中的所有 response 节点
+    fn parse_webdav_responses(xml_body: &str) -> Result<Vec<WebDAVResponse>, StorageError> {
         // 先检查内容是否像XML
         let body_trimmed = xml_body.trim();
         if body_trimmed.is_empty() {
@@ -543,7 +932,7 @@ impl WebDAVClient {
         let mut reader = Reader::from_str(xml_body);
         reader.trim_text(true);
 
-        let mut files = Vec::new();
+        let mut responses = Vec::new();
         let mut current_response = WebDAVResponse::default();
         let mut in_response = false;
         let mut in_href = false;
@@ -552,6 +941,9 @@ impl WebDAVClient {
         let mut in_getcontentlength = false;
         let mut in_getlastmodified = false;
         let mut in_getcontenttype = false;
+        let mut in_getetag = false;
+        let mut in_creationdate = false;
+        let mut in_displayname = false;
 
         let mut buf = Vec::new();
 
@@ -580,6 +972,17 @@ impl WebDAVClient {
                     {
                         in_getcontenttype = true
                     }
+                    tag if in_prop && (tag.ends_with(b":getetag") || tag == b"getetag") => {
+                        in_getetag = true
+                    }
+                    tag if in_prop
+                        && (tag.ends_with(b":creationdate") || tag == b"creationdate") =>
+                    {
+                        in_creationdate = true
+                    }
+                    tag if in_prop && (tag.ends_with(b":displayname") || tag == b"displayname") => {
+                        in_displayname = true
+                    }
                     b"D:collection" | b"collection" if in_resourcetype => {
                         current_response.is_directory = true;
                     }
@@ -588,12 +991,7 @@ impl WebDAVClient {
                 Ok(Event::End(ref e)) => match e.name().as_ref() {
                     b"D:response" | b"response" => {
                         if in_response {
-                            if let Some(file) = self.webdav_response_to_storage_file(
-                                current_response.clone(),
-                                current_path,
-                            ) {
-                                files.push(file);
-                            }
+                            responses.push(current_response.clone());
                             in_response = false;
                         }
                     }
@@ -609,6 +1007,13 @@ impl WebDAVClient {
                     tag if tag.ends_with(b":getcontenttype") || tag == b"getcontenttype" => {
                         in_getcontenttype = false
                     }
+                    tag if tag.ends_with(b":getetag") || tag == b"getetag" => in_getetag = false,
+                    tag if tag.ends_with(b":creationdate") || tag == b"creationdate" => {
+                        in_creationdate = false
+                    }
+                    tag if tag.ends_with(b":displayname") || tag == b"displayname" => {
+                        in_displayname = false
+                    }
                     _ => {}
                 },
                 Ok(Event::Text(e)) => {
@@ -621,6 +1026,12 @@ impl WebDAVClient {
                         current_response.lastmod = text.to_string();
                     } else if in_getcontenttype {
                         current_response.content_type = Some(text.to_string());
+                    } else if in_getetag {
+                        current_response.etag = Some(text.to_string());
+                    } else if in_creationdate {
+                        current_response.creation_date = Some(text.to_string());
+                    } else if in_displayname {
+                        current_response.display_name = Some(text.to_string());
                     }
                 }
                 Ok(Event::Eof) => break,
@@ -634,7 +1045,7 @@ impl WebDAVClient {
             buf.clear();
         }
 
-        Ok(files)
+        Ok(responses)
     }
 
     fn webdav_response_to_storage_file(
@@ -712,7 +1123,7 @@ impl WebDAVClient {
             }, // 目录大小为0
             file_type,
             mime,
-            etag: None,
+            etag: resp.etag,
         })
     }
 
@@ -721,24 +1132,11 @@ impl WebDAVClient {
         mut files: Vec<StorageFile>,
         options: &ListOptions,
     ) -> Vec<StorageFile> {
-        // 应用前缀过滤
+        // 应用前缀过滤，排序和分页由 StorageManager 统一处理
         if let Some(prefix) = &options.prefix {
             files.retain(|f| f.filename.starts_with(prefix));
         }
 
-        // 应用分页
-        if let Some(page_size) = options.page_size {
-            let start_index = if let Some(marker) = &options.marker {
-                // 简单实现：marker作为起始索引
-                marker.parse::<usize>().unwrap_or(0)
-            } else {
-                0
-            };
-
-            let end_index = std::cmp::min(start_index + page_size as usize, files.len());
-            files = files[start_index..end_index].to_vec();
-        }
-
         files
     }
 
@@ -816,4 +1214,7 @@ struct WebDAVResponse {
     lastmod: String,
     content_type: Option<String>,
     is_directory: bool,
+    etag: Option<String>,
+    creation_date: Option<String>,
+    display_name: Option<String>,
 }
@@ -0,0 +1,801 @@
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{Client, Method, RequestBuilder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::storage::traits::{
+    ByteStream, ConnectionConfig, DirectoryResult, FileMetadata, ListOptions, ProgressCallback,
+    StorageClient, StorageError, StorageFile,
+};
+use crate::utils::http_downloader::{HttpDownloadConfig, HttpDownloader};
+use crate::utils::http_uploader::HttpUploader;
+
+// WebHDFS REST API 路径前缀
+const WEBHDFS_PREFIX: &str = "/webhdfs/v1";
+
+/// HDFS 文件状态（LISTSTATUS、GETFILESTATUS 返回的 FileStatus 对象）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HdfsFileStatus {
+    #[serde(default)]
+    path_suffix: String,
+    #[serde(rename = "type")]
+    file_type: String,
+    #[serde(default)]
+    length: u64,
+    #[serde(default)]
+    modification_time: i64,
+    #[serde(default)]
+    access_time: i64,
+    permission: Option<String>,
+    owner: Option<String>,
+    group: Option<String>,
+    replication: Option<u32>,
+    block_size: Option<u64>,
+}
+
+impl HdfsFileStatus {
+    fn is_dir(&self) -> bool {
+        self.file_type == "DIRECTORY"
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FileStatuses {
+    #[serde(rename = "FileStatus", default)]
+    file_status: Vec<HdfsFileStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListStatusResponse {
+    #[serde(rename = "FileStatuses")]
+    file_statuses: FileStatuses,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartialListing {
+    partial_listing: ListStatusResponse,
+    #[serde(default)]
+    remaining_entries: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListStatusBatchResponse {
+    #[serde(rename = "DirectoryListing")]
+    directory_listing: PartialListing,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileStatusResponse {
+    #[serde(rename = "FileStatus")]
+    file_status: HdfsFileStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct BooleanResponse {
+    boolean: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteExceptionResponse {
+    #[serde(rename = "RemoteException")]
+    remote_exception: RemoteException,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteException {
+    #[serde(default)]
+    exception: String,
+    #[serde(default)]
+    message: String,
+}
+
+pub struct WebHdfsClient {
+    client: Client,
+    download_client: Client, // 专门用于下载的客户端，配置更长超时
+    // 不自动跟随重定向，CREATE 需要手动把数据发送到 DataNode
+    upload_client: Client,
+    config: ConnectionConfig,
+    // NameNode 或 HttpFS 地址，不含 /webhdfs/v1
+    base_url: String,
+    root_path: String,
+    user_name: Option<String>,
+    delegation_token: Option<String>,
+    // 服务器不支持 LISTSTATUS_BATCH 时改用 LISTSTATUS
+    batch_unsupported: AtomicBool,
+    connected: AtomicBool,
+}
+
+impl WebHdfsClient {
+    pub fn new(config: ConnectionConfig) -> Result<Self, StorageError> {
+        let base_url = Self::parse_base_url(&config)?;
+
+        let root_path = config
+            .root_path
+            .clone()
+            .filter(|root| !root.is_empty())
+            .unwrap_or_else(|| "/".to_string());
+        let root_path = format!("/{}", root_path.trim_matches('/'));
+
+        // 提供委托令牌时使用令牌认证，否则使用 simple 认证的 user.name
+        // 令牌是凭据，保存在 password 字段中，与其他密钥一样加密存储且不会返回给前端
+        let delegation_token = config.password.clone().filter(|token| !token.is_empty());
+        let user_name = config.username.clone().filter(|name| !name.is_empty());
+
+        let build_client = |timeout: u64, redirect: reqwest::redirect::Policy| {
+            Client::builder()
+                .timeout(Duration::from_secs(timeout))
+                .connect_timeout(Duration::from_secs(10))
+                .pool_idle_timeout(Duration::from_secs(90))
+                .tcp_keepalive(Duration::from_secs(60))
+                .redirect(redirect)
+                .build()
+                .map_err(|e| {
+                    StorageError::InvalidConfig(format!("Failed to create HTTP client: {}", e))
+                })
+        };
+
+        Ok(Self {
+            client: build_client(30, reqwest::redirect::Policy::default())?,
+            download_client: build_client(600, reqwest::redirect::Policy::default())?,
+            upload_client: build_client(3600, reqwest::redirect::Policy::none())?,
+            config,
+            base_url,
+            root_path,
+            user_name,
+            delegation_token,
+            batch_unsupported: AtomicBool::new(false),
+            connected: AtomicBool::new(false),
+        })
+    }
+
+    /// 解析 NameNode 地址，支持 http(s):// 和 webhdfs:// / swebhdfs:// 写法
+    fn parse_base_url(config: &ConnectionConfig) -> Result<String, StorageError> {
+        let url = config
+            .url
+            .as_deref()
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| StorageError::InvalidConfig("WebHDFS URL is required".to_string()))?;
+
+        let (url, default_port) = if let Some(rest) = url.strip_prefix("webhdfs://") {
+            (format!("http://{}", rest), Some(9870))
+        } else if let Some(rest) = url.strip_prefix("swebhdfs://") {
+            (format!("https://{}", rest), Some(9871))
+        } else {
+            (url.to_string(), None)
+        };
+
+        let mut parsed = url::Url::parse(&url)
+            .map_err(|e| StorageError::InvalidConfig(format!("Invalid WebHDFS URL: {}", e)))?;
+        if parsed.scheme() != "http" && parsed.scheme() != "https" {
+            return Err(StorageError::InvalidConfig(format!(
+                "Unsupported URL scheme: {}",
+                parsed.scheme()
+            )));
+        }
+        if parsed.port().is_none() {
+            if let Some(port) = config.port.or(default_port) {
+                let _ = parsed.set_port(Some(port));
+            }
+        }
+
+        let path = parsed.path().trim_end_matches('/').to_string();
+        let path = path
+            .strip_suffix(WEBHDFS_PREFIX)
+            .unwrap_or(&path)
+            .to_string();
+        parsed.set_path(&path);
+        parsed.set_query(None);
+
+        Ok(parsed.as_str().trim_end_matches('/').to_string())
+    }
+
+    /// 将相对路径转换为 HDFS 绝对路径（支持 hdfs:// 和 webhdfs:// 协议 URL）
+    fn get_full_path(&self, path: &str) -> String {
+        for scheme in ["hdfs://", "webhdfs://", "swebhdfs://"] {
+            if path.starts_with(scheme) {
+                if let Ok(url) = url::Url::parse(path) {
+                    return urlencoding::decode(url.path())
+                        .map(|p| p.to_string())
+                        .unwrap_or_else(|_| url.path().to_string());
+                }
+            }
+        }
+
+        let clean_path = path.trim_matches('/');
+        if clean_path.is_empty() {
+            self.root_path.clone()
+        } else if self.root_path == "/" {
+            format!("/{}", clean_path)
+        } else {
+            format!("{}/{}", self.root_path, clean_path)
+        }
+    }
+
+    /// 构造操作 URL，路径逐级编码并附加认证参数
+    fn op_url(&self, full_path: &str, op: &str, params: &[(&str, String)]) -> String {
+        let encoded_path = full_path
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut query = vec![format!("op={}", op)];
+        if let Some(ref token) = self.delegation_token {
+            query.push(format!("delegation={}", urlencoding::encode(token)));
+        } else if let Some(ref user) = self.user_name {
+            query.push(format!("user.name={}", urlencoding::encode(user)));
+        }
+        for (key, value) in params {
+            query.push(format!("{}={}", key, urlencoding::encode(value)));
+        }
+
+        format!(
+            "{}{}{}?{}",
+            self.base_url,
+            WEBHDFS_PREFIX,
+            encoded_path,
+            query.join("&")
+        )
+    }
+
+    fn request(
+        &self,
+        method: Method,
+        full_path: &str,
+        op: &str,
+        params: &[(&str, String)],
+    ) -> RequestBuilder {
+        self.client
+            .request(method, self.op_url(full_path, op, params))
+    }
+
+    /// 根据 RemoteException 构造错误，异常类型比 HTTP 状态码更准确
+    async fn error_from_response(response: reqwest::Response, op: &str) -> StorageError {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+
+        let Ok(remote) = serde_json::from_str::<RemoteExceptionResponse>(&body) else {
+            return StorageError::from_http_status(
+                status,
+                &headers,
+                format!("WebHDFS {} failed with status {}: {}", op, status, body),
+            );
+        };
+
+        let exception = remote.remote_exception;
+        let message = format!("{}: {}", exception.exception, exception.message);
+        match exception.exception.as_str() {
+            "FileNotFoundException" => StorageError::NotFound(exception.message),
+            "AccessControlException" | "SecurityException" => {
+                StorageError::PermissionDenied(message)
+            }
+            "InvalidToken" | "AuthenticationException" => {
+                StorageError::AuthenticationFailed(message)
+            }
+            "FileAlreadyExistsException" | "PathIsNotEmptyDirectoryException" => {
+                StorageError::Conflict(message)
+            }
+            // NameNode 处于 standby 或安全模式时可以稍后重试
+            "StandbyException" | "RetriableException" | "SafeModeException" => {
+                StorageError::RateLimited(message, None)
+            }
+            "UnsupportedOperationException" => StorageError::ProtocolNotSupported(message),
+            _ => StorageError::from_http_status(status, &headers, message),
+        }
+    }
+
+    /// 发送请求并解析 JSON 响应
+    async fn send_json<T: serde::de::DeserializeOwned>(
+        &self,
+        builder: RequestBuilder,
+        op: &str,
+    ) -> Result<T, StorageError> {
+        let response = builder.send().await.map_err(StorageError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response, op).await);
+        }
+
+        let body = response.text().await.map_err(|e| {
+            StorageError::NetworkError(format!("Failed to read response body: {}", e))
+        })?;
+        serde_json::from_str(&body).map_err(|e| {
+            StorageError::RequestFailed(format!("Failed to parse {} response: {}", op, e))
+        })
+    }
+
+    async fn get_file_status(&self, full_path: &str) -> Result<HdfsFileStatus, StorageError> {
+        let response: FileStatusResponse = self
+            .send_json(
+                self.request(Method::GET, full_path, "GETFILESTATUS", &[]),
+                "GETFILESTATUS",
+            )
+            .await?;
+        Ok(response.file_status)
+    }
+
+    /// 列出目录，返回条目和是否还有剩余条目
+    /// 优先使用 LISTSTATUS_BATCH 按 startAfter 分批获取，不支持时一次性 LISTSTATUS 后在本地截取
+    async fn list_batch(
+        &self,
+        full_path: &str,
+        start_after: Option<&str>,
+    ) -> Result<(Vec<HdfsFileStatus>, bool), StorageError> {
+        if !self.batch_unsupported.load(Ordering::Relaxed) {
+            let params: Vec<(&str, String)> = start_after
+                .map(|name| vec![("startAfter", name.to_string())])
+                .unwrap_or_default();
+            let result: Result<ListStatusBatchResponse, StorageError> = self
+                .send_json(
+                    self.request(Method::GET, full_path, "LISTSTATUS_BATCH", &params),
+                    "LISTSTATUS_BATCH",
+                )
+                .await;
+
+            match result {
+                Ok(response) => {
+                    let listing = response.directory_listing;
+                    return Ok((
+                        listing.partial_listing.file_statuses.file_status,
+                        listing.remaining_entries > 0,
+                    ));
+                }
+                // HttpFS 和旧版本 NameNode 不支持 LISTSTATUS_BATCH
+                Err(StorageError::ProtocolNotSupported(_))
+                | Err(StorageError::RequestFailed(_)) => {
+                    self.batch_unsupported.store(true, Ordering::Relaxed);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let response: ListStatusResponse = self
+            .send_json(
+                self.request(Method::GET, full_path, "LISTSTATUS", &[]),
+                "LISTSTATUS",
+            )
+            .await?;
+        let mut entries = response.file_statuses.file_status;
+        if let Some(start_after) = start_after {
+            entries.retain(|entry| entry.path_suffix.as_str() > start_after);
+        }
+        Ok((entries, false))
+    }
+
+    fn format_time(millis: i64) -> String {
+        chrono::DateTime::from_timestamp_millis(millis)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_default()
+    }
+
+    fn to_storage_file(status: HdfsFileStatus) -> StorageFile {
+        let is_dir = status.is_dir();
+        StorageFile {
+            filename: status.path_suffix.clone(),
+            basename: status.path_suffix,
+            lastmod: Self::format_time(status.modification_time),
+            size: if is_dir { 0 } else { status.length }.to_string(),
+            file_type: if is_dir { "directory" } else { "file" }.to_string(),
+            mime: None,
+            etag: None,
+        }
+    }
+
+    fn open_url(&self, full_path: &str, offset: u64, length: Option<u64>) -> String {
+        let mut params = Vec::new();
+        if offset > 0 {
+            params.push(("offset", offset.to_string()));
+        }
+        if let Some(length) = length {
+            params.push(("length", length.to_string()));
+        }
+        self.op_url(full_path, "OPEN", &params)
+    }
+
+    /// 两步 CREATE：先向 NameNode 请求写入地址，再把数据发送到重定向的 DataNode
+    async fn create_file(
+        &self,
+        full_path: &str,
+        body: reqwest::Body,
+        file_size: u64,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let create_url = self.op_url(full_path, "CREATE", &[("overwrite", "true".to_string())]);
+        let response = self
+            .upload_client
+            .put(&create_url)
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+
+        let status = response.status();
+        if !status.is_redirection() {
+            return Err(Self::error_from_response(response, "CREATE").await);
+        }
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| {
+                StorageError::RequestFailed("CREATE response has no Location header".to_string())
+            })?
+            .to_string();
+
+        // HttpFS 需要声明 octet-stream 才会接收数据
+        let req_builder = self
+            .upload_client
+            .put(&location)
+            .header("Content-Type", "application/octet-stream")
+            .header("Content-Length", file_size)
+            .body(body);
+        let response = HttpUploader::send_with_cancel(req_builder, cancel_rx).await?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response, "CREATE").await);
+        }
+        Ok(())
+    }
+
+    async fn boolean_op(
+        &self,
+        method: Method,
+        full_path: &str,
+        op: &str,
+        params: &[(&str, String)],
+    ) -> Result<bool, StorageError> {
+        let response: BooleanResponse = self
+            .send_json(self.request(method, full_path, op, params), op)
+            .await?;
+        Ok(response.boolean)
+    }
+}
+
+#[async_trait]
+impl StorageClient for WebHdfsClient {
+    async fn connect(&mut self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        *self = Self::new(config.clone())?;
+
+        let root_path = self.root_path.clone();
+        let status = self
+            .get_file_status(&root_path)
+            .await
+            .map_err(|e| match e {
+                StorageError::NetworkError(message) => StorageError::ConnectionFailed(format!(
+                    "WebHDFS connection test failed: {}",
+                    message
+                )),
+                other => other,
+            })?;
+        if !status.is_dir() {
+            return Err(StorageError::InvalidConfig(format!(
+                "Root path is not a directory: {}",
+                root_path
+            )));
+        }
+
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    fn supports_native_paging(&self, _path: &str, recursive: bool) -> bool {
+        !recursive
+    }
+
+    fn canonical_path(&self, path: &str) -> String {
+        self.get_full_path(path)
+    }
+
+    /// 分页游标为上一页最后一个条目的名称，对应 LISTSTATUS_BATCH 的 startAfter
+    async fn list_directory(
+        &self,
+        path: &str,
+        options: Option<&ListOptions>,
+    ) -> Result<DirectoryResult, StorageError> {
+        if !self.is_connected().await {
+            return Err(StorageError::NotConnected);
+        }
+
+        let full_path = self.get_full_path(path);
+        let marker = options
+            .and_then(|opts| opts.marker.as_deref())
+            .filter(|marker| !marker.is_empty());
+        let page_size = options
+            .and_then(|opts| opts.page_size)
+            .map(|size| size.max(1) as usize);
+
+        let (mut entries, mut has_more) = self.list_batch(&full_path, marker).await?;
+
+        // 每批条目数由服务端的 dfs.ls.limit 决定，按请求的页大小截断
+        if let Some(page_size) = page_size {
+            if entries.len() > page_size {
+                entries.truncate(page_size);
+                has_more = true;
+            }
+        }
+
+        let next_marker = if has_more {
+            entries.last().map(|entry| entry.path_suffix.clone())
+        } else {
+            None
+        };
+
+        Ok(DirectoryResult {
+            files: entries.into_iter().map(Self::to_storage_file).collect(),
+            has_more: next_marker.is_some(),
+            next_marker,
+            total_count: None,
+            path: path.to_string(),
+        })
+    }
+
+    async fn read_file_range(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_file_range_with_progress(path, start, length, None, None)
+            .await
+    }
+
+    /// 使用 OPEN 的 offset 和 length 参数读取，NameNode 会重定向到持有数据块的 DataNode
+    async fn read_file_range_with_progress(
+        &self,
+        path: &str,
+        start: u64,
+        length: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Vec<u8>, StorageError> {
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+
+        let full_path = self.get_full_path(path);
+        let response = self
+            .download_client
+            .get(self.open_url(&full_path, start, Some(length)))
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response, "OPEN").await);
+        }
+
+        let mut result = Vec::with_capacity(length.min(64 * 1024 * 1024) as usize);
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk_result) = stream.next().await {
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    return Err(StorageError::Cancelled);
+                }
+            }
+
+            let chunk = chunk_result
+                .map_err(|e| StorageError::NetworkError(format!("Stream error: {}", e)))?;
+            result.extend_from_slice(&chunk);
+
+            if let Some(ref callback) = progress_callback {
+                callback(result.len() as u64, length);
+            }
+        }
+
+        result.truncate(length as usize);
+        Ok(result)
+    }
+
+    async fn read_full_file(&self, path: &str) -> Result<Vec<u8>, StorageError> {
+        let full_path = self.get_full_path(path);
+        let response = self
+            .download_client
+            .get(self.open_url(&full_path, 0, None))
+            .send()
+            .await
+            .map_err(StorageError::from_reqwest)?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_from_response(response, "OPEN").await);
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("Failed to read response: {}", e)))?;
+        Ok(bytes.to_vec())
+    }
+
+    /// 偏移量通过 OPEN 的 offset 参数传递，DataNode 不一定支持 Range 请求头
+    async fn open_read_stream(&self, path: &str, start: u64) -> Result<ByteStream, StorageError> {
+        let full_path = self.get_full_path(path);
+        let config = HttpDownloadConfig::new(self.open_url(&full_path, start, None));
+        HttpDownloader::open_stream(&self.download_client, config, 0).await
+    }
+
+    async fn stat(&self, path: &str) -> Result<FileMetadata, StorageError> {
+        let full_path = self.get_full_path(path);
+        let status = self.get_file_status(&full_path).await?;
+        let is_dir = status.is_dir();
+
+        let mut extra = HashMap::new();
+        if let Some(replication) = status.replication.filter(|_| !is_dir) {
+            extra.insert("replication".to_string(), replication.to_string());
+        }
+        if let Some(block_size) = status.block_size.filter(|_| !is_dir) {
+            extra.insert("blockSize".to_string(), block_size.to_string());
+        }
+        if status.access_time > 0 {
+            extra.insert(
+                "accessed".to_string(),
+                Self::format_time(status.access_time),
+            );
+        }
+
+        Ok(FileMetadata {
+            filename: path.to_string(),
+            basename: full_path
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            lastmod: Self::format_time(status.modification_time),
+            size: if is_dir { 0 } else { status.length }.to_string(),
+            file_type: if is_dir { "directory" } else { "file" }.to_string(),
+            mime: None,
+            etag: None,
+            checksum: None,
+            permissions: status.permission,
+            owner: status.owner,
+            group: status.group,
+            extra,
+        })
+    }
+
+    async fn get_file_size(&self, path: &str) -> Result<u64, StorageError> {
+        let full_path = self.get_full_path(path);
+        Ok(self.get_file_status(&full_path).await?.length)
+    }
+
+    fn get_download_url(&self, path: &str) -> Result<String, StorageError> {
+        Ok(self.open_url(&self.get_full_path(path), 0, None))
+    }
+
+    fn protocol(&self) -> &str {
+        "webhdfs"
+    }
+
+    fn validate_config(&self, config: &ConnectionConfig) -> Result<(), StorageError> {
+        Self::parse_base_url(config).map(|_| ())
+    }
+
+    async fn download_file(
+        &self,
+        path: &str,
+        save_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let full_path = self.get_full_path(path);
+        let config = HttpDownloadConfig::new(self.open_url(&full_path, 0, None));
+
+        HttpDownloader::download_stream(
+            &self.download_client,
+            config,
+            save_path,
+            progress_callback,
+            cancel_rx,
+        )
+        .await
+    }
+
+    async fn put_file(
+        &self,
+        local_path: &std::path::Path,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let full_path = self.get_full_path(path);
+        let (body, file_size) = HttpUploader::file_body(local_path, progress_callback).await?;
+
+        self.create_file(&full_path, body, file_size, cancel_rx)
+            .await
+    }
+
+    async fn put_stream(
+        &self,
+        stream: ByteStream,
+        size: u64,
+        path: &str,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let full_path = self.get_full_path(path);
+        let body = HttpUploader::stream_body(stream, size, progress_callback);
+
+        self.create_file(&full_path, body, size, cancel_rx).await
+    }
+
+    async fn create_directory(&self, path: &str) -> Result<(), StorageError> {
+        let full_path = self.get_full_path(path);
+        if self
+            .boolean_op(Method::PUT, &full_path, "MKDIRS", &[])
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(StorageError::RequestFailed(format!(
+                "Failed to create directory: {}",
+                full_path
+            )))
+        }
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<(), StorageError> {
+        let from_path = self.get_full_path(from);
+        let to_path = self.get_full_path(to);
+
+        // 目标已存在时 RENAME 只返回 false，提前检查以给出明确的错误
+        match self.get_file_status(&to_path).await {
+            Err(StorageError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+            Ok(_) => {
+                return Err(StorageError::Conflict(format!(
+                    "Destination already exists: {}",
+                    to
+                )))
+            }
+        }
+
+        if self
+            .boolean_op(
+                Method::PUT,
+                &from_path,
+                "RENAME",
+                &[("destination", to_path.clone())],
+            )
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(StorageError::RequestFailed(format!(
+                "Failed to rename {} to {}",
+                from_path, to_path
+            )))
+        }
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), StorageError> {
+        let full_path = self.get_full_path(path);
+        if full_path.trim_end_matches('/') == self.root_path.trim_end_matches('/') {
+            return Err(StorageError::RequestFailed(
+                "Cannot delete the root directory".to_string(),
+            ));
+        }
+
+        if self
+            .boolean_op(
+                Method::DELETE,
+                &full_path,
+                "DELETE",
+                &[("recursive", "true".to_string())],
+            )
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(StorageError::NotFound(path.to_string()))
+        }
+    }
+}
@@ -3,7 +3,7 @@ use reqwest::Client;
 use std::collections::HashMap;
 use tokio::io::AsyncWriteExt;
 
-use crate::storage::traits::{ProgressCallback, StorageError};
+use crate::storage::traits::{ByteStream, ProgressCallback, StorageError};
 
 /// HTTP下载配置
 #[derive(Debug, Clone)]
@@ -76,10 +76,10 @@ impl HttpDownloader {
 
         // 检查响应状态
         if !response.status().is_success() {
-            return Err(StorageError::RequestFailed(format!(
-                "HTTP download failed with status: {}",
-                response.status()
-            )));
+            return Err(StorageError::from_response(
+                &response,
+                format!("HTTP download failed with status: {}", response.status()),
+            ));
         }
 
         // 获取文件大小
@@ -100,9 +100,7 @@ impl HttpDownloader {
                 if cancel_rx.try_recv().is_ok() {
                     // 删除部分下载的文件
                     let _ = tokio::fs::remove_file(save_path).await;
-                    return Err(StorageError::RequestFailed(
-                        "download.cancelled".to_string(),
-                    ));
+                    return Err(StorageError::Cancelled);
                 }
             }
 
@@ -131,6 +129,82 @@ impl HttpDownloader {
         Ok(())
     }
 
+    /// 打开HTTP流式读取，从指定偏移量开始
+    ///
+    /// 偏移量大于0时发送 Range 请求；服务器忽略 Range 返回完整内容时，
+    /// 在流中跳过前面的字节，保证调用方拿到的数据总是从 start 开始
+    pub async fn open_stream(
+        client: &Client,
+        config: HttpDownloadConfig,
+        start: u64,
+    ) -> Result<ByteStream, StorageError> {
+        let mut request_builder = client.get(&config.url);
+
+        // 添加自定义头
+        for (key, value) in &config.headers {
+            request_builder = request_builder.header(key, value);
+        }
+
+        if start > 0 {
+            request_builder = request_builder.header("Range", format!("bytes={}-", start));
+        }
+
+        // 设置超时
+        if let Some(timeout) = config.timeout_seconds {
+            request_builder = request_builder.timeout(std::time::Duration::from_secs(timeout));
+        }
+
+        let response = request_builder
+            .send()
+            .await
+            .map_err(|e| StorageError::NetworkError(format!("HTTP request failed: {}", e)))?;
+
+        let status = response.status();
+
+        // 偏移量超出文件末尾，返回空流
+        if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(Box::pin(futures_util::stream::empty()));
+        }
+
+        if !status.is_success() {
+            return Err(StorageError::from_response(
+                &response,
+                format!("HTTP stream request failed with status: {}", status),
+            ));
+        }
+
+        let mut skip = if start > 0 && status != reqwest::StatusCode::PARTIAL_CONTENT {
+            start
+        } else {
+            0
+        };
+
+        let mut stream = response.bytes_stream();
+
+        Ok(Box::pin(async_stream::stream! {
+            while let Some(chunk_result) = stream.next().await {
+                let mut bytes = match chunk_result {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        yield Err(StorageError::NetworkError(format!("Stream error: {}", e)));
+                        break;
+                    }
+                };
+
+                if skip > 0 {
+                    let skipped = std::cmp::min(skip, bytes.len() as u64);
+                    skip -= skipped;
+                    bytes = bytes.slice(skipped as usize..);
+                    if bytes.is_empty() {
+                        continue;
+                    }
+                }
+
+                yield Ok(bytes);
+            }
+        }))
+    }
+
     /// 简化的HTTP下载方法，用于只需要URL和认证的场景
     pub async fn download_with_auth(
         client: &Client,
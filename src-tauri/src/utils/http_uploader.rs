@@ -0,0 +1,114 @@
+use futures_util::StreamExt;
+use reqwest::{Body, RequestBuilder, Response};
+use tokio::io::AsyncReadExt;
+
+use crate::storage::traits::{ByteStream, ProgressCallback, StorageError};
+use crate::utils::chunk_size;
+
+/// 通用HTTP流式上传工具
+pub struct HttpUploader;
+
+impl HttpUploader {
+    /// 将本地文件包装为流式请求体
+    ///
+    /// # 参数
+    /// - local_path: 本地文件路径
+    /// - progress_callback: 进度回调函数，在每个数据块被发送时调用
+    ///
+    /// # 返回
+    /// - Ok((Body, u64)): 请求体和文件大小（用于设置 Content-Length）
+    /// - Err(StorageError): 打开文件失败
+    pub async fn file_body(
+        local_path: &std::path::Path,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<(Body, u64), StorageError> {
+        let file_size = tokio::fs::metadata(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to get file metadata: {}", e)))?
+            .len();
+
+        let mut file = tokio::fs::File::open(local_path)
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to open local file: {}", e)))?;
+
+        let chunk_size = chunk_size::calculate_optimal_chunk_size(file_size);
+
+        let stream = async_stream::stream! {
+            let mut buffer = vec![0u8; chunk_size];
+            let mut uploaded = 0u64;
+
+            loop {
+                match file.read(&mut buffer).await {
+                    Ok(0) => break,
+                    Ok(bytes_read) => {
+                        uploaded += bytes_read as u64;
+
+                        // 调用进度回调
+                        if let Some(ref callback) = progress_callback {
+                            callback(uploaded, file_size);
+                        }
+
+                        yield Ok::<_, std::io::Error>(bytes::Bytes::copy_from_slice(
+                            &buffer[..bytes_read],
+                        ));
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                }
+            }
+        };
+
+        Ok((Body::wrap_stream(stream), file_size))
+    }
+
+    /// 将其他存储客户端打开的数据流包装为请求体，用于跨存储传输
+    ///
+    /// # 参数
+    /// - stream: 源文件的数据流
+    /// - total_size: 文件总大小，用于进度回调
+    /// - progress_callback: 进度回调函数，在每个数据块被发送时调用
+    pub fn stream_body(
+        stream: ByteStream,
+        total_size: u64,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Body {
+        let mut uploaded = 0u64;
+        let stream = stream.map(move |chunk| {
+            let bytes = chunk.map_err(|e| std::io::Error::other(e.to_string()))?;
+            uploaded += bytes.len() as u64;
+
+            // 调用进度回调
+            if let Some(ref callback) = progress_callback {
+                callback(uploaded, total_size);
+            }
+
+            Ok::<_, std::io::Error>(bytes)
+        });
+
+        Body::wrap_stream(stream)
+    }
+
+    /// 发送上传请求，在收到取消信号时中断请求
+    pub async fn send_with_cancel(
+        request_builder: RequestBuilder,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<Response, StorageError> {
+        let send_future = request_builder.send();
+
+        let result = match cancel_rx {
+            Some(cancel_rx) => {
+                tokio::select! {
+                    result = send_future => result,
+                    Ok(_) = cancel_rx.recv() => {
+                        return Err(StorageError::Cancelled);
+                    }
+                }
+            }
+            None => send_future.await,
+        };
+
+        result.map_err(|e| StorageError::NetworkError(format!("Upload request failed: {}", e)))
+    }
+}
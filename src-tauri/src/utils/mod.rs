@@ -1,4 +1,6 @@
 pub mod chunk_size;
 pub mod crypto;
 pub mod http_downloader;
+pub mod http_uploader;
 pub mod path_utils;
+pub mod stream_io;
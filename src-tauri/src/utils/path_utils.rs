@@ -33,4 +33,22 @@ impl PathUtils {
         }
         Ok(path.to_string())
     }
+
+    /// 拼接存储路径中的目录与子项，目录为空时直接返回子项
+    ///
+    /// # 示例
+    /// ```rust
+    /// // ("data", "a.parquet") -> "data/a.parquet"
+    /// // ("/", "a.parquet") -> "/a.parquet"
+    /// // ("", "a.parquet") -> "a.parquet"
+    /// ```
+    pub fn join_storage_path(directory: &str, name: &str) -> String {
+        if directory.is_empty() {
+            name.to_string()
+        } else if name.is_empty() {
+            directory.to_string()
+        } else {
+            format!("{}/{}", directory.trim_end_matches('/'), name)
+        }
+    }
 }
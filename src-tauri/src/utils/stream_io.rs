@@ -0,0 +1,188 @@
+use bytes::Bytes;
+use futures_util::StreamExt;
+use std::io::Read;
+use tokio::io::{AsyncRead, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio_util::io::StreamReader;
+
+use crate::storage::traits::{ByteStream, ProgressCallback, StorageError};
+
+/// 通用数据流读写工具
+pub struct StreamIo;
+
+impl StreamIo {
+    /// 将数据流写入本地文件
+    ///
+    /// # 参数
+    /// - stream: 由存储客户端打开的数据流
+    /// - save_path: 保存路径
+    /// - total_size: 文件总大小（未知时为0），用于进度回调
+    /// - progress_callback: 进度回调函数
+    /// - cancel_rx: 取消信号接收器
+    ///
+    /// # 返回
+    /// - Ok(()): 写入成功
+    /// - Err(StorageError): 读取或写入失败，取消时会删除已写入的部分文件
+    pub async fn save_to_file(
+        stream: ByteStream,
+        save_path: &std::path::Path,
+        total_size: u64,
+        progress_callback: Option<ProgressCallback>,
+        cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        Self::append_to_file(
+            stream,
+            save_path,
+            0,
+            total_size,
+            progress_callback,
+            cancel_rx,
+        )
+        .await
+    }
+
+    /// 将数据流追加到本地文件的指定偏移量之后，用于断点续传
+    ///
+    /// # 参数
+    /// - stream: 从 offset 处开始的数据流
+    /// - save_path: 保存路径
+    /// - offset: 本地文件中已写入的字节数，为0时会重新创建文件
+    /// - total_size: 文件总大小（未知时为0），用于进度回调
+    /// - progress_callback: 进度回调函数，进度包含已写入的部分
+    /// - cancel_rx: 取消信号接收器
+    pub async fn append_to_file(
+        mut stream: ByteStream,
+        save_path: &std::path::Path,
+        offset: u64,
+        total_size: u64,
+        progress_callback: Option<ProgressCallback>,
+        mut cancel_rx: Option<&mut tokio::sync::broadcast::Receiver<()>>,
+    ) -> Result<(), StorageError> {
+        let mut file = if offset > 0 {
+            let mut file = tokio::fs::OpenOptions::new()
+                .write(true)
+                .open(save_path)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to open file: {}", e)))?;
+            // 丢弃偏移量之后可能残留的不完整数据
+            file.set_len(offset)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to truncate file: {}", e)))?;
+            file.seek(std::io::SeekFrom::Start(offset))
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to seek file: {}", e)))?;
+            file
+        } else {
+            tokio::fs::File::create(save_path)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to create file: {}", e)))?
+        };
+
+        let mut downloaded = offset;
+
+        while let Some(chunk_result) = stream.next().await {
+            // 检查取消信号
+            if let Some(ref mut cancel_rx) = cancel_rx {
+                if cancel_rx.try_recv().is_ok() {
+                    drop(file);
+                    let _ = tokio::fs::remove_file(save_path).await;
+                    return Err(StorageError::Cancelled);
+                }
+            }
+
+            let bytes = chunk_result?;
+
+            file.write_all(&bytes)
+                .await
+                .map_err(|e| StorageError::IoError(format!("Failed to write data: {}", e)))?;
+
+            downloaded += bytes.len() as u64;
+
+            // 调用进度回调
+            if let Some(ref callback) = progress_callback {
+                callback(downloaded, total_size);
+            }
+        }
+
+        // 确保数据写入磁盘
+        file.flush()
+            .await
+            .map_err(|e| StorageError::IoError(format!("Failed to flush file: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// 将数据流包装为 AsyncRead，便于交给解压缩等按字节读取的处理器
+    pub fn into_async_read(stream: ByteStream) -> impl AsyncRead + Send + Unpin {
+        StreamReader::new(stream.map(|chunk| chunk.map_err(std::io::Error::other)))
+    }
+
+    /// 把读取器返回的 I/O 错误还原为存储错误，数据流中的取消、超时等错误保持原有类型
+    /// 解压缩库可能再包装一层错误，因此沿错误来源链查找
+    pub fn storage_error(error: std::io::Error) -> StorageError {
+        let mut source = error
+            .get_ref()
+            .map(|inner| inner as &(dyn std::error::Error + 'static));
+        while let Some(inner) = source {
+            if let Some(storage_error) = inner.downcast_ref::<StorageError>() {
+                return storage_error.clone();
+            }
+            source = match inner.downcast_ref::<std::io::Error>() {
+                Some(io_error) => io_error
+                    .get_ref()
+                    .map(|inner| inner as &(dyn std::error::Error + 'static)),
+                None => inner.source(),
+            };
+        }
+        StorageError::IoError(error.to_string())
+    }
+
+    /// 在阻塞线程中运行同步读取逻辑，把发送的数据块作为数据流返回
+    ///
+    /// 读取逻辑返回的错误作为数据流的最后一项；数据流被丢弃后发送会失败，读取逻辑应随之退出
+    pub fn spawn_blocking_stream<F>(read: F) -> ByteStream
+    where
+        F: FnOnce(&mpsc::Sender<Result<Bytes, StorageError>>) -> Result<(), StorageError>
+            + Send
+            + 'static,
+    {
+        let (tx, rx) = mpsc::channel(2);
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = read(&tx) {
+                let _ = tx.blocking_send(Err(e));
+            }
+        });
+
+        Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        }))
+    }
+
+    /// 跳过读取器开头的 start 字节，之后的数据按 chunk_size 分块发送，只能在阻塞线程中调用
+    pub fn send_from_reader<R: Read>(
+        mut reader: R,
+        start: u64,
+        chunk_size: u64,
+        tx: &mpsc::Sender<Result<Bytes, StorageError>>,
+    ) -> Result<(), StorageError> {
+        std::io::copy(&mut (&mut reader).take(start), &mut std::io::sink())
+            .map_err(Self::storage_error)?;
+
+        loop {
+            let mut chunk = Vec::new();
+            (&mut reader)
+                .take(chunk_size)
+                .read_to_end(&mut chunk)
+                .map_err(Self::storage_error)?;
+
+            let finished = (chunk.len() as u64) < chunk_size;
+            // 接收端已关闭时停止读取
+            if !chunk.is_empty() && tx.blocking_send(Ok(Bytes::from(chunk))).is_err() {
+                return Ok(());
+            }
+            if finished {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -195,6 +195,10 @@ export const ConnectionFormContainer: React.FC<ConnectionFormContainerProps> = (
                 bucket: formData.bucket || '',
                 region: formData.region || '',
                 platform: formData.platform || 'aliyun',
+                anonymous: !!formData.anonymous,
+                sessionToken: formData.sessionToken || '',
+                useDefaultCredentials: !!formData.useDefaultCredentials,
+                credentialProfile: formData.credentialProfile || '',
               }}
               onChange={(config: any) => {
                 const updates: Record<string, any> = {};
@@ -210,11 +214,25 @@ export const ConnectionFormContainer: React.FC<ConnectionFormContainerProps> = (
                 if (config.bucket !== undefined) updates.bucket = config.bucket;
                 if (config.region !== undefined) updates.region = config.region;
                 if (config.platform !== undefined) updates.platform = config.platform;
+                if (config.anonymous !== undefined) updates.anonymous = config.anonymous;
+                if (config.sessionToken !== undefined) {
+                  updates.sessionToken = config.sessionToken;
+                  if (config.sessionToken === '') {
+                    updates.isSessionTokenFromStorage = false;
+                  }
+                }
+                if (config.useDefaultCredentials !== undefined) {
+                  updates.useDefaultCredentials = config.useDefaultCredentials;
+                }
+                if (config.credentialProfile !== undefined) {
+                  updates.credentialProfile = config.credentialProfile;
+                }
                 onFormDataChange(updates);
               }}
               connecting={connecting}
               error={error}
               isPasswordFromStorage={isPasswordFromStorage}
+              isSessionTokenFromStorage={!!formData.isSessionTokenFromStorage}
               onConnect={onConnect}
             />
           ) : storageType === 'huggingface' ? (
@@ -13,7 +13,12 @@ interface OSSConnectionFormProps extends UnifiedConnectionFormProps {
     bucket?: string;
     region?: string;
     platform?: string;
+    anonymous?: boolean;
+    sessionToken?: string;
+    useDefaultCredentials?: boolean;
+    credentialProfile?: string;
   };
+  isSessionTokenFromStorage?: boolean;
 }
 
 /**
@@ -27,6 +32,7 @@ export const OSSConnectionForm: React.FC<OSSConnectionFormProps> = ({
   error,
   onConnect,
   isPasswordFromStorage = false,
+  isSessionTokenFromStorage = false,
 }) => {
   const { t } = useTranslation();
 
@@ -44,6 +50,10 @@ export const OSSConnectionForm: React.FC<OSSConnectionFormProps> = ({
     bucket: config.bucket || '',
     region: config.region || fallbackRegion,
     platform: config.platform || 'aliyun',
+    anonymous: !!config.anonymous,
+    sessionToken: config.sessionToken || '',
+    useDefaultCredentials: !!config.useDefaultCredentials,
+    credentialProfile: config.credentialProfile || '',
   };
 
   // 平台选择相关状态
@@ -124,16 +134,21 @@ export const OSSConnectionForm: React.FC<OSSConnectionFormProps> = ({
       }
     }
 
-    if (!currentConfig.accessKey?.trim()) {
-      newErrors.accessKey = t('error.access.key.required');
-    }
-
-    if (!currentConfig.secretKey?.trim()) {
-      newErrors.secretKey = t('error.secret.key.required');
-    }
+    // 匿名访问公开存储桶时不需要密钥，但必须指定存储桶；
+    // 使用密钥时存储桶可留空，连接后列出所有可访问的存储桶；
+    // 使用默认凭证时由后端从 profile 或环境变量获取密钥
+    if (currentConfig.anonymous) {
+      if (!currentConfig.bucket?.trim()) {
+        newErrors.bucket = t('error.bucket.required');
+      }
+    } else if (!currentConfig.useDefaultCredentials) {
+      if (!currentConfig.accessKey?.trim()) {
+        newErrors.accessKey = t('error.access.key.required');
+      }
 
-    if (!currentConfig.bucket?.trim()) {
-      newErrors.bucket = t('error.bucket.required');
+      if (!currentConfig.secretKey?.trim()) {
+        newErrors.secretKey = t('error.secret.key.required');
+      }
     }
 
     setErrors(newErrors);
@@ -150,6 +165,16 @@ export const OSSConnectionForm: React.FC<OSSConnectionFormProps> = ({
     onConnect();
   };
 
+  const handleAnonymousChange = (anonymous: boolean) => {
+    onChange({ ...currentConfig, anonymous });
+    setErrors({});
+  };
+
+  const handleDefaultCredentialsChange = (useDefaultCredentials: boolean) => {
+    onChange({ ...currentConfig, useDefaultCredentials });
+    setErrors({});
+  };
+
   const handleInputChange = (field: string, value: string) => {
     let processedValue = value.trim();
 
@@ -192,54 +217,127 @@ export const OSSConnectionForm: React.FC<OSSConnectionFormProps> = ({
         />
         {errors.endpoint && <ErrorDisplay error={errors.endpoint} />}
 
-        <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
-          <div>
-            <label
-              htmlFor="accessKey"
-              className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1"
-            >
-              {t('oss.access.key')}
-            </label>
+        <div>
+          <label className="flex items-center space-x-2 text-sm text-gray-700 dark:text-gray-300">
             <input
-              type="text"
-              id="accessKey"
-              value={currentConfig.accessKey}
-              onChange={e => handleInputChange('accessKey', e.target.value)}
-              className={`w-full px-3 py-2 border rounded-md shadow-sm focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:border-gray-600 dark:text-white ${
-                errors.accessKey ? 'border-red-300 dark:border-red-600' : 'border-gray-300'
-              }`}
-              placeholder={t('oss.access.key.placeholder')}
+              type="checkbox"
+              checked={currentConfig.anonymous}
+              onChange={e => handleAnonymousChange(e.target.checked)}
+              className="rounded border-gray-300 dark:border-gray-600 text-blue-600 focus:ring-blue-500 dark:bg-gray-700"
               disabled={connecting}
-              required
             />
-            {errors.accessKey && (
-              <p className="mt-1 text-sm text-red-600 dark:text-red-400">{errors.accessKey}</p>
-            )}
+            <span>{t('oss.anonymous')}</span>
+          </label>
+          <p className="mt-1 text-xs text-gray-500 dark:text-gray-400">
+            {t('oss.anonymous.description')}
+          </p>
+        </div>
+
+        {!currentConfig.anonymous && (
+          <div>
+            <label className="flex items-center space-x-2 text-sm text-gray-700 dark:text-gray-300">
+              <input
+                type="checkbox"
+                checked={currentConfig.useDefaultCredentials}
+                onChange={e => handleDefaultCredentialsChange(e.target.checked)}
+                className="rounded border-gray-300 dark:border-gray-600 text-blue-600 focus:ring-blue-500 dark:bg-gray-700"
+                disabled={connecting}
+              />
+              <span>{t('oss.default.credentials')}</span>
+            </label>
+            <p className="mt-1 text-xs text-gray-500 dark:text-gray-400">
+              {t('oss.default.credentials.description')}
+            </p>
           </div>
+        )}
 
+        {!currentConfig.anonymous && currentConfig.useDefaultCredentials && (
           <div>
             <label
-              htmlFor="secretKey"
+              htmlFor="credentialProfile"
               className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1"
             >
-              {t('oss.secret.key')}
+              {t('oss.credential.profile')}
             </label>
-            <PasswordInput
-              id="secretKey"
-              value={currentConfig.secretKey}
-              onChange={value => handleInputChange('secretKey', value)}
-              placeholder={t('oss.secret.key.placeholder')}
-              isFromStorage={isPasswordFromStorage}
-              className={`w-full px-3 py-2 border rounded-md shadow-sm focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:border-gray-600 dark:text-white ${
-                errors.secretKey ? 'border-red-300 dark:border-red-600' : 'border-gray-300'
-              }`}
-              required
+            <input
+              type="text"
+              id="credentialProfile"
+              value={currentConfig.credentialProfile}
+              onChange={e => handleInputChange('credentialProfile', e.target.value)}
+              className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:text-white"
+              placeholder={t('oss.credential.profile.placeholder')}
+              disabled={connecting}
             />
-            {errors.secretKey && (
-              <p className="mt-1 text-sm text-red-600 dark:text-red-400">{errors.secretKey}</p>
-            )}
           </div>
-        </div>
+        )}
+
+        {!currentConfig.anonymous && !currentConfig.useDefaultCredentials && (
+          <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+            <div>
+              <label
+                htmlFor="accessKey"
+                className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1"
+              >
+                {t('oss.access.key')}
+              </label>
+              <input
+                type="text"
+                id="accessKey"
+                value={currentConfig.accessKey}
+                onChange={e => handleInputChange('accessKey', e.target.value)}
+                className={`w-full px-3 py-2 border rounded-md shadow-sm focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:border-gray-600 dark:text-white ${
+                  errors.accessKey ? 'border-red-300 dark:border-red-600' : 'border-gray-300'
+                }`}
+                placeholder={t('oss.access.key.placeholder')}
+                disabled={connecting}
+                required
+              />
+              {errors.accessKey && (
+                <p className="mt-1 text-sm text-red-600 dark:text-red-400">{errors.accessKey}</p>
+              )}
+            </div>
+
+            <div>
+              <label
+                htmlFor="secretKey"
+                className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1"
+              >
+                {t('oss.secret.key')}
+              </label>
+              <PasswordInput
+                id="secretKey"
+                value={currentConfig.secretKey}
+                onChange={value => handleInputChange('secretKey', value)}
+                placeholder={t('oss.secret.key.placeholder')}
+                isFromStorage={isPasswordFromStorage}
+                className={`w-full px-3 py-2 border rounded-md shadow-sm focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:border-gray-600 dark:text-white ${
+                  errors.secretKey ? 'border-red-300 dark:border-red-600' : 'border-gray-300'
+                }`}
+                required
+              />
+              {errors.secretKey && (
+                <p className="mt-1 text-sm text-red-600 dark:text-red-400">{errors.secretKey}</p>
+              )}
+            </div>
+
+            <div className="md:col-span-2">
+              <label
+                htmlFor="sessionToken"
+                className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1"
+              >
+                {t('oss.session.token')}
+              </label>
+              <PasswordInput
+                id="sessionToken"
+                value={currentConfig.sessionToken}
+                onChange={value => handleInputChange('sessionToken', value)}
+                placeholder={t('oss.session.token.placeholder')}
+                isFromStorage={isSessionTokenFromStorage}
+                className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-700 dark:text-white"
+              />
+            </div>
+          </div>
+        )}
 
         {/* Bucket 字段 - 根据平台类型调整布局 */}
         {selectedPlatform === 'custom' ? (
@@ -261,7 +359,7 @@ export const OSSConnectionForm: React.FC<OSSConnectionFormProps> = ({
                 }`}
                 placeholder={t('oss.bucket.placeholder')}
                 disabled={connecting}
-                required
+                required={currentConfig.anonymous}
               />
               {errors.bucket && (
                 <p className="mt-1 text-sm text-red-600 dark:text-red-400">{errors.bucket}</p>
@@ -305,7 +403,7 @@ export const OSSConnectionForm: React.FC<OSSConnectionFormProps> = ({
               }`}
               placeholder={t('oss.bucket.placeholder')}
               disabled={connecting}
-              required
+              required={currentConfig.anonymous}
             />
             {errors.bucket && (
               <p className="mt-1 text-sm text-red-600 dark:text-red-400">{errors.bucket}</p>
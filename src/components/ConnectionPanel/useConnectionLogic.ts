@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
 import { StorageServiceManager } from '../../services/storage/StorageManager';
-import { StoredConnection } from '../../services/connectionStorage';
+import { StoredConnection, hasStoredSecrets } from '../../services/connectionStorage';
 import { ConnectionConfig, StorageClientType } from '../../services/storage/types';
 import { getStorageAdapter } from '../../services/storage/StorageClient';
 
@@ -79,6 +79,11 @@ export default function useConnectionLogic(onConnectSuccess?: () => void) {
         adapter.buildConnectionConfig?.(formData, selectedStoredConnection) ||
         (formData as ConnectionConfig);
 
+      // 沿用已保存连接的密钥时，通过加密配置库中的配置连接
+      if (selectedStoredConnection?.config.profileId && hasStoredSecrets(config)) {
+        config.profileId = selectedStoredConnection.config.profileId;
+      }
+
       // 2. 直接尝试连接，让后端处理所有验证
       const success = await StorageServiceManager.connectWithConfig(config);
       if (success) {
@@ -23,6 +23,7 @@ interface DownloadProgressProps {
 }
 
 interface DownloadState {
+  taskId: string; // 任务 ID，下载任务为文件名，上传任务为后端生成的唯一 ID
   filename: string;
   progress: number;
   downloaded: number;
@@ -57,8 +58,8 @@ const DownloadItem = ({
   t,
 }: {
   download: DownloadState;
-  onCancel: (filename: string) => void;
-  onRemove: (filename: string) => void;
+  onCancel: (taskId: string) => void;
+  onRemove: (taskId: string) => void;
   onOpenLocation: (filePath: string) => void;
   t: (key: string) => string;
 }) => (
@@ -142,7 +143,7 @@ const DownloadItem = ({
 
       {(download.status === 'downloading' || download.status === 'preparing') && (
         <button
-          onClick={() => onCancel(download.filename)}
+          onClick={() => onCancel(download.taskId)}
           className="ml-2 text-gray-400 dark:text-gray-500 hover:text-red-600 dark:hover:text-red-400 p-1 rounded hover:bg-gray-100 dark:hover:bg-gray-700"
           title={t('download.cancel.tooltip')}
         >
@@ -154,7 +155,7 @@ const DownloadItem = ({
         download.status === 'error' ||
         download.status === 'stopped') && (
         <button
-          onClick={() => onRemove(download.filename)}
+          onClick={() => onRemove(download.taskId)}
           className="ml-2 text-gray-400 dark:text-gray-500 hover:text-gray-600 dark:hover:text-gray-300 p-1 rounded hover:bg-gray-100 dark:hover:bg-gray-700"
           title={download.status === 'stopped' ? t('download.remove.stopped') : undefined}
         >
@@ -175,11 +176,16 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
     if (!isVisible) return;
 
     const unlistenStart = listen('download-started', event => {
-      const { filename, total_size } = event.payload as { filename: string; total_size: number };
+      const { task_id, filename, total_size } = event.payload as {
+        task_id: string;
+        filename: string;
+        total_size: number;
+      };
       setDownloads(
         prev =>
           new Map(
-            prev.set(filename, {
+            prev.set(task_id, {
+              taskId: task_id,
               filename,
               progress: 0,
               downloaded: 0,
@@ -191,8 +197,8 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
     });
 
     const unlistenProgress = listen('download-progress', event => {
-      const { filename, downloaded, total_size, progress } = event.payload as {
-        filename: string;
+      const { task_id, downloaded, total_size, progress } = event.payload as {
+        task_id: string;
         downloaded: number;
         total_size: number;
         progress: number;
@@ -200,9 +206,9 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
 
       setDownloads(prev => {
         const newMap = new Map(prev);
-        const existing = newMap.get(filename);
+        const existing = newMap.get(task_id);
         if (existing) {
-          newMap.set(filename, {
+          newMap.set(task_id, {
             ...existing,
             progress,
             downloaded,
@@ -214,12 +220,12 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
     });
 
     const unlistenCompleted = listen('download-completed', event => {
-      const { filename, file_path } = event.payload as { filename: string; file_path: string };
+      const { task_id, file_path } = event.payload as { task_id: string; file_path: string };
       setDownloads(prev => {
         const newMap = new Map(prev);
-        const existing = newMap.get(filename);
+        const existing = newMap.get(task_id);
         if (existing) {
-          newMap.set(filename, {
+          newMap.set(task_id, {
             ...existing,
             status: 'completed',
             filePath: file_path,
@@ -231,15 +237,15 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
     });
 
     const unlistenError = listen('download-error', event => {
-      const { filename, error } = event.payload as { filename: string; error: string };
+      const { task_id, error } = event.payload as { task_id: string; error: string };
 
       setDownloads(prev => {
         const newMap = new Map(prev);
-        const existing = newMap.get(filename);
+        const existing = newMap.get(task_id);
         if (existing) {
           // 如果错误信息包含 "canceled/cancelled"（忽略大小写），则标记为 stopped 而不是 error
           const isCancelled = /cancell?ed/i.test(error);
-          newMap.set(filename, {
+          newMap.set(task_id, {
             ...existing,
             status: isCancelled ? 'stopped' : 'error',
             error: isCancelled ? undefined : error,
@@ -257,12 +263,12 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
     };
   }, [isVisible]);
 
-  const cancelDownload = async (filename: string) => {
+  const cancelDownload = async (taskId: string) => {
     try {
       const timeoutMs = 5000; // 5秒
 
       await Promise.race([
-        commands.downloadCancel(filename),
+        commands.downloadCancel(taskId),
         new Promise<never>((_, reject) => {
           setTimeout(() => {
             reject(new Error(`取消下载超时 (${timeoutMs}ms)`));
@@ -272,9 +278,9 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
       // 立即更新为stopped状态，与全部取消保持一致
       setDownloads(prev => {
         const newMap = new Map(prev);
-        const existing = newMap.get(filename);
+        const existing = newMap.get(taskId);
         if (existing && existing.status === 'downloading') {
-          newMap.set(filename, {
+          newMap.set(taskId, {
             ...existing,
             status: 'stopped',
           });
@@ -286,10 +292,10 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
     }
   };
 
-  const removeDownload = (filename: string) => {
+  const removeDownload = (taskId: string) => {
     setDownloads(prev => {
       const newMap = new Map(prev);
-      newMap.delete(filename);
+      newMap.delete(taskId);
       return newMap;
     });
   };
@@ -305,9 +311,9 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
   const clearCompleted = () => {
     setDownloads(prev => {
       const newMap = new Map();
-      prev.forEach((download, filename) => {
+      prev.forEach((download, taskId) => {
         if (download.status === 'downloading') {
-          newMap.set(filename, download);
+          newMap.set(taskId, download);
         }
       });
       return newMap;
@@ -316,10 +322,10 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
 
   const stopAllDownloads = async () => {
     try {
-      // 先获取当前正在下载的文件名列表
+      // 先获取当前正在进行的任务列表
       const activeDownloads = Array.from(downloads.values())
         .filter(d => d.status === 'downloading' || d.status === 'preparing')
-        .map(d => d.filename);
+        .map(d => d.taskId);
 
       await FolderDownloadService.stopAllDownloads();
       // 也取消后端的所有下载
@@ -329,10 +335,10 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
       if (activeDownloads.length > 0) {
         setDownloads(prev => {
           const newMap = new Map(prev);
-          activeDownloads.forEach(filename => {
-            const existing = newMap.get(filename);
+          activeDownloads.forEach(taskId => {
+            const existing = newMap.get(taskId);
             if (existing && existing.status === 'downloading') {
-              newMap.set(filename, {
+              newMap.set(taskId, {
                 ...existing,
                 status: 'stopped',
               });
@@ -506,7 +512,7 @@ export default function DownloadProgress({ isVisible, onClose }: DownloadProgres
         ) : (
           downloadList.map(download => (
             <DownloadItem
-              key={download.filename}
+              key={download.taskId}
               download={download}
               onCancel={cancelDownload}
               onRemove={removeDownload}
@@ -1,10 +1,23 @@
-import React, { useState } from 'react';
-import { Settings, Download, RefreshCw, Check, X, Sun, Moon, Trash2 } from 'lucide-react';
+import React, { useEffect, useState } from 'react';
+import {
+  Settings,
+  Download,
+  RefreshCw,
+  Check,
+  X,
+  Sun,
+  Moon,
+  Trash2,
+  Lock,
+  Unlock,
+} from 'lucide-react';
 import { useTranslation } from 'react-i18next';
 import { updateService } from '../../services/updateService';
 import { useTheme } from '../../hooks/useTheme';
 import { navigationHistoryService } from '../../services/navigationHistory';
 import { connectionStorage } from '../../services/connectionStorage';
+import { StorageServiceManager } from '../../services/storage/StorageManager';
+import { commands, ProfileStoreStatus } from '../../types/tauri-commands';
 import { settingsStorage } from '../../services/settingsStorage';
 import { showToast } from '../../utils/clipboard';
 import type { UpdateCheckResult } from '../../types';
@@ -24,6 +37,48 @@ export const SettingsPanel: React.FC<SettingsPanelProps> = ({ isOpen, onClose })
     settingsStorage.getSetting('usePureBlackBg')
   );
   const [isClearingCache, setIsClearingCache] = useState(false);
+  const [profileStatus, setProfileStatus] = useState<ProfileStoreStatus | null>(null);
+  const [masterPassword, setMasterPassword] = useState('');
+  const [rememberPassword, setRememberPassword] = useState(false);
+  const [isUnlocking, setIsUnlocking] = useState(false);
+
+  // 打开设置面板时读取加密配置库状态
+  useEffect(() => {
+    if (!isOpen) return;
+    commands.profileStatus().then(result => {
+      if (result.status === 'ok') {
+        setProfileStatus(result.data);
+      }
+    });
+  }, [isOpen]);
+
+  const handleUnlockProfileStore = async () => {
+    setIsUnlocking(true);
+    try {
+      const status = await StorageServiceManager.unlockProfileStore(
+        masterPassword,
+        rememberPassword
+      );
+      setProfileStatus(status);
+      setMasterPassword('');
+      showToast(t('credentials.unlock.success'), 'success');
+    } catch (error) {
+      console.error('Failed to unlock profile store:', error);
+      showToast(t('credentials.unlock.failed'), 'error');
+    } finally {
+      setIsUnlocking(false);
+    }
+  };
+
+  const handleLockProfileStore = async () => {
+    try {
+      await StorageServiceManager.lockProfileStore();
+      setProfileStatus(prev => (prev ? { ...prev, unlocked: false } : prev));
+      showToast(t('credentials.locked.success'), 'success');
+    } catch (error) {
+      console.error('Failed to lock profile store:', error);
+    }
+  };
   // 切换纯黑色背景
   const handlePureBlackBgToggle = () => {
     const newValue = !usePureBlackBg;
@@ -247,6 +302,69 @@ export const SettingsPanel: React.FC<SettingsPanelProps> = ({ isOpen, onClose })
             </div>
           </div>
 
+          {/* Saved Credentials */}
+          {profileStatus && (
+            <div>
+              <h3 className="text-sm font-medium text-gray-900 dark:text-white mb-3">
+                {t('settings.credentials')}
+              </h3>
+              <div className="space-y-3">
+                <p className="text-xs text-gray-600 dark:text-gray-300">
+                  {t('credentials.description')}
+                </p>
+                {profileStatus.unlocked ? (
+                  <div className="flex items-center justify-between">
+                    <span className="text-sm text-gray-600 dark:text-gray-300">
+                      {t('credentials.unlocked')}
+                    </span>
+                    <button
+                      onClick={handleLockProfileStore}
+                      className="flex items-center space-x-1 px-3 py-1.5 bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600 rounded text-sm"
+                    >
+                      <Lock className="w-3 h-3" />
+                      <span>{t('credentials.lock')}</span>
+                    </button>
+                  </div>
+                ) : (
+                  <>
+                    <input
+                      type="password"
+                      value={masterPassword}
+                      onChange={e => setMasterPassword(e.target.value)}
+                      onKeyDown={e => {
+                        if (e.key === 'Enter' && masterPassword) handleUnlockProfileStore();
+                      }}
+                      placeholder={t('credentials.master.password')}
+                      className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg text-sm bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    />
+                    {profileStatus.keyringAvailable && (
+                      <label
+                        className="flex items-center space-x-2 text-sm text-gray-600 dark:text-gray-300"
+                      >
+                        <input
+                          type="checkbox"
+                          checked={rememberPassword}
+                          onChange={e => setRememberPassword(e.target.checked)}
+                        />
+                        <span>{t('credentials.remember')}</span>
+                      </label>
+                    )}
+                    <button
+                      onClick={handleUnlockProfileStore}
+                      disabled={isUnlocking || !masterPassword}
+                      className="w-full flex items-center justify-center space-x-2 px-4 py-2 bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600 rounded-lg transition-colors disabled:opacity-50"
+                    >
+                      <Unlock className="w-4 h-4" />
+                      <span className="text-sm">
+                        {profileStatus.exists ? t('credentials.unlock') : t('credentials.create')}
+                      </span>
+                    </button>
+                  </>
+                )}
+              </div>
+            </div>
+          )}
+
           {/* Cache Management */}
           <div>
             <h3 className="text-sm font-medium text-gray-900 dark:text-white mb-3">
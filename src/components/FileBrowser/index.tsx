@@ -372,6 +372,11 @@ export const FileBrowser: React.FC<FileBrowserProps> = ({
     try {
       console.log('Loading directory from server:', path);
 
+      // 手动刷新时跳过后端的目录列表缓存
+      if (isManual) {
+        await StorageServiceManager.refreshDirectory(path);
+      }
+
       // 获取当前客户端的默认分页大小
       const currentClient = StorageServiceManager.getCurrentClient();
       const defaultPageSize = currentClient.getDefaultPageSize();
@@ -7,6 +7,7 @@ import { LanguageSwitcher } from '../LanguageSwitcher';
 import { FileIcon } from '../../utils/fileIcons';
 import { copyToClipboard, showCopyToast, showToast } from '../../utils/clipboard';
 import { formatFileSize } from '../../utils/fileUtils';
+import { isCancelledError } from '../../utils/appError';
 
 interface FileViewerHeaderProps {
   file: StorageFile;
@@ -99,7 +100,7 @@ export const FileViewerHeader: React.FC<FileViewerHeaderProps> = ({
       // 如果是用户取消操作，不显示错误弹窗
       const errorMessage =
         err instanceof Error ? err.message : typeof err === 'string' ? err : t('error.unknown');
-      if (errorMessage !== 'download.cancelled') {
+      if (!isCancelledError(err)) {
         showToast(`${t('download.failed')}: ${errorMessage}`, 'error');
       }
     }
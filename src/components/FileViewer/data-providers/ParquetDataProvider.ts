@@ -1,5 +1,6 @@
 import { parquetMetadataAsync, parquetReadObjects, type AsyncBuffer } from 'hyparquet';
 import { commands } from '../../../types/tauri-commands';
+import { toError } from '../../../utils/appError';
 
 export interface DataColumn {
   name: string;
@@ -64,14 +65,16 @@ class StreamingAsyncBuffer implements AsyncBuffer {
 
     try {
       // 使用后端的分块读取 API
+      // 未指定连接 ID，使用当前窗口绑定的连接
       const result = await commands.storageGetFileContent(
+        null,
         this.filePath,
         from.toString(),
         length.toString()
       );
 
       if (result.status === 'error') {
-        throw new Error(`Failed to read file range ${from}-${to}: ${result.error}`);
+        throw toError(result.error, `Failed to read file range ${from}-${to}`);
       }
 
       const uint8Array = new Uint8Array(result.data);
@@ -29,6 +29,7 @@ import {
 } from '../../common';
 import { ManualLoadButton } from '../common';
 import { useTranslation } from 'react-i18next';
+import { isCancelledError } from '../../../utils/appError';
 
 // 错误信息翻译辅助函数
 const translateError = (error: string, t: (key: string) => string): string => {
@@ -638,7 +639,7 @@ export const ArchiveViewer: React.FC<ArchiveViewerProps> = ({ url, filename, sto
       console.error('Failed to download file:', err);
       // 如果是用户取消操作，不显示错误弹窗
       const errorMessage = extractErrorMessage(err, 'error.unknown', t);
-      if (errorMessage !== 'download.cancelled') {
+      if (!isCancelledError(err)) {
         showToast(`${t('download.failed')}: ${errorMessage}`, 'error');
       }
     }
@@ -72,10 +72,21 @@ export const connection = {
   'oss.secret.key': 'Secret Key',
   'oss.secret.key.placeholder': 'Secret Access Key',
   'oss.bucket': 'Bucket Name & Path',
-  'oss.bucket.placeholder': 'Bucket name or path, e.g.: my-bucket or my-bucket/path/prefix',
+  'oss.bucket.placeholder':
+    'Bucket name or path, e.g.: my-bucket or my-bucket/path/prefix; leave empty to list all buckets',
   'oss.region': 'Region',
   'oss.region.placeholder': 'e.g.: cn-hangzhou, us-east-1',
   'oss.region.optional': 'Region (Optional)',
+  'oss.anonymous': 'Anonymous access (public bucket)',
+  'oss.anonymous.description':
+    'Send unsigned requests, for public datasets such as open-data S3 buckets',
+  'oss.session.token': 'Session Token (Optional)',
+  'oss.session.token.placeholder': 'Session token of STS temporary credentials',
+  'oss.default.credentials': 'Use AWS profile / environment credentials',
+  'oss.default.credentials.description':
+    'Read credentials from a shared config profile, environment variables or instance metadata',
+  'oss.credential.profile': 'Profile (Optional)',
+  'oss.credential.profile.placeholder': 'Leave empty to use the default credential chain',
 
   // SSH specific fields
   'ssh.server': 'Server URL',
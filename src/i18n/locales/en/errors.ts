@@ -20,6 +20,21 @@ export const errors = {
   'error.authentication.failed': 'Authentication failed: Please check your credentials',
   'error.network.failed': 'Network connection failed: Please check your connection',
 
+  // Structured backend errors (AppError codes)
+  'error.not.found': 'Not found: {{detail}}',
+  'error.invalid.argument': 'Invalid argument: {{detail}}',
+  'error.unsupported': 'Not supported: {{detail}}',
+  'error.not.connected': 'Not connected to any storage',
+  'error.timeout': 'The operation timed out: {{detail}}',
+  'error.rate.limited': 'Too many requests, please try again later',
+  'error.conflict': 'Conflict: {{detail}}',
+  'error.io': 'File system error: {{detail}}',
+  'error.request.failed': 'Request failed: {{detail}}',
+  'storage.read.only': 'This connection is read-only',
+  'profile.store.locked': 'Saved connections are locked. Please enter the master password',
+  'profile.password.invalid': 'Incorrect master password',
+  'profile.password.required': 'Please enter the master password',
+
   // Download errors
   'download.failed': 'Download failed',
   'errors.download.failed': 'File download failed: {{error}}',
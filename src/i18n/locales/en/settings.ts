@@ -26,6 +26,20 @@ export const settings = {
   'cache.cleared.success': 'Cache and connections cleared successfully',
   'cache.clear.failed': 'Failed to clear cache',
 
+  // Saved credentials
+  'settings.credentials': 'Saved Credentials',
+  'credentials.description':
+    'Passwords and keys of saved connections are kept in an encrypted store protected by a master password',
+  'credentials.unlocked': 'Saved credentials are unlocked',
+  'credentials.master.password': 'Master password',
+  'credentials.remember': 'Remember in system keychain',
+  'credentials.unlock': 'Unlock',
+  'credentials.create': 'Create Store',
+  'credentials.lock': 'Lock',
+  'credentials.unlock.success': 'Saved credentials unlocked',
+  'credentials.unlock.failed': 'Failed to unlock saved credentials',
+  'credentials.locked.success': 'Saved credentials locked',
+
   // About
   about: 'About',
   'app.description': 'Modern dataset viewer tool',
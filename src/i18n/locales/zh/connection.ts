@@ -71,10 +71,19 @@ export const connection = {
   'oss.secret.key': 'Secret Key',
   'oss.secret.key.placeholder': '访问密钥密码',
   'oss.bucket': 'Bucket 名称及路径',
-  'oss.bucket.placeholder': '存储桶名称或路径，如：my-bucket 或 my-bucket/path/prefix',
+  'oss.bucket.placeholder':
+    '存储桶名称或路径，如：my-bucket 或 my-bucket/path/prefix；留空则列出所有存储桶',
   'oss.region': '区域',
   'oss.region.placeholder': '例如：cn-hangzhou、us-east-1',
   'oss.region.optional': '区域 (可选)',
+  'oss.anonymous': '匿名访问（公开存储桶）',
+  'oss.anonymous.description': '不对请求签名，用于 S3 开放数据集等公开数据',
+  'oss.session.token': 'Session Token (可选)',
+  'oss.session.token.placeholder': 'STS 临时凭证的会话令牌',
+  'oss.default.credentials': '使用 AWS profile / 环境变量凭证',
+  'oss.default.credentials.description': '从共享配置文件的 profile、环境变量或实例元数据读取凭证',
+  'oss.credential.profile': 'Profile (可选)',
+  'oss.credential.profile.placeholder': '留空则使用默认凭证链',
 
   // SSH 特定字段
   'ssh.server': '服务器地址',
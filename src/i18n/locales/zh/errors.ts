@@ -21,6 +21,21 @@ export const errors = {
   'error.authentication.failed': '身份验证失败：请检查您的凭据',
   'error.network.failed': '网络连接失败：请检查您的网络连接',
 
+  // 后端结构化错误（AppError 错误码）
+  'error.not.found': '未找到：{{detail}}',
+  'error.invalid.argument': '参数无效：{{detail}}',
+  'error.unsupported': '不支持：{{detail}}',
+  'error.not.connected': '尚未连接到任何存储',
+  'error.timeout': '操作超时：{{detail}}',
+  'error.rate.limited': '请求过于频繁，请稍后重试',
+  'error.conflict': '冲突：{{detail}}',
+  'error.io': '文件系统错误：{{detail}}',
+  'error.request.failed': '请求失败：{{detail}}',
+  'storage.read.only': '该连接为只读连接',
+  'profile.store.locked': '已保存的连接已锁定，请输入主密码',
+  'profile.password.invalid': '主密码错误',
+  'profile.password.required': '请输入主密码',
+
   // 下载错误
   'download.failed': '下载失败',
   'errors.download.failed': '文件下载失败：{{error}}',
@@ -25,6 +25,19 @@ export const settings = {
   'cache.cleared.success': '缓存和连接清理成功',
   'cache.clear.failed': '缓存清理失败',
 
+  // 保存的凭证
+  'settings.credentials': '保存的凭证',
+  'credentials.description': '已保存连接的密码和密钥存放在加密配置库中，由主密码保护',
+  'credentials.unlocked': '保存的凭证已解锁',
+  'credentials.master.password': '主密码',
+  'credentials.remember': '记住到系统钥匙串',
+  'credentials.unlock': '解锁',
+  'credentials.create': '创建配置库',
+  'credentials.lock': '锁定',
+  'credentials.unlock.success': '保存的凭证已解锁',
+  'credentials.unlock.failed': '解锁保存的凭证失败',
+  'credentials.locked.success': '保存的凭证已锁定',
+
   // 关于
   about: '关于',
   'app.description': '现代化的数据集查看工具',
@@ -1,5 +1,6 @@
 import { ArchiveInfo, FilePreview } from '../types';
 import { commands } from '../types/tauri-commands';
+import { toError } from '../utils/appError';
 
 export class CompressionService {
   /**
@@ -13,7 +14,8 @@ export class CompressionService {
     const timeoutMs = 30000; // 30秒
 
     const result = await Promise.race([
-      commands.archiveGetFileInfo(url, filename, maxSize || null),
+      // 未指定连接 ID，使用当前窗口绑定的连接
+      commands.archiveGetFileInfo(null, url, filename, maxSize || null),
       new Promise<never>((_, reject) => {
         setTimeout(() => {
           reject(new Error(`压缩文件分析超时 (${timeoutMs}ms)`));
@@ -22,7 +24,7 @@ export class CompressionService {
     ]);
 
     if (result.status === 'error') {
-      throw new Error(result.error);
+      throw toError(result.error);
     }
 
     return result.data;
@@ -40,7 +42,14 @@ export class CompressionService {
     const timeoutMs = 30000; // 30秒
 
     const response = await Promise.race([
-      commands.archiveGetFileContent(url, filename, entryPath, maxPreviewSize || null, null),
+      commands.archiveGetFileContent(
+        null,
+        url,
+        filename,
+        entryPath,
+        maxPreviewSize || null,
+        null
+      ),
       new Promise<never>((_, reject) => {
         setTimeout(() => {
           reject(new Error(`文件预览提取超时 (${timeoutMs}ms)`));
@@ -49,7 +58,7 @@ export class CompressionService {
     ]);
 
     if (response.status === 'error') {
-      throw new Error(response.error);
+      throw toError(response.error);
     }
 
     const result = response.data;
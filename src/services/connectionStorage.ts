@@ -1,14 +1,45 @@
 import { ConnectionConfig } from './storage/types';
+import { commands, ConnectionConfig as TauriConnectionConfig } from '../types/tauri-commands';
 import { getHostnameFromUrl } from '../utils/urlUtils';
 
 export interface StoredConnection {
   id: string;
   name: string;
-  config: ConnectionConfig; // 直接存储完整的连接配置
+  config: ConnectionConfig; // 连接配置，密钥字段只保存占位值，实际密钥在加密配置库中
   lastConnected?: string;
   isDefault?: boolean;
 }
 
+// 已保存到加密配置库的密钥在本地存储中的占位值
+export const STORED_SECRET = '__stored_secret__';
+
+// 前端连接配置中的密钥字段
+const SECRET_FIELDS = ['password', 'apiToken', 'passphrase', 'sessionToken'] as const;
+
+// 后端连接配置中的密钥字段
+const BACKEND_SECRET_FIELDS = ['password', 'secretKey', 'sessionToken', 'passphrase'] as const;
+
+// 配置中是否包含明文密钥
+export function hasPlaintextSecrets(config: ConnectionConfig): boolean {
+  return SECRET_FIELDS.some(field => !!config[field] && config[field] !== STORED_SECRET);
+}
+
+// 配置中是否引用了加密配置库中保存的密钥
+export function hasStoredSecrets(config: ConnectionConfig): boolean {
+  return SECRET_FIELDS.some(field => config[field] === STORED_SECRET);
+}
+
+// 清除后端配置中的占位值，更新配置库时这些字段保留原有密钥
+export function clearSecretPlaceholders(config: TauriConnectionConfig): TauriConnectionConfig {
+  const result = { ...config };
+  for (const field of BACKEND_SECRET_FIELDS) {
+    if (result[field] === STORED_SECRET) {
+      result[field] = null;
+    }
+  }
+  return result;
+}
+
 class ConnectionStorageService {
   private readonly STORAGE_KEY = 'saved-connections';
 
@@ -36,17 +67,36 @@ class ConnectionStorageService {
   }
 
   // 保存连接配置
-  async saveConnection(config: ConnectionConfig, name?: string): Promise<string> {
+  // 密钥保存到加密配置库，本地存储中只保留占位值；配置库未解锁时不保存密钥
+  async saveConnection(
+    config: ConnectionConfig,
+    name?: string,
+    backendConfig?: TauriConnectionConfig
+  ): Promise<string> {
     const connections = this.getStoredConnections();
 
     // 检查是否已存在相同的连接
     const existingConnection = this.findConnection(config);
+
+    // 生成连接名称
+    let connectionName = existingConnection?.name || name || config.name;
+    if (!connectionName) {
+      connectionName = this.generateConnectionName(config);
+    }
+
+    const protectedConfig = await this.protectSecrets(
+      config,
+      connectionName,
+      backendConfig,
+      config.profileId || existingConnection?.config.profileId
+    );
+
     if (existingConnection) {
       // 重新获取最新的连接列表并更新
       const updatedConnections = this.getStoredConnections();
       const targetConnection = updatedConnections.find(c => c.id === existingConnection.id);
       if (targetConnection) {
-        targetConnection.config = { ...config };
+        targetConnection.config = protectedConfig;
         targetConnection.lastConnected = new Date().toISOString();
         localStorage.setItem(this.STORAGE_KEY, JSON.stringify(updatedConnections));
       }
@@ -56,17 +106,10 @@ class ConnectionStorageService {
 
     const id = this.generateId();
 
-    // 生成连接名称
-    let connectionName = name || config.name;
-    if (!connectionName) {
-      connectionName = this.generateConnectionName(config);
-    }
-
-    // 直接保存完整的连接配置（包含密码信息）
     const storedConnection: StoredConnection = {
       id,
       name: connectionName,
-      config: { ...config },
+      config: protectedConfig,
       lastConnected: new Date().toISOString(),
     };
 
@@ -183,6 +226,118 @@ class ConnectionStorageService {
     }
   }
 
+  // 确保加密配置库可用，保存了钥匙串密钥时自动解锁
+  async ensureProfileStore(): Promise<boolean> {
+    const status = await commands.profileStatus();
+    if (status.status === 'error') {
+      return false;
+    }
+    if (status.data.unlocked) {
+      return true;
+    }
+    if (!status.data.exists || !status.data.keyringEnabled) {
+      return false;
+    }
+
+    const unlocked = await commands.profileUnlock(null, null);
+    return unlocked.status === 'ok' && unlocked.data.unlocked;
+  }
+
+  // 将旧版本以明文保存在本地存储中的密钥迁移到加密配置库
+  async migrateLegacySecrets(
+    toBackendConfig: (config: ConnectionConfig) => TauriConnectionConfig
+  ): Promise<void> {
+    const connections = this.getStoredConnections();
+    const legacyConnections = connections.filter(c => hasPlaintextSecrets(c.config));
+    if (legacyConnections.length === 0 || !(await this.ensureProfileStore())) {
+      return;
+    }
+
+    for (const connection of legacyConnections) {
+      try {
+        connection.config = await this.protectSecrets(
+          connection.config,
+          connection.name,
+          toBackendConfig(connection.config),
+          connection.config.profileId
+        );
+      } catch (error) {
+        console.warn(`Failed to migrate secrets of connection ${connection.name}:`, error);
+      }
+    }
+    localStorage.setItem(this.STORAGE_KEY, JSON.stringify(connections));
+  }
+
+  // 将密钥保存到加密配置库，返回配置 ID；配置库未解锁或保存失败时返回 null
+  private async saveSecrets(
+    name: string,
+    backendConfig: TauriConnectionConfig,
+    profileId?: string
+  ): Promise<string | null> {
+    if (!(await this.ensureProfileStore())) {
+      return null;
+    }
+
+    const config = clearSecretPlaceholders(backendConfig);
+    if (profileId) {
+      const updated = await commands.profileUpdate(profileId, name, config);
+      if (updated.status === 'ok') {
+        return profileId;
+      }
+      // 配置已被删除时重新创建
+    }
+
+    const created = await commands.profileCreate(name, config);
+    if (created.status === 'error') {
+      console.warn('Failed to save connection secrets:', created.error);
+      return null;
+    }
+    return created.data.id;
+  }
+
+  // 生成本地存储使用的配置：已保存到配置库的密钥替换为占位值，无法保存的密钥直接丢弃
+  private async protectSecrets(
+    config: ConnectionConfig,
+    name: string,
+    backendConfig?: TauriConnectionConfig,
+    profileId?: string
+  ): Promise<ConnectionConfig> {
+    const result: ConnectionConfig = { ...config };
+    delete result.profileId;
+    if (!hasPlaintextSecrets(config) && !hasStoredSecrets(config)) {
+      return result;
+    }
+
+    let savedProfileId = backendConfig
+      ? await this.saveSecrets(name, backendConfig, profileId)
+      : null;
+    // 配置库暂不可用时保留已有的引用
+    if (!savedProfileId && !hasPlaintextSecrets(config) && profileId) {
+      savedProfileId = profileId;
+    }
+
+    for (const field of SECRET_FIELDS) {
+      if (!result[field]) continue;
+      if (savedProfileId) {
+        result[field] = STORED_SECRET;
+      } else {
+        delete result[field];
+      }
+    }
+    if (savedProfileId) {
+      result.profileId = savedProfileId;
+    }
+    return result;
+  }
+
+  // 删除加密配置库中对应的配置
+  private deleteProfile(config: ConnectionConfig): void {
+    if (!config.profileId) return;
+    commands.profileDelete(config.profileId).catch(error => {
+      console.warn('Failed to delete saved secrets:', error);
+    });
+  }
+
   // 更新最后连接时间
   updateLastConnected(id: string): void {
     const connections = this.getStoredConnections();
@@ -193,19 +348,30 @@ class ConnectionStorageService {
     }
   }
 
-  // 删除连接
+  // 删除连接，同时删除加密配置库中保存的密钥
   deleteConnection(id: string): void {
     const connections = this.getStoredConnections();
+    const deleted = connections.find(c => c.id === id);
+    if (deleted) {
+      this.deleteProfile(deleted.config);
+    }
     const filtered = connections.filter(c => c.id !== id);
     localStorage.setItem(this.STORAGE_KEY, JSON.stringify(filtered));
   }
 
-  // 恢复已删除的连接
+  // 恢复已删除的连接，删除时已清除保存的密钥，需要重新输入
   restoreConnection(connection: StoredConnection): void {
     const connections = this.getStoredConnections();
     const exists = connections.some(c => c.id === connection.id);
     if (!exists) {
-      connections.push(connection);
+      const config: ConnectionConfig = { ...connection.config };
+      delete config.profileId;
+      for (const field of SECRET_FIELDS) {
+        if (config[field] === STORED_SECRET) {
+          delete config[field];
+        }
+      }
+      connections.push({ ...connection, config });
       localStorage.setItem(this.STORAGE_KEY, JSON.stringify(connections));
     }
   }
@@ -239,6 +405,7 @@ class ConnectionStorageService {
 
   // 清空所有连接
   clearAllConnections(): void {
+    this.getStoredConnections().forEach(c => this.deleteProfile(c.config));
     localStorage.removeItem(this.STORAGE_KEY);
   }
 
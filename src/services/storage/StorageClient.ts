@@ -12,6 +12,8 @@ import {
   ReadOptions,
 } from './types';
 import { ArchiveInfo, FilePreview } from '../../types';
+import { toError } from '../../utils/appError';
+import { connectionStorage, clearSecretPlaceholders, hasStoredSecrets } from '../connectionStorage';
 
 // 导入平台特定的适配器
 import { webdavStorageAdapter } from './adapters/WebDAVAdapter';
@@ -106,6 +108,8 @@ export class StorageClient implements IStorageClient {
   private adapter: StorageAdapter;
   private connection: any = null;
   private connectionConfig: ConnectionConfig | null = null;
+  // 后端返回的连接 ID，所有命令都通过它指定要操作的连接
+  private connectionId: string | null = null;
 
   constructor(storageType: StorageClientType) {
     this.storageType = storageType;
@@ -161,11 +165,7 @@ export class StorageClient implements IStorageClient {
       this.connectionConfig = config;
 
       // 适配器预处理连接配置
-      let processedConnection = config;
-      if (this.adapter.preprocessConnection) {
-        const preprocessed = this.adapter.preprocessConnection(config);
-        processedConnection = { ...config, ...preprocessed };
-      }
+      const processedConnection = this.preprocessConnection(config);
 
       // 构建后端连接配置
       const backendConfig = this.buildBackendConfig(processedConnection);
@@ -190,6 +190,13 @@ export class StorageClient implements IStorageClient {
     }
   }
 
+  /**
+   * 将前端连接配置转换为后端连接配置，用于保存到加密配置库
+   */
+  toBackendConfig(config: ConnectionConfig): TauriConnectionConfig {
+    return this.buildBackendConfig(this.preprocessConnection(config));
+  }
+
   disconnect(): void {
     this.disconnectFromBackend();
     this.connection = null;
@@ -200,6 +207,13 @@ export class StorageClient implements IStorageClient {
     return this.connected;
   }
 
+  /**
+   * 获取后端连接 ID
+   */
+  getConnectionId(): string | null {
+    return this.connectionId;
+  }
+
   // ========== 统一的文件操作方法 ==========
 
   async listDirectory(path: string = '', options?: ListOptions): Promise<DirectoryResult> {
@@ -228,6 +242,9 @@ export class StorageClient implements IStorageClient {
               recursive: options.recursive || null,
               sortBy: options.sortBy || null,
               sortOrder: options.sortOrder || null,
+              showHidden: options.showHidden ?? null,
+              pattern: options.pattern || null,
+              fileType: options.fileType || null,
             }
           : undefined
       );
@@ -237,6 +254,29 @@ export class StorageClient implements IStorageClient {
     }
   }
 
+  /**
+   * 使目录及其子目录的列表缓存失效，下次列出时重新从服务器获取
+   */
+  async refreshDirectory(path: string = ''): Promise<void> {
+    if (!this.connected) {
+      return;
+    }
+
+    let actualPath = path;
+    if (this.adapter.preprocessPath) {
+      actualPath = this.adapter.preprocessPath(
+        path,
+        this.connection,
+        this.connectionConfig || undefined
+      );
+    }
+
+    const result = await commands.storageRefresh(this.connectionId, actualPath);
+    if (result.status === 'error') {
+      console.warn(`Failed to refresh directory cache ${path}:`, result.error);
+    }
+  }
+
   async getFileContent(path: string, options?: ReadOptions): Promise<FileContent> {
     if (!this.connected) {
       throw new Error(`${this.storageType} storage not connected`);
@@ -353,10 +393,10 @@ export class StorageClient implements IStorageClient {
     path: string,
     options?: ListOptions
   ): Promise<DirectoryResult> {
-    const result = await commands.storageList(path, options || null);
+    const result = await commands.storageList(this.connectionId, path, options || null);
 
     if (result.status === 'error') {
-      throw new Error(result.error);
+      throw toError(result.error);
     }
 
     return result.data;
@@ -374,10 +414,15 @@ export class StorageClient implements IStorageClient {
     // 确保 savePath 不是 undefined，如果是则设为 null
     const normalizedSavePath = savePath === undefined ? null : savePath;
 
-    const result = await commands.downloadStart(url, filename, normalizedSavePath);
+    const result = await commands.downloadStart(
+      this.connectionId,
+      url,
+      filename,
+      normalizedSavePath
+    );
 
     if (result.status === 'error') {
-      throw new Error(result.error);
+      throw toError(result.error);
     }
 
     return result.data;
@@ -392,10 +437,15 @@ export class StorageClient implements IStorageClient {
     maxSize?: number
   ): Promise<ArchiveInfo> {
     // 通过Tauri命令调用后端的存储客户端接口
-    const result = await commands.archiveGetFileInfo(path, filename, maxSize || null);
+    const result = await commands.archiveGetFileInfo(
+      this.connectionId,
+      path,
+      filename,
+      maxSize || null
+    );
 
     if (result.status === 'error') {
-      throw new Error(result.error);
+      throw toError(result.error);
     }
 
     return result.data;
@@ -413,6 +463,7 @@ export class StorageClient implements IStorageClient {
   ): Promise<FilePreview> {
     // 通过Tauri命令调用后端的存储客户端接口
     const result = await commands.archiveGetFileContent(
+      this.connectionId,
       path,
       filename,
       entryPath,
@@ -421,7 +472,7 @@ export class StorageClient implements IStorageClient {
     );
 
     if (result.status === 'error') {
-      throw new Error(result.error);
+      throw toError(result.error);
     }
 
     // 转换为主项目的 FilePreview 格式，确保 content 是 Uint8Array
@@ -469,6 +520,7 @@ export class StorageClient implements IStorageClient {
     password?: string | null;
     accessKey?: string | null;
     secretKey?: string | null;
+    sessionToken?: string | null;
     region?: string | null;
     bucket?: string | null;
     endpoint?: string | null;
@@ -477,7 +529,13 @@ export class StorageClient implements IStorageClient {
     try {
       // 构建符合 Tauri 后端 ConnectionConfig 的对象
       const backendConfig = this.buildBackendConfig(config);
-      const result = await commands.storageConnect(backendConfig);
+
+      // 密钥保存在加密配置库中时，通过配置 ID 连接
+      const profileId = this.connectionConfig?.profileId;
+      const result =
+        profileId && hasStoredSecrets(this.connectionConfig!)
+          ? await this.connectWithProfile(profileId, backendConfig)
+          : await commands.storageConnect(backendConfig);
 
       if (result.status === 'error') {
         console.error(`${config.protocol} connection failed:`, result.error);
@@ -485,8 +543,9 @@ export class StorageClient implements IStorageClient {
         return false;
       }
 
-      this.connected = result.data;
-      return result.data;
+      this.connectionId = result.data;
+      this.connected = true;
+      return true;
     } catch (error) {
       console.error(`${config.protocol} connection failed:`, error);
       this.connected = false;
@@ -494,12 +553,31 @@ export class StorageClient implements IStorageClient {
     }
   }
 
+  /**
+   * 使用加密配置库中保存的密钥连接，连接前同步非密钥字段的修改
+   */
+  private async connectWithProfile(profileId: string, backendConfig: TauriConnectionConfig) {
+    if (!(await connectionStorage.ensureProfileStore())) {
+      throw new Error('Saved credentials are locked');
+    }
+
+    const updated = await commands.profileUpdate(
+      profileId,
+      null,
+      clearSecretPlaceholders(backendConfig)
+    );
+    if (updated.status === 'error') {
+      return updated;
+    }
+    return commands.profileConnect(profileId);
+  }
+
   /**
    * 通用断开连接方法
    */
   protected async disconnectFromBackend(): Promise<void> {
     try {
-      const result = await commands.storageDisconnect();
+      const result = await commands.storageDisconnect(this.connectionId);
 
       if (result.status === 'error') {
         console.warn('Failed to disconnect from storage backend:', result.error);
@@ -508,6 +586,7 @@ export class StorageClient implements IStorageClient {
       console.warn('Failed to disconnect from storage backend:', error);
     }
     this.connected = false;
+    this.connectionId = null;
   }
 
   /**
@@ -523,13 +602,14 @@ export class StorageClient implements IStorageClient {
     length?: number
   ): Promise<Uint8Array> {
     const result = await commands.storageGetFileContent(
+      this.connectionId,
       this.toProtocolUrl(path),
       start !== undefined ? start.toString() : null,
       length !== undefined ? length.toString() : null
     );
 
     if (result.status === 'error') {
-      throw new Error(result.error);
+      throw toError(result.error);
     }
 
     return new Uint8Array(result.data);
@@ -541,10 +621,13 @@ export class StorageClient implements IStorageClient {
    * @returns 文件大小
    */
   protected async getFileSizeInternal(path: string): Promise<number> {
-    const result = await commands.storageGetFileInfo(this.toProtocolUrl(path));
+    const result = await commands.storageGetFileInfo(
+      this.connectionId,
+      this.toProtocolUrl(path)
+    );
 
     if (result.status === 'error') {
-      throw new Error(result.error);
+      throw toError(result.error);
     }
 
     return parseInt(result.data.size, 10);
@@ -563,6 +646,16 @@ export class StorageClient implements IStorageClient {
 
   // ========== 辅助方法 ==========
 
+  /**
+   * 适配器预处理连接配置
+   */
+  private preprocessConnection(config: ConnectionConfig): any {
+    if (this.adapter.preprocessConnection) {
+      return { ...config, ...this.adapter.preprocessConnection(config) };
+    }
+    return config;
+  }
+
   /**
    * 构建后端连接配置
    */
@@ -574,6 +667,7 @@ export class StorageClient implements IStorageClient {
       password: config.password || config.apiToken || null,
       accessKey: config.accessKey || null,
       secretKey: config.secretKey || null,
+      sessionToken: config.sessionToken || null,
       region: config.region || null,
       bucket: config.bucket || null,
       endpoint: config.endpoint || null,
@@ -585,7 +679,31 @@ export class StorageClient implements IStorageClient {
       // SMB 特定字段
       share: config.share || null,
       domain: config.domain || null,
-      extraOptions: null,
+      extraOptions: config.extraOptions || null,
+      readOnly: config.readOnly ?? null,
+      blockCache: config.blockCache
+        ? {
+            blockSizeKb: config.blockCache.blockSizeKb ?? null,
+            memoryCapacityMb: config.blockCache.memoryCapacityMb ?? null,
+            diskCache: config.blockCache.diskCache ?? null,
+            diskDir: config.blockCache.diskDir ?? null,
+            diskCapacityMb: config.blockCache.diskCapacityMb ?? null,
+          }
+        : null,
+      retry: config.retry
+        ? {
+            maxAttempts: config.retry.maxAttempts ?? null,
+            initialBackoffMs: config.retry.initialBackoffMs ?? null,
+            maxBackoffMs: config.retry.maxBackoffMs ?? null,
+            jitter: config.retry.jitter ?? null,
+          }
+        : null,
+      listingCache: config.listingCache
+        ? {
+            ttlSecs: config.listingCache.ttlSecs ?? null,
+            maxEntries: config.listingCache.maxEntries ?? null,
+          }
+        : null,
     };
   }
 }
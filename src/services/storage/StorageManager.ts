@@ -2,6 +2,7 @@ import { StorageClient } from './StorageClient';
 import { ConnectionConfig, StorageClientType } from './types';
 import { connectionStorage, StoredConnection } from '../connectionStorage';
 import { commands } from '../../types/tauri-commands';
+import { toError } from '../../utils/appError';
 
 /**
  * 存储客户端工厂 - 简化版
@@ -145,7 +146,8 @@ export class StorageServiceManager {
       // 直接使用 connectionStorage.saveConnection，它会自动处理新建或更新逻辑
       const connectionId = await connectionStorage.saveConnection(
         config,
-        this.currentClient!.generateConnectionName(config)
+        this.currentClient!.generateConnectionName(config),
+        this.currentClient!.toBackendConfig(config)
       );
       return connectionId;
     } catch (error) {
@@ -239,11 +241,44 @@ export class StorageServiceManager {
     };
   }
 
+  /**
+   * 解锁加密配置库，并迁移旧版本明文保存的密钥
+   */
+  static async unlockProfileStore(masterPassword?: string, remember?: boolean) {
+    const result = await commands.profileUnlock(masterPassword ?? null, remember ?? null);
+    if (result.status === 'error') {
+      throw toError(result.error);
+    }
+    await this.migrateLegacySecrets();
+    return result.data;
+  }
+
+  /**
+   * 锁定加密配置库
+   */
+  static async lockProfileStore() {
+    const result = await commands.profileLock();
+    if (result.status === 'error') {
+      throw toError(result.error);
+    }
+  }
+
+  /**
+   * 将旧版本明文保存的密钥迁移到加密配置库
+   */
+  private static async migrateLegacySecrets(): Promise<void> {
+    await connectionStorage.migrateLegacySecrets(config =>
+      StorageClientFactory.createClient(config.type).toBackendConfig(config)
+    );
+  }
+
   /**
    * 自动连接
    */
   static async autoConnect(): Promise<boolean> {
     try {
+      // 配置库可通过钥匙串自动解锁时，先迁移旧版本明文保存的密钥
+      await this.migrateLegacySecrets();
       // 尝试使用默认连接
       const defaultConnection = connectionStorage.getDefaultConnection();
       if (defaultConnection) {
@@ -277,6 +312,14 @@ export class StorageServiceManager {
     return await client.listDirectory(path, options);
   }
 
+  /**
+   * 刷新目录列表缓存
+   */
+  static async refreshDirectory(path: string = '') {
+    const client = this.getCurrentClient();
+    await client.refreshDirectory(path);
+  }
+
   /**
    * 获取文件内容
    */
@@ -412,9 +455,12 @@ export class StorageServiceManager {
    * 获取文件下载URL
    */
   static async getDownloadUrl(path: string): Promise<string> {
-    const result = await commands.storageGetUrl(path);
+    const result = await commands.storageGetUrl(
+      this.currentClient?.getConnectionId() ?? null,
+      path
+    );
     if (result.status === 'error') {
-      throw new Error(result.error);
+      throw toError(result.error);
     }
     return result.data;
   }
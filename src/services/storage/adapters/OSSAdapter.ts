@@ -14,8 +14,9 @@ export const ossStorageAdapter: StorageAdapter = {
   supportsCustomRootDisplay: false,
 
   preprocessConnection: (config: ConnectionConfig) => {
-    // OSS 特有的配置解析和验证
-    if (!config.username || !config.password) {
+    // OSS 特有的配置解析和验证，匿名访问公开存储桶或使用默认凭证时不需要密钥
+    const useKeys = !config.anonymous && !config.useDefaultCredentials;
+    if (useKeys && (!config.username || !config.password)) {
       throw new Error('OSS requires accessKey (username) and secretKey (password)');
     }
 
@@ -32,8 +33,9 @@ export const ossStorageAdapter: StorageAdapter = {
       }
     }
 
-    if (!bucket) {
-      throw new Error('OSS bucket is required');
+    // 未指定 bucket 时连接服务端点，列出所有可访问的存储桶
+    if (!bucket && config.anonymous) {
+      throw new Error('OSS bucket is required for anonymous access');
     }
 
     // 从端点 URL 中提取主机名，而不是从 oss:// URL
@@ -54,7 +56,13 @@ export const ossStorageAdapter: StorageAdapter = {
     let endpoint = '';
     let region = config.region;
 
-    if (hostname.includes('oss-') && hostname.includes('aliyuncs.com')) {
+    if (!bucket) {
+      // 服务端点，存储桶的区域由后端自动解析
+      endpoint = `https://${hostname}`;
+      if (hostname.includes('amazonaws.com')) {
+        region = region || 'us-east-1';
+      }
+    } else if (hostname.includes('oss-') && hostname.includes('aliyuncs.com')) {
       // 阿里云 OSS - 直接使用主机名，不需要额外的 region 处理
       region = region || 'cn-hangzhou'; // 阿里云默认区域
       endpoint = `https://${bucket}.${hostname}`;
@@ -78,22 +86,38 @@ export const ossStorageAdapter: StorageAdapter = {
       endpoint = `https://${hostname}`;
     }
 
+    // 使用默认凭证时由后端按 profile 或默认凭证链获取密钥
+    let extraOptions: Record<string, string> | undefined;
+    if (config.anonymous) {
+      extraOptions = { anonymous: 'true' };
+    } else if (config.useDefaultCredentials && config.credentialProfile) {
+      extraOptions = { profile: config.credentialProfile };
+    }
+
     return {
       url: endpoint, // 使用计算出的正确端点
       endpoint,
       bucket: config.bucket, // 保留原始的 bucket 字段（包含路径），让后端解析
       pathPrefix,
-      accessKey: config.username,
-      secretKey: config.password,
+      accessKey: useKeys ? config.username : undefined,
+      secretKey: useKeys ? config.password : undefined,
+      sessionToken: useKeys ? config.sessionToken : undefined,
       region,
+      extraOptions,
     };
   },
 
   buildProtocolUrl: (path: string, connection: any) => {
-    if (!connection?.bucket) {
+    if (!connection) {
       throw new Error('Not connected to OSS');
     }
 
+    // 未指定 bucket 时路径的第一级即为存储桶名称
+    if (!connection.bucket) {
+      const bucketPath = path.replace(/^\/+/, '');
+      return bucketPath ? `oss://${bucketPath}` : 'oss://';
+    }
+
     // 获取实际的桶名（不包含路径前缀）
     const actualBucket = connection.bucket.split('/')[0];
 
@@ -120,7 +144,7 @@ export const ossStorageAdapter: StorageAdapter = {
   generateConnectionName: (config: ConnectionConfig) => {
     if (config.name) return config.name;
 
-    const bucket = config.bucket || 'Unknown';
+    const bucket = config.bucket || 'All buckets';
     const cleanBucket = bucket.split('/')[0]; // 只显示 bucket 名称，不包含路径
     return `OSS (${cleanBucket})`;
   },
@@ -144,9 +168,16 @@ export const ossStorageAdapter: StorageAdapter = {
         formData.isPasswordFromStorage && existingConnection?.config.password
           ? existingConnection.config.password
           : formData.secretKey || formData.password, // 优先使用 secretKey，兼容 password
+      sessionToken:
+        formData.isSessionTokenFromStorage && existingConnection?.config.sessionToken
+          ? existingConnection.config.sessionToken
+          : formData.sessionToken || undefined,
       name: existingConnection
         ? existingConnection.name
-        : `OSS (${formData.bucket?.trim()?.split('/')[0] || 'unknown'})`,
+        : `OSS (${formData.bucket?.trim()?.split('/')[0] || 'All buckets'})`,
+      anonymous: !!formData.anonymous,
+      useDefaultCredentials: !!formData.useDefaultCredentials,
+      credentialProfile: formData.credentialProfile?.trim() || undefined,
     };
 
     return config;
@@ -159,5 +190,10 @@ export const ossStorageAdapter: StorageAdapter = {
     accessKey: config.username || '', // 将 config.username 映射到 accessKey 字段
     secretKey: config.password ? '******' : '', // 使用占位符回显已保存的密钥，映射到 secretKey 字段
     isPasswordFromStorage: !!config.password, // 如果有密钥，标记为来自存储
+    sessionToken: config.sessionToken ? '******' : '',
+    isSessionTokenFromStorage: !!config.sessionToken,
+    anonymous: !!config.anonymous,
+    useDefaultCredentials: !!config.useDefaultCredentials,
+    credentialProfile: config.credentialProfile || '',
   }),
 };
@@ -31,6 +31,10 @@ export interface ConnectionConfig {
   region?: string; // OSS 区域
   endpoint?: string; // OSS 端点地址（可选，通常从 url 解析）
   platform?: string; // OSS 平台类型 (aws, aliyun, tencent, huawei, minio, custom)
+  sessionToken?: string; // STS 临时凭证的会话令牌
+  anonymous?: boolean; // 匿名访问公开存储桶，请求不签名
+  useDefaultCredentials?: boolean; // 使用 AWS profile 或环境变量等默认凭证，不填写密钥
+  credentialProfile?: string; // 共享配置文件中的 profile 名称，留空时依次尝试默认凭证来源
   // HuggingFace 特定配置
   apiToken?: string; // HF API token for private datasets
   organization?: string; // 组织名称 (可选)
@@ -42,9 +46,37 @@ export interface ConnectionConfig {
   share?: string; // SMB 共享名称
   domain?: string; // SMB 域名或工作组
   // 连接元数据
+  profileId?: string; // 加密配置库中的配置 ID，密钥只保存在后端
+  readOnly?: boolean; // 只读模式，禁止上传、创建、重命名和删除
+  blockCache?: BlockCacheOptions; // 块缓存配置，加速压缩包浏览等重复的范围读取
+  retry?: RetryOptions; // 重试策略，未设置时使用默认策略
+  listingCache?: ListingCacheOptions; // 目录列表缓存，未设置时使用默认配置
   isTemporary?: boolean; // 临时连接，不保存到已保存连接中（如文件关联）
 }
 
+// 块缓存配置
+export interface BlockCacheOptions {
+  blockSizeKb?: number; // 缓存块大小，默认 256 KB
+  memoryCapacityMb?: number; // 内存缓存容量，默认 64 MB
+  diskCache?: boolean; // 是否启用磁盘缓存层
+  diskDir?: string; // 磁盘缓存目录，默认为系统缓存目录
+  diskCapacityMb?: number; // 磁盘缓存容量，默认 512 MB
+}
+
+// 重试策略配置
+export interface RetryOptions {
+  maxAttempts?: number; // 最大尝试次数（含首次），设为 1 时关闭重试，默认 3
+  initialBackoffMs?: number; // 首次重试前的等待时间，默认 500 毫秒
+  maxBackoffMs?: number; // 单次等待时间上限，默认 10 秒
+  jitter?: boolean; // 是否为等待时间加入随机抖动，默认开启
+}
+
+// 目录列表缓存配置
+export interface ListingCacheOptions {
+  ttlSecs?: number; // 缓存有效期，设为 0 时关闭缓存，默认 30 秒
+  maxEntries?: number; // 缓存的条目总数上限，默认 200000
+}
+
 // 文件内容接口
 export interface FileContent {
   content: string;
@@ -0,0 +1,96 @@
+/**
+ * Tauri 命令返回的结构化 AppError 的辅助函数
+ */
+import type { AppError, ErrorCode } from '../types/tauri-commands';
+
+/**
+ * 代替命令返回的 AppError 抛出的错误，保留结构化字段
+ */
+export class CommandError extends Error {
+  readonly code: ErrorCode;
+  readonly i18nKey: string;
+  readonly params: Partial<Record<string, string>>;
+  readonly retryable: boolean;
+  readonly sources: string[];
+
+  constructor(error: AppError) {
+    super(error.message);
+    this.name = 'CommandError';
+    this.code = error.code;
+    this.i18nKey = error.i18nKey;
+    this.params = error.params;
+    this.retryable = error.retryable;
+    this.sources = error.sources;
+  }
+}
+
+/**
+ * 将命令错误转换为可抛出的 Error
+ * @param error 命令结果中的 AppError
+ * @param context 可选的上下文描述，添加在错误信息前
+ */
+export function toError(error: AppError, context?: string): CommandError {
+  const commandError = new CommandError(error);
+  if (context) {
+    commandError.message = `${context}: ${error.message}`;
+  }
+  return commandError;
+}
+
+/**
+ * 判断未知值是否为结构化的 AppError
+ */
+export function isAppError(value: unknown): value is AppError {
+  return (
+    !!value &&
+    typeof value === 'object' &&
+    'code' in value &&
+    'i18nKey' in value &&
+    'message' in value
+  );
+}
+
+/**
+ * 获取抛出的错误或 AppError 的错误码，没有时返回 null
+ */
+export function getErrorCode(err: unknown): ErrorCode | null {
+  if (err instanceof CommandError || isAppError(err)) {
+    return err.code;
+  }
+  return null;
+}
+
+/**
+ * 判断错误是否由用户取消操作引起
+ */
+export function isCancelledError(err: unknown): boolean {
+  if (getErrorCode(err) === 'cancelled') {
+    return true;
+  }
+  const message = err instanceof Error ? err.message : typeof err === 'string' ? err : '';
+  return message === 'download.cancelled';
+}
+
+/**
+ * 使用错误的 i18n 键和参数翻译错误信息，没有对应翻译时使用原始信息
+ * @param err 抛出的错误或 AppError
+ * @param t i18n 翻译函数
+ * @param fallbackKey 没有错误信息时使用的翻译键
+ */
+export function translateError(
+  err: unknown,
+  t: (key: string, options?: Record<string, unknown>) => string,
+  fallbackKey = 'error.unknown'
+): string {
+  if (err instanceof CommandError || isAppError(err)) {
+    const translated = t(err.i18nKey, { ...err.params, defaultValue: err.message });
+    return translated === err.i18nKey ? err.message : translated;
+  }
+  if (err instanceof Error) {
+    return err.message;
+  }
+  if (typeof err === 'string') {
+    return err;
+  }
+  return t(fallbackKey);
+}